@@ -0,0 +1,337 @@
+//! Incrementally maintains the daily trade and settlement aggregates that back
+//! the analytics endpoints of the orderbook.
+
+// see database/sql/V063__analytics.sql
+//
+// Summing up all trades on every analytics request gets too slow with the
+// number of trades growing. Instead this background task reads trades and
+// settlements in batches following the last aggregated event, adds them to the
+// aggregates of the day their block was mined in and stores the progress in the
+// same transaction.
+//
+// The day every block was aggregated in is stored as well, so that the
+// aggregates of the affected days can be recomputed when events get replaced
+// because of a reorg (see `database::analytics::rewind`).
+//
+// Events are only aggregated from final blocks (see `Finality`) whose
+// settlements have already been processed by the `OnSettlementEventUpdater`
+// because the auction a trade was settled in is needed to convert its amounts
+// to the native token.
+
+use {
+    crate::database::Postgres,
+    anyhow::{Context, Result},
+    chrono::{NaiveDate, TimeZone, Utc},
+    database::{
+        analytics::{
+            AnalyticsSource,
+            SettlementAggregate,
+            SettlementRow,
+            TradeAggregate,
+            TradeRow,
+        },
+        events::EventIndex,
+        Address,
+    },
+    ethrpc::{
+        current_block::{into_stream, CurrentBlockStream},
+        Web3,
+    },
+    futures::{StreamExt, TryStreamExt},
+    shared::event_handling::Finality,
+    sqlx::PgConnection,
+    std::collections::{HashMap, HashSet},
+    web3::types::{BlockId, BlockNumber},
+};
+
+/// How many events get aggregated in one transaction.
+const BATCH_SIZE: i64 = 1000;
+/// How many blocks get fetched from the node concurrently.
+const MAX_CONCURRENT_BLOCK_REQUESTS: usize = 10;
+
+pub struct AnalyticsUpdater {
+    pub web3: Web3,
+    pub db: Postgres,
+    pub finality: Finality,
+}
+
+impl AnalyticsUpdater {
+    pub async fn run_forever(self, block_stream: CurrentBlockStream) -> ! {
+        let mut current_block = *block_stream.borrow();
+        let mut block_stream = into_stream(block_stream);
+        loop {
+            match self.update(current_block.number).await {
+                Ok(true) => {
+                    tracing::debug!(
+                        block = current_block.number,
+                        "analytics updater aggregated events"
+                    );
+                    // Don't wait until next block in case there are more events to aggregate.
+                    continue;
+                }
+                Ok(false) => {
+                    tracing::debug!(
+                        block = current_block.number,
+                        "analytics updater ran without update"
+                    );
+                }
+                Err(err) => {
+                    tracing::error!(?err, "analytics update task failed");
+                }
+            }
+            current_block = block_stream.next().await.expect("blockchains never end");
+        }
+    }
+
+    /// Aggregates the next batch of trades and settlements.
+    ///
+    /// Returns whether any events were aggregated.
+    async fn update(&self, current_block: u64) -> Result<bool> {
+        let reorg_safe_block: i64 = self
+            .finality
+            .last_final_block(&self.web3, current_block)
+            .await?
+            .try_into()
+            .context("convert block")?;
+        let trades = self
+            .update_trades(reorg_safe_block)
+            .await
+            .context("update trades")?;
+        let settlements = self
+            .update_settlements(reorg_safe_block)
+            .await
+            .context("update settlements")?;
+        Ok(trades || settlements)
+    }
+
+    async fn update_trades(&self, max_block_number: i64) -> Result<bool> {
+        let mut ex = self.db.0.begin().await.context("acquire DB connection")?;
+        let progress = database::analytics::progress(&mut ex, AnalyticsSource::Trades)
+            .await
+            .context("progress")?
+            .unwrap_or_default();
+        let trades =
+            database::analytics::fetch_trades(&mut ex, &progress, max_block_number, BATCH_SIZE)
+                .await
+                .context("fetch_trades")?;
+        let Some(last) = trades.last() else {
+            return Ok(false);
+        };
+        let last = EventIndex {
+            block_number: last.block_number,
+            log_index: last.log_index,
+        };
+
+        let days = self
+            .days(&mut ex, trades.iter().map(|trade| trade.block_number))
+            .await?;
+        for aggregate in aggregate_trades(trades, &days) {
+            database::analytics::add_trades(&mut ex, &aggregate)
+                .await
+                .context("add_trades")?;
+        }
+        database::analytics::save_progress(&mut ex, AnalyticsSource::Trades, &last)
+            .await
+            .context("save_progress")?;
+        ex.commit().await.context("commit")?;
+        Ok(true)
+    }
+
+    async fn update_settlements(&self, max_block_number: i64) -> Result<bool> {
+        let mut ex = self.db.0.begin().await.context("acquire DB connection")?;
+        let progress = database::analytics::progress(&mut ex, AnalyticsSource::Settlements)
+            .await
+            .context("progress")?
+            .unwrap_or_default();
+        let settlements = database::analytics::fetch_settlements(
+            &mut ex,
+            &progress,
+            max_block_number,
+            BATCH_SIZE,
+        )
+        .await
+        .context("fetch_settlements")?;
+        let Some(last) = settlements.last() else {
+            return Ok(false);
+        };
+        let last = EventIndex {
+            block_number: last.block_number,
+            log_index: last.log_index,
+        };
+
+        let days = self
+            .days(
+                &mut ex,
+                settlements.iter().map(|settlement| settlement.block_number),
+            )
+            .await?;
+        for aggregate in aggregate_settlements(settlements, &days) {
+            database::analytics::add_settlements(&mut ex, &aggregate)
+                .await
+                .context("add_settlements")?;
+        }
+        database::analytics::save_progress(&mut ex, AnalyticsSource::Settlements, &last)
+            .await
+            .context("save_progress")?;
+        ex.commit().await.context("commit")?;
+        Ok(true)
+    }
+
+    /// Returns the UTC day every block was mined in. Days of blocks that
+    /// haven't been aggregated before get fetched from the node and stored.
+    async fn days(
+        &self,
+        ex: &mut PgConnection,
+        blocks: impl Iterator<Item = i64>,
+    ) -> Result<HashMap<i64, NaiveDate>> {
+        let blocks = blocks
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let mut days = database::analytics::days(ex, &blocks)
+            .await
+            .context("days")?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        let missing = blocks
+            .into_iter()
+            .filter(|number| !days.contains_key(number))
+            .map(|number| async move {
+                let block = self
+                    .web3
+                    .eth()
+                    .block(BlockId::Number(BlockNumber::Number((number as u64).into())))
+                    .await
+                    .with_context(|| format!("get block {number}"))?
+                    .with_context(|| format!("no block {number}"))?;
+                let timestamp =
+                    i64::try_from(block.timestamp.as_u64()).context("convert timestamp")?;
+                let day = Utc
+                    .timestamp_opt(timestamp, 0)
+                    .single()
+                    .with_context(|| format!("invalid timestamp of block {number}"))?
+                    .date_naive();
+                Result::<_, anyhow::Error>::Ok((number, day))
+            });
+        let missing: Vec<_> = futures::stream::iter(missing)
+            .buffer_unordered(MAX_CONCURRENT_BLOCK_REQUESTS)
+            .try_collect()
+            .await?;
+        for (number, day) in missing {
+            database::analytics::save_day(ex, number, day)
+                .await
+                .context("save_day")?;
+            days.insert(number, day);
+        }
+        Ok(days)
+    }
+}
+
+/// Sums up the trades per day, owner, token pair and app code.
+fn aggregate_trades(trades: Vec<TradeRow>, days: &HashMap<i64, NaiveDate>) -> Vec<TradeAggregate> {
+    let mut aggregates = HashMap::<(NaiveDate, Address, Address, Address, String), _>::new();
+    for trade in trades {
+        let day = days[&trade.block_number];
+        let app_code = trade
+            .full_app_data
+            .as_deref()
+            .and_then(shared::app_data::app_code)
+            .unwrap_or_default();
+        let aggregate: &mut TradeAggregate = aggregates
+            .entry((
+                day,
+                trade.owner,
+                trade.sell_token,
+                trade.buy_token,
+                app_code.clone(),
+            ))
+            .or_insert_with(|| TradeAggregate {
+                day,
+                owner: trade.owner,
+                sell_token: trade.sell_token,
+                buy_token: trade.buy_token,
+                app_code,
+                ..Default::default()
+            });
+        aggregate.trades += 1;
+        aggregate.sell_amount += trade.sell_amount;
+        aggregate.buy_amount += trade.buy_amount;
+        aggregate.volume += trade.volume;
+        aggregate.surplus += trade.surplus;
+        aggregate.fee += trade.fee;
+    }
+    aggregates.into_values().collect()
+}
+
+/// Sums up the settlements per day and solver.
+fn aggregate_settlements(
+    settlements: Vec<SettlementRow>,
+    days: &HashMap<i64, NaiveDate>,
+) -> Vec<SettlementAggregate> {
+    let mut aggregates = HashMap::<(NaiveDate, Address), _>::new();
+    for settlement in settlements {
+        let day = days[&settlement.block_number];
+        let aggregate: &mut SettlementAggregate = aggregates
+            .entry((day, settlement.solver))
+            .or_insert_with(|| SettlementAggregate {
+                day,
+                solver: settlement.solver,
+                ..Default::default()
+            });
+        aggregate.settlements += 1;
+        aggregate.gas_used += settlement.gas_used;
+        aggregate.gas_cost += settlement.gas_cost;
+        aggregate.surplus += settlement.surplus;
+        aggregate.fee += settlement.fee;
+    }
+    aggregates.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, database::byte_array::ByteArray};
+
+    #[test]
+    fn aggregates_trades_per_day_pair_and_app_code() {
+        let monday = NaiveDate::from_ymd_opt(2023, 5, 1).unwrap();
+        let tuesday = NaiveDate::from_ymd_opt(2023, 5, 2).unwrap();
+        let days = HashMap::from([(1, monday), (2, monday), (3, tuesday)]);
+        let trade = |block_number, app_data: Option<&str>, volume: u32| TradeRow {
+            block_number,
+            owner: ByteArray([1; 20]),
+            sell_token: ByteArray([2; 20]),
+            buy_token: ByteArray([3; 20]),
+            full_app_data: app_data.map(|app_data| app_data.as_bytes().to_vec()),
+            volume: volume.into(),
+            ..Default::default()
+        };
+        let trades = vec![
+            trade(1, Some(r#"{"appCode":"CoW Swap"}"#), 1),
+            trade(2, Some(r#"{"appCode":"CoW Swap","version":"0.9.0"}"#), 2),
+            trade(2, Some("{}"), 4),
+            trade(3, None, 8),
+            trade(3, Some("{}"), 16),
+        ];
+
+        let mut aggregates = aggregate_trades(trades, &days)
+            .into_iter()
+            .map(|aggregate| {
+                (
+                    aggregate.day,
+                    aggregate.app_code,
+                    aggregate.trades,
+                    aggregate.volume,
+                )
+            })
+            .collect::<Vec<_>>();
+        aggregates.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+        assert_eq!(
+            aggregates,
+            vec![
+                (monday, "".to_string(), 1, 4.into()),
+                (monday, "CoW Swap".to_string(), 2, 3.into()),
+                (tuesday, "".to_string(), 2, 24.into()),
+            ]
+        );
+    }
+}
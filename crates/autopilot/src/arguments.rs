@@ -1,6 +1,7 @@
 use {
     primitive_types::{H160, U256},
     shared::{
+        address_list::AddressListSource,
         arguments::{display_list, display_option, ExternalSolver},
         bad_token::token_owner_finder,
         http_client,
@@ -75,6 +76,22 @@ pub struct Arguments {
     )]
     pub token_quality_cache_expiry: Duration,
 
+    /// The amount of time in seconds a classification of a token into good or
+    /// bad is stored in the database for. Stored classifications are shared
+    /// between services and survive restarts.
+    #[clap(
+        long,
+        env,
+        default_value = "86400",
+        value_parser = shared::arguments::duration_from_seconds,
+    )]
+    pub token_quality_db_expiry: Duration,
+
+    /// Support tokens that take a fee on transfer instead of treating them as
+    /// bad tokens. Their transfer fee gets communicated to the solvers.
+    #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub allow_taxed_tokens: bool,
+
     /// The number of pairs that are automatically updated in the pool cache.
     #[clap(long, env, default_value = "200")]
     pub pool_cache_lru_size: NonZeroUsize,
@@ -106,6 +123,27 @@ pub struct Arguments {
     #[clap(long, env, use_value_delimiter = true)]
     pub banned_users: Vec<H160>,
 
+    /// Where to periodically load additional unsupported tokens from. Either
+    /// `database` (the `deny_lists` table), a path to a local file or an
+    /// http(s) URL serving a JSON array of token addresses.
+    #[clap(long, env)]
+    pub unsupported_tokens_source: Option<AddressListSource>,
+
+    /// Where to periodically load additional banned users from. Accepts the
+    /// same values as `unsupported_tokens_source`.
+    #[clap(long, env)]
+    pub banned_users_source: Option<AddressListSource>,
+
+    /// How often in seconds the unsupported tokens and banned users get
+    /// reloaded from their sources.
+    #[clap(
+        long,
+        env,
+        default_value = "60",
+        value_parser = shared::arguments::duration_from_seconds,
+    )]
+    pub deny_list_update_interval: Duration,
+
     /// If the auction hasn't been updated in this amount of time the pod fails
     /// the liveness check. Expects a value in seconds.
     #[clap(
@@ -203,6 +241,51 @@ pub struct Arguments {
         value_parser = shared::arguments::duration_from_seconds,
     )]
     pub solve_deadline: Duration,
+
+    /// How often to check for order events that webhooks need to be notified
+    /// about and to send out due deliveries.
+    #[clap(
+        long,
+        env,
+        default_value = "5",
+        value_parser = shared::arguments::duration_from_seconds,
+    )]
+    pub webhook_update_interval: Duration,
+
+    /// How long to wait before notifying webhooks about an order event. This
+    /// gives trades and order events that were committed late time to get
+    /// indexed.
+    #[clap(
+        long,
+        env,
+        default_value = "60",
+        value_parser = shared::arguments::duration_from_seconds,
+    )]
+    pub webhook_event_delay: Duration,
+
+    /// How often a webhook delivery gets attempted before it ends up in the
+    /// dead letters.
+    #[clap(long, env, default_value = "10")]
+    pub webhook_max_attempts: u32,
+
+    /// How long to wait before retrying a failed webhook delivery the first
+    /// time. The delay doubles with every failed attempt.
+    #[clap(
+        long,
+        env,
+        default_value = "10",
+        value_parser = shared::arguments::duration_from_seconds,
+    )]
+    pub webhook_min_backoff: Duration,
+
+    /// The longest time to wait before retrying a failed webhook delivery.
+    #[clap(
+        long,
+        env,
+        default_value = "3600",
+        value_parser = shared::arguments::duration_from_seconds,
+    )]
+    pub webhook_max_backoff: Duration,
 }
 
 impl std::fmt::Display for Arguments {
@@ -229,6 +312,12 @@ impl std::fmt::Display for Arguments {
             "token_quality_cache_expiry: {:?}",
             self.token_quality_cache_expiry
         )?;
+        writeln!(
+            f,
+            "token_quality_db_expiry: {:?}",
+            self.token_quality_db_expiry
+        )?;
+        writeln!(f, "allow_taxed_tokens: {}", self.allow_taxed_tokens)?;
         writeln!(f, "pool_cache_lru_size: {}", self.pool_cache_lru_size)?;
         writeln!(
             f,
@@ -241,6 +330,17 @@ impl std::fmt::Display for Arguments {
             self.min_order_validity_period
         )?;
         writeln!(f, "banned_users: {:?}", self.banned_users)?;
+        writeln!(
+            f,
+            "unsupported_tokens_source: {:?}",
+            self.unsupported_tokens_source
+        )?;
+        writeln!(f, "banned_users_source: {:?}", self.banned_users_source)?;
+        writeln!(
+            f,
+            "deny_list_update_interval: {:?}",
+            self.deny_list_update_interval
+        )?;
         writeln!(f, "max_auction_age: {:?}", self.max_auction_age)?;
         writeln!(
             f,
@@ -270,6 +370,15 @@ impl std::fmt::Display for Arguments {
         writeln!(f, "score_cap: {}", self.score_cap)?;
         display_option(f, "shadow", &self.shadow)?;
         writeln!(f, "solve_deadline: {:?}", self.solve_deadline)?;
+        writeln!(
+            f,
+            "webhook_update_interval: {:?}",
+            self.webhook_update_interval
+        )?;
+        writeln!(f, "webhook_event_delay: {:?}", self.webhook_event_delay)?;
+        writeln!(f, "webhook_max_attempts: {}", self.webhook_max_attempts)?;
+        writeln!(f, "webhook_min_backoff: {:?}", self.webhook_min_backoff)?;
+        writeln!(f, "webhook_max_backoff: {:?}", self.webhook_max_backoff)?;
         Ok(())
     }
 }
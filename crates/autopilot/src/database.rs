@@ -2,6 +2,7 @@ mod auction;
 pub mod auction_prices;
 pub mod auction_transaction;
 pub mod competition;
+pub mod deny_lists;
 pub mod ethflow_events;
 mod events;
 pub mod on_settlement_event_updater;
@@ -10,6 +11,8 @@ pub mod order_events;
 pub mod orders;
 mod quotes;
 pub mod recent_settlements;
+mod reorgs;
+pub mod token_quality;
 
 use {
     sqlx::{PgConnection, PgPool},
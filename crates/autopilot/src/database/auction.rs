@@ -3,13 +3,19 @@ use {
     anyhow::{Context, Result},
     database::auction::AuctionId,
     futures::{StreamExt, TryStreamExt},
-    model::{auction::Auction, order::Order},
-    std::ops::DerefMut,
+    model::{
+        auction::Auction,
+        order::{AllOrdersCancellation, Order},
+    },
+    primitive_types::H160,
+    std::{collections::HashMap, ops::DerefMut},
 };
 
 pub struct SolvableOrders {
     pub orders: Vec<Order>,
     pub latest_settlement_block: u64,
+    /// The latest cancellations of all orders by owner.
+    pub cancellations: HashMap<H160, Vec<AllOrdersCancellation>>,
 }
 use {
     chrono::{DateTime, Utc},
@@ -76,7 +82,7 @@ impl Postgres {
 
         let mut ex = self.0.begin().await?;
         // Set the transaction isolation level to REPEATABLE READ
-        // so the both SELECT queries below are executed in the same database snapshot
+        // so all SELECT queries below are executed in the same database snapshot
         // taken at the moment before the first query is executed.
         sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
             .execute(ex.deref_mut())
@@ -90,9 +96,25 @@ impl Postgres {
             .await?;
         let latest_settlement_block =
             database::orders::latest_settlement_block(&mut ex).await? as u64;
+        let mut cancellations = HashMap::<_, Vec<_>>::new();
+        for cancellation in database::owner_order_cancellations::fetch_latest(&mut ex).await? {
+            cancellations
+                .entry(H160(cancellation.owner.0))
+                .or_default()
+                .push(AllOrdersCancellation {
+                    cancelled_before: cancellation
+                        .cancelled_before
+                        .timestamp()
+                        .try_into()
+                        .context("cancelled_before is not a u32 timestamp")?,
+                    sell_token: cancellation.sell_token.map(|token| H160(token.0)),
+                    buy_token: cancellation.buy_token.map(|token| H160(token.0)),
+                });
+        }
         Ok(SolvableOrders {
             orders,
             latest_settlement_block,
+            cancellations,
         })
     }
 
@@ -45,6 +45,9 @@ pub struct OrderExecution {
 pub struct Competition {
     pub auction_id: AuctionId,
     pub winner: H160,
+    /// The account the winner submits the settlement from. Solvers can submit
+    /// from additional accounts, so this isn't necessarily the winner.
+    pub submission_address: H160,
     pub winning_score: U256,
     pub reference_score: U256,
     /// Addresses to which the CIP20 participation rewards will be payed out.
@@ -109,6 +112,7 @@ impl super::Postgres {
                     .competition_simulation_block
                     .try_into()
                     .context("convert simulation block")?,
+                submission_address: ByteArray(competition.submission_address.0),
             },
         )
         .await
@@ -0,0 +1,30 @@
+use {
+    anyhow::Result,
+    database::deny_lists::DenyListKind,
+    primitive_types::H160,
+    shared::address_list::AddressListFetching,
+    std::collections::HashSet,
+};
+
+/// Loads the addresses of one of the deny lists stored in the database.
+pub struct DenyList {
+    pub db: super::Postgres,
+    pub kind: DenyListKind,
+}
+
+#[async_trait::async_trait]
+impl AddressListFetching for DenyList {
+    async fn fetch(&self) -> Result<HashSet<H160>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["fetch_deny_list"])
+            .start_timer();
+
+        let mut ex = self.db.0.acquire().await?;
+        Ok(database::deny_lists::fetch(&mut ex, self.kind)
+            .await?
+            .into_iter()
+            .map(|address| H160(address.0))
+            .collect())
+    }
+}
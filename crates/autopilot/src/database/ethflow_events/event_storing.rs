@@ -5,7 +5,7 @@ use {
     anyhow::Result,
     database::ethflow_orders::Refund,
     ethrpc::current_block::RangeInclusive,
-    shared::event_handling::EventStoring,
+    shared::event_handling::{EventStoring, Reorg},
 };
 
 fn get_refunds(events: Vec<ethcontract::Event<EthFlowEvent>>) -> Result<Vec<Refund>> {
@@ -62,6 +62,7 @@ impl EventStoring<EthFlowEvent> for Postgres {
         &mut self,
         events: Vec<ethcontract::Event<EthFlowEvent>>,
         range: RangeInclusive<u64>,
+        reorg: Option<Reorg>,
     ) -> Result<()> {
         let refunds = get_refunds(events)?;
         let _timer = crate::database::Metrics::get()
@@ -76,6 +77,9 @@ impl EventStoring<EthFlowEvent> for Postgres {
         )
         .await?;
         database::ethflow_orders::insert_refund_tx_hashes(&mut ex, &refunds).await?;
+        if let Some(reorg) = reorg {
+            Postgres::insert_reorg(&mut ex, "ethflow_refunds", reorg).await?;
+        }
         ex.commit().await?;
         Ok(())
     }
@@ -18,7 +18,7 @@ use {
     ethcontract::{Event as EthContractEvent, EventMetadata},
     ethrpc::current_block::RangeInclusive,
     number::conversions::u256_to_big_decimal,
-    shared::event_handling::EventStoring,
+    shared::event_handling::{EventStoring, Reorg},
     std::convert::TryInto,
 };
 
@@ -78,6 +78,7 @@ impl EventStoring<ContractEvent> for Postgres {
         &mut self,
         events: Vec<EthContractEvent<ContractEvent>>,
         range: RangeInclusive<u64>,
+        reorg: Option<Reorg>,
     ) -> Result<()> {
         let _timer = super::Metrics::get()
             .database_queries
@@ -86,12 +87,18 @@ impl EventStoring<ContractEvent> for Postgres {
 
         let events = contract_to_db_events(events)?;
         let mut transaction = self.0.begin().await?;
+        database::analytics::rewind(&mut transaction, *range.start() as i64)
+            .await
+            .context("rewind analytics failed")?;
         database::events::delete(&mut transaction, *range.start() as i64)
             .await
             .context("delete_events failed")?;
         database::events::append(&mut transaction, events.as_slice())
             .await
             .context("insert_events failed")?;
+        if let Some(reorg) = reorg {
+            Self::insert_reorg(&mut transaction, "settlement_contract", reorg).await?;
+        }
         transaction.commit().await.context("commit")?;
         Ok(())
     }
@@ -94,6 +94,17 @@ impl super::Postgres {
             )
             .await
             .context("failed to insert auction_transaction")?;
+            // A settlement that gets processed again after a reorg finds its
+            // auction already linked to its own transaction.
+            let linked = insert_succesful
+                || !database::auction_transaction::linked_to_other_transaction(
+                    ex,
+                    auction_data.auction_id.assume_verified(),
+                    &ByteArray(settlement_update.tx_from.0),
+                    settlement_update.tx_nonce,
+                )
+                .await
+                .context("linked_to_other_transaction")?;
 
             // It's always fine to store a settlement observation.
             database::settlement_observations::insert(
@@ -110,7 +121,7 @@ impl super::Postgres {
             .await
             .context("insert_settlement_observations")?;
 
-            if insert_succesful || matches!(auction_data.auction_id, AuctionId::Centralized(_)) {
+            if linked || matches!(auction_data.auction_id, AuctionId::Centralized(_)) {
                 // update order executions for orders with solver computed fees (limit orders)
                 // for limit orders, fee is called surplus_fee and is determined by the solver
                 // therefore, when transaction is settled onchain we calculate the fee and save
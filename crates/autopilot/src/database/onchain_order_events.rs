@@ -41,7 +41,7 @@ use {
             sell_token_source_into,
             signing_scheme_into,
         },
-        event_handling::EventStoring,
+        event_handling::{EventStoring, Reorg},
         order_quoting::{OrderQuoting, Quote, QuoteSearchParameters},
         order_validation::{
             convert_signing_scheme_into_quote_signing_scheme,
@@ -155,6 +155,7 @@ impl<T: Sync + Send + Clone, W: Sync + Send + Clone> EventStoring<ContractEvent>
         &mut self,
         events: Vec<EthContractEvent<ContractEvent>>,
         range: RangeInclusive<u64>,
+        reorg: Option<Reorg>,
     ) -> Result<()> {
         let order_placement_events = events
             .clone()
@@ -222,6 +223,9 @@ impl<T: Sync + Send + Clone, W: Sync + Send + Clone> EventStoring<ContractEvent>
         database::orders::insert_orders_and_ignore_conflicts(&mut transaction, orders.as_slice())
             .await
             .context("insert_orders failed")?;
+        if let Some(reorg) = reorg {
+            Postgres::insert_reorg(&mut transaction, "onchain_orders", reorg).await?;
+        }
         transaction.commit().await.context("commit")?;
 
         for order in &invalided_order_uids {
@@ -501,6 +505,16 @@ async fn parse_general_onchain_order_placement_data<'a>(
                     sell_amount: u256_to_big_decimal(&quote.sell_amount),
                     buy_amount: u256_to_big_decimal(&quote.buy_amount),
                     solver: ByteArray(quote.data.solver.0),
+                    estimator: quote
+                        .data
+                        .estimator
+                        .as_ref()
+                        .map(|estimator| estimator.name.clone()),
+                    estimator_latency_ms: quote
+                        .data
+                        .estimator
+                        .as_ref()
+                        .map(|estimator| estimator.latency.as_millis() as i64),
                 }),
                 Err(err) => {
                     let err_label = err.to_metrics_label();
@@ -1412,6 +1426,8 @@ mod test {
             sell_amount: u256_to_big_decimal(&quote.sell_amount),
             buy_amount: u256_to_big_decimal(&quote.buy_amount),
             solver: ByteArray(quote.data.solver.0),
+            estimator: None,
+            estimator_latency_ms: None,
         };
         assert_eq!(result.1, vec![Some(expected_quote)]);
         assert_eq!(
@@ -0,0 +1,29 @@
+use {
+    anyhow::{Context, Result},
+    database::byte_array::ByteArray,
+    shared::event_handling::Reorg,
+    sqlx::PgConnection,
+};
+
+impl super::Postgres {
+    /// Records a reorg that caused the events of `source` to be rolled back.
+    /// Should be called in the transaction rolling back the events.
+    pub async fn insert_reorg(ex: &mut PgConnection, source: &str, reorg: Reorg) -> Result<()> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["insert_reorg"])
+            .start_timer();
+
+        let reorg = database::reorgs::Reorg {
+            detected_at: chrono::Utc::now(),
+            source: source.to_string(),
+            reorged_block_number: reorg.reorged_block.0.try_into().context("block number")?,
+            reorged_block_hash: ByteArray(reorg.reorged_block.1 .0),
+            replaced_from_block: reorg.replaced_from.try_into().context("block number")?,
+            current_block_number: reorg.current_block.0.try_into().context("block number")?,
+            current_block_hash: ByteArray(reorg.current_block.1 .0),
+        };
+        database::reorgs::insert(ex, &reorg).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use {
+    anyhow::Result,
+    chrono::{DateTime, Utc},
+    database::byte_array::ByteArray,
+    primitive_types::H160,
+    shared::bad_token::persisted::{StoredTokenQuality, TokenQualityStoring},
+};
+
+#[async_trait::async_trait]
+impl TokenQualityStoring for super::Postgres {
+    async fn get(
+        &self,
+        token: H160,
+        allow_taxed_tokens: bool,
+        now: DateTime<Utc>,
+    ) -> Result<Option<StoredTokenQuality>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["get_token_quality"])
+            .start_timer();
+
+        let mut ex = self.0.acquire().await?;
+        database::token_quality::fetch(&mut ex, &ByteArray(token.0), allow_taxed_tokens, now)
+            .await?
+            .map(StoredTokenQuality::from_row)
+            .transpose()
+    }
+
+    async fn save(
+        &self,
+        token: H160,
+        allow_taxed_tokens: bool,
+        quality: StoredTokenQuality,
+    ) -> Result<()> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["save_token_quality"])
+            .start_timer();
+
+        let row = quality.into_row(token, allow_taxed_tokens)?;
+        let mut ex = self.0.acquire().await?;
+        database::token_quality::upsert(&mut ex, &row).await?;
+        Ok(())
+    }
+}
@@ -1,23 +1,30 @@
 use {
-    crate::driver_model::{reveal, settle, solve},
+    crate::driver_model::{health, reveal, settle, solve},
     anyhow::{anyhow, Context, Result},
     reqwest::Client,
-    shared::{arguments::ExternalSolver, http_client::response_body_with_size_limit},
+    shared::{
+        arguments::ExternalSolver,
+        http_client::response_body_with_size_limit,
+        request_signing::{Secret, Signer},
+    },
     std::time::Duration,
     url::Url,
 };
 
 const RESPONSE_SIZE_LIMIT: usize = 10_000_000;
 const RESPONSE_TIME_LIMIT: Duration = Duration::from_secs(60);
+/// Checking the health of a driver should not noticeably delay the auction.
+const HEALTH_TIME_LIMIT: Duration = Duration::from_secs(1);
 
 pub struct Driver {
     pub name: String,
     pub url: Url,
     client: Client,
+    signer: Option<Signer>,
 }
 
 impl Driver {
-    pub fn new(driver: ExternalSolver) -> Self {
+    pub fn new(driver: ExternalSolver, auth_secret: Option<Secret>) -> Self {
         Self {
             name: driver.name,
             url: driver.url,
@@ -25,6 +32,35 @@ impl Driver {
                 .timeout(RESPONSE_TIME_LIMIT)
                 .build()
                 .unwrap(),
+            signer: auth_secret.map(Signer::new),
+        }
+    }
+
+    pub async fn health(&self) -> Result<health::Response> {
+        let mut response = self
+            .client
+            .get(shared::url::join(&self.url, "health"))
+            .timeout(HEALTH_TIME_LIMIT)
+            .send()
+            .await
+            .context("send")?
+            .error_for_status()?;
+        let body = response_body_with_size_limit(&mut response, RESPONSE_SIZE_LIMIT)
+            .await
+            .context("body")?;
+        serde_json::from_slice(&body).context("bad json")
+    }
+
+    /// Whether the driver reports its solver as available. Drivers that can't
+    /// report their health are assumed to be available, so that failing to
+    /// fetch the health doesn't exclude them from the auction.
+    pub async fn is_available(&self) -> bool {
+        match self.health().await {
+            Ok(health) => health.available,
+            Err(err) => {
+                tracing::debug!(?err, driver = %self.name, "failed to fetch driver health");
+                true
+            }
         }
     }
 
@@ -60,7 +96,17 @@ impl Driver {
             body=%serde_json::to_string_pretty(request).unwrap(),
             "request",
         );
-        let mut request = self.client.post(url.clone()).json(request);
+        let body = serde_json::to_vec(request).context("serialize")?;
+        let mut request = self
+            .client
+            .post(url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(signer) = &self.signer {
+            for (name, value) in signer.headers("POST", &url, &body) {
+                request = request.header(name, value);
+            }
+        }
+        let mut request = request.body(body);
 
         if let Some(timeout) = timeout {
             request = request.timeout(timeout);
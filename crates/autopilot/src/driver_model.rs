@@ -3,6 +3,16 @@
 
 // TODO: parse proper error type with kind and description, that driver returns.
 
+pub mod health {
+    use serde::Deserialize;
+
+    #[derive(Clone, Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Response {
+        pub available: bool,
+    }
+}
+
 pub mod quote {
     use {
         number::serialization::HexOrDecimalU256,
@@ -83,6 +93,9 @@ pub mod solve {
         #[serde_as(as = "Option<HexOrDecimalU256>")]
         pub price: Option<U256>,
         pub trusted: bool,
+        /// The fee in basis points that the token takes on every transfer.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub transfer_fee_bps: Option<u32>,
     }
 
     #[serde_as]
@@ -160,6 +173,7 @@ pub mod solve {
 pub mod reveal {
     use {
         model::{bytes_hex, order::OrderUid},
+        primitive_types::H160,
         serde::{Deserialize, Serialize},
         serde_with::serde_as,
     };
@@ -188,13 +202,17 @@ pub mod reveal {
     pub struct Response {
         pub orders: Vec<OrderUid>,
         pub calldata: Calldata,
+        /// The account the settlement will be submitted from. Not reported by
+        /// older drivers.
+        #[serde(default)]
+        pub account: Option<H160>,
     }
 }
 
 pub mod settle {
     use {
         model::bytes_hex,
-        primitive_types::H256,
+        primitive_types::{H160, H256},
         serde::{Deserialize, Serialize},
         serde_with::serde_as,
     };
@@ -214,6 +232,10 @@ pub mod settle {
     pub struct Response {
         pub calldata: Calldata,
         pub tx_hash: H256,
+        /// The account which submitted the transaction. Not reported by older
+        /// drivers.
+        #[serde(default)]
+        pub account: Option<H160>,
     }
 
     #[serde_as]
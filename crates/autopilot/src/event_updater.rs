@@ -3,7 +3,7 @@ use {
     contracts::gpv2_settlement,
     ethrpc::current_block::{BlockNumberHash, BlockRetrieving},
     shared::{
-        event_handling::{EventHandler, EventRetrieving, EventStoring},
+        event_handling::{EventHandler, EventRetrieving, EventStoring, Finality},
         impl_event_retrieving,
         maintenance::Maintaining,
     },
@@ -35,13 +35,12 @@ where
         db: Database,
         block_retriever: Arc<dyn BlockRetrieving>,
         start_sync_at_block: Option<BlockNumberHash>,
+        finality: Finality,
     ) -> Self {
-        Self(Mutex::new(EventHandler::new(
-            block_retriever,
-            contract,
-            db,
-            start_sync_at_block,
-        )))
+        Self(Mutex::new(
+            EventHandler::new(block_retriever, contract, db, start_sync_at_block)
+                .with_finality(finality),
+        ))
     }
 
     /// Creates a new event updater.
@@ -56,6 +55,7 @@ where
         db: Database,
         block_retriever: Arc<dyn BlockRetrieving>,
         start_sync_at_block: BlockNumberHash,
+        finality: Finality,
     ) -> Result<Self> {
         Ok(Self(Mutex::new(
             EventHandler::new_skip_blocks_before(
@@ -64,7 +64,8 @@ where
                 db,
                 start_sync_at_block,
             )
-            .await?,
+            .await?
+            .with_finality(finality),
         )))
     }
 }
@@ -1,3 +1,4 @@
+pub mod analytics_updater;
 pub mod arguments;
 pub mod database;
 pub mod decoded_settlement;
@@ -10,5 +11,6 @@ pub mod run;
 pub mod run_loop;
 pub mod shadow;
 pub mod solvable_orders;
+pub mod webhooks;
 
 pub use self::run::{run, start};
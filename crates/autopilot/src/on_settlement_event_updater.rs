@@ -29,6 +29,11 @@
 // After a transaction is mined we calculate the surplus and fees for each
 // transaction and insert them into the database (settlement_observations
 // table).
+//
+// Observations are only computed for settlements in final blocks (see
+// `Finality`). If a reorg still affects an observed settlement, the event
+// handler replaces its event, which also deletes the observation, and the
+// settlement gets processed again here.
 
 use {
     crate::{
@@ -47,7 +52,7 @@ use {
     },
     futures::StreamExt,
     primitive_types::{H160, H256},
-    shared::{event_handling::MAX_REORG_BLOCK_COUNT, external_prices::ExternalPrices},
+    shared::{event_handling::Finality, external_prices::ExternalPrices},
     sqlx::PgConnection,
     web3::types::{Transaction, TransactionId},
 };
@@ -57,6 +62,7 @@ pub struct OnSettlementEventUpdater {
     pub contract: GPv2Settlement,
     pub native_token: H160,
     pub db: Postgres,
+    pub finality: Finality,
 }
 
 impl OnSettlementEventUpdater {
@@ -91,9 +97,10 @@ impl OnSettlementEventUpdater {
     ///
     /// Returns whether an update was performed.
     async fn update(&self, current_block: u64) -> Result<bool> {
-        let reorg_safe_block: i64 = current_block
-            .checked_sub(MAX_REORG_BLOCK_COUNT)
-            .context("no reorg safe block")?
+        let reorg_safe_block: i64 = self
+            .finality
+            .last_final_block(&self.web3, current_block)
+            .await?
             .try_into()
             .context("convert block")?;
 
@@ -119,6 +126,15 @@ impl OnSettlementEventUpdater {
             .await
             .with_context(|| format!("get tx {hash:?}"))?
             .with_context(|| format!("no tx {hash:?}"))?;
+        // The event is stale if the transaction is no longer included in the
+        // block the event was indexed from. Wait for the event handler to
+        // replace it instead of storing data for a reorged settlement.
+        let tx_block = transaction.block_number.map(|block| block.as_u64() as i64);
+        anyhow::ensure!(
+            tx_block == Some(event.block_number),
+            "settlement event of tx {hash:?} at block {} got reorged, tx now in block {tx_block:?}",
+            event.block_number,
+        );
         let tx_from = transaction
             .from
             .with_context(|| format!("no from {hash:?}"))?;
@@ -259,9 +275,21 @@ impl OnSettlementEventUpdater {
             }
         };
 
+        let tx_nonce: i64 = tx
+            .nonce
+            .try_into()
+            .map_err(|err| anyhow!("{}", err))
+            .context("convert nonce")?;
         let score = database::settlement_scores::fetch(ex, auction_id).await?;
-        let data_already_recorded =
-            database::auction_transaction::data_exists(ex, auction_id).await?;
+        // Data recorded for this very transaction (before a reorg caused it to
+        // be processed again) gets recomputed.
+        let data_already_recorded = database::auction_transaction::linked_to_other_transaction(
+            ex,
+            auction_id,
+            &ByteArray(tx_from.0),
+            tx_nonce,
+        )
+        .await?;
         match (score, data_already_recorded) {
             (None, _) => {
                 tracing::debug!(
@@ -270,19 +298,20 @@ impl OnSettlementEventUpdater {
                 );
                 Ok(None)
             }
-            (Some(score), _) if score.winner.0 != tx_from.0 => {
+            (Some(score), _) if score.submission_address.0 != tx_from.0 => {
                 tracing::warn!(
                     auction_id,
                     ?tx_from,
                     winner = ?score.winner,
-                    "solution submitted by solver other than the winner"
+                    submission_address = ?score.submission_address,
+                    "solution submitted from an account other than the winner's"
                 );
                 Ok(None)
             }
             (Some(_), true) => {
                 tracing::warn!(
                     auction_id,
-                    "settlement data already recorded for this auction"
+                    "settlement data already recorded for another tx of this auction"
                 );
                 Ok(None)
             }
@@ -316,6 +345,7 @@ mod tests {
             db,
             native_token,
             contract,
+            finality: Finality::ReorgDepth,
         };
 
         assert!(!updater.update(15875900).await.unwrap());
@@ -2,6 +2,7 @@ use {
     crate::{
         arguments::Arguments,
         database::{
+            deny_lists::DenyList,
             ethflow_events::event_retriever::EthFlowRefundRetriever,
             onchain_order_events::{
                 ethflow_events::{determine_ethflow_indexing_start, EthFlowOnchainOrderParser},
@@ -19,16 +20,19 @@ use {
     },
     clap::Parser,
     contracts::{BalancerV2Vault, IUniswapV3Factory, WETH9},
+    database::deny_lists::DenyListKind,
     ethcontract::{errors::DeployError, BlockNumber},
     ethrpc::current_block::block_number_to_block_number_hash,
     futures::StreamExt,
     model::DomainSeparator,
     shared::{
         account_balances,
+        address_list::{AddressListConfiguration, AutoUpdatingAddressList},
         bad_token::{
             cache::CachingDetector,
             instrumented::InstrumentedBadTokenDetectorExt,
             list_based::{ListBasedDetector, UnknownTokenStrategy},
+            persisted::PersistedDetector,
             token_owner_finder,
             trace_call::TraceCallDetector,
         },
@@ -240,7 +244,33 @@ pub async fn run(args: Arguments) {
     let mut allowed_tokens = args.allowed_tokens.clone();
     allowed_tokens.extend(base_tokens.tokens().iter().copied());
     allowed_tokens.push(model::order::BUY_ETH_ADDRESS);
-    let unsupported_tokens = args.unsupported_tokens.clone();
+    let deny_list = |name, hardcoded, source, kind| {
+        let db = db.clone();
+        AutoUpdatingAddressList::from_configuration(
+            AddressListConfiguration {
+                name,
+                hardcoded,
+                source,
+                client: http_factory.create(),
+                update_interval: args.deny_list_update_interval,
+            },
+            move || Arc::new(DenyList { db, kind }),
+        )
+    };
+    let unsupported_tokens = deny_list(
+        "unsupported_tokens",
+        args.unsupported_tokens.clone(),
+        args.unsupported_tokens_source.clone(),
+        DenyListKind::UnsupportedToken,
+    )
+    .await;
+    let banned_users = deny_list(
+        "banned_users",
+        args.banned_users.clone(),
+        args.banned_users_source.clone(),
+        DenyListKind::BannedUser,
+    )
+    .await;
 
     let finder = token_owner_finder::init(
         &args.token_owner_finder,
@@ -257,16 +287,22 @@ pub async fn run(args: Arguments) {
 
     let trace_call_detector = args.tracing_node_url.as_ref().map(|tracing_node_url| {
         Box::new(CachingDetector::new(
-            Box::new(TraceCallDetector {
-                web3: shared::ethrpc::web3(
-                    &args.shared.ethrpc,
-                    &http_factory,
-                    tracing_node_url,
-                    "trace",
-                ),
-                finder,
-                settlement_contract: settlement_contract.address(),
-            }),
+            Box::new(PersistedDetector::new(
+                Box::new(TraceCallDetector {
+                    web3: shared::ethrpc::web3(
+                        &args.shared.ethrpc,
+                        &http_factory,
+                        tracing_node_url,
+                        "trace",
+                    ),
+                    finder,
+                    settlement_contract: settlement_contract.address(),
+                    allow_taxed_tokens: args.allow_taxed_tokens,
+                }),
+                Arc::new(db.clone()),
+                args.token_quality_db_expiry,
+                args.allow_taxed_tokens,
+            )),
             args.token_quality_cache_expiry,
         ))
     });
@@ -442,6 +478,7 @@ pub async fn run(args: Arguments) {
         db.clone(),
         block_retriever.clone(),
         skip_event_sync_start,
+        args.shared.finality,
     ));
     let mut maintainers: Vec<Arc<dyn Maintaining>> =
         vec![pool_fetcher.clone(), event_updater, Arc::new(db.clone())];
@@ -508,6 +545,7 @@ pub async fn run(args: Arguments) {
                 db.clone(),
                 block_retriever.clone(),
                 start_block,
+                args.shared.finality,
             )
             .await
             .unwrap(),
@@ -532,6 +570,7 @@ pub async fn run(args: Arguments) {
                 onchain_order_event_parser,
                 block_retriever,
                 start_block,
+                args.shared.finality,
             )
             .await
             .expect("Should be able to initialize event updater. Database read issues?"),
@@ -554,7 +593,7 @@ pub async fn run(args: Arguments) {
     let solvable_orders_cache = SolvableOrdersCache::new(
         args.min_order_validity_period,
         db.clone(),
-        args.banned_users.iter().copied().collect(),
+        banned_users,
         balance_fetcher.clone(),
         bad_token_detector.clone(),
         current_block_stream.clone(),
@@ -584,6 +623,7 @@ pub async fn run(args: Arguments) {
             contract: settlement_contract,
             native_token: native_token.address(),
             db: db.clone(),
+            finality: args.shared.finality,
         };
     tokio::task::spawn(
         on_settlement_event_updater
@@ -591,6 +631,38 @@ pub async fn run(args: Arguments) {
             .instrument(tracing::info_span!("on_settlement_event_updater")),
     );
 
+    let analytics_updater = crate::analytics_updater::AnalyticsUpdater {
+        web3: web3.clone(),
+        db: db.clone(),
+        finality: args.shared.finality,
+    };
+    tokio::task::spawn(
+        analytics_updater
+            .run_forever(current_block_stream.clone())
+            .instrument(tracing::info_span!("analytics_updater")),
+    );
+
+    if let Some(signing_key) = args.shared.webhook_signing_key.clone() {
+        let webhooks = crate::webhooks::Webhooks {
+            db: db.clone(),
+            client: http_factory
+                .configure(|builder| builder.redirect(reqwest::redirect::Policy::none())),
+            signing_key,
+            config: crate::webhooks::Config {
+                update_interval: args.webhook_update_interval,
+                event_delay: args.webhook_event_delay,
+                max_attempts: args.webhook_max_attempts,
+                min_backoff: args.webhook_min_backoff,
+                max_backoff: args.webhook_max_backoff,
+            },
+        };
+        tokio::task::spawn(
+            webhooks
+                .run_forever()
+                .instrument(tracing::info_span!("webhooks")),
+        );
+    }
+
     if args.enable_colocation {
         if args.drivers.is_empty() {
             panic!("colocation is enabled but no drivers are configured");
@@ -609,7 +681,11 @@ pub async fn run(args: Arguments) {
         let run = RunLoop {
             solvable_orders_cache,
             database: db,
-            drivers: args.drivers.into_iter().map(Driver::new).collect(),
+            drivers: args
+                .drivers
+                .into_iter()
+                .map(|driver| Driver::new(driver, args.shared.driver_auth_secret.clone()))
+                .collect(),
             current_block: current_block_stream,
             web3,
             network_block_interval: network_time_between_blocks,
@@ -619,6 +695,7 @@ pub async fn run(args: Arguments) {
             score_cap: args.score_cap,
             max_settlement_transaction_wait: args.max_settlement_transaction_wait,
             solve_deadline: args.solve_deadline,
+            bad_token_detector,
         };
         run.run_forever().await;
         unreachable!("run loop exited");
@@ -639,7 +716,11 @@ async fn shadow_mode(args: Arguments) -> ! {
     if args.drivers.is_empty() {
         panic!("shadow mode is enabled but no drivers are configured");
     }
-    let drivers = args.drivers.into_iter().map(Driver::new).collect();
+    let drivers = args
+        .drivers
+        .into_iter()
+        .map(|driver| Driver::new(driver, args.shared.driver_auth_secret.clone()))
+        .collect();
 
     let trusted_tokens = {
         let web3 = shared::ethrpc::web3(
@@ -32,9 +32,9 @@ use {
     number::nonzero::U256 as NonZeroU256,
     primitive_types::{H160, U256},
     rand::seq::SliceRandom,
-    shared::{remaining_amounts, token_list::AutoUpdatingTokenList},
+    shared::{bad_token::BadTokenDetecting, remaining_amounts, token_list::AutoUpdatingTokenList},
     std::{
-        collections::{BTreeMap, HashSet},
+        collections::{BTreeMap, HashMap, HashSet},
         sync::Arc,
         time::{Duration, Instant},
     },
@@ -54,6 +54,7 @@ pub struct RunLoop {
     pub score_cap: U256,
     pub max_settlement_transaction_wait: Duration,
     pub solve_deadline: Duration,
+    pub bad_token_detector: Arc<dyn BadTokenDetecting>,
 }
 
 impl RunLoop {
@@ -153,6 +154,7 @@ impl RunLoop {
             self.database.store_order_events(&events).await;
 
             let winner = solution.account;
+            let submission_address = revealed.account.unwrap_or(winner);
             let winning_score = solution.score.get();
             let reference_score = solutions
                 .iter()
@@ -265,6 +267,7 @@ impl RunLoop {
             let competition = Competition {
                 auction_id,
                 winner,
+                submission_address,
                 winning_score,
                 reference_score,
                 participants,
@@ -300,6 +303,7 @@ impl RunLoop {
             id,
             auction,
             &self.market_makable_token_list.all(),
+            &self.transfer_fees(auction).await,
             self.score_cap,
             self.solve_deadline,
         );
@@ -358,10 +362,17 @@ impl RunLoop {
         driver: &Driver,
         request: &solve::Request,
     ) -> Result<Vec<Result<Solution, ZeroScoreError>>, SolveError> {
-        let response = tokio::time::timeout(self.solve_deadline, driver.solve(request))
+        // All drivers get checked concurrently, so fetching the health counts
+        // towards the deadline like solving.
+        let solve = async {
+            if !driver.is_available().await {
+                return Err(SolveError::Unavailable);
+            }
+            driver.solve(request).await.map_err(SolveError::Failure)
+        };
+        let response = tokio::time::timeout(self.solve_deadline, solve)
             .await
-            .map_err(|_| SolveError::Timeout)?
-            .map_err(SolveError::Failure)?;
+            .map_err(|_| SolveError::Timeout)??;
         if response.solutions.is_empty() {
             return Err(SolveError::NoSolutions);
         }
@@ -420,11 +431,11 @@ impl RunLoop {
             solution_id: solved.id,
         };
 
-        let tx_hash = driver
+        let settled = driver
             .settle(&request, self.max_settlement_transaction_wait)
             .await
-            .map_err(SettleError::Failure)?
-            .tx_hash;
+            .map_err(SettleError::Failure)?;
+        let tx_hash = settled.tx_hash;
 
         let events = revealed
             .orders
@@ -432,11 +443,45 @@ impl RunLoop {
             .map(|uid| (*uid, OrderEventLabel::Traded))
             .collect_vec();
         self.database.store_order_events(&events).await;
-        tracing::debug!(?tx_hash, "solution settled");
+        if let (Some(account), Some(expected)) = (settled.account, revealed.account) {
+            if account != expected {
+                tracing::warn!(
+                    ?account,
+                    ?expected,
+                    "solution settled from a different account than revealed"
+                );
+            }
+        }
+        tracing::debug!(?tx_hash, account = ?settled.account, "solution settled");
 
         Ok(())
     }
 
+    /// Looks up the transfer fees of the taxed tokens traded in the auction.
+    ///
+    /// The detector caches its verdicts and the tokens of the auction already
+    /// got checked when building it, so this only causes requests for tokens
+    /// whose verdicts expired in the meantime.
+    async fn transfer_fees(&self, auction: &Auction) -> HashMap<H160, u32> {
+        let qualities = futures::future::join_all(
+            auction
+                .prices
+                .keys()
+                .map(|&token| async move { (token, self.bad_token_detector.detect(token).await) }),
+        )
+        .await;
+        qualities
+            .into_iter()
+            .filter_map(|(token, quality)| match quality {
+                Ok(quality) => Some((token, quality.transfer_fee_bps()?)),
+                Err(err) => {
+                    tracing::warn!(?err, ?token, "failed to detect token quality");
+                    None
+                }
+            })
+            .collect()
+    }
+
     /// Saves the competition data to the database
     async fn save_competition(&self, competition: &Competition) -> Result<()> {
         self.database.save_competition(competition).await
@@ -447,6 +492,7 @@ pub fn solve_request(
     id: AuctionId,
     auction: &Auction,
     trusted_tokens: &HashSet<H160>,
+    transfer_fees: &HashMap<H160, u32>,
     score_cap: U256,
     time_limit: Duration,
 ) -> solve::Request {
@@ -509,11 +555,13 @@ pub fn solve_request(
                 address: address.to_owned(),
                 price: Some(price.to_owned()),
                 trusted: trusted_tokens.contains(address),
+                transfer_fee_bps: transfer_fees.get(address).copied(),
             })
             .chain(trusted_tokens.iter().map(|&address| solve::Token {
                 address,
                 price: None,
                 trusted: true,
+                transfer_fee_bps: transfer_fees.get(&address).copied(),
             }))
             .unique_by(|token| token.address)
             .collect(),
@@ -539,6 +587,8 @@ enum SolveError {
     Timeout,
     #[error("driver did not propose any solutions")]
     NoSolutions,
+    #[error("driver reported its solver as unavailable")]
+    Unavailable,
     #[error(transparent)]
     Failure(anyhow::Error),
 }
@@ -604,6 +654,7 @@ impl Metrics {
         let label = match err {
             SolveError::Timeout => "timeout",
             SolveError::NoSolutions => "no_solutions",
+            SolveError::Unavailable => "unavailable",
             SolveError::Failure(_) => "error",
         };
         Self::get()
@@ -180,6 +180,7 @@ impl RunLoop {
             id,
             auction,
             &self.trusted_tokens.all(),
+            &Default::default(),
             self.score_cap,
             self.solve_deadline,
         );
@@ -7,7 +7,7 @@ use {
     itertools::Itertools,
     model::{
         auction::Auction,
-        order::{Order, OrderClass, OrderUid},
+        order::{AllOrdersCancellation, Order, OrderClass, OrderUid},
         signature::Signature,
         time::now_in_epoch_seconds,
     },
@@ -17,6 +17,7 @@ use {
     prometheus::{IntCounter, IntCounterVec, IntGauge, IntGaugeVec},
     shared::{
         account_balances::{BalanceFetching, Query},
+        address_list::AutoUpdatingAddressList,
         bad_token::BadTokenDetecting,
         price_estimation::native_price_cache::CachingNativePriceEstimator,
         remaining_amounts,
@@ -67,7 +68,7 @@ pub struct Metrics {
 pub struct SolvableOrdersCache {
     min_order_validity_period: Duration,
     database: Postgres,
-    banned_users: HashSet<H160>,
+    banned_users: AutoUpdatingAddressList,
     balance_fetcher: Arc<dyn BalanceFetching>,
     bad_token_detector: Arc<dyn BadTokenDetecting>,
     cache: Mutex<Inner>,
@@ -102,7 +103,7 @@ impl SolvableOrdersCache {
     pub fn new(
         min_order_validity_period: Duration,
         database: Postgres,
-        banned_users: HashSet<H160>,
+        banned_users: AutoUpdatingAddressList,
         balance_fetcher: Arc<dyn BalanceFetching>,
         bad_token_detector: Arc<dyn BadTokenDetecting>,
         current_block: CurrentBlockStream,
@@ -163,10 +164,16 @@ impl SolvableOrdersCache {
         let mut counter = OrderFilterCounter::new(self.metrics, &db_solvable_orders.orders);
         let mut order_events = vec![];
 
-        let orders = filter_banned_user_orders(db_solvable_orders.orders, &self.banned_users);
+        let orders = filter_banned_user_orders(db_solvable_orders.orders, &self.banned_users.all());
         let removed = counter.checkpoint("banned_user", &orders);
         order_events.extend(removed.into_iter().map(|o| (o, OrderEventLabel::Invalid)));
 
+        // Orders covered by a cancellation of all orders are already cancelled in the
+        // database unless their creation raced with the cancellation.
+        let orders = filter_cancelled_by_owner(orders, &db_solvable_orders.cancellations);
+        let removed = counter.checkpoint("cancelled_by_owner", &orders);
+        order_events.extend(removed.into_iter().map(|o| (o, OrderEventLabel::Cancelled)));
+
         let orders = filter_unsupported_tokens(orders, self.bad_token_detector.as_ref()).await?;
         let removed = counter.checkpoint("unsupported_token", &orders);
         order_events.extend(removed.into_iter().map(|o| (o, OrderEventLabel::Invalid)));
@@ -313,6 +320,24 @@ fn filter_banned_user_orders(mut orders: Vec<Order>, banned_users: &HashSet<H160
     orders
 }
 
+/// Removes orders whose owner signed a cancellation of all orders covering
+/// them.
+fn filter_cancelled_by_owner(
+    mut orders: Vec<Order>,
+    cancellations: &HashMap<H160, Vec<AllOrdersCancellation>>,
+) -> Vec<Order> {
+    orders.retain(|order| {
+        cancellations
+            .get(&order.metadata.owner)
+            .map_or(true, |cancellations| {
+                !cancellations
+                    .iter()
+                    .any(|cancellation| cancellation.cancels(order))
+            })
+    });
+    orders
+}
+
 /// Filters EIP-1271 orders whose signatures are no longer validating.
 async fn filter_invalid_signature_orders(
     orders: Vec<Order>,
@@ -713,8 +738,9 @@ impl OrderFilterCounter {
 mod tests {
     use {
         super::*,
+        chrono::{TimeZone, Utc},
         futures::FutureExt,
-        maplit::{btreemap, hashset},
+        maplit::{btreemap, hashmap, hashset},
         mockall::predicate::eq,
         model::{
             interaction::InteractionData,
@@ -880,6 +906,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn filters_orders_cancelled_by_owner() {
+        let cancellations = hashmap! {
+            H160([1; 20]) => vec![
+                AllOrdersCancellation {
+                    cancelled_before: 100,
+                    ..Default::default()
+                },
+            ],
+            H160([2; 20]) => vec![
+                AllOrdersCancellation {
+                    cancelled_before: 100,
+                    sell_token: Some(H160([0x11; 20])),
+                    buy_token: None,
+                },
+            ],
+        };
+        let orders = [
+            (1, 99, 0x11),
+            (1, 100, 0x11),
+            (2, 99, 0x11),
+            (2, 99, 0x22),
+            (3, 99, 0x11),
+        ]
+        .into_iter()
+        .map(|(owner, created, sell_token)| {
+            let mut order = OrderBuilder::default()
+                .with_creation_date(Utc.timestamp_opt(created, 0).unwrap())
+                .with_sell_token(H160([sell_token; 20]))
+                .build();
+            order.metadata.owner = H160([owner; 20]);
+            order
+        })
+        .collect();
+
+        let filtered_orders = filter_cancelled_by_owner(orders, &cancellations);
+        let filtered = filtered_orders
+            .iter()
+            .map(|order| {
+                (
+                    order.metadata.owner,
+                    order.metadata.creation_date.timestamp(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            filtered,
+            [
+                (H160([1; 20]), 100),
+                (H160([2; 20]), 99),
+                (H160([3; 20]), 99)
+            ],
+        );
+    }
+
     #[tokio::test]
     async fn filters_invalidated_eip1271_signatures() {
         let orders = vec![
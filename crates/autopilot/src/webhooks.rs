@@ -0,0 +1,329 @@
+//! Notifies the webhooks registered by integrators about orders that traded,
+//! got partially filled, expired or got filtered.
+
+// see database/sql/V066__webhooks.sql
+//
+// Notifications are created in two steps to make sure none get lost:
+//
+// 1. Order events and order expiries are read in time windows following the
+//    stored progress of their source. For every event a delivery to every
+//    matching webhook gets queued and the progress is stored in the same
+//    transaction. Windows end `event_delay` in the past so that events whose
+//    transaction committed late and trades that are indexed after the order
+//    event are picked up.
+// 2. Due deliveries are posted to their webhooks signed with the secret of the
+//    webhook, which is derived from the signing key and never stored.
+//    Deliveries that fail get retried with exponential backoff until they run
+//    out of attempts and end up in the dead letters. Redirects are not followed
+//    so deliveries only reach the urls validated at registration.
+
+use {
+    crate::database::Postgres,
+    anyhow::{Context, Result},
+    chrono::{DateTime, Utc},
+    database::webhooks::{self, DueDelivery, OrderEvent, Webhook, WebhookSource},
+    futures::future,
+    model::webhook::{WebhookEvent, WebhookPayload},
+    primitive_types::H160,
+    reqwest::Client,
+    shared::request_signing::{self, Secret, Signer},
+    std::{collections::HashMap, time::Duration},
+};
+
+/// Events of at most this period get turned into deliveries in one
+/// transaction.
+const MAX_WINDOW: Duration = Duration::from_secs(600);
+
+/// How many deliveries get sent out concurrently.
+const DELIVERY_BATCH_SIZE: i64 = 100;
+
+/// Header containing the id of the delivery. Deliveries can be sent multiple
+/// times so webhooks should use it to ignore duplicates.
+const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub update_interval: Duration,
+    pub event_delay: Duration,
+    pub max_attempts: u32,
+    pub min_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+pub struct Webhooks {
+    pub db: Postgres,
+    pub client: Client,
+    pub signing_key: Secret,
+    pub config: Config,
+}
+
+impl Webhooks {
+    pub async fn run_forever(self) -> ! {
+        loop {
+            for source in [WebhookSource::OrderEvents, WebhookSource::Expiries] {
+                // Catch up on all events before sending deliveries.
+                loop {
+                    match self.queue_deliveries(source, Utc::now()).await {
+                        Ok(true) => continue,
+                        Ok(false) => (),
+                        Err(err) => {
+                            tracing::error!(?err, ?source, "failed to queue webhook deliveries")
+                        }
+                    }
+                    break;
+                }
+            }
+            if let Err(err) = self.send_deliveries(Utc::now()).await {
+                tracing::error!(?err, "failed to send webhook deliveries");
+            }
+            tokio::time::sleep(self.config.update_interval).await;
+        }
+    }
+
+    /// Queues the deliveries of the next window of events of the source.
+    ///
+    /// Returns whether there are more events that can be queued right away.
+    async fn queue_deliveries(&self, source: WebhookSource, now: DateTime<Utc>) -> Result<bool> {
+        let end = now - chrono::Duration::from_std(self.config.event_delay)?;
+        let mut ex = self.db.0.begin().await.context("begin transaction")?;
+        // Only notify about events that happen after the first start.
+        let start = webhooks::progress(&mut ex, source)
+            .await
+            .context("progress")?
+            .unwrap_or(end);
+        if start >= end {
+            return Ok(false);
+        }
+        let until = end.min(start + chrono::Duration::from_std(MAX_WINDOW)?);
+
+        let hooks = webhooks::webhooks(&mut ex).await.context("webhooks")?;
+        if !hooks.is_empty() {
+            let events = match source {
+                WebhookSource::OrderEvents => webhooks::fetch_order_events(&mut ex, start, until)
+                    .await
+                    .context("fetch_order_events")?,
+                WebhookSource::Expiries => webhooks::fetch_expiries(&mut ex, start, until)
+                    .await
+                    .context("fetch_expiries")?,
+            };
+            let mut queued = 0;
+            for event in &events {
+                let payload = payload(event);
+                let body = serde_json::to_string(&payload)?;
+                for hook in hooks.iter().filter(|hook| matches(hook, &payload)) {
+                    if webhooks::insert_delivery(&mut ex, hook.id, event, &body, now)
+                        .await
+                        .context("insert_delivery")?
+                    {
+                        queued += 1;
+                    }
+                }
+            }
+            Metrics::get()
+                .queued
+                .with_label_values(&[source_label(source)])
+                .inc_by(queued);
+        }
+
+        webhooks::save_progress(&mut ex, source, until)
+            .await
+            .context("save_progress")?;
+        ex.commit().await.context("commit")?;
+        Ok(until < end)
+    }
+
+    /// Sends out the deliveries that are due and records the results.
+    async fn send_deliveries(&self, now: DateTime<Utc>) -> Result<()> {
+        let mut ex = self.db.0.acquire().await.context("acquire")?;
+        let deliveries = webhooks::due_deliveries(&mut ex, now, DELIVERY_BATCH_SIZE)
+            .await
+            .context("due_deliveries")?;
+        // Derive the secret of every webhook only once per batch.
+        let mut signers = HashMap::new();
+        for delivery in &deliveries {
+            signers.entry(delivery.webhook_id).or_insert_with(|| {
+                Signer::new(request_signing::webhook_secret(
+                    &self.signing_key,
+                    delivery.webhook_id,
+                ))
+            });
+        }
+        let results = future::join_all(
+            deliveries
+                .iter()
+                .map(|delivery| self.send(delivery, &signers[&delivery.webhook_id])),
+        )
+        .await;
+
+        for (delivery, result) in deliveries.iter().zip(results) {
+            let outcome = match result {
+                Ok(()) => {
+                    webhooks::mark_delivered(&mut ex, delivery.id, Utc::now())
+                        .await
+                        .context("mark_delivered")?;
+                    "delivered"
+                }
+                Err(err) => {
+                    let attempts = u32::try_from(delivery.attempts).unwrap_or_default() + 1;
+                    let next_attempt = (attempts < self.config.max_attempts)
+                        .then(|| {
+                            chrono::Duration::from_std(backoff(
+                                attempts,
+                                self.config.min_backoff,
+                                self.config.max_backoff,
+                            ))
+                            .map(|backoff| Utc::now() + backoff)
+                        })
+                        .transpose()?;
+                    tracing::debug!(id = delivery.id, attempts, ?err, "webhook delivery failed");
+                    webhooks::mark_failed(&mut ex, delivery.id, &format!("{err:#}"), next_attempt)
+                        .await
+                        .context("mark_failed")?;
+                    match next_attempt {
+                        Some(_) => "failed",
+                        None => "dead",
+                    }
+                }
+            };
+            Metrics::get()
+                .deliveries
+                .with_label_values(&[outcome])
+                .inc();
+        }
+        Ok(())
+    }
+
+    async fn send(&self, delivery: &DueDelivery, signer: &Signer) -> Result<()> {
+        let url: reqwest::Url = delivery.url.parse().context("invalid url")?;
+        let mut request = self
+            .client
+            .post(url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(DELIVERY_HEADER, delivery.id.to_string());
+        for (name, value) in signer.headers("POST", &url, delivery.payload.as_bytes()) {
+            request = request.header(name, value);
+        }
+        let response = request
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .context("send")?;
+        let status = response.status();
+        anyhow::ensure!(status.is_success(), "status {status}");
+        Ok(())
+    }
+}
+
+fn payload(event: &OrderEvent) -> WebhookPayload {
+    WebhookPayload {
+        event: match event.event {
+            webhooks::WebhookEvent::Traded => WebhookEvent::Traded,
+            webhooks::WebhookEvent::PartiallyFilled => WebhookEvent::PartiallyFilled,
+            webhooks::WebhookEvent::Expired => WebhookEvent::Expired,
+            webhooks::WebhookEvent::Filtered => WebhookEvent::Filtered,
+        },
+        order_uid: model::order::OrderUid(event.order_uid.0),
+        owner: H160(event.owner.0),
+        app_code: event
+            .full_app_data
+            .as_deref()
+            .and_then(shared::app_data::app_code),
+        timestamp: event.timestamp,
+    }
+}
+
+/// Whether the webhook wants to be notified about the event.
+fn matches(webhook: &Webhook, payload: &WebhookPayload) -> bool {
+    webhook
+        .owner
+        .map_or(true, |owner| owner.0 == payload.owner.0)
+        && webhook
+            .app_code
+            .as_ref()
+            .map_or(true, |app_code| Some(app_code) == payload.app_code.as_ref())
+}
+
+/// How long to wait before retrying a delivery that failed `attempts` times.
+fn backoff(attempts: u32, min: Duration, max: Duration) -> Duration {
+    let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+    min.saturating_mul(factor).min(max)
+}
+
+fn source_label(source: WebhookSource) -> &'static str {
+    match source {
+        WebhookSource::OrderEvents => "order_events",
+        WebhookSource::Expiries => "expiries",
+    }
+}
+
+#[derive(prometheus_metric_storage::MetricStorage)]
+#[metric(subsystem = "webhooks")]
+struct Metrics {
+    /// Number of queued webhook deliveries per event source.
+    #[metric(labels("source"))]
+    queued: prometheus::IntCounterVec,
+
+    /// Number of delivery attempts per outcome.
+    #[metric(labels("outcome"))]
+    deliveries: prometheus::IntCounterVec,
+}
+
+impl Metrics {
+    fn get() -> &'static Self {
+        Metrics::instance(observe::metrics::get_storage_registry()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, chrono::TimeZone, database::byte_array::ByteArray};
+
+    fn event(full_app_data: Option<&str>) -> OrderEvent {
+        OrderEvent {
+            order_uid: ByteArray([1; 56]),
+            timestamp: Utc.timestamp_opt(0, 0).unwrap(),
+            event: webhooks::WebhookEvent::Expired,
+            owner: ByteArray([2; 20]),
+            full_app_data: full_app_data.map(|app_data| app_data.as_bytes().to_vec()),
+        }
+    }
+
+    #[test]
+    fn builds_payload() {
+        let payload = payload(&event(Some(r#"{"appCode":"CoW Swap"}"#)));
+        assert_eq!(payload.event, WebhookEvent::Expired);
+        assert_eq!(payload.owner, H160([2; 20]));
+        assert_eq!(payload.app_code.as_deref(), Some("CoW Swap"));
+        assert_eq!(super::payload(&event(Some("{}"))).app_code, None);
+    }
+
+    #[test]
+    fn matches_scope() {
+        let payload = payload(&event(Some(r#"{"appCode":"CoW Swap"}"#)));
+        let webhook = |owner: Option<u8>, app_code: Option<&str>| Webhook {
+            owner: owner.map(|owner| ByteArray([owner; 20])),
+            app_code: app_code.map(str::to_string),
+            ..Default::default()
+        };
+        assert!(matches(&webhook(None, None), &payload));
+        assert!(matches(&webhook(Some(2), None), &payload));
+        assert!(matches(&webhook(Some(2), Some("CoW Swap")), &payload));
+        assert!(!matches(&webhook(Some(3), None), &payload));
+        assert!(!matches(&webhook(None, Some("other")), &payload));
+        assert!(!matches(
+            &webhook(None, Some("CoW Swap")),
+            &super::payload(&event(None))
+        ));
+    }
+
+    #[test]
+    fn backs_off_exponentially() {
+        let min = Duration::from_secs(10);
+        let max = Duration::from_secs(100);
+        assert_eq!(backoff(1, min, max), Duration::from_secs(10));
+        assert_eq!(backoff(2, min, max), Duration::from_secs(20));
+        assert_eq!(backoff(4, min, max), Duration::from_secs(80));
+        assert_eq!(backoff(5, min, max), max);
+        assert_eq!(backoff(100, min, max), max);
+    }
+}
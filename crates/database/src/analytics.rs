@@ -0,0 +1,798 @@
+//! Daily aggregates of trades and settlements that back the analytics
+//! endpoints.
+//!
+//! The aggregates are updated incrementally: events are read in order starting
+//! after the last aggregated event of their source, added to the aggregates and
+//! the progress gets stored in the same transaction. When events get replaced
+//! because of a reorg, the aggregates of the affected days are deleted and
+//! recomputed from the events that replaced them (see [`rewind`]).
+
+use {
+    crate::{events::EventIndex, Address},
+    bigdecimal::BigDecimal,
+    chrono::NaiveDate,
+    sqlx::PgConnection,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "AnalyticsSource")]
+#[sqlx(rename_all = "lowercase")]
+pub enum AnalyticsSource {
+    Trades,
+    Settlements,
+}
+
+/// Returns the last event of the source that has been aggregated.
+///
+/// The progress stays locked until the end of the transaction so that
+/// aggregating events and rewinding the aggregates can't interleave.
+pub async fn progress(
+    ex: &mut PgConnection,
+    source: AnalyticsSource,
+) -> Result<Option<EventIndex>, sqlx::Error> {
+    const QUERY: &str =
+        r#"SELECT block_number, log_index FROM analytics_progress WHERE source = $1 FOR UPDATE;"#;
+    sqlx::query_as(QUERY).bind(source).fetch_optional(ex).await
+}
+
+pub async fn save_progress(
+    ex: &mut PgConnection,
+    source: AnalyticsSource,
+    event: &EventIndex,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO analytics_progress (source, block_number, log_index)
+VALUES ($1, $2, $3)
+ON CONFLICT (source) DO UPDATE
+SET block_number = EXCLUDED.block_number, log_index = EXCLUDED.log_index
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(source)
+        .bind(event.block_number)
+        .bind(event.log_index)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Returns the days the specified blocks were aggregated in. Blocks whose
+/// events haven't been aggregated yet are missing.
+pub async fn days(
+    ex: &mut PgConnection,
+    blocks: &[i64],
+) -> Result<Vec<(i64, NaiveDate)>, sqlx::Error> {
+    const QUERY: &str = r#"SELECT block_number, day FROM analytics_blocks WHERE block_number = ANY($1) ORDER BY block_number;"#;
+    sqlx::query_as(QUERY).bind(blocks).fetch_all(ex).await
+}
+
+pub async fn save_day(
+    ex: &mut PgConnection,
+    block_number: i64,
+    day: NaiveDate,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO analytics_blocks (block_number, day)
+VALUES ($1, $2)
+ON CONFLICT (block_number) DO NOTHING
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(block_number)
+        .bind(day)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Removes the events of all blocks starting at `block_number` from the
+/// aggregates. Has to be called in the same transaction that deletes these
+/// events.
+///
+/// Because the aggregates only store daily sums, the aggregates of every day
+/// with a removed event get deleted completely and the progress is rewound to
+/// the start of the first of these days. The remaining events of these days get
+/// aggregated again afterwards.
+pub async fn rewind(ex: &mut PgConnection, block_number: i64) -> Result<(), sqlx::Error> {
+    const LOCK_PROGRESS: &str = r#"SELECT source FROM analytics_progress FOR UPDATE;"#;
+    sqlx::query(LOCK_PROGRESS).execute(&mut *ex).await?;
+
+    const FIRST_DAY: &str = r#"SELECT MIN(day) FROM analytics_blocks WHERE block_number >= $1;"#;
+    let first_day: Option<NaiveDate> = sqlx::query_scalar(FIRST_DAY)
+        .bind(block_number)
+        .fetch_one(&mut *ex)
+        .await?;
+    let Some(first_day) = first_day else {
+        return Ok(());
+    };
+    const FIRST_BLOCK: &str = r#"SELECT MIN(block_number) FROM analytics_blocks WHERE day >= $1;"#;
+    let first_block: i64 = sqlx::query_scalar(FIRST_BLOCK)
+        .bind(first_day)
+        .fetch_one(&mut *ex)
+        .await?;
+
+    const DELETE_TRADES: &str = r#"DELETE FROM trade_analytics WHERE day >= $1;"#;
+    sqlx::query(DELETE_TRADES)
+        .bind(first_day)
+        .execute(&mut *ex)
+        .await?;
+    const DELETE_SETTLEMENTS: &str = r#"DELETE FROM settlement_analytics WHERE day >= $1;"#;
+    sqlx::query(DELETE_SETTLEMENTS)
+        .bind(first_day)
+        .execute(&mut *ex)
+        .await?;
+    const DELETE_BLOCKS: &str = r#"DELETE FROM analytics_blocks WHERE block_number >= $1;"#;
+    sqlx::query(DELETE_BLOCKS)
+        .bind(first_block)
+        .execute(&mut *ex)
+        .await?;
+    // Sources that haven't reached the first block yet have nothing
+    // aggregated on the deleted days and keep their progress. The others
+    // continue with the first event of the first block.
+    const REWIND_PROGRESS: &str = r#"
+UPDATE analytics_progress
+SET block_number = $1 - 1, log_index = $2
+WHERE block_number >= $1
+    ;"#;
+    sqlx::query(REWIND_PROGRESS)
+        .bind(first_block)
+        .bind(i64::MAX)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// A trade with its amounts converted to wei using the native prices of the
+/// auction it was settled in. Converted amounts are 0 if the auction or its
+/// prices are unknown.
+#[derive(Clone, Debug, Default, PartialEq, sqlx::FromRow)]
+pub struct TradeRow {
+    pub block_number: i64,
+    pub log_index: i64,
+    pub owner: Address,
+    pub sell_token: Address,
+    pub buy_token: Address,
+    pub full_app_data: Option<Vec<u8>>,
+    pub sell_amount: BigDecimal,
+    pub buy_amount: BigDecimal,
+    pub volume: BigDecimal,
+    pub surplus: BigDecimal,
+    pub fee: BigDecimal,
+}
+
+/// Fetches up to `limit` trades that come after the specified event in blocks
+/// up to `max_block_number`.
+///
+/// Trades are only returned up to the first settlement that has not been
+/// associated with its transaction yet because the auction of the trades is
+/// unknown until then.
+pub async fn fetch_trades(
+    ex: &mut PgConnection,
+    after: &EventIndex,
+    max_block_number: i64,
+    limit: i64,
+) -> Result<Vec<TradeRow>, sqlx::Error> {
+    const QUERY: &str = r#"
+WITH trade_components AS (
+    SELECT
+        t.block_number,
+        t.log_index,
+        COALESCE(opo.sender, o.owner) AS owner,
+        o.sell_token,
+        o.buy_token,
+        ad.full_app_data,
+        t.sell_amount,
+        t.buy_amount,
+        CASE o.kind
+            -- so much was bought more than the limit price requires
+            WHEN 'sell' THEN (t.buy_amount - (t.sell_amount - t.fee_amount) * o.buy_amount / o.sell_amount) * bp.price
+            -- so much was sold less than the limit price allows
+            WHEN 'buy' THEN (t.buy_amount * o.sell_amount / o.buy_amount - (t.sell_amount - t.fee_amount)) * sp.price
+        END AS surplus,
+        t.sell_amount * sp.price AS volume,
+        (t.fee_amount + COALESCE(oe.surplus_fee, 0)) * sp.price AS fee
+    FROM trades t
+    JOIN orders o ON o.uid = t.order_uid
+    LEFT OUTER JOIN onchain_placed_orders opo ON opo.uid = t.order_uid
+    LEFT OUTER JOIN app_data ad ON ad.contract_app_data = o.app_data
+    JOIN LATERAL (
+        SELECT tx_from, tx_nonce FROM settlements s
+        WHERE s.block_number = t.block_number
+        AND   s.log_index > t.log_index
+        ORDER BY s.log_index ASC
+        LIMIT 1
+    ) AS s ON true
+    LEFT OUTER JOIN auction_transaction at ON at.tx_from = s.tx_from AND at.tx_nonce = s.tx_nonce
+    LEFT OUTER JOIN order_execution oe ON oe.order_uid = t.order_uid AND oe.auction_id = at.auction_id
+    LEFT OUTER JOIN auction_prices sp ON sp.auction_id = at.auction_id AND sp.token = o.sell_token
+    LEFT OUTER JOIN auction_prices bp ON bp.auction_id = at.auction_id AND bp.token = o.buy_token
+    WHERE (t.block_number, t.log_index) > ($1, $2)
+    AND t.block_number <= $3
+    AND t.block_number < COALESCE(
+        (SELECT MIN(block_number) FROM settlements WHERE tx_from IS NULL),
+        $3 + 1
+    )
+    ORDER BY t.block_number ASC, t.log_index ASC
+    LIMIT $4
+)
+SELECT
+    block_number,
+    log_index,
+    owner,
+    sell_token,
+    buy_token,
+    full_app_data,
+    sell_amount,
+    buy_amount,
+    COALESCE(TRUNC(volume / POWER(10, 18)), 0) AS volume,
+    COALESCE(GREATEST(TRUNC(surplus / POWER(10, 18)), 0), 0) AS surplus,
+    COALESCE(TRUNC(fee / POWER(10, 18)), 0) AS fee
+FROM trade_components
+ORDER BY block_number ASC, log_index ASC
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(after.block_number)
+        .bind(after.log_index)
+        .bind(max_block_number)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
+/// A settlement together with its observation. Observed amounts are 0 if the
+/// settlement has not been observed.
+#[derive(Clone, Debug, Default, PartialEq, sqlx::FromRow)]
+pub struct SettlementRow {
+    pub block_number: i64,
+    pub log_index: i64,
+    pub solver: Address,
+    pub gas_used: BigDecimal,
+    pub gas_cost: BigDecimal,
+    pub surplus: BigDecimal,
+    pub fee: BigDecimal,
+}
+
+/// Fetches up to `limit` settlements that come after the specified event in
+/// blocks up to `max_block_number`.
+///
+/// Like with [`fetch_trades`], settlements are only returned up to the first
+/// settlement that has not been associated with its transaction yet. Its
+/// observation gets stored at the same time.
+pub async fn fetch_settlements(
+    ex: &mut PgConnection,
+    after: &EventIndex,
+    max_block_number: i64,
+    limit: i64,
+) -> Result<Vec<SettlementRow>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT
+    s.block_number,
+    s.log_index,
+    s.solver,
+    COALESCE(so.gas_used, 0) AS gas_used,
+    COALESCE(so.gas_used * so.effective_gas_price, 0) AS gas_cost,
+    COALESCE(so.surplus, 0) AS surplus,
+    COALESCE(so.fee, 0) AS fee
+FROM settlements s
+LEFT OUTER JOIN settlement_observations so
+    ON so.block_number = s.block_number AND so.log_index = s.log_index
+WHERE (s.block_number, s.log_index) > ($1, $2)
+AND s.block_number <= $3
+AND s.block_number < COALESCE(
+    (SELECT MIN(block_number) FROM settlements WHERE tx_from IS NULL),
+    $3 + 1
+)
+ORDER BY s.block_number ASC, s.log_index ASC
+LIMIT $4
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(after.block_number)
+        .bind(after.log_index)
+        .bind(max_block_number)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
+/// Trades of a single day, owner, token pair and app code that get added to
+/// the aggregates.
+#[derive(Clone, Debug, Default, PartialEq, sqlx::FromRow)]
+pub struct TradeAggregate {
+    pub day: NaiveDate,
+    pub owner: Address,
+    pub sell_token: Address,
+    pub buy_token: Address,
+    pub app_code: String,
+    pub trades: i64,
+    pub sell_amount: BigDecimal,
+    pub buy_amount: BigDecimal,
+    pub volume: BigDecimal,
+    pub surplus: BigDecimal,
+    pub fee: BigDecimal,
+}
+
+pub async fn add_trades(
+    ex: &mut PgConnection,
+    aggregate: &TradeAggregate,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO trade_analytics (day, owner, sell_token, buy_token, app_code, trades, sell_amount, buy_amount, volume, surplus, fee)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+ON CONFLICT (day, owner, sell_token, buy_token, app_code) DO UPDATE
+SET
+    trades = trade_analytics.trades + EXCLUDED.trades,
+    sell_amount = trade_analytics.sell_amount + EXCLUDED.sell_amount,
+    buy_amount = trade_analytics.buy_amount + EXCLUDED.buy_amount,
+    volume = trade_analytics.volume + EXCLUDED.volume,
+    surplus = trade_analytics.surplus + EXCLUDED.surplus,
+    fee = trade_analytics.fee + EXCLUDED.fee
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(aggregate.day)
+        .bind(aggregate.owner)
+        .bind(aggregate.sell_token)
+        .bind(aggregate.buy_token)
+        .bind(&aggregate.app_code)
+        .bind(aggregate.trades)
+        .bind(&aggregate.sell_amount)
+        .bind(&aggregate.buy_amount)
+        .bind(&aggregate.volume)
+        .bind(&aggregate.surplus)
+        .bind(&aggregate.fee)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Settlements of a single day and solver that get added to the aggregates.
+#[derive(Clone, Debug, Default, PartialEq, sqlx::FromRow)]
+pub struct SettlementAggregate {
+    pub day: NaiveDate,
+    pub solver: Address,
+    pub settlements: i64,
+    pub gas_used: BigDecimal,
+    pub gas_cost: BigDecimal,
+    pub surplus: BigDecimal,
+    pub fee: BigDecimal,
+}
+
+pub async fn add_settlements(
+    ex: &mut PgConnection,
+    aggregate: &SettlementAggregate,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO settlement_analytics (day, solver, settlements, gas_used, gas_cost, surplus, fee)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+ON CONFLICT (day, solver) DO UPDATE
+SET
+    settlements = settlement_analytics.settlements + EXCLUDED.settlements,
+    gas_used = settlement_analytics.gas_used + EXCLUDED.gas_used,
+    gas_cost = settlement_analytics.gas_cost + EXCLUDED.gas_cost,
+    surplus = settlement_analytics.surplus + EXCLUDED.surplus,
+    fee = settlement_analytics.fee + EXCLUDED.fee
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(aggregate.day)
+        .bind(aggregate.solver)
+        .bind(aggregate.settlements)
+        .bind(&aggregate.gas_used)
+        .bind(&aggregate.gas_cost)
+        .bind(&aggregate.surplus)
+        .bind(&aggregate.fee)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Length of the periods aggregates get summed up over.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Interval {
+    Day,
+    Week,
+    Month,
+}
+
+impl Interval {
+    /// The field name `date_trunc` expects for this interval.
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+}
+
+/// Additionally breaks down the trade aggregates of every period by this key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TradeGrouping {
+    SellToken,
+    BuyToken,
+    AppCode,
+}
+
+impl TradeGrouping {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::SellToken => "sell_token",
+            Self::BuyToken => "buy_token",
+            Self::AppCode => "app_code",
+        }
+    }
+}
+
+/// Restricts which trades are summed up. Unset fields match all trades.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TradeFilter {
+    pub owner: Option<Address>,
+    /// Matches trades that sell or buy the token.
+    pub token: Option<Address>,
+    pub app_code: Option<String>,
+    /// First day (inclusive).
+    pub from: Option<NaiveDate>,
+    /// Last day (exclusive).
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, sqlx::FromRow)]
+pub struct TradeBucket {
+    /// First day of the period.
+    pub period: NaiveDate,
+    /// Hex encoded token address or app code depending on the grouping.
+    /// `None` if the aggregates are not grouped.
+    pub key: Option<String>,
+    pub trades: i64,
+    pub volume: BigDecimal,
+    pub surplus: BigDecimal,
+    pub fee: BigDecimal,
+}
+
+/// Sums up the trade aggregates per period, latest period first.
+pub async fn trade_buckets(
+    ex: &mut PgConnection,
+    interval: Interval,
+    grouping: Option<TradeGrouping>,
+    filter: &TradeFilter,
+    offset: i64,
+    limit: i64,
+) -> Result<Vec<TradeBucket>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT
+    date_trunc($1, day::timestamp)::date AS period,
+    CASE $2
+        WHEN 'sell_token' THEN '0x' || encode(sell_token, 'hex')
+        WHEN 'buy_token' THEN '0x' || encode(buy_token, 'hex')
+        WHEN 'app_code' THEN app_code
+    END AS key,
+    SUM(trades)::bigint AS trades,
+    SUM(volume) AS volume,
+    SUM(surplus) AS surplus,
+    SUM(fee) AS fee
+FROM trade_analytics
+WHERE ($3::bytea IS NULL OR owner = $3)
+AND ($4::bytea IS NULL OR sell_token = $4 OR buy_token = $4)
+AND ($5::text IS NULL OR app_code = $5)
+AND ($6::date IS NULL OR day >= $6)
+AND ($7::date IS NULL OR day < $7)
+GROUP BY 1, 2
+ORDER BY 1 DESC, 2 ASC
+OFFSET $8
+LIMIT $9
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(interval.as_str())
+        .bind(grouping.map(|grouping| grouping.as_str()))
+        .bind(filter.owner)
+        .bind(filter.token)
+        .bind(&filter.app_code)
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
+/// Restricts which settlements are summed up. Unset fields match all
+/// settlements.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SettlementFilter {
+    pub solver: Option<Address>,
+    /// First day (inclusive).
+    pub from: Option<NaiveDate>,
+    /// Last day (exclusive).
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, sqlx::FromRow)]
+pub struct SettlementBucket {
+    /// First day of the period.
+    pub period: NaiveDate,
+    pub settlements: i64,
+    pub gas_used: BigDecimal,
+    pub gas_cost: BigDecimal,
+    pub surplus: BigDecimal,
+    pub fee: BigDecimal,
+}
+
+/// Sums up the settlement aggregates per period, latest period first.
+pub async fn settlement_buckets(
+    ex: &mut PgConnection,
+    interval: Interval,
+    filter: &SettlementFilter,
+    offset: i64,
+    limit: i64,
+) -> Result<Vec<SettlementBucket>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT
+    date_trunc($1, day::timestamp)::date AS period,
+    SUM(settlements)::bigint AS settlements,
+    SUM(gas_used) AS gas_used,
+    SUM(gas_cost) AS gas_cost,
+    SUM(surplus) AS surplus,
+    SUM(fee) AS fee
+FROM settlement_analytics
+WHERE ($2::bytea IS NULL OR solver = $2)
+AND ($3::date IS NULL OR day >= $3)
+AND ($4::date IS NULL OR day < $4)
+GROUP BY 1
+ORDER BY 1 DESC
+OFFSET $5
+LIMIT $6
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(interval.as_str())
+        .bind(filter.solver)
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_progress_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        assert_eq!(
+            progress(&mut db, AnalyticsSource::Trades).await.unwrap(),
+            None
+        );
+        let event = EventIndex {
+            block_number: 1,
+            log_index: 2,
+        };
+        save_progress(&mut db, AnalyticsSource::Trades, &event)
+            .await
+            .unwrap();
+        let later = EventIndex {
+            block_number: 3,
+            log_index: 0,
+        };
+        save_progress(&mut db, AnalyticsSource::Trades, &later)
+            .await
+            .unwrap();
+        assert_eq!(
+            progress(&mut db, AnalyticsSource::Trades).await.unwrap(),
+            Some(later)
+        );
+        assert_eq!(
+            progress(&mut db, AnalyticsSource::Settlements)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_rewind_deletes_aggregates_of_affected_days() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let day = |day| NaiveDate::from_ymd_opt(2023, 5, day).unwrap();
+        // Blocks 1 and 2 were mined on the first day, 3 and 4 on the second.
+        for (block_number, day) in [(1, day(1)), (2, day(1)), (3, day(2)), (4, day(2))] {
+            save_day(&mut db, block_number, day).await.unwrap();
+        }
+        for day in [day(1), day(2)] {
+            let aggregate = TradeAggregate {
+                day,
+                trades: 1,
+                ..Default::default()
+            };
+            add_trades(&mut db, &aggregate).await.unwrap();
+        }
+        let trades = EventIndex {
+            block_number: 4,
+            log_index: 0,
+        };
+        save_progress(&mut db, AnalyticsSource::Trades, &trades)
+            .await
+            .unwrap();
+        let settlements = EventIndex {
+            block_number: 2,
+            log_index: 0,
+        };
+        save_progress(&mut db, AnalyticsSource::Settlements, &settlements)
+            .await
+            .unwrap();
+
+        // Nothing was aggregated from block 5 on.
+        rewind(&mut db, 5).await.unwrap();
+        assert_eq!(
+            progress(&mut db, AnalyticsSource::Trades).await.unwrap(),
+            Some(trades)
+        );
+
+        rewind(&mut db, 4).await.unwrap();
+        let days = trade_buckets(&mut db, Interval::Day, None, &Default::default(), 0, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|bucket| bucket.period)
+            .collect::<Vec<_>>();
+        assert_eq!(days, vec![day(1)]);
+        assert_eq!(
+            progress(&mut db, AnalyticsSource::Trades).await.unwrap(),
+            Some(EventIndex {
+                block_number: 2,
+                log_index: i64::MAX,
+            })
+        );
+        assert_eq!(
+            progress(&mut db, AnalyticsSource::Settlements)
+                .await
+                .unwrap(),
+            Some(settlements)
+        );
+        assert_eq!(
+            super::days(&mut db, &[1, 2, 3, 4]).await.unwrap(),
+            vec![(1, day(1)), (2, day(1))]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_trade_buckets() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let day = |day| NaiveDate::from_ymd_opt(2023, 5, day).unwrap();
+        let aggregate = |day, sell_token, app_code: &str, volume: u32| TradeAggregate {
+            day,
+            owner: ByteArray([1; 20]),
+            sell_token: ByteArray([sell_token; 20]),
+            buy_token: ByteArray([9; 20]),
+            app_code: app_code.to_string(),
+            trades: 1,
+            volume: volume.into(),
+            ..Default::default()
+        };
+        // 2023-05-01 is a monday.
+        add_trades(&mut db, &aggregate(day(1), 2, "CoW Swap", 1))
+            .await
+            .unwrap();
+        // Adds to the existing aggregate.
+        add_trades(&mut db, &aggregate(day(1), 2, "CoW Swap", 2))
+            .await
+            .unwrap();
+        add_trades(&mut db, &aggregate(day(2), 3, "", 4))
+            .await
+            .unwrap();
+        add_trades(&mut db, &aggregate(day(8), 2, "CoW Swap", 8))
+            .await
+            .unwrap();
+
+        let weekly = trade_buckets(&mut db, Interval::Week, None, &Default::default(), 0, 10)
+            .await
+            .unwrap();
+        let summary = |buckets: Vec<TradeBucket>| {
+            buckets
+                .into_iter()
+                .map(|bucket| (bucket.period, bucket.key, bucket.trades, bucket.volume))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            summary(weekly),
+            vec![(day(8), None, 1, 8.into()), (day(1), None, 3, 7.into()),]
+        );
+
+        let daily_by_app = trade_buckets(
+            &mut db,
+            Interval::Day,
+            Some(TradeGrouping::AppCode),
+            &TradeFilter {
+                token: Some(ByteArray([2; 20])),
+                ..Default::default()
+            },
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            summary(daily_by_app),
+            vec![
+                (day(8), Some("CoW Swap".to_string()), 1, 8.into()),
+                (day(1), Some("CoW Swap".to_string()), 2, 3.into()),
+            ]
+        );
+
+        let paginated = trade_buckets(
+            &mut db,
+            Interval::Day,
+            Some(TradeGrouping::SellToken),
+            &TradeFilter {
+                to: Some(day(8)),
+                ..Default::default()
+            },
+            1,
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            summary(paginated),
+            vec![(day(1), Some(format!("0x{}", "02".repeat(20))), 2, 3.into())]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_settlement_buckets() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let day = |day| NaiveDate::from_ymd_opt(2023, 5, day).unwrap();
+        let aggregate = |day, solver, gas_cost: u32| SettlementAggregate {
+            day,
+            solver: ByteArray([solver; 20]),
+            settlements: 1,
+            gas_cost: gas_cost.into(),
+            ..Default::default()
+        };
+        add_settlements(&mut db, &aggregate(day(1), 1, 1))
+            .await
+            .unwrap();
+        add_settlements(&mut db, &aggregate(day(1), 1, 2))
+            .await
+            .unwrap();
+        add_settlements(&mut db, &aggregate(day(31), 2, 4))
+            .await
+            .unwrap();
+
+        let monthly = settlement_buckets(&mut db, Interval::Month, &Default::default(), 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly[0].period, day(1));
+        assert_eq!(monthly[0].settlements, 3);
+        assert_eq!(monthly[0].gas_cost, 7.into());
+
+        let filtered = settlement_buckets(
+            &mut db,
+            Interval::Day,
+            &SettlementFilter {
+                solver: Some(ByteArray([1; 20])),
+                ..Default::default()
+            },
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].settlements, 2);
+        assert_eq!(filtered[0].gas_cost, 3.into());
+    }
+}
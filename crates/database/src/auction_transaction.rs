@@ -112,10 +112,24 @@ pub async fn get_auction_id(
     Ok(auction)
 }
 
-pub async fn data_exists(ex: &mut PgConnection, auction_id: i64) -> Result<bool, sqlx::Error> {
-    const QUERY: &str = r#"SELECT COUNT(*) FROM auction_transaction WHERE auction_id = $1;"#;
+/// Whether the auction is linked to a transaction other than the specified
+/// one. A settlement that gets processed again after a reorg finds its auction
+/// already linked to its own transaction.
+pub async fn linked_to_other_transaction(
+    ex: &mut PgConnection,
+    auction_id: AuctionId,
+    tx_from: &Address,
+    tx_nonce: i64,
+) -> Result<bool, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT COUNT(*)
+FROM auction_transaction
+WHERE auction_id = $1 AND (tx_from != $2 OR tx_nonce != $3)
+    ;"#;
     let count: i64 = sqlx::query_scalar(QUERY)
         .bind(auction_id)
+        .bind(tx_from)
+        .bind(tx_nonce)
         .fetch_one(ex)
         .await?;
     Ok(count >= 1)
@@ -315,4 +329,29 @@ mod tests {
             .unwrap();
         assert!(!inserted);
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn linked_to_other_transaction_test() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let linked = linked_to_other_transaction(&mut db, 3, &Default::default(), 1)
+            .await
+            .unwrap();
+        assert!(!linked);
+
+        try_insert_auction_transaction(&mut db, 3, &Default::default(), 1)
+            .await
+            .unwrap();
+        let linked = linked_to_other_transaction(&mut db, 3, &Default::default(), 1)
+            .await
+            .unwrap();
+        assert!(!linked);
+        let linked = linked_to_other_transaction(&mut db, 3, &Default::default(), 2)
+            .await
+            .unwrap();
+        assert!(linked);
+    }
 }
@@ -0,0 +1,92 @@
+//! Addresses that are denied by the services and can be managed at runtime.
+
+use {crate::Address, sqlx::PgConnection};
+
+/// Which deny list an address belongs to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "DenyListKind")]
+#[sqlx(rename_all = "snake_case")]
+pub enum DenyListKind {
+    /// Token that can not be traded.
+    UnsupportedToken,
+    /// Account that can not place orders.
+    BannedUser,
+}
+
+pub async fn insert(
+    ex: &mut PgConnection,
+    kind: DenyListKind,
+    address: &Address,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO deny_lists (kind, address)
+VALUES ($1, $2)
+ON CONFLICT DO NOTHING
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(kind)
+        .bind(address)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete(
+    ex: &mut PgConnection,
+    kind: DenyListKind,
+    address: &Address,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"DELETE FROM deny_lists WHERE kind = $1 AND address = $2;"#;
+    sqlx::query(QUERY)
+        .bind(kind)
+        .bind(address)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+pub async fn fetch(ex: &mut PgConnection, kind: DenyListKind) -> Result<Vec<Address>, sqlx::Error> {
+    const QUERY: &str = r#"SELECT address FROM deny_lists WHERE kind = $1;"#;
+    sqlx::query_scalar(QUERY).bind(kind).fetch_all(ex).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let token = ByteArray([1; 20]);
+        let user = ByteArray([2; 20]);
+        insert(&mut db, DenyListKind::UnsupportedToken, &token)
+            .await
+            .unwrap();
+        // inserting twice is a no-op
+        insert(&mut db, DenyListKind::UnsupportedToken, &token)
+            .await
+            .unwrap();
+        insert(&mut db, DenyListKind::BannedUser, &user)
+            .await
+            .unwrap();
+
+        let tokens = fetch(&mut db, DenyListKind::UnsupportedToken)
+            .await
+            .unwrap();
+        assert_eq!(tokens, vec![token]);
+        let users = fetch(&mut db, DenyListKind::BannedUser).await.unwrap();
+        assert_eq!(users, vec![user]);
+
+        delete(&mut db, DenyListKind::UnsupportedToken, &token)
+            .await
+            .unwrap();
+        let tokens = fetch(&mut db, DenyListKind::UnsupportedToken)
+            .await
+            .unwrap();
+        assert!(tokens.is_empty());
+    }
+}
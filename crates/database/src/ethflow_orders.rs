@@ -1,6 +1,16 @@
 use {
-    crate::{OrderUid, PgTransaction, TransactionHash},
-    sqlx::{Executor, PgConnection},
+    crate::{
+        order_events::{OrderEventLabel, REFUND_LABELS},
+        Address,
+        OrderUid,
+        PgTransaction,
+        TransactionHash,
+    },
+    sqlx::{
+        types::chrono::{DateTime, Utc},
+        Executor,
+        PgConnection,
+    },
 };
 
 #[derive(Clone, Debug, Default, sqlx::FromRow, Eq, PartialEq)]
@@ -150,6 +160,61 @@ AND eo.valid_to - extract(epoch from creation_timestamp)::int > $2
         .await
 }
 
+/// An ethflow order of a user that got refunded or that the refunder
+/// considered refunding.
+#[derive(Clone, Debug, sqlx::FromRow, Eq, PartialEq)]
+pub struct UserRefund {
+    pub uid: OrderUid,
+    /// The ethflow contract owning the order.
+    pub owner: Address,
+    pub creation_timestamp: DateTime<Utc>,
+    pub valid_to: i64,
+    pub tx_hash: Option<TransactionHash>,
+    /// The latest event recorded while refunding the order.
+    pub event: Option<OrderEventLabel>,
+    pub event_timestamp: Option<DateTime<Utc>>,
+}
+
+/// Returns the refunds of ethflow orders placed by `user`, newest orders
+/// first.
+pub async fn user_refunds(
+    ex: &mut PgConnection,
+    user: &Address,
+    offset: i64,
+    limit: i64,
+) -> Result<Vec<UserRefund>, sqlx::Error> {
+    const QUERY: &str = const_format::concatcp!(
+        r#"
+SELECT o.uid, o.owner, o.creation_timestamp, eo.valid_to, r.tx_hash, e.label AS event, e.timestamp AS event_timestamp
+FROM onchain_placed_orders opo
+JOIN orders o ON o.uid = opo.uid
+JOIN ethflow_orders eo ON eo.uid = o.uid
+LEFT JOIN ethflow_refunds r ON r.order_uid = o.uid
+LEFT JOIN LATERAL (
+    SELECT label, timestamp FROM order_events
+    WHERE order_uid = o.uid AND "#,
+        REFUND_LABELS,
+        r#"
+    ORDER BY timestamp DESC
+    LIMIT 1
+) e ON true
+WHERE
+    opo.sender = $1 AND
+    NOT opo.is_reorged AND
+    (r.tx_hash IS NOT NULL OR e.label IS NOT NULL)
+ORDER BY o.creation_timestamp DESC
+LIMIT $2
+OFFSET $3
+"#
+    );
+    sqlx::query_as(QUERY)
+        .bind(user)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(ex)
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -157,7 +222,9 @@ mod tests {
         crate::{
             byte_array::ByteArray,
             events::{insert_trade, EventIndex, Trade},
+            onchain_broadcasted_orders::{insert_onchain_order, OnchainOrderPlacement},
             onchain_invalidations::insert_onchain_invalidation,
+            order_events::{insert_refund_event, OrderEvent},
             orders::{insert_order, insert_quote, Order, Quote},
         },
         bigdecimal::BigDecimal,
@@ -463,4 +530,105 @@ mod tests {
         println!("{elapsed:?}");
         assert!(elapsed < std::time::Duration::from_secs(1));
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_user_refunds() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let user = ByteArray([1; 20]);
+        let contract = ByteArray([2; 20]);
+        let timestamp = |seconds: i64| Utc.timestamp_opt(seconds, 0).unwrap();
+        for (i, sender) in [(1u8, user), (2, user), (3, user), (4, ByteArray([3; 20]))] {
+            let uid = ByteArray([i; 56]);
+            insert_order(
+                &mut db,
+                &Order {
+                    uid,
+                    owner: contract,
+                    creation_timestamp: timestamp(i.into()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+            insert_or_overwrite_ethflow_order(
+                &mut db,
+                &EthOrderPlacement {
+                    uid,
+                    valid_to: i.into(),
+                },
+            )
+            .await
+            .unwrap();
+            insert_onchain_order(
+                &mut db,
+                &EventIndex {
+                    block_number: i.into(),
+                    log_index: 0,
+                },
+                &OnchainOrderPlacement {
+                    order_uid: uid,
+                    sender,
+                    placement_error: None,
+                },
+            )
+            .await
+            .unwrap();
+            // Order 3 was not considered for a refund yet.
+            if i != 3 {
+                insert_refund_event(
+                    &mut db,
+                    &OrderEvent {
+                        order_uid: uid,
+                        timestamp: timestamp(10),
+                        label: OrderEventLabel::RefundSubmitted,
+                    },
+                )
+                .await
+                .unwrap();
+            }
+        }
+        insert_refund_tx_hash(
+            &mut db,
+            &Refund {
+                order_uid: ByteArray([1; 56]),
+                tx_hash: ByteArray([4; 32]),
+                block_number: 1,
+            },
+        )
+        .await
+        .unwrap();
+
+        let refunds = user_refunds(&mut db, &user, 0, 10).await.unwrap();
+        assert_eq!(
+            refunds,
+            vec![
+                UserRefund {
+                    uid: ByteArray([2; 56]),
+                    owner: contract,
+                    creation_timestamp: timestamp(2),
+                    valid_to: 2,
+                    tx_hash: None,
+                    event: Some(OrderEventLabel::RefundSubmitted),
+                    event_timestamp: Some(timestamp(10)),
+                },
+                UserRefund {
+                    uid: ByteArray([1; 56]),
+                    owner: contract,
+                    creation_timestamp: timestamp(1),
+                    valid_to: 1,
+                    tx_hash: Some(ByteArray([4; 32])),
+                    event: Some(OrderEventLabel::RefundSubmitted),
+                    event_timestamp: Some(timestamp(10)),
+                },
+            ]
+        );
+
+        let refunds = user_refunds(&mut db, &user, 1, 10).await.unwrap();
+        assert_eq!(refunds.len(), 1);
+        assert_eq!(refunds[0].uid, ByteArray([1; 56]));
+    }
 }
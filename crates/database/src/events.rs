@@ -64,6 +64,18 @@ pub async fn delete(
     ex.execute(sqlx::query(QUERY_TRADE).bind(delete_from_block_number))
         .await?;
 
+    // Reorged settlements must not keep their auction linked to their
+    // transaction, since the auction might get settled by another transaction
+    // now. The links of settlements that are still canonical get restored once
+    // they are processed again.
+    const QUERY_AUCTION_TRANSACTIONS: &str = r#"
+DELETE FROM auction_transaction a
+USING settlements s
+WHERE s.block_number >= $1 AND a.tx_from = s.tx_from AND a.tx_nonce = s.tx_nonce
+    ;"#;
+    ex.execute(sqlx::query(QUERY_AUCTION_TRANSACTIONS).bind(delete_from_block_number))
+        .await?;
+
     const QUERY_SETTLEMENTS: &str = "DELETE FROM settlements WHERE block_number >= $1;";
     ex.execute(sqlx::query(QUERY_SETTLEMENTS).bind(delete_from_block_number))
         .await?;
@@ -178,7 +190,7 @@ async fn insert_presignature(
 
 #[cfg(test)]
 mod tests {
-    use {super::*, sqlx::Connection};
+    use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
 
     #[tokio::test]
     #[ignore]
@@ -263,4 +275,50 @@ mod tests {
         }
         assert_eq!(last_block(&mut db).await.unwrap(), 2);
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_delete_unlinks_reorged_settlements() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let tx_from = ByteArray([1; 20]);
+        for block_number in [1, 2] {
+            let index = EventIndex {
+                block_number,
+                log_index: 0,
+            };
+            append(&mut db, &[(index, Event::Settlement(Default::default()))])
+                .await
+                .unwrap();
+            crate::auction_transaction::insert_settlement_tx_info(
+                &mut db,
+                block_number,
+                0,
+                &tx_from,
+                block_number,
+            )
+            .await
+            .unwrap();
+            crate::auction_transaction::upsert_auction_transaction(
+                &mut db,
+                block_number,
+                &tx_from,
+                block_number,
+            )
+            .await
+            .unwrap();
+        }
+
+        delete(&mut db, 2).await.unwrap();
+        for (nonce, auction) in [(1, Some(1)), (2, None)] {
+            assert_eq!(
+                crate::auction_transaction::get_auction_id(&mut db, &tx_from, nonce)
+                    .await
+                    .unwrap(),
+                auction
+            );
+        }
+    }
 }
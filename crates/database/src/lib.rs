@@ -1,9 +1,11 @@
+pub mod analytics;
 pub mod app_data;
 pub mod auction;
 pub mod auction_participants;
 pub mod auction_prices;
 pub mod auction_transaction;
 pub mod byte_array;
+pub mod deny_lists;
 pub mod ethflow_orders;
 pub mod events;
 pub mod onchain_broadcasted_orders;
@@ -11,13 +13,19 @@ pub mod onchain_invalidations;
 pub mod order_events;
 pub mod order_execution;
 pub mod orders;
+pub mod owner_order_cancellations;
+pub mod quote_accuracy;
 pub mod quotes;
+pub mod reorgs;
 pub mod settlement_call_data;
 pub mod settlement_observations;
 pub mod settlement_scores;
 pub mod settlements;
 pub mod solver_competition;
+pub mod solver_rewards;
+pub mod token_quality;
 pub mod trades;
+pub mod webhooks;
 
 use {
     byte_array::ByteArray,
@@ -66,6 +74,17 @@ pub const ALL_TABLES: &[&str] = &[
     "auction_prices",
     "auction_participants",
     "app_data",
+    "deny_lists",
+    "token_quality",
+    "reorgs",
+    "owner_order_cancellations",
+    "trade_analytics",
+    "settlement_analytics",
+    "analytics_progress",
+    "analytics_blocks",
+    "webhooks",
+    "webhook_deliveries",
+    "webhook_progress",
 ];
 
 /// Delete all data in the database. Only used by tests.
@@ -30,8 +30,21 @@ pub enum OrderEventLabel {
     Traded,
     /// Order was cancelled by the user.
     Cancelled,
+    /// The refunder is about to refund the expired EthFlow order.
+    RefundPending,
+    /// The refunder submitted a transaction refunding the EthFlow order.
+    RefundSubmitted,
+    /// The refunder failed to submit the refund transaction and will retry.
+    RefundFailed,
+    /// The EthFlow order got refunded on-chain.
+    Refunded,
 }
 
+/// SQL condition matching the events that describe the progress of refunding
+/// an EthFlow order.
+pub const REFUND_LABELS: &str =
+    "label IN ('refundpending', 'refundsubmitted', 'refundfailed', 'refunded')";
+
 /// Contains a single event of the life cycle of an order and when it was
 /// registered.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type, sqlx::FromRow)]
@@ -66,3 +79,102 @@ VALUES ($1, $2, $3)
         .await
         .map(|_| ())
 }
+
+/// Inserts a refund related event unless it is already the latest refund
+/// related event of the order. This keeps the refunder from recording the same
+/// state of an order again every time it checks it. Returns whether the event
+/// got inserted.
+pub async fn insert_refund_event(
+    ex: &mut PgConnection,
+    event: &OrderEvent,
+) -> Result<bool, sqlx::Error> {
+    const QUERY: &str = const_format::concatcp!(
+        r#"
+INSERT INTO order_events (order_uid, timestamp, label)
+SELECT $1, $2, $3
+WHERE $3 IS DISTINCT FROM (
+    SELECT label FROM order_events
+    WHERE order_uid = $1 AND "#,
+        REFUND_LABELS,
+        r#"
+    ORDER BY timestamp DESC
+    LIMIT 1
+)
+"#
+    );
+    let result = sqlx::query(QUERY)
+        .bind(event.order_uid)
+        .bind(event.timestamp)
+        .bind(event.label)
+        .execute(ex)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns the latest refund related event of the order.
+pub async fn latest_refund_event(
+    ex: &mut PgConnection,
+    order_uid: &OrderUid,
+) -> Result<Option<OrderEvent>, sqlx::Error> {
+    const QUERY: &str = const_format::concatcp!(
+        "SELECT order_uid, timestamp, label FROM order_events WHERE order_uid = $1 AND ",
+        REFUND_LABELS,
+        " ORDER BY timestamp DESC LIMIT 1"
+    );
+    sqlx::query_as(QUERY)
+        .bind(order_uid)
+        .fetch_optional(ex)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::byte_array::ByteArray, chrono::TimeZone, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_refund_events() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let uid = ByteArray([1; 56]);
+        let event = |timestamp: i64, label| OrderEvent {
+            order_uid: uid,
+            timestamp: Utc.timestamp_opt(timestamp, 0).unwrap(),
+            label,
+        };
+        assert_eq!(latest_refund_event(&mut db, &uid).await.unwrap(), None);
+
+        let pending = event(1, OrderEventLabel::RefundPending);
+        assert!(insert_refund_event(&mut db, &pending).await.unwrap());
+        // The same state doesn't get recorded twice in a row.
+        assert!(
+            !insert_refund_event(&mut db, &event(2, OrderEventLabel::RefundPending))
+                .await
+                .unwrap()
+        );
+        // Other events don't affect the refund state.
+        insert_order_event(&mut db, &event(3, OrderEventLabel::Invalid))
+            .await
+            .unwrap();
+        assert_eq!(
+            latest_refund_event(&mut db, &uid).await.unwrap(),
+            Some(pending)
+        );
+
+        let failed = event(4, OrderEventLabel::RefundFailed);
+        assert!(insert_refund_event(&mut db, &failed).await.unwrap());
+        assert!(
+            insert_refund_event(&mut db, &event(5, OrderEventLabel::RefundPending))
+                .await
+                .unwrap()
+        );
+        let refunded = event(6, OrderEventLabel::Refunded);
+        assert!(insert_refund_event(&mut db, &refunded).await.unwrap());
+        assert_eq!(
+            latest_refund_event(&mut db, &uid).await.unwrap(),
+            Some(refunded)
+        );
+    }
+}
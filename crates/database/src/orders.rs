@@ -2,6 +2,7 @@ use {
     crate::{
         auction::AuctionId,
         onchain_broadcasted_orders::OnchainOrderPlacementError,
+        order_events::{OrderEventLabel, REFUND_LABELS},
         Address,
         AppId,
         OrderUid,
@@ -319,6 +320,8 @@ pub struct Quote {
     pub sell_amount: BigDecimal,
     pub buy_amount: BigDecimal,
     pub solver: Address,
+    pub estimator: Option<String>,
+    pub estimator_latency_ms: Option<i64>,
 }
 
 pub async fn insert_quotes(ex: &mut PgConnection, quotes: &[Quote]) -> Result<(), sqlx::Error> {
@@ -336,9 +339,11 @@ INSERT INTO order_quotes (
     sell_token_price,
     sell_amount,
     buy_amount,
-    solver
+    solver,
+    estimator,
+    estimator_latency_ms
 )
-VALUES ($1, $2, $3, $4, $5, $6, $7)"#;
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#;
 
 pub async fn insert_quote_and_update_on_conflict(
     ex: &mut PgConnection,
@@ -352,7 +357,8 @@ pub async fn insert_quote_and_update_on_conflict(
         " ON CONFLICT (order_uid) DO UPDATE
 SET gas_amount = $2, gas_price = $3,
 sell_token_price = $4, sell_amount = $5,
-buy_amount = $6
+buy_amount = $6, estimator = $8,
+estimator_latency_ms = $9
     "
     );
     sqlx::query(QUERY)
@@ -363,6 +369,8 @@ buy_amount = $6
         .bind(&quote.sell_amount)
         .bind(&quote.buy_amount)
         .bind(quote.solver)
+        .bind(&quote.estimator)
+        .bind(quote.estimator_latency_ms)
         .execute(ex)
         .await?;
     Ok(())
@@ -377,6 +385,8 @@ pub async fn insert_quote(ex: &mut PgConnection, quote: &Quote) -> Result<(), sq
         .bind(&quote.sell_amount)
         .bind(&quote.buy_amount)
         .bind(quote.solver)
+        .bind(&quote.estimator)
+        .bind(quote.estimator_latency_ms)
         .execute(ex)
         .await?;
     Ok(())
@@ -453,6 +463,8 @@ pub struct FullOrder {
     pub pre_interactions: Vec<RawInteraction>,
     pub post_interactions: Vec<RawInteraction>,
     pub ethflow_data: Option<(Option<TransactionHash>, i64)>,
+    /// The latest event recorded while refunding the EthFlow order.
+    pub refund_event: Option<OrderEventLabel>,
     pub onchain_user: Option<Address>,
     pub onchain_placement_error: Option<OnchainOrderPlacementError>,
     pub executed_surplus_fee: BigDecimal,
@@ -493,7 +505,8 @@ impl FullOrder {
 // SET enable_nestloop = false;
 // to get a better idea of what indexes postgres *could* use even if it decides
 // that with the current amount of data this wouldn't be better.
-const ORDERS_SELECT: &str = r#"
+const ORDERS_SELECT: &str = const_format::concatcp!(
+    r#"
 o.uid, o.owner, o.creation_timestamp, o.sell_token, o.buy_token, o.sell_amount, o.buy_amount,
 o.valid_to, o.app_data, o.fee_amount, o.full_fee_amount, o.kind, o.partially_fillable, o.signature,
 o.receiver, o.signing_scheme, o.settlement_contract, o.sell_token_balance, o.buy_token_balance,
@@ -517,12 +530,17 @@ array(Select (p.target, p.value, p.data) from interactions p where p.order_uid =
 (SELECT (tx_hash, eth_o.valid_to) from ethflow_orders eth_o
     left join ethflow_refunds on ethflow_refunds.order_uid=eth_o.uid
     where eth_o.uid = o.uid limit 1) as ethflow_data,
+(SELECT label FROM order_events WHERE order_uid = o.uid AND "#,
+    REFUND_LABELS,
+    r#"
+    ORDER BY timestamp DESC LIMIT 1) as refund_event,
 (SELECT onchain_o.sender from onchain_placed_orders onchain_o where onchain_o.uid = o.uid limit 1) as onchain_user,
 (SELECT onchain_o.placement_error from onchain_placed_orders onchain_o where onchain_o.uid = o.uid limit 1) as onchain_placement_error,
 COALESCE((SELECT SUM(surplus_fee) FROM order_execution oe WHERE oe.order_uid = o.uid), 0) as executed_surplus_fee,
 COALESCE((SELECT SUM(solver_fee) FROM order_execution oe WHERE oe.order_uid = o.uid), 0) as executed_solver_fee,
 (SELECT full_app_data FROM app_data ad WHERE o.app_data = ad.contract_app_data LIMIT 1) as full_app_data
-"#;
+"#
+);
 
 const ORDERS_FROM: &str = "orders o";
 
@@ -910,6 +928,23 @@ mod tests {
             Some((Some(Default::default()), user_valid_to)),
             order_.ethflow_data
         );
+        assert_eq!(order_.refund_event, None);
+
+        crate::order_events::insert_refund_event(
+            &mut db,
+            &crate::order_events::OrderEvent {
+                order_uid: order.uid,
+                timestamp: Utc::now(),
+                label: OrderEventLabel::Refunded,
+            },
+        )
+        .await
+        .unwrap();
+        let order_ = single_full_order(&mut db, &order.uid)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(order_.refund_event, Some(OrderEventLabel::Refunded));
     }
 
     #[tokio::test]
@@ -1155,6 +1190,8 @@ mod tests {
             sell_amount: 4.into(),
             buy_amount: 5.into(),
             solver: ByteArray([1; 20]),
+            estimator: Some("Baseline".to_string()),
+            estimator_latency_ms: Some(100),
         };
         insert_quote(&mut db, &quote).await.unwrap();
         insert_quote_and_update_on_conflict(&mut db, &quote)
@@ -1192,6 +1229,8 @@ mod tests {
             sell_amount: 4.into(),
             buy_amount: 5.into(),
             solver: ByteArray([1; 20]),
+            estimator: Some("Baseline".to_string()),
+            estimator_latency_ms: Some(100),
         };
         insert_quote(&mut db, &quote).await.unwrap();
         let quote_ = read_quote(&mut db, &quote.order_uid)
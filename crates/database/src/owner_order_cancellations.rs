@@ -0,0 +1,183 @@
+//! Cancellations of all off-chain orders of an owner that were created before
+//! a point in time.
+
+use {
+    crate::{Address, OrderUid},
+    chrono::{DateTime, Utc},
+    sqlx::PgConnection,
+};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, sqlx::FromRow)]
+pub struct OwnerOrderCancellation {
+    pub owner: Address,
+    pub cancelled_before: DateTime<Utc>,
+    /// `None` matches orders selling any token.
+    pub sell_token: Option<Address>,
+    /// `None` matches orders buying any token.
+    pub buy_token: Option<Address>,
+}
+
+/// Stores the cancellation unless there already is one with the same filters
+/// that cancels orders created up to a later point in time.
+pub async fn upsert(
+    ex: &mut PgConnection,
+    cancellation: &OwnerOrderCancellation,
+    submitted_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO owner_order_cancellations (owner, cancelled_before, sell_token, buy_token, submitted_at)
+VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (owner, COALESCE(sell_token, ''::bytea), COALESCE(buy_token, ''::bytea)) DO UPDATE
+SET cancelled_before = EXCLUDED.cancelled_before, submitted_at = EXCLUDED.submitted_at
+WHERE owner_order_cancellations.cancelled_before < EXCLUDED.cancelled_before
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(cancellation.owner)
+        .bind(cancellation.cancelled_before)
+        .bind(cancellation.sell_token)
+        .bind(cancellation.buy_token)
+        .bind(submitted_at)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Cancels all ECDSA signed orders that are covered by the cancellation and
+/// are neither cancelled nor expired yet. Returns the UIDs of the orders that
+/// were cancelled.
+pub async fn cancel_orders(
+    ex: &mut PgConnection,
+    cancellation: &OwnerOrderCancellation,
+    now: DateTime<Utc>,
+) -> Result<Vec<OrderUid>, sqlx::Error> {
+    const QUERY: &str = r#"
+UPDATE orders
+SET cancellation_timestamp = $1
+WHERE owner = $2
+AND creation_timestamp < $3
+AND ($4::bytea IS NULL OR sell_token = $4)
+AND ($5::bytea IS NULL OR buy_token = $5)
+AND signing_scheme IN ('eip712', 'ethsign')
+AND valid_to >= $6
+AND cancellation_timestamp IS NULL
+RETURNING uid
+    ;"#;
+    sqlx::query_scalar(QUERY)
+        .bind(now)
+        .bind(cancellation.owner)
+        .bind(cancellation.cancelled_before)
+        .bind(cancellation.sell_token)
+        .bind(cancellation.buy_token)
+        .bind(now.timestamp())
+        .fetch_all(ex)
+        .await
+}
+
+/// Fetches the latest cancellation of every owner and token filter
+/// combination. Older cancellations with the same filters are superseded by
+/// these.
+pub async fn fetch_latest(
+    ex: &mut PgConnection,
+) -> Result<Vec<OwnerOrderCancellation>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT owner, cancelled_before, sell_token, buy_token
+FROM owner_order_cancellations
+    ;"#;
+    sqlx::query_as(QUERY).fetch_all(ex).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            byte_array::ByteArray,
+            orders::{self, Order, SigningScheme},
+        },
+        chrono::TimeZone,
+        sqlx::Connection,
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_cancel_orders() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let owner = ByteArray([1; 20]);
+        let other = ByteArray([9; 20]);
+        let time = |seconds| Utc.timestamp_opt(seconds, 0).unwrap();
+        let now = time(1_000);
+        let cases = [
+            // Covered by the cancellation.
+            (1, owner, 10, [2; 20], SigningScheme::Eip712, 2_000),
+            // Created afterwards.
+            (2, owner, 100, [2; 20], SigningScheme::Eip712, 2_000),
+            // Different sell token.
+            (3, owner, 10, [3; 20], SigningScheme::EthSign, 2_000),
+            // Different owner.
+            (4, other, 10, [2; 20], SigningScheme::Eip712, 2_000),
+            // On-chain signature.
+            (5, owner, 10, [2; 20], SigningScheme::PreSign, 2_000),
+            // Expired.
+            (6, owner, 10, [2; 20], SigningScheme::Eip712, 500),
+        ];
+        for (uid, owner, created, sell_token, signing_scheme, valid_to) in cases {
+            orders::insert_order(
+                &mut db,
+                &Order {
+                    uid: ByteArray([uid; 56]),
+                    owner,
+                    creation_timestamp: time(created),
+                    sell_token: ByteArray(sell_token),
+                    signing_scheme,
+                    valid_to,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let cancellation = OwnerOrderCancellation {
+            owner,
+            cancelled_before: time(100),
+            sell_token: Some(ByteArray([2; 20])),
+            buy_token: None,
+        };
+        upsert(&mut db, &cancellation, now).await.unwrap();
+        let cancelled = cancel_orders(&mut db, &cancellation, now).await.unwrap();
+        assert_eq!(cancelled, vec![ByteArray([1; 56])]);
+        // Cancelling again has no effect on already cancelled orders.
+        let cancelled = cancel_orders(&mut db, &cancellation, now).await.unwrap();
+        assert!(cancelled.is_empty());
+
+        let older = OwnerOrderCancellation {
+            cancelled_before: time(50),
+            ..cancellation.clone()
+        };
+        upsert(&mut db, &older, now).await.unwrap();
+        let unfiltered = OwnerOrderCancellation {
+            sell_token: None,
+            ..older.clone()
+        };
+        upsert(&mut db, &unfiltered, now).await.unwrap();
+
+        // Replaying a request doesn't add rows.
+        upsert(&mut db, &unfiltered, now).await.unwrap();
+
+        let mut latest = fetch_latest(&mut db).await.unwrap();
+        latest.sort_by_key(|cancellation| cancellation.sell_token.is_some());
+        assert_eq!(latest, vec![unfiltered.clone(), cancellation]);
+
+        let newer = OwnerOrderCancellation {
+            cancelled_before: time(200),
+            ..unfiltered
+        };
+        upsert(&mut db, &newer, now).await.unwrap();
+        let mut latest = fetch_latest(&mut db).await.unwrap();
+        latest.sort_by_key(|cancellation| cancellation.sell_token.is_some());
+        assert_eq!(latest[0], newer);
+    }
+}
@@ -0,0 +1,157 @@
+//! Joins executed orders with the quotes they were created with to measure how
+//! well the price estimators predicted the executed prices.
+
+use {
+    crate::{orders::OrderKind, Address},
+    bigdecimal::BigDecimal,
+    sqlx::{
+        types::chrono::{DateTime, Utc},
+        PgConnection,
+    },
+};
+
+/// A market order that got (partially) executed together with the quote it was
+/// created with.
+#[derive(Clone, Debug, PartialEq, sqlx::FromRow)]
+pub struct ExecutedQuote {
+    pub estimator: String,
+    pub estimator_latency_ms: Option<i64>,
+    pub sell_token: Address,
+    pub buy_token: Address,
+    pub kind: OrderKind,
+    pub quoted_sell_amount: BigDecimal,
+    pub quoted_buy_amount: BigDecimal,
+    /// Sum of the sell amounts of all trades excluding fees.
+    pub executed_sell_amount: BigDecimal,
+    pub executed_buy_amount: BigDecimal,
+}
+
+/// Returns the executed market orders created at or after `since` whose quote
+/// records the price estimator that provided it.
+pub async fn executed_quotes(
+    ex: &mut PgConnection,
+    since: DateTime<Utc>,
+) -> Result<Vec<ExecutedQuote>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT
+    oq.estimator,
+    oq.estimator_latency_ms,
+    o.sell_token,
+    o.buy_token,
+    o.kind,
+    oq.sell_amount AS quoted_sell_amount,
+    oq.buy_amount AS quoted_buy_amount,
+    t.sell_amount AS executed_sell_amount,
+    t.buy_amount AS executed_buy_amount
+FROM orders o
+JOIN order_quotes oq ON oq.order_uid = o.uid
+JOIN LATERAL (
+    SELECT SUM(sell_amount - fee_amount) AS sell_amount, SUM(buy_amount) AS buy_amount
+    FROM trades
+    WHERE order_uid = o.uid
+) t ON t.buy_amount IS NOT NULL
+WHERE
+    o.creation_timestamp >= $1 AND
+    o.class = 'market' AND
+    oq.estimator IS NOT NULL
+    "#;
+    sqlx::query_as(QUERY).bind(since).fetch_all(ex).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            byte_array::ByteArray,
+            events::{insert_trade, EventIndex, Trade},
+            orders::{insert_order, insert_quote, Order, OrderClass, Quote},
+        },
+        chrono::{Duration, TimeZone},
+        sqlx::Connection,
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_executed_quotes() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let insert = |uid: u8, class: OrderClass, estimator: Option<&str>, age: i64| {
+            (
+                Order {
+                    uid: ByteArray([uid; 56]),
+                    sell_token: ByteArray([1; 20]),
+                    buy_token: ByteArray([2; 20]),
+                    class,
+                    creation_timestamp: now - Duration::seconds(age),
+                    ..Default::default()
+                },
+                Quote {
+                    order_uid: ByteArray([uid; 56]),
+                    sell_amount: 100.into(),
+                    buy_amount: 200.into(),
+                    estimator: estimator.map(str::to_string),
+                    estimator_latency_ms: Some(50),
+                    ..Default::default()
+                },
+            )
+        };
+        let orders = [
+            // included
+            insert(1, OrderClass::Market, Some("Baseline"), 0),
+            // no estimator
+            insert(2, OrderClass::Market, None, 0),
+            // limit order
+            insert(3, OrderClass::Limit, Some("Baseline"), 0),
+            // too old
+            insert(4, OrderClass::Market, Some("Baseline"), 60),
+            // not executed
+            insert(5, OrderClass::Market, Some("Baseline"), 0),
+        ];
+        for (i, (order, quote)) in orders.iter().enumerate() {
+            insert_order(&mut db, order).await.unwrap();
+            insert_quote(&mut db, quote).await.unwrap();
+            if order.uid.0[0] == 5 {
+                continue;
+            }
+            for log_index in 0..2 {
+                insert_trade(
+                    &mut db,
+                    &EventIndex {
+                        block_number: i as i64,
+                        log_index,
+                    },
+                    &Trade {
+                        order_uid: order.uid,
+                        sell_amount_including_fee: 55.into(),
+                        buy_amount: 99.into(),
+                        fee_amount: 5.into(),
+                    },
+                )
+                .await
+                .unwrap();
+            }
+        }
+
+        let quotes = executed_quotes(&mut db, now - Duration::seconds(30))
+            .await
+            .unwrap();
+        assert_eq!(
+            quotes,
+            vec![ExecutedQuote {
+                estimator: "Baseline".to_string(),
+                estimator_latency_ms: Some(50),
+                sell_token: ByteArray([1; 20]),
+                buy_token: ByteArray([2; 20]),
+                kind: OrderKind::Sell,
+                quoted_sell_amount: 100.into(),
+                quoted_buy_amount: 200.into(),
+                executed_sell_amount: 100.into(),
+                executed_buy_amount: 198.into(),
+            }]
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use {
-    crate::{orders::OrderKind, Address},
+    crate::{orders::OrderKind, Address, AppId},
     bigdecimal::BigDecimal,
     sqlx::{
         types::chrono::{DateTime, Utc},
@@ -34,6 +34,14 @@ pub struct Quote {
     pub expiration_timestamp: DateTime<Utc>,
     pub quote_kind: QuoteKind,
     pub solver: Address,
+    /// Whether the quote was verified by simulating the trade.
+    pub verified: bool,
+    /// Name of the price estimator that provided the winning estimate.
+    pub estimator: Option<String>,
+    /// How long the winning price estimator took to respond.
+    pub estimator_latency_ms: Option<i64>,
+    /// Hash of the hooks the trade was simulated with.
+    pub hooks_hash: Option<AppId>,
 }
 
 /// Stores the quote and returns the id. The id of the quote parameter is not
@@ -51,9 +59,13 @@ INSERT INTO quotes (
     order_kind,
     expiration_timestamp,
     quote_kind,
-    solver
+    solver,
+    verified,
+    estimator,
+    estimator_latency_ms,
+    hooks_hash
 )
-VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
 RETURNING id
     "#;
     let (id,) = sqlx::query_as(QUERY)
@@ -68,6 +80,10 @@ RETURNING id
         .bind(quote.expiration_timestamp)
         .bind(&quote.quote_kind)
         .bind(quote.solver)
+        .bind(quote.verified)
+        .bind(&quote.estimator)
+        .bind(quote.estimator_latency_ms)
+        .bind(quote.hooks_hash)
         .fetch_one(ex)
         .await?;
     Ok(id)
@@ -93,6 +109,10 @@ pub struct QuoteSearchParameters {
     pub kind: OrderKind,
     pub expiration: DateTime<Utc>,
     pub quote_kind: QuoteKind,
+    /// Hash of the order's hooks. Verified quotes with different hooks don't
+    /// cover their gas, so it gets added to their cost.
+    pub hooks_hash: Option<AppId>,
+    pub hooks_gas: i64,
 }
 
 pub async fn find(
@@ -113,7 +133,11 @@ WHERE
     order_kind = $6 AND
     expiration_timestamp >= $7 AND
     quote_kind = $8
-ORDER BY gas_amount * gas_price * sell_token_price ASC
+ORDER BY
+    (
+        gas_amount +
+        CASE WHEN verified AND hooks_hash = $9 THEN 0 ELSE $10 END
+    ) * gas_price * sell_token_price ASC
 LIMIT 1
     "#;
     sqlx::query_as(QUERY)
@@ -125,6 +149,8 @@ LIMIT 1
         .bind(params.kind)
         .bind(params.expiration)
         .bind(&params.quote_kind)
+        .bind(params.hooks_hash)
+        .bind(params.hooks_gas)
         .fetch_optional(ex)
         .await
 }
@@ -181,6 +207,10 @@ mod tests {
             expiration_timestamp: now,
             quote_kind: QuoteKind::Standard,
             solver: ByteArray([1; 20]),
+            verified: false,
+            estimator: None,
+            estimator_latency_ms: None,
+            hooks_hash: None,
         };
         let id = save(&mut db, &quote).await.unwrap();
         quote.id = id;
@@ -214,6 +244,10 @@ mod tests {
             expiration_timestamp: now,
             quote_kind: QuoteKind::Standard,
             solver: ByteArray([1; 20]),
+            verified: false,
+            estimator: None,
+            estimator_latency_ms: None,
+            hooks_hash: None,
         };
 
         let token_b = ByteArray([2; 20]);
@@ -230,6 +264,10 @@ mod tests {
             expiration_timestamp: now,
             quote_kind: QuoteKind::Standard,
             solver: ByteArray([2; 20]),
+            verified: false,
+            estimator: None,
+            estimator_latency_ms: None,
+            hooks_hash: None,
         };
 
         // Save two measurements for token_a
@@ -278,6 +316,8 @@ mod tests {
             kind: quote_a.order_kind,
             expiration: now,
             quote_kind: QuoteKind::Standard,
+            hooks_hash: None,
+            hooks_gas: 0,
         };
         assert_eq!(
             find(&mut db, &search_a).await.unwrap().unwrap(),
@@ -338,6 +378,8 @@ mod tests {
             kind: quote_b.order_kind,
             expiration: now,
             quote_kind: QuoteKind::Standard,
+            hooks_hash: None,
+            hooks_gas: 0,
         };
         assert_eq!(
             find(&mut db, &search_b).await.unwrap().unwrap(),
@@ -401,6 +443,10 @@ mod tests {
                 expiration_timestamp: now,
                 quote_kind: QuoteKind::Eip1271OnchainOrder,
                 solver: ByteArray([1; 20]),
+                verified: false,
+                estimator: None,
+                estimator_latency_ms: None,
+                hooks_hash: None,
             };
             let id = save(&mut db, &quote).await.unwrap();
             quote.id = id;
@@ -416,10 +462,66 @@ mod tests {
             kind: quote.order_kind,
             expiration: quote.expiration_timestamp,
             quote_kind: quote.quote_kind.clone(),
+            hooks_hash: None,
+            hooks_gas: 0,
         };
 
         assert_eq!(find(&mut db, &search_a).await.unwrap().unwrap(), quote,);
         search_a.quote_kind = QuoteKind::Standard;
         assert_eq!(find(&mut db, &search_a).await.unwrap(), None,);
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_find_quote_adds_gas_of_unverified_hooks() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let now = low_precision_now();
+        let hooks = ByteArray([9; 32]);
+        let save_quote = |gas_amount: f64, verified: bool| Quote {
+            id: Default::default(),
+            sell_token: ByteArray([1; 20]),
+            buy_token: ByteArray([2; 20]),
+            sell_amount: 3.into(),
+            buy_amount: 4.into(),
+            gas_amount,
+            gas_price: 1.,
+            sell_token_price: 1.,
+            order_kind: OrderKind::Sell,
+            expiration_timestamp: now,
+            quote_kind: QuoteKind::Standard,
+            solver: ByteArray([1; 20]),
+            verified,
+            estimator: None,
+            estimator_latency_ms: None,
+            hooks_hash: Some(hooks),
+        };
+        let mut verified = save_quote(100., true);
+        verified.id = save(&mut db, &verified).await.unwrap();
+        let mut unverified = save_quote(50., false);
+        unverified.id = save(&mut db, &unverified).await.unwrap();
+
+        let search = QuoteSearchParameters {
+            sell_token: verified.sell_token,
+            buy_token: verified.buy_token,
+            sell_amount_0: verified.sell_amount.clone(),
+            sell_amount_1: verified.sell_amount.clone(),
+            buy_amount: verified.buy_amount.clone(),
+            kind: verified.order_kind,
+            expiration: now,
+            quote_kind: QuoteKind::Standard,
+            hooks_hash: Some(hooks),
+            hooks_gas: 100,
+        };
+        // The verified quote already covers the hooks.
+        assert_eq!(find(&mut db, &search).await.unwrap().unwrap(), verified);
+        // With different hooks the verified quote has to pay for them, too.
+        let search = QuoteSearchParameters {
+            hooks_hash: Some(ByteArray([8; 32])),
+            ..search
+        };
+        assert_eq!(find(&mut db, &search).await.unwrap().unwrap(), unverified);
+    }
 }
@@ -0,0 +1,75 @@
+//! Audit trail of reorgs that caused indexed events to be rolled back.
+
+use {
+    crate::TransactionHash,
+    chrono::{DateTime, Utc},
+    sqlx::PgConnection,
+};
+
+/// Block hashes have the same size as transaction hashes.
+pub type BlockHash = TransactionHash;
+
+#[derive(Clone, Debug, Eq, PartialEq, sqlx::FromRow)]
+pub struct Reorg {
+    pub detected_at: DateTime<Utc>,
+    /// Which events were rolled back.
+    pub source: String,
+    /// The oldest indexed block that is no longer canonical.
+    pub reorged_block_number: i64,
+    pub reorged_block_hash: BlockHash,
+    /// Events of this block and all following blocks were replaced.
+    pub replaced_from_block: i64,
+    pub current_block_number: i64,
+    pub current_block_hash: BlockHash,
+}
+
+pub async fn insert(ex: &mut PgConnection, reorg: &Reorg) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO reorgs (detected_at, source, reorged_block_number, reorged_block_hash, replaced_from_block, current_block_number, current_block_hash)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(reorg.detected_at)
+        .bind(&reorg.source)
+        .bind(reorg.reorged_block_number)
+        .bind(reorg.reorged_block_hash)
+        .bind(reorg.replaced_from_block)
+        .bind(reorg.current_block_number)
+        .bind(reorg.current_block_hash)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::byte_array::ByteArray, chrono::TimeZone, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let reorg = |block| Reorg {
+            detected_at: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            source: "settlement_contract".to_string(),
+            reorged_block_number: block,
+            reorged_block_hash: ByteArray([1; 32]),
+            replaced_from_block: block - 1,
+            current_block_number: block + 5,
+            current_block_hash: ByteArray([2; 32]),
+        };
+        insert(&mut db, &reorg(10)).await.unwrap();
+        insert(&mut db, &reorg(20)).await.unwrap();
+
+        const QUERY: &str = r#"
+SELECT detected_at, source, reorged_block_number, reorged_block_hash, replaced_from_block, current_block_number, current_block_hash
+FROM reorgs
+ORDER BY id
+    ;"#;
+        let stored: Vec<Reorg> = sqlx::query_as(QUERY).fetch_all(&mut *db).await.unwrap();
+        assert_eq!(stored, vec![reorg(10), reorg(20)]);
+    }
+}
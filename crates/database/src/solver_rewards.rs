@@ -0,0 +1,228 @@
+use {
+    crate::{auction::AuctionId, Address, TransactionHash},
+    bigdecimal::BigDecimal,
+    sqlx::PgConnection,
+    std::ops::Range,
+};
+
+/// Everything about an auction that is needed to compute the reward of its
+/// winning solver.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct AuctionOutcome {
+    pub auction_id: AuctionId,
+    pub winner: Address,
+    pub winning_score: BigDecimal,
+    pub reference_score: BigDecimal,
+    pub block_deadline: i64,
+    /// The settlement of the auction, if it was settled on chain.
+    pub tx_hash: Option<TransactionHash>,
+    pub settlement_block: Option<i64>,
+    /// The observation of the settlement, which only exists once its block is
+    /// final.
+    pub surplus: Option<BigDecimal>,
+    pub fee: Option<BigDecimal>,
+    pub gas_used: Option<BigDecimal>,
+    pub effective_gas_price: Option<BigDecimal>,
+}
+
+/// Fetches the outcomes of all auctions whose block deadline lies in the
+/// specified range, ordered by auction ID.
+pub async fn fetch_auction_outcomes(
+    ex: &mut PgConnection,
+    block_deadlines: Range<i64>,
+) -> Result<Vec<AuctionOutcome>, sqlx::Error> {
+    // A transaction can in theory contain multiple settlements, in which case
+    // the first one counts.
+    const QUERY: &str = r#"
+SELECT DISTINCT ON (ss.auction_id)
+    ss.auction_id, ss.winner, ss.winning_score, ss.reference_score, ss.block_deadline,
+    s.tx_hash, s.block_number AS settlement_block,
+    so.surplus, so.fee, so.gas_used, so.effective_gas_price
+FROM settlement_scores ss
+LEFT OUTER JOIN auction_transaction at ON at.auction_id = ss.auction_id
+LEFT OUTER JOIN settlements s ON s.tx_from = at.tx_from AND s.tx_nonce = at.tx_nonce
+LEFT OUTER JOIN settlement_observations so
+    ON so.block_number = s.block_number AND so.log_index = s.log_index
+WHERE ss.block_deadline >= $1 AND ss.block_deadline < $2
+ORDER BY ss.auction_id, s.block_number, s.log_index
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(block_deadlines.start)
+        .bind(block_deadlines.end)
+        .fetch_all(ex)
+        .await
+}
+
+/// The number of auctions a solver participated in.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Participation {
+    pub participant: Address,
+    pub auctions: i64,
+}
+
+/// Counts the auctions that every solver participated in for all auctions
+/// whose block deadline lies in the specified range, ordered by solver.
+pub async fn fetch_participation(
+    ex: &mut PgConnection,
+    block_deadlines: Range<i64>,
+) -> Result<Vec<Participation>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT ap.participant, COUNT(*) AS auctions
+FROM auction_participants ap
+JOIN settlement_scores ss ON ss.auction_id = ap.auction_id
+WHERE ss.block_deadline >= $1 AND ss.block_deadline < $2
+GROUP BY ap.participant
+ORDER BY ap.participant
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(block_deadlines.start)
+        .bind(block_deadlines.end)
+        .fetch_all(ex)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction_participants::{self, Participant},
+            auction_transaction,
+            byte_array::ByteArray,
+            events::{self, Event, EventIndex, Settlement},
+            settlement_observations::{self, Observation},
+            settlement_scores::{self, Score},
+        },
+        sqlx::Connection,
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_auction_outcomes() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let winner = ByteArray([1; 20]);
+        for (auction_id, block_deadline) in [(1, 10), (2, 11), (3, 20)] {
+            settlement_scores::insert(
+                &mut db,
+                Score {
+                    auction_id,
+                    winner,
+                    winning_score: 10.into(),
+                    reference_score: 9.into(),
+                    block_deadline,
+                    simulation_block: 0,
+                    submission_address: winner,
+                },
+            )
+            .await
+            .unwrap();
+        }
+        auction_participants::insert(
+            &mut db,
+            &[
+                Participant {
+                    auction_id: 1,
+                    participant: winner,
+                },
+                Participant {
+                    auction_id: 2,
+                    participant: winner,
+                },
+                Participant {
+                    auction_id: 2,
+                    participant: ByteArray([2; 20]),
+                },
+            ],
+        )
+        .await
+        .unwrap();
+
+        // Auction 1 gets settled, auction 2 doesn't.
+        let event = EventIndex {
+            block_number: 8,
+            log_index: 0,
+        };
+        events::append(
+            &mut db,
+            &[(
+                event,
+                Event::Settlement(Settlement {
+                    solver: winner,
+                    transaction_hash: ByteArray([3; 32]),
+                }),
+            )],
+        )
+        .await
+        .unwrap();
+        auction_transaction::insert_settlement_tx_info(&mut db, 8, 0, &winner, 0)
+            .await
+            .unwrap();
+        auction_transaction::upsert_auction_transaction(&mut db, 1, &winner, 0)
+            .await
+            .unwrap();
+        settlement_observations::insert(
+            &mut db,
+            Observation {
+                gas_used: 1.into(),
+                effective_gas_price: 2.into(),
+                surplus: 3.into(),
+                fee: 4.into(),
+                block_number: 8,
+                log_index: 0,
+            },
+        )
+        .await
+        .unwrap();
+
+        let outcomes = fetch_auction_outcomes(&mut db, 10..20).await.unwrap();
+        assert_eq!(
+            outcomes,
+            vec![
+                AuctionOutcome {
+                    auction_id: 1,
+                    winner,
+                    winning_score: 10.into(),
+                    reference_score: 9.into(),
+                    block_deadline: 10,
+                    tx_hash: Some(ByteArray([3; 32])),
+                    settlement_block: Some(8),
+                    surplus: Some(3.into()),
+                    fee: Some(4.into()),
+                    gas_used: Some(1.into()),
+                    effective_gas_price: Some(2.into()),
+                },
+                AuctionOutcome {
+                    auction_id: 2,
+                    winner,
+                    winning_score: 10.into(),
+                    reference_score: 9.into(),
+                    block_deadline: 11,
+                    tx_hash: None,
+                    settlement_block: None,
+                    surplus: None,
+                    fee: None,
+                    gas_used: None,
+                    effective_gas_price: None,
+                },
+            ]
+        );
+
+        let participation = fetch_participation(&mut db, 10..20).await.unwrap();
+        assert_eq!(
+            participation,
+            vec![
+                Participation {
+                    participant: winner,
+                    auctions: 2,
+                },
+                Participation {
+                    participant: ByteArray([2; 20]),
+                    auctions: 1,
+                },
+            ]
+        );
+    }
+}
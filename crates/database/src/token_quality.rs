@@ -0,0 +1,129 @@
+//! Persisted results of the bad token detection.
+
+use {
+    crate::Address,
+    chrono::Utc,
+    sqlx::{types::chrono::DateTime, PgConnection},
+};
+
+#[derive(Clone, Debug, Eq, PartialEq, sqlx::FromRow)]
+pub struct TokenQuality {
+    pub token: Address,
+    pub good: bool,
+    /// Why the token is considered bad. `None` for good tokens.
+    pub reason: Option<String>,
+    /// Until when the verdict is valid.
+    pub expiration: DateTime<Utc>,
+    /// The fee in basis points the token takes on every transfer.
+    pub transfer_fee_bps: Option<i32>,
+    /// Whether taxed tokens were allowed when detecting the verdict.
+    pub allow_taxed_tokens: bool,
+}
+
+/// Inserts the token quality or replaces the existing one for the token and
+/// mode.
+pub async fn upsert(ex: &mut PgConnection, quality: &TokenQuality) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO token_quality (token, good, reason, expiration, transfer_fee_bps, allow_taxed_tokens)
+VALUES ($1, $2, $3, $4, $5, $6)
+ON CONFLICT (token, allow_taxed_tokens) DO UPDATE
+SET good = EXCLUDED.good, reason = EXCLUDED.reason, expiration = EXCLUDED.expiration,
+    transfer_fee_bps = EXCLUDED.transfer_fee_bps
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(quality.token)
+        .bind(quality.good)
+        .bind(&quality.reason)
+        .bind(quality.expiration)
+        .bind(quality.transfer_fee_bps)
+        .bind(quality.allow_taxed_tokens)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Fetches the token quality detected in the specified mode if it has not
+/// expired yet.
+pub async fn fetch(
+    ex: &mut PgConnection,
+    token: &Address,
+    allow_taxed_tokens: bool,
+    now: DateTime<Utc>,
+) -> Result<Option<TokenQuality>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT * FROM token_quality
+WHERE token = $1 AND allow_taxed_tokens = $2 AND expiration > $3
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(token)
+        .bind(allow_taxed_tokens)
+        .bind(now)
+        .fetch_optional(ex)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::byte_array::ByteArray, chrono::TimeZone, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let token = ByteArray([1; 20]);
+        let now = Utc.timestamp_opt(1_000, 0).unwrap();
+        let mut quality = TokenQuality {
+            token,
+            good: true,
+            reason: None,
+            expiration: Utc.timestamp_opt(2_000, 0).unwrap(),
+            transfer_fee_bps: None,
+            allow_taxed_tokens: true,
+        };
+        upsert(&mut db, &quality).await.unwrap();
+        assert_eq!(
+            fetch(&mut db, &token, true, now).await.unwrap(),
+            Some(quality.clone())
+        );
+
+        quality.transfer_fee_bps = Some(100);
+        upsert(&mut db, &quality).await.unwrap();
+        assert_eq!(
+            fetch(&mut db, &token, true, now).await.unwrap(),
+            Some(quality.clone())
+        );
+
+        quality.good = false;
+        quality.reason = Some("transfer fails".to_string());
+        quality.transfer_fee_bps = None;
+        upsert(&mut db, &quality).await.unwrap();
+        assert_eq!(
+            fetch(&mut db, &token, true, now).await.unwrap(),
+            Some(quality.clone())
+        );
+
+        // verdicts of the other mode are stored separately
+        let strict = TokenQuality {
+            good: true,
+            reason: None,
+            allow_taxed_tokens: false,
+            ..quality.clone()
+        };
+        upsert(&mut db, &strict).await.unwrap();
+        assert_eq!(
+            fetch(&mut db, &token, false, now).await.unwrap(),
+            Some(strict)
+        );
+        assert_eq!(
+            fetch(&mut db, &token, true, now).await.unwrap(),
+            Some(quality.clone())
+        );
+
+        // expired verdicts are ignored
+        let later = Utc.timestamp_opt(3_000, 0).unwrap();
+        assert_eq!(fetch(&mut db, &token, true, later).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,774 @@
+//! Webhooks of integrators and the queue of notifications that get delivered
+//! to them.
+//!
+//! Deliveries are created from order events and order expiries that come after
+//! the stored progress of their source. Creating the deliveries and storing
+//! the progress happens in the same transaction so every event gets turned into
+//! deliveries exactly once. Deliveries stay pending until the webhook
+//! acknowledged them which makes the delivery at-least-once.
+
+use {
+    crate::{Address, OrderUid, PgTransaction},
+    chrono::{DateTime, Utc},
+    sqlx::{Executor, PgConnection},
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "WebhookEvent")]
+#[sqlx(rename_all = "lowercase")]
+pub enum WebhookEvent {
+    /// The order got completely filled.
+    Traded,
+    /// A partially fillable order got filled without being completely filled.
+    PartiallyFilled,
+    /// The order expired without being completely filled.
+    Expired,
+    /// The order was filtered from the auction.
+    Filtered,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "WebhookDeliveryStatus")]
+#[sqlx(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    /// The delivery ran out of attempts.
+    Dead,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "WebhookSource")]
+#[sqlx(rename_all = "lowercase")]
+pub enum WebhookSource {
+    OrderEvents,
+    Expiries,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, sqlx::FromRow)]
+pub struct Webhook {
+    pub id: i64,
+    pub url: String,
+    /// `None` matches orders of any owner.
+    pub owner: Option<Address>,
+    /// `None` matches orders with any app code.
+    pub app_code: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Inserts the webhook ignoring its `id` and returns the id it got assigned.
+pub async fn insert_webhook(ex: &mut PgConnection, webhook: &Webhook) -> Result<i64, sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO webhooks (url, owner, app_code, created_at)
+VALUES ($1, $2, $3, $4)
+RETURNING id
+    ;"#;
+    sqlx::query_scalar(QUERY)
+        .bind(&webhook.url)
+        .bind(webhook.owner)
+        .bind(&webhook.app_code)
+        .bind(webhook.created_at)
+        .fetch_one(ex)
+        .await
+}
+
+pub async fn webhooks(ex: &mut PgConnection) -> Result<Vec<Webhook>, sqlx::Error> {
+    const QUERY: &str = r#"SELECT * FROM webhooks ORDER BY id;"#;
+    sqlx::query_as(QUERY).fetch_all(ex).await
+}
+
+/// Deletes the webhook together with its deliveries. Returns whether the
+/// webhook existed.
+pub async fn delete_webhook(ex: &mut PgTransaction<'_>, id: i64) -> Result<bool, sqlx::Error> {
+    const DELETE_DELIVERIES: &str = r#"DELETE FROM webhook_deliveries WHERE webhook_id = $1;"#;
+    const DELETE_WEBHOOK: &str = r#"DELETE FROM webhooks WHERE id = $1;"#;
+    ex.execute(sqlx::query(DELETE_DELIVERIES).bind(id)).await?;
+    let result = ex.execute(sqlx::query(DELETE_WEBHOOK).bind(id)).await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns up to which point in time the events of the source have been turned
+/// into deliveries.
+pub async fn progress(
+    ex: &mut PgConnection,
+    source: WebhookSource,
+) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    const QUERY: &str = r#"SELECT timestamp FROM webhook_progress WHERE source = $1;"#;
+    sqlx::query_scalar(QUERY)
+        .bind(source)
+        .fetch_optional(ex)
+        .await
+}
+
+pub async fn save_progress(
+    ex: &mut PgConnection,
+    source: WebhookSource,
+    timestamp: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO webhook_progress (source, timestamp)
+VALUES ($1, $2)
+ON CONFLICT (source) DO UPDATE SET timestamp = EXCLUDED.timestamp
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(source)
+        .bind(timestamp)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Something that happened to an order that webhooks get notified about.
+#[derive(Clone, Debug, Eq, PartialEq, sqlx::FromRow)]
+pub struct OrderEvent {
+    pub order_uid: OrderUid,
+    pub timestamp: DateTime<Utc>,
+    pub event: WebhookEvent,
+    /// The user that placed the order. For on-chain orders this is the sender
+    /// of the order placement and not the contract owning the order.
+    pub owner: Address,
+    pub full_app_data: Option<Vec<u8>>,
+}
+
+/// SQL condition that is true if the order `o` has been completely filled by
+/// the trades summed up in `t`.
+const FILLED: &str = r#"
+CASE o.kind
+    WHEN 'sell' THEN COALESCE(t.sell_amount, 0) >= o.sell_amount
+    ELSE COALESCE(t.buy_amount, 0) >= o.buy_amount
+END
+"#;
+
+/// Sums up the trades of the order `o` as `t`.
+const TRADES: &str = r#"
+LEFT JOIN LATERAL (
+    SELECT SUM(sell_amount - fee_amount) AS sell_amount, SUM(buy_amount) AS buy_amount
+    FROM trades
+    WHERE order_uid = o.uid
+) t ON true
+"#;
+
+/// Sums up the first `e.trades` trades of the order `o` as `t`, i.e. the fill
+/// of the order right after the trade of the event `e`. Sums up all trades if
+/// `e.trades` is NULL.
+const TRADES_UNTIL_EVENT: &str = r#"
+LEFT JOIN LATERAL (
+    SELECT SUM(sell_amount - fee_amount) AS sell_amount, SUM(buy_amount) AS buy_amount
+    FROM (
+        SELECT sell_amount, fee_amount, buy_amount
+        FROM trades
+        WHERE order_uid = o.uid
+        ORDER BY block_number ASC, log_index ASC
+        LIMIT e.trades
+    ) tr
+) t ON true
+"#;
+
+/// Returns the orders that traded or got filtered in `(after, until]`
+/// according to the order events. Only the first time an order got filtered in
+/// the interval is returned.
+///
+/// Whether a trade filled the order completely is decided by the fill right
+/// after that trade. The n-th traded event of an order is matched with its
+/// n-th trade, so earlier trades of an order that got filled completely later
+/// are still reported as partial fills.
+pub async fn fetch_order_events(
+    ex: &mut PgConnection,
+    after: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<OrderEvent>, sqlx::Error> {
+    const QUERY: &str = const_format::concatcp!(
+        r#"
+SELECT
+    e.order_uid,
+    e.timestamp,
+    CASE
+        WHEN e.label = 'filtered' THEN 'filtered'::WebhookEvent
+        WHEN o.partially_fillable AND NOT ("#,
+        FILLED,
+        r#") THEN 'partiallyfilled'::WebhookEvent
+        ELSE 'traded'::WebhookEvent
+    END AS event,
+    COALESCE(opo.sender, o.owner) AS owner,
+    ad.full_app_data
+FROM (
+    SELECT order_uid, label, MIN(timestamp) AS timestamp, NULL::bigint AS trades
+    FROM order_events
+    WHERE timestamp > $1 AND timestamp <= $2 AND label = 'filtered'
+    GROUP BY order_uid, label
+    UNION ALL
+    SELECT order_uid, label, timestamp, trades
+    FROM (
+        SELECT
+            order_uid, label, timestamp,
+            ROW_NUMBER() OVER (PARTITION BY order_uid ORDER BY timestamp ASC) AS trades
+        FROM order_events
+        WHERE timestamp <= $2 AND label = 'traded' AND order_uid IN (
+            SELECT order_uid FROM order_events
+            WHERE timestamp > $1 AND timestamp <= $2 AND label = 'traded'
+        )
+    ) traded
+    WHERE timestamp > $1
+) e
+JOIN orders o ON o.uid = e.order_uid
+LEFT OUTER JOIN onchain_placed_orders opo ON opo.uid = o.uid
+LEFT OUTER JOIN app_data ad ON ad.contract_app_data = o.app_data
+"#,
+        TRADES_UNTIL_EVENT,
+        r#"
+ORDER BY e.timestamp ASC
+    ;"#
+    );
+    sqlx::query_as(QUERY)
+        .bind(after)
+        .bind(until)
+        .fetch_all(ex)
+        .await
+}
+
+/// Returns the orders that expired in `(after, until]` without being
+/// completely filled or cancelled. For ethflow orders the expiry chosen by the
+/// user is used.
+pub async fn fetch_expiries(
+    ex: &mut PgConnection,
+    after: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<OrderEvent>, sqlx::Error> {
+    const QUERY: &str = const_format::concatcp!(
+        r#"
+SELECT o.uid AS order_uid, to_timestamp(o.valid_to) AS timestamp, 'expired'::WebhookEvent AS event,
+    COALESCE(opo.sender, o.owner) AS owner, ad.full_app_data
+FROM orders o
+LEFT OUTER JOIN onchain_placed_orders opo ON opo.uid = o.uid
+LEFT OUTER JOIN app_data ad ON ad.contract_app_data = o.app_data
+"#,
+        TRADES,
+        r#"
+WHERE o.valid_to > $1 AND o.valid_to <= $2
+AND o.class <> 'liquidity'
+AND o.cancellation_timestamp IS NULL
+AND NOT EXISTS (SELECT 1 FROM invalidations i WHERE i.order_uid = o.uid)
+AND NOT EXISTS (SELECT 1 FROM onchain_order_invalidations oi WHERE oi.uid = o.uid)
+AND NOT ("#,
+        FILLED,
+        r#")
+UNION ALL
+SELECT o.uid AS order_uid, to_timestamp(eo.valid_to) AS timestamp, 'expired'::WebhookEvent AS event,
+    opo.sender AS owner, ad.full_app_data
+FROM ethflow_orders eo
+JOIN orders o ON o.uid = eo.uid
+JOIN onchain_placed_orders opo ON opo.uid = o.uid
+LEFT OUTER JOIN app_data ad ON ad.contract_app_data = o.app_data
+"#,
+        TRADES,
+        r#"
+WHERE eo.valid_to > $1 AND eo.valid_to <= $2
+AND NOT opo.is_reorged
+AND NOT EXISTS (SELECT 1 FROM onchain_order_invalidations oi WHERE oi.uid = o.uid)
+AND NOT ("#,
+        FILLED,
+        r#")
+ORDER BY timestamp ASC
+    ;"#
+    );
+    sqlx::query_as(QUERY)
+        .bind(after.timestamp())
+        .bind(until.timestamp())
+        .fetch_all(ex)
+        .await
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, sqlx::FromRow)]
+pub struct Delivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub order_uid: OrderUid,
+    pub event: WebhookEvent,
+    pub event_timestamp: DateTime<Utc>,
+    /// The JSON body that gets sent to the webhook.
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// Queues a delivery of the event to the webhook unless the same event has
+/// already been queued. Webhooks get notified only once about an order getting
+/// filtered since that usually happens in many consecutive auctions. Returns
+/// whether the delivery got queued.
+pub async fn insert_delivery(
+    ex: &mut PgConnection,
+    webhook_id: i64,
+    event: &OrderEvent,
+    payload: &str,
+    now: DateTime<Utc>,
+) -> Result<bool, sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO webhook_deliveries (webhook_id, order_uid, event, event_timestamp, payload, status, attempts, next_attempt)
+SELECT $1, $2, $3, $4, $5, 'pending', 0, $6
+WHERE $3 <> 'filtered' OR NOT EXISTS (
+    SELECT 1 FROM webhook_deliveries
+    WHERE webhook_id = $1 AND order_uid = $2 AND event = 'filtered'
+)
+ON CONFLICT DO NOTHING
+    ;"#;
+    let result = sqlx::query(QUERY)
+        .bind(webhook_id)
+        .bind(event.order_uid)
+        .bind(event.event)
+        .bind(event.timestamp)
+        .bind(payload)
+        .bind(now)
+        .execute(ex)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// A pending delivery together with the webhook it has to be sent to.
+#[derive(Clone, Debug, Eq, PartialEq, sqlx::FromRow)]
+pub struct DueDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub url: String,
+    pub payload: String,
+    pub attempts: i32,
+}
+
+/// Returns up to `limit` pending deliveries whose next attempt is due, the
+/// longest overdue first.
+pub async fn due_deliveries(
+    ex: &mut PgConnection,
+    now: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<DueDelivery>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT d.id, d.webhook_id, w.url, d.payload, d.attempts
+FROM webhook_deliveries d
+JOIN webhooks w ON w.id = d.webhook_id
+WHERE d.status = 'pending' AND d.next_attempt <= $1
+ORDER BY d.next_attempt ASC
+LIMIT $2
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(now)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
+pub async fn mark_delivered(
+    ex: &mut PgConnection,
+    id: i64,
+    now: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+UPDATE webhook_deliveries
+SET status = 'delivered', attempts = attempts + 1, delivered_at = $2, last_error = NULL
+WHERE id = $1
+    ;"#;
+    sqlx::query(QUERY).bind(id).bind(now).execute(ex).await?;
+    Ok(())
+}
+
+/// Records a failed attempt. The delivery gets retried at `next_attempt` or
+/// moves to the dead letters if that is `None`.
+pub async fn mark_failed(
+    ex: &mut PgConnection,
+    id: i64,
+    error: &str,
+    next_attempt: Option<DateTime<Utc>>,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+UPDATE webhook_deliveries
+SET
+    status = CASE WHEN $3::timestamptz IS NULL THEN 'dead' ELSE 'pending' END::WebhookDeliveryStatus,
+    attempts = attempts + 1,
+    last_error = $2,
+    next_attempt = COALESCE($3, next_attempt)
+WHERE id = $1
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(id)
+        .bind(error)
+        .bind(next_attempt)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
+/// Returns the deliveries that ran out of attempts, the most recent first.
+pub async fn dead_deliveries(
+    ex: &mut PgConnection,
+    webhook_id: Option<i64>,
+    offset: i64,
+    limit: i64,
+) -> Result<Vec<Delivery>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT * FROM webhook_deliveries
+WHERE status = 'dead' AND ($1::bigint IS NULL OR webhook_id = $1)
+ORDER BY id DESC
+LIMIT $2
+OFFSET $3
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(webhook_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(ex)
+        .await
+}
+
+/// Moves a dead delivery back into the queue with a fresh set of attempts.
+/// Returns whether a dead delivery with this id existed.
+pub async fn retry_delivery(
+    ex: &mut PgConnection,
+    id: i64,
+    now: DateTime<Utc>,
+) -> Result<bool, sqlx::Error> {
+    const QUERY: &str = r#"
+UPDATE webhook_deliveries
+SET status = 'pending', attempts = 0, next_attempt = $2
+WHERE id = $1 AND status = 'dead'
+    ;"#;
+    let result = sqlx::query(QUERY).bind(id).bind(now).execute(ex).await?;
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            byte_array::ByteArray,
+            events::{insert_trade, EventIndex, Trade},
+            order_events::{insert_order_event, OrderEvent as DbOrderEvent, OrderEventLabel},
+            orders::{insert_order, Order},
+        },
+        chrono::{Duration, TimeZone},
+        sqlx::Connection,
+    };
+
+    fn timestamp(seconds: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(seconds, 0).unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_webhooks_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let webhook = Webhook {
+            url: "https://example.com".to_string(),
+            owner: Some(ByteArray([1; 20])),
+            app_code: None,
+            created_at: timestamp(1),
+            ..Default::default()
+        };
+        let id = insert_webhook(&mut db, &webhook).await.unwrap();
+        assert_eq!(
+            webhooks(&mut db).await.unwrap(),
+            vec![Webhook { id, ..webhook }]
+        );
+
+        assert!(delete_webhook(&mut db, id).await.unwrap());
+        assert!(!delete_webhook(&mut db, id).await.unwrap());
+        assert!(webhooks(&mut db).await.unwrap().is_empty());
+
+        assert_eq!(
+            progress(&mut db, WebhookSource::Expiries).await.unwrap(),
+            None
+        );
+        save_progress(&mut db, WebhookSource::Expiries, timestamp(2))
+            .await
+            .unwrap();
+        save_progress(&mut db, WebhookSource::Expiries, timestamp(3))
+            .await
+            .unwrap();
+        assert_eq!(
+            progress(&mut db, WebhookSource::Expiries).await.unwrap(),
+            Some(timestamp(3))
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_fetch_order_events() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let partially_filled = Order {
+            uid: ByteArray([1; 56]),
+            owner: ByteArray([1; 20]),
+            kind: crate::orders::OrderKind::Sell,
+            sell_amount: 100.into(),
+            partially_fillable: true,
+            ..Default::default()
+        };
+        let filtered = Order {
+            uid: ByteArray([2; 56]),
+            ..partially_filled.clone()
+        };
+        let filled_later = Order {
+            uid: ByteArray([3; 56]),
+            ..partially_filled.clone()
+        };
+        for order in [&partially_filled, &filtered, &filled_later] {
+            insert_order(&mut db, order).await.unwrap();
+        }
+        let trades = [
+            (partially_filled.uid, 0),
+            (filled_later.uid, 1),
+            (filled_later.uid, 2),
+        ];
+        for (order_uid, log_index) in trades {
+            insert_trade(
+                &mut db,
+                &EventIndex {
+                    block_number: 0,
+                    log_index,
+                },
+                &Trade {
+                    order_uid,
+                    sell_amount_including_fee: 50.into(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+        let events = [
+            (partially_filled.uid, 2, OrderEventLabel::Traded),
+            (filled_later.uid, 2, OrderEventLabel::Traded),
+            (filled_later.uid, 3, OrderEventLabel::Traded),
+            (filtered.uid, 2, OrderEventLabel::Filtered),
+            (filtered.uid, 3, OrderEventLabel::Filtered),
+            (filtered.uid, 3, OrderEventLabel::Ready),
+            // outside of the interval
+            (filtered.uid, 1, OrderEventLabel::Traded),
+            (filtered.uid, 5, OrderEventLabel::Traded),
+        ];
+        for (order_uid, seconds, label) in events {
+            insert_order_event(
+                &mut db,
+                &DbOrderEvent {
+                    order_uid,
+                    timestamp: timestamp(seconds),
+                    label,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let mut events = fetch_order_events(&mut db, timestamp(1), timestamp(4))
+            .await
+            .unwrap();
+        events.sort_by_key(|event| event.order_uid);
+        assert_eq!(
+            events,
+            vec![
+                OrderEvent {
+                    order_uid: partially_filled.uid,
+                    timestamp: timestamp(2),
+                    event: WebhookEvent::PartiallyFilled,
+                    owner: ByteArray([1; 20]),
+                    full_app_data: None,
+                },
+                OrderEvent {
+                    order_uid: filtered.uid,
+                    timestamp: timestamp(2),
+                    event: WebhookEvent::Filtered,
+                    owner: ByteArray([1; 20]),
+                    full_app_data: None,
+                },
+                // Only the second trade filled the order completely.
+                OrderEvent {
+                    order_uid: filled_later.uid,
+                    timestamp: timestamp(2),
+                    event: WebhookEvent::PartiallyFilled,
+                    owner: ByteArray([1; 20]),
+                    full_app_data: None,
+                },
+                OrderEvent {
+                    order_uid: filled_later.uid,
+                    timestamp: timestamp(3),
+                    event: WebhookEvent::Traded,
+                    owner: ByteArray([1; 20]),
+                    full_app_data: None,
+                },
+            ]
+        );
+
+        // Trades of earlier intervals count towards the fill.
+        let mut events = fetch_order_events(&mut db, timestamp(2), timestamp(4))
+            .await
+            .unwrap();
+        events.sort_by_key(|event| event.order_uid);
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| (event.order_uid, event.event))
+                .collect::<Vec<_>>(),
+            vec![
+                (filtered.uid, WebhookEvent::Filtered),
+                (filled_later.uid, WebhookEvent::Traded),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_fetch_expiries() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let order = |uid: u8, valid_to: i64| Order {
+            uid: ByteArray([uid; 56]),
+            kind: crate::orders::OrderKind::Sell,
+            sell_amount: 100.into(),
+            valid_to,
+            ..Default::default()
+        };
+        let expired = order(1, 10);
+        let cancelled = Order {
+            cancellation_timestamp: Some(timestamp(0)),
+            ..order(2, 10)
+        };
+        let filled = order(3, 10);
+        let later = order(4, 20);
+        for order in [&expired, &cancelled, &filled, &later] {
+            insert_order(&mut db, order).await.unwrap();
+        }
+        insert_trade(
+            &mut db,
+            &EventIndex::default(),
+            &Trade {
+                order_uid: filled.uid,
+                sell_amount_including_fee: 100.into(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let expiries = fetch_expiries(&mut db, timestamp(5), timestamp(15))
+            .await
+            .unwrap();
+        assert_eq!(
+            expiries,
+            vec![OrderEvent {
+                order_uid: expired.uid,
+                timestamp: timestamp(10),
+                event: WebhookEvent::Expired,
+                owner: Default::default(),
+                full_app_data: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_delivery_lifecycle() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let webhook_id = insert_webhook(
+            &mut db,
+            &Webhook {
+                url: "https://example.com".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let now = timestamp(100);
+        let event = |seconds: i64, event| OrderEvent {
+            order_uid: ByteArray([1; 56]),
+            timestamp: timestamp(seconds),
+            event,
+            owner: Default::default(),
+            full_app_data: None,
+        };
+
+        let traded = event(1, WebhookEvent::Traded);
+        assert!(insert_delivery(&mut db, webhook_id, &traded, "{}", now)
+            .await
+            .unwrap());
+        // The same event only gets queued once.
+        assert!(!insert_delivery(&mut db, webhook_id, &traded, "{}", now)
+            .await
+            .unwrap());
+        // Orders only get reported as filtered once.
+        assert!(insert_delivery(
+            &mut db,
+            webhook_id,
+            &event(2, WebhookEvent::Filtered),
+            "{}",
+            now
+        )
+        .await
+        .unwrap());
+        assert!(!insert_delivery(
+            &mut db,
+            webhook_id,
+            &event(3, WebhookEvent::Filtered),
+            "{}",
+            now
+        )
+        .await
+        .unwrap());
+
+        let due = due_deliveries(&mut db, now, 10).await.unwrap();
+        assert_eq!(due.len(), 2);
+        assert!(due_deliveries(&mut db, now - Duration::seconds(1), 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        mark_delivered(&mut db, due[0].id, now).await.unwrap();
+        mark_failed(
+            &mut db,
+            due[1].id,
+            "timeout",
+            Some(now + Duration::seconds(10)),
+        )
+        .await
+        .unwrap();
+        assert!(due_deliveries(&mut db, now, 10).await.unwrap().is_empty());
+        let due = due_deliveries(&mut db, now + Duration::seconds(10), 10)
+            .await
+            .unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].attempts, 1);
+
+        mark_failed(&mut db, due[0].id, "status 500", None)
+            .await
+            .unwrap();
+        let dead = dead_deliveries(&mut db, Some(webhook_id), 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].id, due[0].id);
+        assert_eq!(dead[0].attempts, 2);
+        assert_eq!(dead[0].last_error.as_deref(), Some("status 500"));
+        assert!(dead_deliveries(&mut db, Some(webhook_id + 1), 0, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert!(retry_delivery(&mut db, dead[0].id, now).await.unwrap());
+        assert!(!retry_delivery(&mut db, dead[0].id, now).await.unwrap());
+        assert!(dead_deliveries(&mut db, None, 0, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(due_deliveries(&mut db, now, 10).await.unwrap().len(), 1);
+    }
+}
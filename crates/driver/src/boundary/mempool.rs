@@ -2,7 +2,7 @@ use {
     crate::{
         boundary::{self, Result},
         domain::{competition::solution::settlement::Settlement, eth, mempools},
-        infra::{blockchain::Ethereum, solver::Solver},
+        infra::{blockchain::Ethereum, solver::Lease},
     },
     async_trait::async_trait,
     ethcontract::{transaction::TransactionBuilder, transport::DynTransport},
@@ -128,16 +128,16 @@ impl Mempool {
         })
     }
 
-    /// Publish the settlement and wait for it to be confirmed.
+    /// Publish the settlement from the specified account and wait for it to
+    /// be confirmed.
     pub async fn execute(
         &self,
-        solver: &Solver,
+        account: &Lease,
         settlement: Settlement,
     ) -> Result<eth::TxId, mempools::Error> {
         let web3 = boundary::web3(&self.eth);
-        let nonce = web3
-            .eth()
-            .transaction_count(solver.address().into(), None)
+        let nonce = account
+            .nonce(&self.eth)
             .await
             .map_err(anyhow::Error::from)?;
         let max_fee_per_gas = eth::U256::from(settlement.gas.price).to_f64_lossy();
@@ -161,10 +161,9 @@ impl Mempool {
         };
         let estimator = AccessListEstimator(settlement.access_list.clone());
         // TODO: move tx submission logic from legacy code into the driver (#1543)
-        let account = solver.account();
         let submitter = Submitter::new(
             self.eth.contracts().settlement(),
-            &account,
+            account.account(),
             nonce,
             self.submit_api.as_ref(),
             &gas_price_estimator,
@@ -193,9 +192,13 @@ impl Mempool {
             .await
             .map_err(|err| match err {
                 SubmissionError::SimulationRevert(_) => mempools::Error::SimulationRevert,
-                SubmissionError::Revert(hash) => mempools::Error::Revert(hash.into()),
+                SubmissionError::Revert(hash) => {
+                    account.mined(nonce);
+                    mempools::Error::Revert(hash.into())
+                }
                 _ => mempools::Error::Other(anyhow::Error::from(err)),
             })?;
+        account.mined(nonce);
         Ok(receipt.transaction_hash.into())
     }
 
@@ -268,6 +268,7 @@ impl Tokens {
             price: None,
             available_balance: Default::default(),
             trusted: false,
+            transfer_fee: None,
         })
     }
 
@@ -286,6 +287,32 @@ pub struct Token {
     pub available_balance: eth::U256,
     /// Is this token well-known and trusted by the protocol?
     pub trusted: bool,
+    /// The fee charged by the token contract on every transfer, if this is a
+    /// fee-on-transfer token.
+    pub transfer_fee: Option<TransferFee>,
+}
+
+/// A fee in basis points that a token contract deducts from every transferred
+/// amount, meaning that the receiver gets less than what was sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferFee(u32);
+
+impl TransferFee {
+    pub fn from_bps(bps: u32) -> Self {
+        Self(bps.min(10_000))
+    }
+
+    pub fn bps(self) -> u32 {
+        self.0
+    }
+
+    /// The amount lost when transferring `amount` of the token, rounded up.
+    pub fn loss(self, amount: eth::U256) -> eth::U256 {
+        let bps = eth::U256::from(self.0);
+        let whole = amount / 10_000 * bps;
+        let remainder = (amount % 10_000 * bps + 9_999) / 10_000;
+        whole + remainder
+    }
 }
 
 /// The price of a token in wei. This represents how much wei is needed to buy
@@ -1,10 +1,11 @@
 use {
     self::solution::settlement,
-    super::Mempools,
+    super::{mempools, Mempools},
     crate::{
-        domain::{competition::solution::Settlement, eth},
+        domain::{competition::solution::Settlement, eth, liquidity},
         infra::{
             self,
+            archive,
             blockchain::Ethereum,
             notify,
             observe,
@@ -13,10 +14,13 @@ use {
         },
         util::Bytes,
     },
-    futures::{future::join_all, StreamExt},
+    futures::{future::join_all, stream::FuturesUnordered, StreamExt},
     itertools::Itertools,
     rand::seq::SliceRandom,
-    std::{collections::HashSet, sync::Mutex},
+    std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+    },
     tap::TapFallible,
 };
 
@@ -44,12 +48,31 @@ pub struct Competition {
     pub liquidity: infra::liquidity::Fetcher,
     pub simulator: Simulator,
     pub mempools: Mempools,
-    pub settlement: Mutex<Option<Settlement>>,
+    /// The settlement of the last solved auction. Shared with the competition
+    /// replacing this one when the driver config gets reloaded, so that
+    /// reloading doesn't affect ongoing auctions.
+    pub settlement: Arc<Mutex<Option<Candidate>>>,
+    /// Archives the liquidity and the solver communication of every round, if
+    /// configured.
+    pub archive: Option<infra::Archive>,
+    /// How long it took to finish the last round of a streaming solver after
+    /// its solutions stopped being collected, i.e. to encode the remaining
+    /// solutions, merge and score them. Streaming solvers are only read until
+    /// this much time is left before the deadline. Shared like the settlement.
+    pub post_processing: Arc<Mutex<std::time::Duration>>,
 }
 
 impl Competition {
     /// Solve an auction as part of this competition.
     pub async fn solve(&self, auction: &Auction) -> Result<Option<Solved>, Error> {
+        // Fail fast without fetching liquidity if the solver is unhealthy.
+        if !self.solver.is_available() {
+            let err = Error::from(solver::Error::Unavailable);
+            self.archive_outcome(auction, || archive::Outcome::failed(&err));
+            return Err(err);
+        }
+
+        let block = self.eth.current_block().borrow().number;
         let liquidity = match self.solver.liquidity() {
             solver::Liquidity::Fetch => {
                 self.liquidity
@@ -61,97 +84,45 @@ impl Competition {
             }
             solver::Liquidity::Skip => Default::default(),
         };
+        if let (Some(archive), Some(id)) = (&self.archive, auction.id()) {
+            archive.save_liquidity(self.solver.name(), id, block, &liquidity);
+        }
 
-        // Fetch the solutions from the solver.
-        let solutions = self
-            .solver
-            .solve(auction, &liquidity, auction.deadline().timeout()?)
-            .await?;
-
-        // Discard solutions that don't have unique ID.
-        let mut ids = HashSet::new();
-        let solutions = solutions.into_iter().filter(|solution| {
-            if !ids.insert(solution.id()) {
-                observe::duplicated_solution_id(self.solver.name(), solution.id());
-                notify::duplicated_solution_id(&self.solver, auction.id(), solution.id());
-                false
-            } else {
-                true
-            }
-        });
-
-        // Empty solutions aren't useful, so discard them.
-        let solutions = solutions.filter(|solution| {
-            if solution.is_empty() {
-                observe::empty_solution(self.solver.name(), solution.id());
-                notify::empty_solution(&self.solver, auction.id(), solution.id());
-                false
-            } else {
-                true
-            }
-        });
-
-        // Encode the solutions into settlements.
-        let settlements = join_all(solutions.map(|solution| async move {
-            observe::encoding(solution.id());
-            (
-                solution.id(),
-                solution.encode(auction, &self.eth, &self.simulator).await,
+        // Fetch the solutions from the solver and encode them into settlements.
+        let (mut settlements, collected) = if self.solver.streams() {
+            let (settlements, collected) = self.solve_streaming(auction, &liquidity).await?;
+            (settlements, Some(collected))
+        } else {
+            let response = self.exchange(auction, &liquidity).await?;
+            let solutions = self.solver.solutions(&response, auction, &liquidity)?;
+            let solutions = self.discard_invalid(auction, solutions, &mut HashSet::new());
+            let settlements = join_all(
+                solutions
+                    .into_iter()
+                    .map(|solution| self.encode(auction, solution)),
             )
-        }))
-        .await;
-
-        // Filter out solutions that failed to encode.
-        let mut settlements = settlements
+            .await
             .into_iter()
-            .filter_map(|(id, result)| {
-                result
-                    .tap_err(|err| {
-                        observe::encoding_failed(self.solver.name(), id, err);
-                        notify::encoding_failed(&self.solver, auction.id(), id, err);
-                    })
-                    .ok()
-            })
+            .flatten()
             .collect_vec();
+            (settlements, None)
+        };
 
         // TODO(#1483): parallelize this
         // TODO(#1480): more optimal approach for settlement merging
 
         // Merge the settlements in random order.
         settlements.shuffle(&mut rand::thread_rng());
-
-        // The merging algorithm works as follows: the [`settlements`] vector keeps the
-        // "most merged" settlements until they can't be merged anymore, at
-        // which point they are moved into the [`results`] vector.
-
-        // The merged settlements in their final form.
-        let mut results = Vec::new();
-        while let Some(settlement) = settlements.pop() {
-            // Has [`settlement`] been merged into another settlement?
-            let mut merged = false;
-            // Try to merge [`settlement`] into some other settlement.
-            for other in settlements.iter_mut() {
-                match other.merge(&settlement, &self.eth, &self.simulator).await {
-                    Ok(m) => {
-                        *other = m;
-                        merged = true;
-                        observe::merged(&settlement, other);
-                        break;
-                    }
-                    Err(err) => {
-                        observe::not_merged(&settlement, other, err);
-                    }
-                }
-            }
-            // If [`settlement`] can't be merged into any other settlement, this is its
-            // final, most optimal form. Push it to the results.
-            if !merged {
-                results.push(settlement);
-            }
+        let order = settlements.iter().flat_map(archive::solutions).collect();
+        let (settlements, attempts) = merge(settlements, &self.eth, &self.simulator).await;
+        if let (Some(archive), Some(id)) = (&self.archive, auction.id()) {
+            archive.save_merging(
+                self.solver.name(),
+                id,
+                &archive::Merging { order, attempts },
+            );
         }
 
-        let settlements = results;
-
         // Score the settlements.
         let scores = settlements
             .into_iter()
@@ -187,22 +158,29 @@ impl Competition {
         for (score, settlement) in scores.iter() {
             observe::score(settlement, score);
         }
+        if let Some(collected) = collected {
+            *self.post_processing.lock().unwrap() = collected.elapsed();
+        }
 
-        // Pick the best-scoring settlement.
-        let (mut score, settlement) = scores
-            .into_iter()
-            .max_by_key(|(score, _)| score.to_owned())
-            .map(|(score, settlement)| (Solved { score }, settlement))
-            .unzip();
-
-        *self.settlement.lock().unwrap() = settlement.clone();
-
-        let settlement = match settlement {
-            Some(settlement) => settlement,
+        // Pick the best-scoring settlement. The settlement of the previous auction
+        // gets discarded.
+        self.settlement.lock().unwrap().take();
+        let (score, settlement) = match scores.into_iter().max_by_key(|(score, _)| score.to_owned())
+        {
+            Some(best) => best,
             // Don't wait for the deadline because we can't produce a solution anyway.
-            None => return Ok(score),
+            None => return Ok(None),
         };
 
+        let mut score = Some(Solved {
+            score,
+            account: self.solver.address(),
+        });
+        *self.settlement.lock().unwrap() = Some(Candidate {
+            settlement: settlement.clone(),
+            account: None,
+        });
+
         // Re-simulate the solution on every new block until the deadline ends to make
         // sure we actually submit a working solution close to when the winner
         // gets picked by the procotol.
@@ -227,14 +205,191 @@ impl Competition {
         Ok(score)
     }
 
+    /// Fetches the solutions from a solver which streams them. Solutions get
+    /// encoded and simulated as soon as they arrive while the solver keeps
+    /// searching for better ones.
+    ///
+    /// Solutions are collected until the time it took to post-process the
+    /// previous round is left before the deadline. The solutions whose encoding
+    /// is still in progress at that point get finished until the deadline.
+    /// Returns the encoded settlements along with the time solutions stopped
+    /// being collected.
+    async fn solve_streaming(
+        &self,
+        auction: &Auction,
+        liquidity: &[liquidity::Liquidity],
+    ) -> Result<(Vec<Settlement>, std::time::Instant), Error> {
+        let stream = async {
+            let timeout = auction.deadline().timeout()?;
+            let lines = self
+                .solver
+                .exchange_stream(auction, liquidity, timeout)
+                .await?;
+            Ok::<_, Error>((timeout, lines))
+        };
+        let (timeout, mut lines) = stream
+            .await
+            .tap_err(|err| self.archive_outcome(auction, || archive::Outcome::failed(err)))?;
+
+        let timeout = timeout.duration().to_std().unwrap_or_default();
+        let deadline = tokio::time::Instant::now() + timeout;
+        let margin = *self.post_processing.lock().unwrap();
+        let collect_until = deadline - margin.min(timeout);
+
+        let mut response = String::new();
+        let mut settlements = Vec::new();
+        let mut encodings = FuturesUnordered::new();
+        let collect = async {
+            let mut ids = HashSet::new();
+            loop {
+                tokio::select! {
+                    line = lines.next() => match line {
+                        Some(Ok(line)) => {
+                            response.push_str(&line);
+                            match self.solver.solutions(&line, auction, liquidity) {
+                                Ok(solutions) => encodings.extend(
+                                    self.discard_invalid(auction, solutions, &mut ids)
+                                        .into_iter()
+                                        .map(|solution| self.encode(auction, solution)),
+                                ),
+                                Err(err) => tracing::warn!(?err, "discarding invalid solutions"),
+                            }
+                        }
+                        Some(Err(_)) | None => break,
+                    },
+                    Some(settlement) = encodings.next() => settlements.extend(settlement),
+                }
+            }
+        };
+        let timed_out = tokio::time::timeout_at(collect_until, collect)
+            .await
+            .is_err();
+        let collected = std::time::Instant::now();
+        if timed_out {
+            tracing::debug!(?margin, "stopped collecting solutions before the deadline");
+        }
+
+        // Finish encoding the solutions collected so far.
+        let finish = async {
+            while let Some(settlement) = encodings.next().await {
+                settlements.extend(settlement);
+            }
+        };
+        if tokio::time::timeout_at(deadline, finish).await.is_err() {
+            tracing::warn!(
+                unfinished = encodings.len(),
+                "reached deadline before all solutions were encoded"
+            );
+        }
+
+        self.archive_outcome(auction, || {
+            if timed_out {
+                archive::Outcome::TimedOut { response }
+            } else {
+                archive::Outcome::Solved { response }
+            }
+        });
+        Ok((settlements, collected))
+    }
+
+    /// Fetches the raw response of the solver, archiving how the
+    /// communication with the solver ended.
+    async fn exchange(
+        &self,
+        auction: &Auction,
+        liquidity: &[liquidity::Liquidity],
+    ) -> Result<String, Error> {
+        let exchange = async {
+            let timeout = auction.deadline().timeout()?;
+            Ok(self.solver.exchange(auction, liquidity, timeout).await?)
+        };
+        let result = exchange.await;
+        self.archive_outcome(auction, || match &result {
+            Ok(response) => archive::Outcome::Solved {
+                response: response.clone(),
+            },
+            Err(Error::Solver(err)) if err.is_timeout() => archive::Outcome::TimedOut {
+                response: Default::default(),
+            },
+            Err(err) => archive::Outcome::failed(err),
+        });
+        result
+    }
+
+    /// Archives the outcome of the round, if configured.
+    fn archive_outcome(&self, auction: &Auction, outcome: impl FnOnce() -> archive::Outcome) {
+        if let (Some(archive), Some(id)) = (&self.archive, auction.id()) {
+            archive.save_outcome(self.solver.name(), id, &outcome());
+        }
+    }
+
+    /// Discards the solutions that don't have a unique ID among all solutions
+    /// of the round, as well as empty solutions since they aren't useful.
+    fn discard_invalid(
+        &self,
+        auction: &Auction,
+        solutions: Vec<Solution>,
+        ids: &mut HashSet<solution::Id>,
+    ) -> Vec<Solution> {
+        solutions
+            .into_iter()
+            .filter(|solution| {
+                if !ids.insert(solution.id()) {
+                    observe::duplicated_solution_id(self.solver.name(), solution.id());
+                    notify::duplicated_solution_id(&self.solver, auction.id(), solution.id());
+                    false
+                } else {
+                    true
+                }
+            })
+            .filter(|solution| {
+                if solution.is_empty() {
+                    observe::empty_solution(self.solver.name(), solution.id());
+                    notify::empty_solution(&self.solver, auction.id(), solution.id());
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect()
+    }
+
+    /// Encodes a solution into a settlement. Returns `None` if the encoding
+    /// failed.
+    async fn encode(&self, auction: &Auction, solution: Solution) -> Option<Settlement> {
+        let id = solution.id();
+        observe::encoding(id);
+        solution
+            .encode(auction, &self.eth, &self.simulator)
+            .await
+            .tap_err(|err| {
+                observe::encoding_failed(self.solver.name(), id, err);
+                notify::encoding_failed(&self.solver, auction.id(), id, err);
+            })
+            .ok()
+    }
+
+    /// Reveals the solution generated as part of this competition and reserves
+    /// the account it will be submitted from.
     pub async fn reveal(&self) -> Result<Revealed, Error> {
-        let settlement = self
-            .settlement
-            .lock()
-            .unwrap()
-            .as_ref()
-            .cloned()
-            .ok_or(Error::SolutionNotAvailable)?;
+        let mut candidate = self.settlement.lock().unwrap();
+        let Candidate {
+            settlement,
+            account,
+        } = candidate.as_mut().ok_or(Error::SolutionNotAvailable)?;
+        // The settlement has to be submitted from the revealed account, so the
+        // lease is kept until the settlement gets executed.
+        let account = match account {
+            Some(account) => account.address(),
+            None => account
+                .insert(
+                    self.solver
+                        .accounts()
+                        .acquire()
+                        .ok_or(Error::NoAccountAvailable)?,
+                )
+                .address(),
+        };
         Ok(Revealed {
             orders: settlement.orders(),
             internalized_calldata: settlement
@@ -249,25 +404,54 @@ impl Competition {
                     settlement::Internalization::Disable,
                 )
                 .into(),
+            account,
         })
     }
 
     /// Execute the solution generated as part of this competition. Use
     /// [`Competition::solve`] to generate the solution.
     pub async fn settle(&self) -> Result<Settled, Error> {
-        let settlement = self
+        let Candidate {
+            settlement,
+            account,
+        } = self
             .settlement
             .lock()
             .unwrap()
             .take()
             .ok_or(Error::SolutionNotAvailable)?;
 
-        let executed = self.mempools.execute(&self.solver, &settlement).await;
+        // The account is usually reserved when the solution gets revealed.
+        let account = match account {
+            Some(account) => account,
+            None => self
+                .solver
+                .accounts()
+                .acquire()
+                .ok_or(Error::NoAccountAvailable)?,
+        };
+        let executed = self
+            .mempools
+            .execute(&self.solver, &account, &settlement)
+            .await;
+        // Find out whether a transaction of the account is still pending before
+        // freeing it for the next settlement.
+        let eth = self.eth.clone();
+        let address = account.address();
+        tokio::task::spawn(async move { account.refresh(&eth).await });
+        // Tell the solver why the settlement failed if it reverts by now.
+        let revert = match (&executed, settlement.notify_id()) {
+            (Err(mempools::Error::SimulationRevert | mempools::Error::Other(_)), Some(_)) => {
+                settlement.revert(address, &self.eth, &self.simulator).await
+            }
+            _ => None,
+        };
         notify::executed(
             &self.solver,
             settlement.auction_id,
             settlement.notify_id(),
             &executed,
+            revert.as_ref(),
         );
 
         match executed {
@@ -286,6 +470,7 @@ impl Competition {
                     )
                     .into(),
                 tx_hash,
+                account: address,
             }),
         }
     }
@@ -296,7 +481,7 @@ impl Competition {
             .lock()
             .unwrap()
             .as_ref()
-            .map(|s| s.auction_id)
+            .map(|candidate| candidate.settlement.auction_id)
     }
 
     /// Returns whether the settlement can be executed or would revert.
@@ -320,11 +505,68 @@ impl Competition {
     }
 }
 
+/// The best settlement of the auction being competed on.
+#[derive(Debug)]
+pub struct Candidate {
+    settlement: Settlement,
+    /// The account reserved for submitting the settlement once it got
+    /// revealed.
+    account: Option<solver::Lease>,
+}
+
+/// Merges the settlements, starting with the last one, and returns the merged
+/// settlements together with every attempted merge. The result depends on the
+/// order of the settlements, so replaying a round merges them in the archived
+/// order.
+pub async fn merge(
+    mut settlements: Vec<Settlement>,
+    eth: &Ethereum,
+    simulator: &Simulator,
+) -> (Vec<Settlement>, Vec<archive::Merge>) {
+    // The merging algorithm works as follows: the [`settlements`] vector keeps the
+    // "most merged" settlements until they can't be merged anymore, at
+    // which point they are moved into the [`results`] vector.
+
+    // The merged settlements in their final form.
+    let mut results = Vec::new();
+    let mut attempts = Vec::new();
+    while let Some(settlement) = settlements.pop() {
+        // Has [`settlement`] been merged into another settlement?
+        let mut merged = false;
+        // Try to merge [`settlement`] into some other settlement.
+        for other in settlements.iter_mut() {
+            match other.merge(&settlement, eth, simulator).await {
+                Ok(m) => {
+                    attempts.push(archive::Merge::new(&settlement, other, true));
+                    *other = m;
+                    merged = true;
+                    observe::merged(&settlement, other);
+                    break;
+                }
+                Err(err) => {
+                    attempts.push(archive::Merge::new(&settlement, other, false));
+                    observe::not_merged(&settlement, other, err);
+                }
+            }
+        }
+        // If [`settlement`] can't be merged into any other settlement, this is its
+        // final, most optimal form. Push it to the results.
+        if !merged {
+            results.push(settlement);
+        }
+    }
+    (results, attempts)
+}
+
 /// Solution information sent to the protocol by the driver before the solution
 /// ranking happens.
 #[derive(Debug)]
 pub struct Solved {
     pub score: Score,
+    /// The address of the solver, which the solution gets scored and rewarded
+    /// for. The settlement might be submitted from an additional account of
+    /// the solver, see [`Revealed::account`].
+    pub account: eth::Address,
 }
 
 /// Winning solution information revealed to the protocol by the driver before
@@ -340,6 +582,8 @@ pub struct Revealed {
     /// can manually enforce certain rules which can not be enforced
     /// automatically.
     pub uninternalized_calldata: Bytes<Vec<u8>>,
+    /// The account reserved for submitting the settlement.
+    pub account: eth::Address,
 }
 
 #[derive(Debug)]
@@ -351,6 +595,8 @@ pub struct Settled {
     pub uninternalized_calldata: Bytes<Vec<u8>>,
     /// The transaction hash in which the solution was submitted.
     pub tx_hash: eth::TxId,
+    /// The account which submitted the transaction.
+    pub account: eth::Address,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -366,4 +612,6 @@ pub enum Error {
     Solver(#[from] solver::Error),
     #[error("failed to submit the solution")]
     SubmissionError,
+    #[error("all accounts of the solver have a settlement in flight")]
+    NoAccountAvailable,
 }
@@ -1,5 +1,6 @@
 use crate::{
     domain::{self, eth, liquidity},
+    infra::simulator,
     util::Bytes,
 };
 
@@ -39,6 +40,21 @@ impl Interaction {
         }
     }
 
+    /// Whether the settlement contract executes this interaction with the
+    /// specified call. Liquidity interactions are encoded outside of the
+    /// domain, so only their target can be compared.
+    pub fn matches(&self, call: &simulator::revert::Call, exact: bool) -> bool {
+        match self {
+            Interaction::Custom(custom) => {
+                eth::Address::from(custom.target) == call.to
+                    && (!exact || custom.call_data == call.input)
+            }
+            Interaction::Liquidity(liquidity) => {
+                !exact && liquidity.liquidity.kind.venue().map(eth::Address::from) == Some(call.to)
+            }
+        }
+    }
+
     /// Returns the ERC20 approvals required for executing this interaction
     /// onchain.
     pub fn allowances(&self) -> Vec<eth::allowance::Required> {
@@ -309,6 +309,15 @@ impl From<Id> for u64 {
     }
 }
 
+/// The settlement of a solution reverted in simulation.
+#[derive(Debug)]
+pub struct Revert {
+    pub error: simulator::WithTxError,
+    /// The index of the interaction of the solution which reverted, if it
+    /// could be determined.
+    pub interaction: Option<usize>,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("blockchain error: {0:?}")]
@@ -317,6 +326,8 @@ pub enum Error {
     Boundary(#[from] boundary::Error),
     #[error("simulation error: {0:?}")]
     Simulation(#[from] simulator::Error),
+    #[error("settlement reverted in simulation: {0:?}")]
+    SimulationRevert(Box<Revert>),
     #[error(
         "invalid asset flow: token amounts entering the settlement do not equal token amounts \
          exiting the settlement"
@@ -7,11 +7,12 @@ use {
             eth,
             mempools,
         },
-        infra::{blockchain::Ethereum, observe, Simulator},
+        infra::{blockchain::Ethereum, observe, simulator, Simulator},
         util::conv::u256::U256Ext,
     },
     bigdecimal::{Signed, Zero},
     futures::future::try_join_all,
+    itertools::Itertools,
     num::zero,
     std::collections::{BTreeSet, HashMap, HashSet},
 };
@@ -88,10 +89,15 @@ impl Settlement {
 
         // For trades, the sold amounts are always entering the contract (positive
         // flow), whereas the bought amounts are always exiting the contract
-        // (negative flow).
+        // (negative flow). Taxed tokens only credit the contract with the sold
+        // amount net of the transfer fee.
         for trade in solution.trades.iter() {
             let trade::Execution { sell, buy } = trade.execution(&solution)?;
-            *flow.entry(sell.token).or_default() += eth::U256::from(sell.amount).to_big_int();
+            let sold = match auction.tokens().get(sell.token).transfer_fee {
+                Some(fee) => sell.amount.0 - fee.loss(sell.amount.0),
+                None => sell.amount.0,
+            };
+            *flow.entry(sell.token).or_default() += sold.to_big_int();
             // Within the settlement contract, the orders which buy ETH are wrapped into
             // WETH, and hence contribute to WETH flow.
             *flow.entry(buy.token.wrap(solution.weth)).or_default() -=
@@ -177,7 +183,8 @@ impl Settlement {
             simulator,
             Internalization::Enable,
         )
-        .await?;
+        .await
+        .map_err(|err| Self::simulation_revert(err, &solutions))?;
         let price = eth.gas_price().await?;
         let gas = Gas::new(gas, price);
 
@@ -208,7 +215,8 @@ impl Settlement {
                 simulator,
                 Internalization::Disable,
             )
-            .await?;
+            .await
+            .map_err(|err| Self::simulation_revert(err, &solutions))?;
         }
 
         Ok(Self {
@@ -220,6 +228,34 @@ impl Settlement {
         })
     }
 
+    /// Turns a reverted simulation into [`Error::SimulationRevert`], adding the
+    /// interaction which reverted if it can be determined.
+    fn simulation_revert(err: Error, solutions: &HashMap<solution::Id, Solution>) -> Error {
+        let Error::Simulation(simulator::Error::WithTx(error)) = err else {
+            return err;
+        };
+        // The interaction indices are only meaningful if the settlement consists
+        // of a single solution.
+        let interaction = match (
+            solutions.values().exactly_one(),
+            &error.revert().failed_call,
+        ) {
+            (Ok(solution), Some(call)) => {
+                let interactions = solution.interactions();
+                interactions
+                    .iter()
+                    .position(|interaction| interaction.matches(call, true))
+                    .or_else(|| {
+                        interactions
+                            .iter()
+                            .position(|interaction| interaction.matches(call, false))
+                    })
+            }
+            _ => None,
+        };
+        Error::SimulationRevert(Box::new(solution::Revert { error, interaction }))
+    }
+
     /// Simulate executing this settlement on the blockchain. This process
     /// ensures that the settlement does not revert, and calculates the
     /// access list and gas needed to settle the solution.
@@ -260,6 +296,31 @@ impl Settlement {
             .into()
     }
 
+    /// Simulates submitting the settlement from the specified account to find
+    /// out why it reverts. Returns `None` if the settlement doesn't revert in
+    /// simulation.
+    pub async fn revert(
+        &self,
+        account: eth::Address,
+        eth: &Ethereum,
+        simulator: &Simulator,
+    ) -> Option<solution::Revert> {
+        let tx = eth::Tx {
+            from: account,
+            ..self.boundary.tx(
+                self.auction_id,
+                eth.contracts().settlement(),
+                Internalization::Enable,
+            )
+        }
+        .set_access_list(self.access_list.clone());
+        let err = simulator.gas(tx).await.err()?;
+        match Self::simulation_revert(err.into(), &self.solutions) {
+            Error::SimulationRevert(revert) => Some(*revert),
+            _ => None,
+        }
+    }
+
     // TODO(#1494): score() should be defined on Solution rather than Settlement.
     /// Calculate the score for this settlement.
     pub fn score(
@@ -268,9 +329,17 @@ impl Settlement {
         auction: &competition::Auction,
         revert_protection: &mempools::RevertProtection,
     ) -> Result<competition::Score, score::Error> {
+        // Users receive less than the executed amounts of taxed tokens, so that
+        // part of the surplus is never realized.
         let objective_value = self
             .boundary
-            .objective_value(eth, auction, self.gas.estimate)?;
+            .objective_value(eth, auction, self.gas.estimate)?
+            .0
+            .saturating_sub(self.transfer_loss(auction).0);
+        if objective_value.is_zero() {
+            return Err(score::Error::ObjectiveValueNonPositive);
+        }
+        let objective_value = score::ObjectiveValue(objective_value);
 
         let score = match self.boundary.score() {
             competition::SolverScore::Solver(score) => competition::Score(score),
@@ -305,6 +374,34 @@ impl Settlement {
         Ok(score)
     }
 
+    /// The value of the bought taxed tokens that users lose to transfer fees,
+    /// denominated in the native token.
+    fn transfer_loss(&self, auction: &competition::Auction) -> eth::Ether {
+        self.solutions
+            .values()
+            .flat_map(|solution| {
+                solution
+                    .trades
+                    .iter()
+                    .filter(|trade| match trade {
+                        trade::Trade::Fulfillment(fulfillment) => {
+                            !matches!(fulfillment.order().kind, order::Kind::Liquidity)
+                        }
+                        trade::Trade::Jit(_) => false,
+                    })
+                    .filter_map(move |trade| trade.execution(solution).ok())
+            })
+            .filter_map(|trade::Execution { buy, .. }| {
+                let token = auction.tokens().get(buy.token);
+                let loss = token.transfer_fee?.loss(buy.amount.0);
+                // Prices are denominated in wei per 10**18 units of the token.
+                Some(eth::Ether(
+                    token.price?.apply(loss.into()).0 / eth::U256::exp10(18),
+                ))
+            })
+            .fold(zero(), |total, loss| total + loss)
+    }
+
     // TODO(#1478): merge() should be defined on Solution rather than Settlement.
     /// Merge another settlement into this settlement.
     ///
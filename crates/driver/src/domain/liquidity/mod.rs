@@ -61,6 +61,33 @@ pub enum Kind {
     ZeroEx(zeroex::LimitOrder),
 }
 
+impl Kind {
+    /// The contract through which this liquidity gets used, i.e. the router for
+    /// Uniswap-like pools and the vault for Balancer pools.
+    pub fn venue(&self) -> Option<eth::ContractAddress> {
+        match self {
+            Kind::UniswapV2(pool) => Some(pool.router),
+            Kind::UniswapV3(pool) => Some(pool.router),
+            Kind::BalancerV2Stable(pool) => Some(pool.vault),
+            Kind::BalancerV2Weighted(pool) => Some(pool.vault),
+            Kind::Swapr(pool) => Some(pool.base.router),
+            Kind::ZeroEx(_) => None,
+        }
+    }
+
+    /// The tokens that can be traded with this liquidity.
+    pub fn tokens(&self) -> Vec<eth::TokenAddress> {
+        match self {
+            Kind::UniswapV2(pool) => pool.reserves.iter().map(|r| r.token).collect(),
+            Kind::UniswapV3(pool) => vec![pool.tokens.get().0, pool.tokens.get().1],
+            Kind::BalancerV2Stable(pool) => pool.reserves.tokens().collect(),
+            Kind::BalancerV2Weighted(pool) => pool.reserves.tokens().collect(),
+            Kind::Swapr(pool) => pool.base.reserves.iter().map(|r| r.token).collect(),
+            Kind::ZeroEx(order) => vec![order.maker_token, order.taker_token],
+        }
+    }
+}
+
 impl From<&Kind> for &'static str {
     fn from(val: &Kind) -> &'static str {
         match *val {
@@ -1,5 +1,12 @@
+use crate::domain::eth;
+
 /// A signed 0x Protocol Limit Order [^1].
 ///
 /// [^1]: <https://docs.0x.org/limit-orders-advanced-traders/docs/introduction>
 #[derive(Clone, Debug)]
-pub struct LimitOrder {}
+pub struct LimitOrder {
+    /// The token the maker of the order sells.
+    pub maker_token: eth::TokenAddress,
+    /// The token the maker of the order buys.
+    pub taker_token: eth::TokenAddress,
+}
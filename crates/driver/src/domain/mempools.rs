@@ -2,7 +2,11 @@ use {
     super::eth,
     crate::{
         domain::competition::solution::Settlement,
-        infra::{self, observe, solver::Solver},
+        infra::{
+            self,
+            observe,
+            solver::{Lease, Solver},
+        },
     },
     futures::{future::select_ok, FutureExt},
     thiserror::Error,
@@ -22,10 +26,12 @@ impl Mempools {
         }
     }
 
-    /// Publish a settlement to the mempools.
+    /// Publish a settlement to the mempools, submitting it from the specified
+    /// account of the solver.
     pub async fn execute(
         &self,
         solver: &Solver,
+        account: &Lease,
         settlement: &Settlement,
     ) -> Result<eth::TxId, Error> {
         let auction_id = settlement.auction_id;
@@ -33,13 +39,14 @@ impl Mempools {
 
         let (tx_hash, _remaining_futures) = select_ok(self.0.iter().cloned().map(|mempool| {
             async move {
-                let result = mempool.execute(solver, settlement.clone()).await;
+                let result = mempool.execute(account, settlement.clone()).await;
                 observe::mempool_executed(&mempool, settlement, &result);
                 result
             }
             .instrument(tracing::info_span!(
                 "execute",
                 solver = ?solver_name,
+                account = ?account.address(),
                 ?auction_id,
             ))
             .boxed()
@@ -139,6 +139,7 @@ impl Order {
                     price: None,
                     available_balance: sell_token_metadata.map(|m| m.balance.0).unwrap_or_default(),
                     trusted: false,
+                    transfer_fee: None,
                 },
                 auction::Token {
                     decimals: buy_token_metadata.and_then(|m| m.decimals),
@@ -147,6 +148,7 @@ impl Order {
                     price: None,
                     available_balance: buy_token_metadata.map(|m| m.balance.0).unwrap_or_default(),
                     trusted: false,
+                    transfer_fee: None,
                 },
             ]
             .into_iter(),
@@ -0,0 +1,87 @@
+//! Middleware rejecting requests that are not signed with a shared secret.
+
+use {
+    super::Error,
+    axum::{
+        extract::{OriginalUri, State},
+        http::Request,
+        middleware::Next,
+        response::{IntoResponse, Response},
+    },
+    hyper::Body,
+    shared::request_signing,
+    std::sync::Arc,
+};
+
+pub async fn verify(
+    State(verifier): State<Arc<request_signing::Verifier>>,
+    OriginalUri(uri): OriginalUri,
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    match request_signing::verify_request(&verifier, &uri, request).await {
+        Ok(request) => next.run(request).await,
+        Err(err) => <(hyper::StatusCode, axum::Json<Error>)>::from(err).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        axum::routing::post,
+        hyper::StatusCode,
+        std::time::Duration,
+        tower::ServiceExt,
+    };
+
+    /// Several solvers behind one driver share the verifier and receive the
+    /// same auction at the same time.
+    #[tokio::test]
+    async fn accepts_same_body_for_multiple_solvers() {
+        let secret: request_signing::Secret = "secret".parse().unwrap();
+        let signer = request_signing::Signer::new(secret.clone());
+        let verifier = Arc::new(request_signing::Verifier::new(
+            vec![secret],
+            Duration::from_secs(30),
+        ));
+        let solver = || {
+            axum::Router::new()
+                .route("/solve", post(|| async { "ok" }))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    verifier.clone(),
+                    verify,
+                ))
+        };
+        let app = axum::Router::new()
+            .nest("/solver1", solver())
+            .nest("/solver2", solver());
+        let send = |path: &'static str, headers: [(&'static str, String); 2]| {
+            let mut request = Request::post(path);
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            let request = request.body(Body::from("auction")).unwrap();
+            let app = app.clone();
+            async move { app.oneshot(request).await.unwrap().status() }
+        };
+
+        let url = |path: &str| -> reqwest::Url { format!("http://driver{path}").parse().unwrap() };
+        let solver1 = signer.headers("POST", &url("/solver1/solve"), b"auction");
+        let solver2 = signer.headers("POST", &url("/solver2/solve"), b"auction");
+        assert_eq!(
+            send("/solver1/solve", solver1.clone()).await,
+            StatusCode::OK
+        );
+        assert_eq!(send("/solver2/solve", solver2).await, StatusCode::OK);
+        // Replaying a request is rejected, both for the same and another solver.
+        assert_eq!(
+            send("/solver1/solve", solver1.clone()).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            send("/solver2/solve", solver1).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
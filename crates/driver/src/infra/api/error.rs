@@ -1,9 +1,10 @@
 use {
     crate::{
         domain::{competition, quote},
-        infra::api,
+        infra::{api, solver},
     },
     serde::Serialize,
+    shared::request_signing,
 };
 
 #[derive(Debug, Clone, Copy, Serialize)]
@@ -20,6 +21,9 @@ enum Kind {
     InvalidAmounts,
     QuoteSameTokens,
     FailedToSubmit,
+    Unauthorized,
+    SolverUnavailable,
+    NoAccountAvailable,
 }
 
 #[derive(Debug, Serialize)]
@@ -51,9 +55,22 @@ impl From<Kind> for (hyper::StatusCode, axum::Json<Error>) {
                  or sell amount"
             }
             Kind::FailedToSubmit => "Could not submit the solution to the blockchain",
+            Kind::Unauthorized => "The request is not correctly signed",
+            Kind::SolverUnavailable => {
+                "Solver engine is unhealthy and currently not accepting auctions"
+            }
+            Kind::NoAccountAvailable => {
+                "All accounts of the solver have a settlement in flight, so the solution can't be \
+                 submitted"
+            }
+        };
+        let status = match value {
+            Kind::Unauthorized => hyper::StatusCode::UNAUTHORIZED,
+            Kind::SolverUnavailable => hyper::StatusCode::SERVICE_UNAVAILABLE,
+            _ => hyper::StatusCode::BAD_REQUEST,
         };
         (
-            hyper::StatusCode::BAD_REQUEST,
+            status,
             axum::Json(Error {
                 kind: value,
                 description,
@@ -67,6 +84,7 @@ impl From<quote::Error> for (hyper::StatusCode, axum::Json<Error>) {
         let error = match value {
             quote::Error::QuotingFailed(_) => Kind::QuotingFailed,
             quote::Error::DeadlineExceeded(_) => Kind::DeadlineExceeded,
+            quote::Error::Solver(solver::Error::Unavailable) => Kind::SolverUnavailable,
             quote::Error::Solver(_) => Kind::SolverFailed,
             quote::Error::Blockchain(_) => Kind::Unknown,
             quote::Error::Boundary(_) => Kind::Unknown,
@@ -80,8 +98,10 @@ impl From<competition::Error> for (hyper::StatusCode, axum::Json<Error>) {
         let error = match value {
             competition::Error::SolutionNotAvailable => Kind::SolutionNotAvailable,
             competition::Error::DeadlineExceeded(_) => Kind::DeadlineExceeded,
+            competition::Error::Solver(solver::Error::Unavailable) => Kind::SolverUnavailable,
             competition::Error::Solver(_) => Kind::SolverFailed,
             competition::Error::SubmissionError => Kind::FailedToSubmit,
+            competition::Error::NoAccountAvailable => Kind::NoAccountAvailable,
         };
         error.into()
     }
@@ -108,3 +128,13 @@ impl From<api::routes::OrderError> for (hyper::StatusCode, axum::Json<Error>) {
         error.into()
     }
 }
+
+impl From<request_signing::Error> for (hyper::StatusCode, axum::Json<Error>) {
+    fn from(value: request_signing::Error) -> Self {
+        let error = match value {
+            request_signing::Error::Body => Kind::Unknown,
+            _ => Kind::Unauthorized,
+        };
+        error.into()
+    }
+}
@@ -1,15 +1,29 @@
 use {
     crate::{
-        domain,
-        domain::Mempools,
-        infra::{self, liquidity, solver::Solver, tokens, Ethereum, Simulator},
+        domain::{self, eth, Mempools},
+        infra::{
+            self,
+            liquidity,
+            solver::{self, Solver},
+            tokens,
+            Archive,
+            Ethereum,
+            Simulator,
+        },
     },
     error::Error,
     futures::Future,
-    std::{net::SocketAddr, sync::Arc},
-    tokio::sync::oneshot,
+    shared::request_signing,
+    std::{
+        collections::HashMap,
+        net::SocketAddr,
+        sync::{Arc, RwLock},
+    },
+    tokio::sync::{mpsc, oneshot},
+    tower::ServiceExt,
 };
 
+mod auth;
 mod error;
 mod routes;
 
@@ -21,12 +35,28 @@ pub struct Api {
     pub simulator: Simulator,
     pub eth: Ethereum,
     pub mempools: Mempools,
+    /// Verifies that requests to the competition endpoints are signed, if
+    /// configured.
+    pub auth: Option<Arc<request_signing::Verifier>>,
+    /// Archives every `/solve` round, if configured.
+    pub archive: Option<Archive>,
+    /// Receives the reloaded driver configs, if reloading is enabled.
+    pub reloads: Option<mpsc::UnboundedReceiver<Reload>>,
     pub addr: SocketAddr,
     /// If this channel is specified, the bound address will be sent to it. This
     /// allows the driver to bind to 0.0.0.0:0 during testing.
     pub addr_sender: Option<oneshot::Sender<SocketAddr>>,
 }
 
+/// The parts of a reloaded driver config which get applied without a
+/// restart. The solver routes are swapped atomically, while the liquidity
+/// fetcher is kept and so are the settlements of solvers which still settle
+/// with the same account.
+pub struct Reload {
+    pub solvers: Vec<Solver>,
+    pub mempools: Mempools,
+}
+
 impl Api {
     pub async fn serve(
         self,
@@ -41,8 +71,6 @@ impl Api {
                 .layer(tower_http::trace::TraceLayer::new_for_http()),
         );
 
-        let tokens = tokens::Fetcher::new(self.eth.clone());
-
         // Add the metrics endpoint.
         app = routes::metrics(app);
 
@@ -50,15 +78,104 @@ impl Api {
         // on the same driver so only one liquidity collector collects the liquidity
         // for all of them. This is important because liquidity collection is
         // computationally expensive for the Ethereum node.
-        for solver in self.solvers {
+        let mut mounts = Mounts {
+            tokens: tokens::Fetcher::new(self.eth.clone()),
+            eth: self.eth,
+            liquidity: self.liquidity,
+            simulator: self.simulator,
+            mempools: self.mempools,
+            auth: self.auth,
+            archive: self.archive,
+            solvers: Default::default(),
+        };
+        let solvers = Arc::new(RwLock::new(mounts.router(self.solvers)));
+        if let Some(mut reloads) = self.reloads {
+            let solvers = solvers.clone();
+            tokio::task::spawn(async move {
+                while let Some(reload) = reloads.recv().await {
+                    mounts.mempools = reload.mempools;
+                    let router = mounts.router(reload.solvers);
+                    *solvers.write().unwrap() = router;
+                    infra::observe::reloaded_config(mounts.solvers.keys());
+                }
+            });
+        }
+        // Requests to the solvers are dispatched to the currently mounted
+        // solver routes, so that they can be swapped while serving.
+        app = app.fallback(move |req: axum::http::Request<axum::body::Body>| {
+            let router = solvers.read().unwrap().clone();
+            async move {
+                match router.oneshot(req).await {
+                    Ok(res) => res,
+                    Err(err) => match err {},
+                }
+            }
+        });
+
+        let make_svc = observe::make_service_with_task_local_storage!(app);
+
+        // Start the server.
+        let server = axum::Server::bind(&self.addr).serve(make_svc);
+        tracing::info!(port = server.local_addr().port(), "serving driver");
+        if let Some(addr_sender) = self.addr_sender {
+            addr_sender.send(server.local_addr()).unwrap();
+        }
+        server.with_graceful_shutdown(shutdown).await
+    }
+}
+
+/// The solvers mounted on the API and the components they share.
+struct Mounts {
+    eth: Ethereum,
+    liquidity: liquidity::Fetcher,
+    simulator: Simulator,
+    mempools: Mempools,
+    tokens: tokens::Fetcher,
+    auth: Option<Arc<request_signing::Verifier>>,
+    archive: Option<Archive>,
+    solvers: HashMap<solver::Name, Mount>,
+}
+
+struct Mount {
+    state: State,
+    /// Probes the solver while its circuit breaker is open.
+    probe: tokio::task::JoinHandle<()>,
+}
+
+impl Mounts {
+    /// Builds the routes for the specified solvers, replacing the previously
+    /// mounted ones.
+    fn router(&mut self, solvers: Vec<Solver>) -> axum::Router {
+        let remounts = remounts(
+            self.solvers
+                .iter()
+                .map(|(name, mount)| (name, mount.state.solver().address())),
+            solvers
+                .iter()
+                .map(|solver| (solver.name(), solver.address())),
+        );
+        let previous = std::mem::take(&mut self.solvers);
+        for (name, mount) in &previous {
+            mount.probe.abort();
+            if remounts[name] == Remount::Unmount {
+                infra::observe::unmounting_solver(name);
+            }
+        }
+        let mut app = axum::Router::new();
+        for solver in solvers {
             let name = solver.name().clone();
-            let router = axum::Router::new();
-            let router = routes::info(router);
-            let router = routes::quote(router);
-            let router = routes::solve(router);
-            let router = routes::reveal(router);
-            let router = routes::settle(router);
-            let router = router.with_state(State(Arc::new(Inner {
+            let settlement = match (&remounts[&name], previous.get(&name)) {
+                (Remount::KeepSettlement, Some(mount)) => {
+                    mount.state.competition().settlement.clone()
+                }
+                _ => Default::default(),
+            };
+            let post_processing = previous
+                .get(&name)
+                .map(|mount| mount.state.competition().post_processing.clone())
+                .unwrap_or_default();
+            let probe = tokio::task::spawn(solver.clone().probe_while_unhealthy());
+            let state = State(Arc::new(Inner {
                 eth: self.eth.clone(),
                 solver: solver.clone(),
                 competition: domain::Competition {
@@ -67,26 +184,88 @@ impl Api {
                     liquidity: self.liquidity.clone(),
                     simulator: self.simulator.clone(),
                     mempools: self.mempools.clone(),
-                    settlement: Default::default(),
+                    settlement,
+                    archive: self.archive.clone(),
+                    post_processing,
                 },
                 liquidity: self.liquidity.clone(),
-                tokens: tokens.clone(),
-            })));
+                tokens: self.tokens.clone(),
+                archive: self.archive.clone(),
+            }));
+
+            let router = axum::Router::new();
+            let router = routes::solve(router);
+            let router = routes::reveal(router);
+            let router = routes::settle(router);
+            let router = routes::quote(router);
+            // Only the routes added so far, i.e. the ones used by the autopilot
+            // and the orderbook, require authentication.
+            let router = match &self.auth {
+                Some(verifier) => router.route_layer(axum::middleware::from_fn_with_state(
+                    verifier.clone(),
+                    auth::verify,
+                )),
+                None => router,
+            };
+            let router = routes::info(router);
+            let router = routes::health(router);
+            let router = router.with_state(state.clone());
             let path = format!("/{name}");
             infra::observe::mounting_solver(&name, &path);
             app = app.nest(&path, router);
+            self.solvers.insert(name, Mount { state, probe });
         }
+        app
+    }
+}
 
-        let make_svc = observe::make_service_with_task_local_storage!(app);
+/// What happens to a solver when the mounted solvers get replaced. The routes
+/// of all solvers are rebuilt in any case.
+#[derive(Debug, PartialEq, Eq)]
+enum Remount {
+    /// The solver was not mounted before.
+    New,
+    /// The settlement of the previous competition is kept so that it can still
+    /// be revealed and settled.
+    KeepSettlement,
+    /// The solver now settles with a different account so the settlement of
+    /// the previous competition is dropped.
+    DropSettlement,
+    /// The solver is no longer configured.
+    Unmount,
+}
 
-        // Start the server.
-        let server = axum::Server::bind(&self.addr).serve(make_svc);
-        tracing::info!(port = server.local_addr().port(), "serving driver");
-        if let Some(addr_sender) = self.addr_sender {
-            addr_sender.send(server.local_addr()).unwrap();
-        }
-        server.with_graceful_shutdown(shutdown).await
+/// Determines how the solvers change when replacing the `mounted` solvers with
+/// `solvers`, given the accounts they settle with.
+fn remounts<'a>(
+    mounted: impl IntoIterator<Item = (&'a solver::Name, eth::Address)>,
+    solvers: impl IntoIterator<Item = (&'a solver::Name, eth::Address)>,
+) -> HashMap<solver::Name, Remount> {
+    let mounted = mounted.into_iter().collect::<HashMap<_, _>>();
+    let mut remounts = mounted
+        .keys()
+        .map(|name| ((*name).clone(), Remount::Unmount))
+        .collect::<HashMap<_, _>>();
+    for (name, address) in solvers {
+        let remount = match mounted.get(name) {
+            None => Remount::New,
+            Some(mounted) if *mounted == address => Remount::KeepSettlement,
+            Some(_) => Remount::DropSettlement,
+        };
+        remounts.insert(name.clone(), remount);
     }
+    remounts
+}
+
+/// Converts an auction archived by the `/solve` endpoint back into the domain
+/// representation.
+pub async fn archived_auction(
+    auction: serde_json::Value,
+    eth: &Ethereum,
+    tokens: &tokens::Fetcher,
+) -> anyhow::Result<domain::competition::Auction> {
+    let auction: routes::Auction = serde_json::from_value(auction)?;
+    Ok(auction.into_domain(eth, tokens).await?)
 }
 
 #[derive(Clone)]
@@ -112,6 +291,10 @@ impl State {
     fn tokens(&self) -> &tokens::Fetcher {
         &self.0.tokens
     }
+
+    fn archive(&self) -> Option<&Archive> {
+        self.0.archive.as_ref()
+    }
 }
 
 struct Inner {
@@ -120,4 +303,31 @@ struct Inner {
     competition: domain::Competition,
     liquidity: liquidity::Fetcher,
     tokens: tokens::Fetcher,
+    archive: Option<Archive>,
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, primitive_types::H160};
+
+    #[test]
+    fn remounts_reloaded_solvers() {
+        let name = |name: &str| solver::Name(name.to_string());
+        let address = |n| eth::Address(H160::from_low_u64_be(n));
+        let (a, b, c, d) = (name("a"), name("b"), name("c"), name("d"));
+
+        let remounts = remounts(
+            [(&a, address(1)), (&b, address(2)), (&c, address(3))],
+            [(&a, address(1)), (&b, address(4)), (&d, address(5))],
+        );
+        assert_eq!(
+            remounts,
+            HashMap::from([
+                (a, Remount::KeepSettlement),
+                (b, Remount::DropSettlement),
+                (c, Remount::Unmount),
+                (d, Remount::New),
+            ])
+        );
+    }
 }
@@ -0,0 +1,26 @@
+use {crate::infra::api::State, serde::Serialize};
+
+pub(in crate::infra::api) fn health(app: axum::Router<State>) -> axum::Router<State> {
+    app.route("/health", axum::routing::get(route))
+}
+
+async fn route(state: axum::extract::State<State>) -> axum::Json<Health> {
+    let health = state.solver().health();
+    axum::Json(Health {
+        available: !health.open,
+        consecutive_failures: health.consecutive_failures,
+        failure_rate: health.failure_rate,
+        timeout_rate: health.timeout_rate,
+    })
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Health {
+    /// Whether the circuit breaker of the solver is closed, i.e. auctions are
+    /// sent to the solver.
+    available: bool,
+    consecutive_failures: u32,
+    failure_rate: f64,
+    timeout_rate: f64,
+}
@@ -1,3 +1,4 @@
+mod health;
 mod info;
 mod metrics;
 mod quote;
@@ -6,10 +7,11 @@ mod settle;
 mod solve;
 
 pub(super) use {
+    health::health,
     info::info,
     metrics::metrics,
     quote::{quote, OrderError},
     reveal::reveal,
     settle::settle,
-    solve::{solve, AuctionError},
+    solve::{solve, Auction, AuctionError},
 };
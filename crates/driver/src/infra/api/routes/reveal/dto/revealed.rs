@@ -15,6 +15,7 @@ impl Revealed {
                 internalized: reveal.internalized_calldata.into(),
                 uninternalized: reveal.uninternalized_calldata.into(),
             },
+            account: reveal.account.into(),
         }
     }
 }
@@ -26,6 +27,8 @@ pub struct Revealed {
     #[serde_as(as = "Vec<serialize::Hex>")]
     orders: Vec<[u8; order::UID_LEN]>,
     calldata: Calldata,
+    /// The account the settlement will be submitted from.
+    account: primitive_types::H160,
 }
 
 #[serde_as]
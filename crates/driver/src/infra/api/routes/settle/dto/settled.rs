@@ -12,6 +12,7 @@ impl Settled {
                 uninternalized: settled.uninternalized_calldata.into(),
             },
             tx_hash: settled.tx_hash.0,
+            account: settled.account.into(),
         }
     }
 }
@@ -22,6 +23,8 @@ impl Settled {
 pub struct Settled {
     calldata: CalldataInner,
     tx_hash: primitive_types::H256,
+    /// The account which submitted the transaction.
+    account: primitive_types::H160,
 }
 
 #[serde_as]
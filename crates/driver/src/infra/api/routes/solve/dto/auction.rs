@@ -8,7 +8,7 @@ use {
         infra::{tokens, Ethereum},
         util::serialize,
     },
-    serde::Deserialize,
+    serde::{Deserialize, Serialize},
     serde_with::serde_as,
 };
 
@@ -125,6 +125,9 @@ impl Auction {
                     price: token.price.map(Into::into),
                     available_balance: info.map(|i| i.balance).unwrap_or(0.into()).into(),
                     trusted: token.trusted,
+                    transfer_fee: token
+                        .transfer_fee_bps
+                        .map(competition::auction::TransferFee::from_bps),
                 }
             }),
             self.deadline.into(),
@@ -167,7 +170,7 @@ impl From<auction::Error> for Error {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Auction {
     #[serde_as(as = "serde_with::DisplayFromStr")]
@@ -186,17 +189,18 @@ impl Auction {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct Token {
     pub address: eth::H160,
     #[serde_as(as = "Option<serialize::U256>")]
     pub price: Option<eth::U256>,
     pub trusted: bool,
+    pub transfer_fee_bps: Option<u32>,
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct Order {
     #[serde_as(as = "serialize::Hex")]
@@ -233,7 +237,7 @@ struct Order {
     signature: Vec<u8>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase", deny_unknown_fields)]
 enum Kind {
     Sell,
@@ -241,7 +245,7 @@ enum Kind {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct Interaction {
     target: eth::H160,
@@ -251,7 +255,7 @@ struct Interaction {
     call_data: Vec<u8>,
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase", deny_unknown_fields)]
 enum SellTokenBalance {
     #[default]
@@ -260,7 +264,7 @@ enum SellTokenBalance {
     External,
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase", deny_unknown_fields)]
 enum BuyTokenBalance {
     #[default]
@@ -268,7 +272,7 @@ enum BuyTokenBalance {
     Internal,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase", deny_unknown_fields)]
 enum SigningScheme {
     Eip712,
@@ -277,7 +281,7 @@ enum SigningScheme {
     Eip1271,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase", deny_unknown_fields)]
 enum Class {
     Market,
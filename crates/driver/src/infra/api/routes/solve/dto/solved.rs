@@ -1,7 +1,6 @@
 use {
     crate::{
         domain::{competition, eth},
-        infra::Solver,
         util::serialize,
     },
     serde::Serialize,
@@ -9,10 +8,10 @@ use {
 };
 
 impl Solved {
-    pub fn new(solved: Option<competition::Solved>, solver: &Solver) -> Self {
+    pub fn new(solved: Option<competition::Solved>) -> Self {
         let solutions = solved
             .into_iter()
-            .map(|solved| Solution::new(0, solved, solver))
+            .map(|solved| Solution::new(0, solved))
             .collect();
         Self { solutions }
     }
@@ -26,11 +25,11 @@ pub struct Solved {
 }
 
 impl Solution {
-    pub fn new(solution_id: u64, solved: competition::Solved, solver: &Solver) -> Self {
+    pub fn new(solution_id: u64, solved: competition::Solved) -> Self {
         Self {
             solution_id,
             score: solved.score.into(),
-            submission_address: solver.address().into(),
+            submission_address: solved.account.into(),
         }
     }
 }
@@ -45,5 +44,7 @@ pub struct Solution {
     solution_id: u64,
     #[serde_as(as = "serialize::U256")]
     score: eth::U256,
+    /// The address of the solver. The settlement might be submitted from an
+    /// additional account of the solver, which `/reveal` reports.
     submission_address: eth::H160,
 }
@@ -1,10 +1,14 @@
 mod dto;
 
-pub use dto::AuctionError;
+pub use dto::{Auction, AuctionError};
 use {
-    crate::infra::{
-        api::{Error, State},
-        observe,
+    crate::{
+        domain::competition::auction,
+        infra::{
+            api::{Error, State},
+            archive,
+            observe,
+        },
     },
     tap::TapFallible,
     tracing::Instrument,
@@ -20,19 +24,26 @@ async fn route(
 ) -> Result<axum::Json<dto::Solved>, (hyper::StatusCode, axum::Json<Error>)> {
     let auction_id = auction.id();
     let handle_request = async {
+        let round = state.archive().zip(auction::Id::try_from(auction_id).ok());
+        if let Some((archive, id)) = round {
+            archive.save_auction(state.solver().name(), id, &auction.0);
+        }
         let auction = auction
             .0
             .into_domain(state.eth(), state.tokens())
             .await
             .tap_err(|err| {
                 observe::invalid_dto(err, "auction");
+                if let Some((archive, id)) = round {
+                    archive.save_outcome(state.solver().name(), id, &archive::Outcome::failed(err));
+                }
             })?;
         let auction = auction.prioritize(state.eth()).await;
         observe::auction(&auction);
         let competition = state.competition();
         let result = competition.solve(&auction).await;
         observe::solved(state.solver().name(), &result);
-        Ok(axum::Json(dto::Solved::new(result?, &competition.solver)))
+        Ok(axum::Json(dto::Solved::new(result?)))
     };
 
     handle_request
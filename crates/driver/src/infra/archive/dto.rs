@@ -0,0 +1,366 @@
+//! Lossless representation of the fetched liquidity, so that archived rounds
+//! can be replayed with exactly the liquidity the solver was given.
+
+use {
+    crate::{
+        domain::{eth, liquidity},
+        util::serialize,
+    },
+    serde::{Deserialize, Serialize},
+    serde_with::serde_as,
+    std::collections::BTreeMap,
+};
+
+impl Liquidity {
+    pub fn new(liquidity: &liquidity::Liquidity) -> Self {
+        Self {
+            id: liquidity.id.into(),
+            gas: liquidity.gas.0,
+            kind: match &liquidity.kind {
+                liquidity::Kind::UniswapV2(pool) => Kind::UniswapV2(UniswapV2Pool::new(pool)),
+                liquidity::Kind::UniswapV3(pool) => Kind::UniswapV3(UniswapV3Pool {
+                    router: pool.router.0,
+                    address: pool.address.0,
+                    tokens: [pool.tokens.get().0.into(), pool.tokens.get().1.into()],
+                    sqrt_price: pool.sqrt_price.0,
+                    liquidity: pool.liquidity.0,
+                    tick: pool.tick.0,
+                    liquidity_net: pool
+                        .liquidity_net
+                        .iter()
+                        .map(|(tick, net)| (tick.0, net.0))
+                        .collect(),
+                    fee: (*pool.fee.0.numer(), *pool.fee.0.denom()),
+                }),
+                liquidity::Kind::BalancerV2Stable(pool) => {
+                    Kind::BalancerV2Stable(BalancerV2StablePool {
+                        vault: pool.vault.0,
+                        id: pool.id.into(),
+                        reserves: pool
+                            .reserves
+                            .iter()
+                            .map(|reserve| BalancerV2Reserve {
+                                token: reserve.asset.token.into(),
+                                balance: reserve.asset.amount.into(),
+                                scale: reserve.scale.as_raw(),
+                                weight: None,
+                            })
+                            .collect(),
+                        amplification_factor: pool.amplification_parameter.factor(),
+                        amplification_precision: pool.amplification_parameter.precision(),
+                        fee: pool.fee.as_raw(),
+                    })
+                }
+                liquidity::Kind::BalancerV2Weighted(pool) => {
+                    Kind::BalancerV2Weighted(BalancerV2WeightedPool {
+                        vault: pool.vault.0,
+                        id: pool.id.into(),
+                        reserves: pool
+                            .reserves
+                            .iter()
+                            .map(|reserve| BalancerV2Reserve {
+                                token: reserve.asset.token.into(),
+                                balance: reserve.asset.amount.into(),
+                                scale: reserve.scale.as_raw(),
+                                weight: Some(reserve.weight.as_raw()),
+                            })
+                            .collect(),
+                        fee: pool.fee.as_raw(),
+                        version: match pool.version {
+                            liquidity::balancer::v2::weighted::Version::V0 => {
+                                BalancerV2WeightedVersion::V0
+                            }
+                            liquidity::balancer::v2::weighted::Version::V3Plus => {
+                                BalancerV2WeightedVersion::V3Plus
+                            }
+                        },
+                    })
+                }
+                liquidity::Kind::Swapr(pool) => Kind::Swapr(SwaprPool {
+                    base: UniswapV2Pool::new(&pool.base),
+                    fee_bps: pool.fee.bps(),
+                }),
+                liquidity::Kind::ZeroEx(order) => Kind::ZeroEx(ZeroExLimitOrder {
+                    maker_token: order.maker_token.into(),
+                    taker_token: order.taker_token.into(),
+                }),
+            },
+        }
+    }
+
+    pub fn into_domain(self) -> Result<liquidity::Liquidity, Error> {
+        Ok(liquidity::Liquidity {
+            id: self.id.into(),
+            gas: self.gas.into(),
+            kind: match self.kind {
+                Kind::UniswapV2(pool) => liquidity::Kind::UniswapV2(pool.into_domain()?),
+                Kind::UniswapV3(pool) => {
+                    if pool.fee.1 == 0 {
+                        return Err(Error::InvalidParameters);
+                    }
+                    liquidity::Kind::UniswapV3(liquidity::uniswap::v3::Pool {
+                        router: pool.router.into(),
+                        address: pool.address.into(),
+                        tokens: liquidity::TokenPair::new(
+                            pool.tokens[0].into(),
+                            pool.tokens[1].into(),
+                        )
+                        .map_err(|_| Error::InvalidTokens)?,
+                        sqrt_price: liquidity::uniswap::v3::SqrtPrice(pool.sqrt_price),
+                        liquidity: liquidity::uniswap::v3::Liquidity(pool.liquidity),
+                        tick: liquidity::uniswap::v3::Tick(pool.tick),
+                        liquidity_net: pool
+                            .liquidity_net
+                            .into_iter()
+                            .map(|(tick, net)| {
+                                (
+                                    liquidity::uniswap::v3::Tick(tick),
+                                    liquidity::uniswap::v3::LiquidityNet(net),
+                                )
+                            })
+                            .collect(),
+                        fee: liquidity::uniswap::v3::Fee(num::rational::Ratio::new(
+                            pool.fee.0, pool.fee.1,
+                        )),
+                    })
+                }
+                Kind::BalancerV2Stable(pool) => {
+                    liquidity::Kind::BalancerV2Stable(liquidity::balancer::v2::stable::Pool {
+                        vault: pool.vault.into(),
+                        id: pool.id.into(),
+                        reserves: liquidity::balancer::v2::stable::Reserves::new(
+                            pool.reserves
+                                .into_iter()
+                                .map(|reserve| {
+                                    Ok(liquidity::balancer::v2::stable::Reserve {
+                                        asset: reserve.asset(),
+                                        scale: reserve.scale()?,
+                                    })
+                                })
+                                .collect::<Result<_, Error>>()?,
+                        )
+                        .map_err(|_| Error::InvalidTokens)?,
+                        amplification_parameter:
+                            liquidity::balancer::v2::stable::AmplificationParameter::new(
+                                pool.amplification_factor,
+                                pool.amplification_precision,
+                            )
+                            .map_err(|_| Error::InvalidParameters)?,
+                        fee: liquidity::balancer::v2::Fee::from_raw(pool.fee),
+                    })
+                }
+                Kind::BalancerV2Weighted(pool) => {
+                    liquidity::Kind::BalancerV2Weighted(liquidity::balancer::v2::weighted::Pool {
+                        vault: pool.vault.into(),
+                        id: pool.id.into(),
+                        reserves: liquidity::balancer::v2::weighted::Reserves::new(
+                            pool.reserves
+                                .into_iter()
+                                .map(|reserve| {
+                                    Ok(liquidity::balancer::v2::weighted::Reserve {
+                                        asset: reserve.asset(),
+                                        scale: reserve.scale()?,
+                                        weight: liquidity::balancer::v2::weighted::Weight::from_raw(
+                                            reserve.weight.ok_or(Error::InvalidParameters)?,
+                                        ),
+                                    })
+                                })
+                                .collect::<Result<_, Error>>()?,
+                        )
+                        .map_err(|_| Error::InvalidTokens)?,
+                        fee: liquidity::balancer::v2::Fee::from_raw(pool.fee),
+                        version: match pool.version {
+                            BalancerV2WeightedVersion::V0 => {
+                                liquidity::balancer::v2::weighted::Version::V0
+                            }
+                            BalancerV2WeightedVersion::V3Plus => {
+                                liquidity::balancer::v2::weighted::Version::V3Plus
+                            }
+                        },
+                    })
+                }
+                Kind::Swapr(pool) => liquidity::Kind::Swapr(liquidity::swapr::Pool {
+                    base: pool.base.into_domain()?,
+                    fee: liquidity::swapr::Fee::new(pool.fee_bps)
+                        .map_err(|_| Error::InvalidParameters)?,
+                }),
+                Kind::ZeroEx(order) => liquidity::Kind::ZeroEx(liquidity::zeroex::LimitOrder {
+                    maker_token: order.maker_token.into(),
+                    taker_token: order.taker_token.into(),
+                }),
+            },
+        })
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Liquidity {
+    id: usize,
+    #[serde_as(as = "serialize::U256")]
+    gas: eth::U256,
+    kind: Kind,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum Kind {
+    UniswapV2(UniswapV2Pool),
+    UniswapV3(UniswapV3Pool),
+    BalancerV2Stable(BalancerV2StablePool),
+    BalancerV2Weighted(BalancerV2WeightedPool),
+    Swapr(SwaprPool),
+    ZeroEx(ZeroExLimitOrder),
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UniswapV2Pool {
+    address: eth::H160,
+    router: eth::H160,
+    reserves: [UniswapV2Reserve; 2],
+}
+
+impl UniswapV2Pool {
+    fn new(pool: &liquidity::uniswap::v2::Pool) -> Self {
+        let (a, b) = {
+            let mut reserves = pool.reserves.iter();
+            (reserves.next().unwrap(), reserves.next().unwrap())
+        };
+        Self {
+            address: pool.address.0,
+            router: pool.router.0,
+            reserves: [a, b].map(|asset| UniswapV2Reserve {
+                token: asset.token.into(),
+                balance: asset.amount.into(),
+            }),
+        }
+    }
+
+    fn into_domain(self) -> Result<liquidity::uniswap::v2::Pool, Error> {
+        let [a, b] = self.reserves.map(|reserve| eth::Asset {
+            token: reserve.token.into(),
+            amount: reserve.balance.into(),
+        });
+        Ok(liquidity::uniswap::v2::Pool {
+            address: self.address.into(),
+            router: self.router.into(),
+            reserves: liquidity::uniswap::v2::Reserves::new(a, b)
+                .map_err(|_| Error::InvalidTokens)?,
+        })
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UniswapV2Reserve {
+    token: eth::H160,
+    #[serde_as(as = "serialize::U256")]
+    balance: eth::U256,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UniswapV3Pool {
+    router: eth::H160,
+    address: eth::H160,
+    tokens: [eth::H160; 2],
+    #[serde_as(as = "serialize::U256")]
+    sqrt_price: eth::U256,
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    liquidity: u128,
+    tick: i32,
+    #[serde_as(as = "BTreeMap<serde_with::DisplayFromStr, serde_with::DisplayFromStr>")]
+    liquidity_net: BTreeMap<i32, i128>,
+    /// The fee as a fraction `(numerator, denominator)`.
+    fee: (u32, u32),
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BalancerV2StablePool {
+    vault: eth::H160,
+    id: eth::H256,
+    reserves: Vec<BalancerV2Reserve>,
+    #[serde_as(as = "serialize::U256")]
+    amplification_factor: eth::U256,
+    #[serde_as(as = "serialize::U256")]
+    amplification_precision: eth::U256,
+    #[serde_as(as = "serialize::U256")]
+    fee: eth::U256,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BalancerV2WeightedPool {
+    vault: eth::H160,
+    id: eth::H256,
+    reserves: Vec<BalancerV2Reserve>,
+    #[serde_as(as = "serialize::U256")]
+    fee: eth::U256,
+    version: BalancerV2WeightedVersion,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BalancerV2Reserve {
+    token: eth::H160,
+    #[serde_as(as = "serialize::U256")]
+    balance: eth::U256,
+    #[serde_as(as = "serialize::U256")]
+    scale: eth::U256,
+    /// Only set for weighted pools.
+    #[serde_as(as = "Option<serialize::U256>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<eth::U256>,
+}
+
+impl BalancerV2Reserve {
+    fn asset(&self) -> eth::Asset {
+        eth::Asset {
+            token: self.token.into(),
+            amount: self.balance.into(),
+        }
+    }
+
+    fn scale(&self) -> Result<liquidity::balancer::v2::ScalingFactor, Error> {
+        liquidity::balancer::v2::ScalingFactor::from_raw(self.scale)
+            .map_err(|_| Error::InvalidParameters)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum BalancerV2WeightedVersion {
+    V0,
+    V3Plus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwaprPool {
+    base: UniswapV2Pool,
+    fee_bps: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ZeroExLimitOrder {
+    maker_token: eth::H160,
+    taker_token: eth::H160,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("invalid tokens in archived liquidity")]
+    InvalidTokens,
+    #[error("invalid parameters in archived liquidity")]
+    InvalidParameters,
+}
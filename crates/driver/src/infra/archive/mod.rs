@@ -0,0 +1,352 @@
+//! Archive of the `/solve` rounds of the competition. Everything needed to
+//! reproduce how the driver processed the solutions of a solver is written to
+//! disk, so that the round can be inspected later with the `replay`
+//! subcommand.
+//!
+//! Each round is stored in its own directory `<dir>/<solver>/<auction id>/`.
+
+use {
+    crate::{
+        domain::{
+            competition::{auction, solution::Settlement},
+            liquidity,
+        },
+        infra::solver,
+    },
+    itertools::Itertools,
+    serde::{de::DeserializeOwned, Deserialize, Serialize},
+    std::{
+        path::{Path, PathBuf},
+        time::Duration,
+    },
+    tracing::Instrument,
+};
+
+mod dto;
+
+/// The auction as received from the autopilot.
+const AUCTION_FILE: &str = "auction.json";
+/// The liquidity fetched for the auction.
+const LIQUIDITY_FILE: &str = "liquidity.json";
+/// The outcome of the communication with the solver engine.
+const SOLVER_FILE: &str = "solver.json";
+/// The order in which the settlements got merged and the result of every
+/// attempted merge.
+const MERGING_FILE: &str = "merging.json";
+
+#[derive(Debug, Clone)]
+pub struct Archive {
+    dir: PathBuf,
+    retention: Retention,
+}
+
+/// Which archived rounds are kept. Older rounds are deleted whenever a new
+/// round of the same solver gets archived.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Retention {
+    /// Rounds archived longer ago than this get deleted.
+    pub max_age: Option<Duration>,
+    /// At most this many of the most recent rounds are kept per solver.
+    pub max_rounds: Option<usize>,
+}
+
+impl Archive {
+    pub fn new(dir: PathBuf, retention: Retention) -> Self {
+        Self { dir, retention }
+    }
+
+    /// Archives the auction as received from the autopilot.
+    pub fn save_auction(
+        &self,
+        solver: &solver::Name,
+        auction: auction::Id,
+        value: &impl Serialize,
+    ) {
+        self.save(solver, auction, AUCTION_FILE, value);
+        self.prune(solver);
+    }
+
+    /// Archives the liquidity fetched at the specified block.
+    pub fn save_liquidity(
+        &self,
+        solver: &solver::Name,
+        auction: auction::Id,
+        block: u64,
+        liquidity: &[liquidity::Liquidity],
+    ) {
+        let snapshot = Snapshot {
+            block,
+            liquidity: liquidity.iter().map(dto::Liquidity::new).collect(),
+        };
+        self.save(solver, auction, LIQUIDITY_FILE, &snapshot)
+    }
+
+    /// Archives the outcome of the communication with the solver engine.
+    pub fn save_outcome(&self, solver: &solver::Name, auction: auction::Id, outcome: &Outcome) {
+        self.save(solver, auction, SOLVER_FILE, outcome)
+    }
+
+    /// Archives how the settlements of the round got merged.
+    pub fn save_merging(&self, solver: &solver::Name, auction: auction::Id, merging: &Merging) {
+        self.save(solver, auction, MERGING_FILE, merging)
+    }
+
+    /// Writes the file in the background. Failing to archive a round must not
+    /// affect the competition, so errors only get logged.
+    fn save(
+        &self,
+        solver: &solver::Name,
+        auction: auction::Id,
+        file: &str,
+        value: &impl Serialize,
+    ) {
+        let dir = self.dir.join(solver.as_str()).join(auction.0.to_string());
+        let value = match serde_json::to_vec_pretty(value) {
+            Ok(value) => value,
+            Err(err) => {
+                tracing::warn!(?err, file, "failed to serialize archived data");
+                return;
+            }
+        };
+        let path = dir.join(file);
+        let write = async move {
+            let result = async {
+                tokio::fs::create_dir_all(&dir).await?;
+                tokio::fs::write(&path, value).await
+            };
+            if let Err(err) = result.await {
+                tracing::warn!(?err, ?path, "failed to archive round");
+            }
+        };
+        tokio::task::spawn(write.in_current_span());
+    }
+
+    /// Deletes the rounds of the solver which are no longer retained in the
+    /// background.
+    fn prune(&self, solver: &solver::Name) {
+        if self.retention.max_age.is_none() && self.retention.max_rounds.is_none() {
+            return;
+        }
+        let dir = self.dir.join(solver.as_str());
+        let retention = self.retention;
+        let prune = async move {
+            if let Err(err) = prune(&dir, retention).await {
+                tracing::warn!(?err, ?dir, "failed to prune archived rounds");
+            }
+        };
+        tokio::task::spawn(prune.in_current_span());
+    }
+}
+
+async fn prune(dir: &Path, retention: Retention) -> std::io::Result<()> {
+    let mut rounds = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        // Skip anything that isn't an archived round.
+        let Some(id) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<i64>().ok())
+        else {
+            continue;
+        };
+        let modified = entry.metadata().await?.modified()?;
+        rounds.push((id, modified, entry.path()));
+    }
+
+    // Auction IDs are increasing, so the most recent rounds come first.
+    rounds.sort_unstable_by_key(|(id, ..)| std::cmp::Reverse(*id));
+    for (i, (_, modified, path)) in rounds.into_iter().enumerate() {
+        let too_many = retention.max_rounds.is_some_and(|max| i >= max);
+        let too_old = retention
+            .max_age
+            .is_some_and(|max| modified.elapsed().unwrap_or_default() > max);
+        if too_many || too_old {
+            tokio::fs::remove_dir_all(&path).await?;
+        }
+    }
+    Ok(())
+}
+
+/// The liquidity fetched for a round.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Snapshot {
+    /// The block at which the liquidity was fetched.
+    block: u64,
+    liquidity: Vec<dto::Liquidity>,
+}
+
+/// How the communication with the solver engine ended.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Outcome {
+    /// The solver responded in time with the raw response.
+    Solved { response: String },
+    /// The solver didn't respond before the deadline. Streaming solvers might
+    /// have sent part of their response until then.
+    TimedOut { response: String },
+    /// The round failed before the solver responded.
+    Failed { error: String },
+}
+
+impl Outcome {
+    pub fn failed(err: &impl std::fmt::Display) -> Self {
+        Self::Failed {
+            error: err.to_string(),
+        }
+    }
+}
+
+/// How the settlements of a round got merged.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Merging {
+    /// The solutions of the encoded settlements in the (random) order they
+    /// were merged in.
+    pub order: Vec<u64>,
+    /// Every attempted merge in the order it was attempted.
+    pub attempts: Vec<Merge>,
+}
+
+/// An attempt to merge a settlement into another one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Merge {
+    /// The solutions of the settlement which was merged.
+    pub settlement: Vec<u64>,
+    /// The solutions of the settlement it was merged into.
+    pub into: Vec<u64>,
+    pub merged: bool,
+}
+
+impl Merge {
+    pub fn new(settlement: &Settlement, into: &Settlement, merged: bool) -> Self {
+        Self {
+            settlement: solutions(settlement),
+            into: solutions(into),
+            merged,
+        }
+    }
+}
+
+/// The solutions of the settlement as sorted IDs.
+pub fn solutions(settlement: &Settlement) -> Vec<u64> {
+    settlement
+        .solutions()
+        .into_iter()
+        .map(|id| id.0)
+        .sorted()
+        .collect()
+}
+
+/// An archived round.
+#[derive(Debug)]
+pub struct Round {
+    pub auction: serde_json::Value,
+    /// The block at which the liquidity was fetched. Contains `None` if the
+    /// round failed before fetching liquidity.
+    pub block: Option<u64>,
+    pub liquidity: Vec<liquidity::Liquidity>,
+    pub outcome: Outcome,
+    /// How the settlements got merged. Contains `None` if the round ended
+    /// before any settlements got merged.
+    pub merging: Option<Merging>,
+}
+
+impl Round {
+    /// Loads a round from the directory it was archived in.
+    pub async fn load(dir: &Path) -> Result<Self, Error> {
+        let snapshot = read_optional::<Snapshot>(&dir.join(LIQUIDITY_FILE)).await?;
+        Ok(Self {
+            auction: read(&dir.join(AUCTION_FILE)).await?,
+            block: snapshot.as_ref().map(|snapshot| snapshot.block),
+            liquidity: snapshot
+                .map(|snapshot| snapshot.liquidity)
+                .unwrap_or_default()
+                .into_iter()
+                .map(dto::Liquidity::into_domain)
+                .collect::<Result<_, _>>()?,
+            outcome: read(&dir.join(SOLVER_FILE)).await?,
+            merging: read_optional(&dir.join(MERGING_FILE)).await?,
+        })
+    }
+}
+
+async fn read<T: DeserializeOwned>(path: &Path) -> Result<T, Error> {
+    let data = tokio::fs::read(path).await?;
+    Ok(serde_json::from_slice(&data)?)
+}
+
+/// Reads a file which is only archived if the round got far enough.
+async fn read_optional<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, Error> {
+    match read(path).await {
+        Ok(value) => Ok(Some(value)),
+        Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error: {0:?}")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error: {0:?}")]
+    Json(#[from] serde_json::Error),
+    #[error("liquidity error: {0:?}")]
+    Liquidity(#[from] dto::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn rounds(dir: &Path) -> Vec<String> {
+        let mut rounds = Vec::new();
+        let mut entries = tokio::fs::read_dir(dir).await.unwrap();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            rounds.push(entry.file_name().into_string().unwrap());
+        }
+        rounds.sort();
+        rounds
+    }
+
+    #[tokio::test]
+    async fn prunes_rounds_beyond_max_rounds() {
+        let dir = tempfile::tempdir().unwrap();
+        for round in ["8", "9", "10", "11", "notes"] {
+            tokio::fs::create_dir(dir.path().join(round)).await.unwrap();
+        }
+
+        prune(
+            dir.path(),
+            Retention {
+                max_age: None,
+                max_rounds: Some(2),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(rounds(dir.path()).await, ["10", "11", "notes"]);
+    }
+
+    #[tokio::test]
+    async fn prunes_rounds_beyond_max_age() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::create_dir(dir.path().join("1")).await.unwrap();
+
+        let retention = |max_age| Retention {
+            max_age: Some(max_age),
+            max_rounds: None,
+        };
+        prune(dir.path(), retention(Duration::from_secs(60)))
+            .await
+            .unwrap();
+        assert_eq!(rounds(dir.path()).await, ["1"]);
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        prune(dir.path(), retention(Duration::ZERO)).await.unwrap();
+        assert!(rounds(dir.path()).await.is_empty());
+    }
+}
@@ -181,6 +181,18 @@ impl Ethereum {
             .map_err(Into::into)
     }
 
+    /// Returns the nonce of the next transaction of the specified account.
+    /// Transactions which are pending in the mempool are only counted if
+    /// `pending` is set.
+    pub async fn nonce(&self, address: eth::Address, pending: bool) -> Result<eth::U256, Error> {
+        let block = pending.then_some(web3::types::BlockNumber::Pending);
+        self.web3
+            .eth()
+            .transaction_count(address.into(), block)
+            .await
+            .map_err(Into::into)
+    }
+
     /// Returns a [`token::Erc20`] for the specified address.
     pub fn erc20(&self, address: eth::TokenAddress) -> token::Erc20 {
         token::Erc20::new(self, address)
@@ -1,6 +1,6 @@
 use {
     reqwest::Url,
-    std::{net::SocketAddr, path::PathBuf},
+    std::{net::SocketAddr, path::PathBuf, time::Duration},
 };
 
 #[derive(Debug, clap::Parser)]
@@ -24,6 +24,37 @@ pub struct Args {
     /// Path to the driver configuration file. This file should be in TOML
     /// format. For an example see
     /// https://github.com/cowprotocol/services/blob/main/crates/driver/example.toml.
+    /// The solvers and mempools get reloaded without a restart when the file
+    /// changes or the driver receives SIGHUP.
     #[clap(long, env)]
     pub config: PathBuf,
+
+    /// Directory in which every `/solve` round gets archived, so that it can
+    /// be replayed later.
+    #[clap(long, env)]
+    pub archive_dir: Option<PathBuf>,
+
+    /// Archived rounds older than this many seconds get deleted.
+    #[clap(long, env, value_parser = shared::arguments::duration_from_seconds)]
+    pub archive_max_age: Option<Duration>,
+
+    /// At most this many of the most recent rounds of each solver are kept in
+    /// the archive.
+    #[clap(long, env)]
+    pub archive_max_rounds: Option<usize>,
+
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Replay an archived `/solve` round against the configured node and
+    /// print a JSON report of how the solutions got encoded, merged and scored
+    /// to stdout. Exits with a non-zero code if the round can't be replayed.
+    Replay {
+        /// The directory of the round, i.e. `<archive dir>/<solver>/<auction
+        /// id>`.
+        round: PathBuf,
+    },
 }
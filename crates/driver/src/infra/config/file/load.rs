@@ -4,7 +4,9 @@ use {
         infra::{self, blockchain, config::file, liquidity, mempool, simulator, solver},
     },
     futures::future::join_all,
-    std::path::Path,
+    itertools::Itertools,
+    shared::request_signing,
+    std::{path::Path, sync::Arc, time::Duration},
     tokio::fs,
 };
 
@@ -15,45 +17,48 @@ use {
 ///
 /// This method panics if the config is invalid or on I/O errors.
 pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
-    let data = fs::read_to_string(path)
+    try_load(network, path)
         .await
-        .unwrap_or_else(|e| panic!("I/O error while reading {path:?}: {e:?}"));
+        .unwrap_or_else(|err| panic!("invalid driver config at {path:?}: {err}"))
+}
+
+/// Load the driver configuration from a TOML file for the specifed Ethereum
+/// network, returning an error if the config is invalid. Used to validate a
+/// changed config before reloading it.
+pub async fn try_load(network: &blockchain::Network, path: &Path) -> Result<infra::Config, Error> {
+    let data = fs::read_to_string(path).await?;
 
-    let config: file::Config = toml::de::from_str(&data).unwrap_or_else(|err| {
+    let config: file::Config = toml::de::from_str(&data).map_err(|err| {
         if std::env::var("TOML_TRACE_ERROR").is_ok_and(|v| v == "1") {
-            panic!("failed to parse TOML config at {path:?}: {err:#?}")
+            Error::Parse(format!("{err:#?}"))
         } else {
-            panic!(
-                "failed to parse TOML config at: {path:?}. Set TOML_TRACE_ERROR=1 to print \
-                 parsing error but this may leak secrets."
+            Error::Parse(
+                "set TOML_TRACE_ERROR=1 to print parsing error but this may leak secrets".into(),
             )
         }
-    });
+    })?;
 
-    assert_eq!(
-        config.chain_id.map(eth::ChainId).unwrap_or(network.chain),
-        network.chain,
-        "The configured chain ID does not match connected Ethereum node"
-    );
+    if config.chain_id.map(eth::ChainId).unwrap_or(network.chain) != network.chain {
+        return Err(Error::ChainId);
+    }
+    if let Some(name) = config
+        .solvers
+        .iter()
+        .map(|solver| &solver.name)
+        .duplicates()
+        .next()
+    {
+        return Err(Error::DuplicateSolver(name.clone()));
+    }
 
-    infra::Config {
+    Ok(infra::Config {
         solvers: join_all(config.solvers.into_iter().map(|config| async move {
-            let account = match config.account {
-                file::Account::PrivateKey(private_key) => ethcontract::Account::Offline(
-                    ethcontract::PrivateKey::from_raw(private_key.0).unwrap(),
-                    None,
-                ),
-                file::Account::Kms(key_id) => {
-                    let config = ethcontract::aws_config::load_from_env().await;
-                    let account =
-                        ethcontract::transaction::kms::Account::new((&config).into(), &key_id.0)
-                            .await
-                            .unwrap_or_else(|_| panic!("Unable to load KMS account {:?}", key_id));
-                    ethcontract::Account::Kms(account, None)
-                }
-                file::Account::Address(address) => ethcontract::Account::Local(address, None),
-            };
-            solver::Config {
+            let account = account(&config.name, config.account).await?;
+            let mut additional_accounts = Vec::new();
+            for additional in config.additional_accounts {
+                additional_accounts.push(account(&config.name, additional).await?);
+            }
+            Ok(solver::Config {
                 endpoint: config.endpoint,
                 name: config.name.into(),
                 slippage: solver::Slippage {
@@ -65,10 +70,42 @@ pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
                 } else {
                     solver::Liquidity::Fetch
                 },
+                liquidity_filter: solver::LiquidityFilter {
+                    kinds: config.liquidity_kinds.map(|kinds| {
+                        kinds
+                            .into_iter()
+                            .map(|kind| match kind {
+                                file::LiquidityKind::UniswapV2 => solver::LiquidityKind::UniswapV2,
+                                file::LiquidityKind::UniswapV3 => solver::LiquidityKind::UniswapV3,
+                                file::LiquidityKind::BalancerV2Stable => {
+                                    solver::LiquidityKind::BalancerV2Stable
+                                }
+                                file::LiquidityKind::BalancerV2Weighted => {
+                                    solver::LiquidityKind::BalancerV2Weighted
+                                }
+                                file::LiquidityKind::Swapr => solver::LiquidityKind::Swapr,
+                                file::LiquidityKind::ZeroEx => solver::LiquidityKind::ZeroEx,
+                            })
+                            .collect()
+                    }),
+                    venues: config
+                        .liquidity_venues
+                        .map(|venues| venues.into_iter().map(Into::into).collect()),
+                    max_hops: config.liquidity_max_hops,
+                },
                 account,
-            }
+                additional_accounts,
+                signer: config.auth_secret.map(request_signing::Signer::new),
+                stream: config.stream_solutions,
+                breaker: solver::breaker::Config {
+                    max_consecutive_failures: config.circuit_breaker.max_consecutive_failures,
+                    probe_interval: Duration::from_secs(config.circuit_breaker.probe_interval_secs),
+                },
+            })
         }))
-        .await,
+        .await
+        .into_iter()
+        .collect::<Result<_, Error>>()?,
         liquidity: liquidity::Config {
             base_tokens: config
                 .liquidity
@@ -82,125 +119,137 @@ pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
                 .uniswap_v2
                 .iter()
                 .cloned()
-                .map(|config| match config {
-                    file::UniswapV2Config::Preset { preset } => match preset {
-                        file::UniswapV2Preset::UniswapV2 => {
-                            liquidity::config::UniswapV2::uniswap_v2(&network.id)
-                        }
-                        file::UniswapV2Preset::SushiSwap => {
-                            liquidity::config::UniswapV2::sushi_swap(&network.id)
-                        }
-                        file::UniswapV2Preset::Honeyswap => {
-                            liquidity::config::UniswapV2::honeyswap(&network.id)
-                        }
-                        file::UniswapV2Preset::Baoswap => {
-                            liquidity::config::UniswapV2::baoswap(&network.id)
-                        }
-                        file::UniswapV2Preset::PancakeSwap => {
-                            liquidity::config::UniswapV2::pancake_swap(&network.id)
+                .map(|config| {
+                    Ok(match config {
+                        file::UniswapV2Config::Preset { preset } => match preset {
+                            file::UniswapV2Preset::UniswapV2 => {
+                                liquidity::config::UniswapV2::uniswap_v2(&network.id)
+                            }
+                            file::UniswapV2Preset::SushiSwap => {
+                                liquidity::config::UniswapV2::sushi_swap(&network.id)
+                            }
+                            file::UniswapV2Preset::Honeyswap => {
+                                liquidity::config::UniswapV2::honeyswap(&network.id)
+                            }
+                            file::UniswapV2Preset::Baoswap => {
+                                liquidity::config::UniswapV2::baoswap(&network.id)
+                            }
+                            file::UniswapV2Preset::PancakeSwap => {
+                                liquidity::config::UniswapV2::pancake_swap(&network.id)
+                            }
                         }
-                    }
-                    .expect("no Uniswap V2 preset for current network"),
-                    file::UniswapV2Config::Manual { router, pool_code } => {
-                        liquidity::config::UniswapV2 {
-                            router: router.into(),
-                            pool_code: pool_code.into(),
+                        .ok_or(Error::Preset("Uniswap V2"))?,
+                        file::UniswapV2Config::Manual { router, pool_code } => {
+                            liquidity::config::UniswapV2 {
+                                router: router.into(),
+                                pool_code: pool_code.into(),
+                            }
                         }
-                    }
+                    })
                 })
-                .collect(),
+                .collect::<Result<_, Error>>()?,
             swapr: config
                 .liquidity
                 .swapr
                 .iter()
                 .cloned()
-                .map(|config| match config {
-                    file::SwaprConfig::Preset { preset } => match preset {
-                        file::SwaprPreset::Swapr => liquidity::config::Swapr::swapr(&network.id),
-                    }
-                    .expect("no Swapr preset for current network"),
-                    file::SwaprConfig::Manual { router, pool_code } => liquidity::config::Swapr {
-                        router: router.into(),
-                        pool_code: pool_code.into(),
-                    },
+                .map(|config| {
+                    Ok(match config {
+                        file::SwaprConfig::Preset { preset } => match preset {
+                            file::SwaprPreset::Swapr => {
+                                liquidity::config::Swapr::swapr(&network.id)
+                            }
+                        }
+                        .ok_or(Error::Preset("Swapr"))?,
+                        file::SwaprConfig::Manual { router, pool_code } => {
+                            liquidity::config::Swapr {
+                                router: router.into(),
+                                pool_code: pool_code.into(),
+                            }
+                        }
+                    })
                 })
-                .collect(),
+                .collect::<Result<_, Error>>()?,
             uniswap_v3: config
                 .liquidity
                 .uniswap_v3
                 .iter()
                 .cloned()
-                .map(|config| match config {
-                    file::UniswapV3Config::Preset {
-                        preset,
-                        max_pools_to_initialize,
-                    } => liquidity::config::UniswapV3 {
-                        max_pools_to_initialize,
-                        ..match preset {
-                            file::UniswapV3Preset::UniswapV3 => {
-                                liquidity::config::UniswapV3::uniswap_v3(&network.id)
+                .map(|config| {
+                    Ok(match config {
+                        file::UniswapV3Config::Preset {
+                            preset,
+                            max_pools_to_initialize,
+                        } => liquidity::config::UniswapV3 {
+                            max_pools_to_initialize,
+                            ..match preset {
+                                file::UniswapV3Preset::UniswapV3 => {
+                                    liquidity::config::UniswapV3::uniswap_v3(&network.id)
+                                }
                             }
-                        }
-                        .expect("no Uniswap V3 preset for current network")
-                    },
-                    file::UniswapV3Config::Manual {
-                        router,
-                        max_pools_to_initialize,
-                    } => liquidity::config::UniswapV3 {
-                        router: router.into(),
-                        max_pools_to_initialize,
-                    },
+                            .ok_or(Error::Preset("Uniswap V3"))?
+                        },
+                        file::UniswapV3Config::Manual {
+                            router,
+                            max_pools_to_initialize,
+                        } => liquidity::config::UniswapV3 {
+                            router: router.into(),
+                            max_pools_to_initialize,
+                        },
+                    })
                 })
-                .collect(),
+                .collect::<Result<_, Error>>()?,
             balancer_v2: config
                 .liquidity
                 .balancer_v2
                 .iter()
                 .cloned()
-                .map(|config| match config {
-                    file::BalancerV2Config::Preset {
-                        preset,
-                        pool_deny_list,
-                    } => liquidity::config::BalancerV2 {
-                        pool_deny_list: pool_deny_list.clone(),
-                        ..match preset {
-                            file::BalancerV2Preset::BalancerV2 => {
-                                liquidity::config::BalancerV2::balancer_v2(&network.id)
+                .map(|config| {
+                    Ok(match config {
+                        file::BalancerV2Config::Preset {
+                            preset,
+                            pool_deny_list,
+                        } => liquidity::config::BalancerV2 {
+                            pool_deny_list: pool_deny_list.clone(),
+                            ..match preset {
+                                file::BalancerV2Preset::BalancerV2 => {
+                                    liquidity::config::BalancerV2::balancer_v2(&network.id)
+                                }
                             }
-                        }
-                        .expect("no Balancer V2 preset for current network")
-                    },
-                    file::BalancerV2Config::Manual {
-                        vault,
-                        weighted,
-                        weighted_v3plus,
-                        stable,
-                        liquidity_bootstrapping,
-                        composable_stable,
-                        pool_deny_list,
-                    } => liquidity::config::BalancerV2 {
-                        vault: vault.into(),
-                        weighted: weighted
-                            .into_iter()
-                            .map(eth::ContractAddress::from)
-                            .collect(),
-                        weighted_v3plus: weighted_v3plus
-                            .into_iter()
-                            .map(eth::ContractAddress::from)
-                            .collect(),
-                        stable: stable.into_iter().map(eth::ContractAddress::from).collect(),
-                        liquidity_bootstrapping: liquidity_bootstrapping
-                            .into_iter()
-                            .map(eth::ContractAddress::from)
-                            .collect(),
-                        composable_stable: composable_stable
-                            .into_iter()
-                            .map(eth::ContractAddress::from)
-                            .collect(),
-                        pool_deny_list: pool_deny_list.clone(),
-                    },
+                            .ok_or(Error::Preset("Balancer V2"))?
+                        },
+                        file::BalancerV2Config::Manual {
+                            vault,
+                            weighted,
+                            weighted_v3plus,
+                            stable,
+                            liquidity_bootstrapping,
+                            composable_stable,
+                            pool_deny_list,
+                        } => liquidity::config::BalancerV2 {
+                            vault: vault.into(),
+                            weighted: weighted
+                                .into_iter()
+                                .map(eth::ContractAddress::from)
+                                .collect(),
+                            weighted_v3plus: weighted_v3plus
+                                .into_iter()
+                                .map(eth::ContractAddress::from)
+                                .collect(),
+                            stable: stable.into_iter().map(eth::ContractAddress::from).collect(),
+                            liquidity_bootstrapping: liquidity_bootstrapping
+                                .into_iter()
+                                .map(eth::ContractAddress::from)
+                                .collect(),
+                            composable_stable: composable_stable
+                                .into_iter()
+                                .map(eth::ContractAddress::from)
+                                .collect(),
+                            pool_deny_list: pool_deny_list.clone(),
+                        },
+                    })
                 })
-                .collect(),
+                .collect::<Result<_, Error>>()?,
         },
         mempools: config
             .submission
@@ -253,7 +302,7 @@ pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
                 url: config.url,
             })),
             (None, None) => None,
-            (Some(_), Some(_)) => panic!("Cannot configure both Tenderly and Enso"),
+            (Some(_), Some(_)) => return Err(Error::MultipleSimulators),
         },
         contracts: blockchain::contracts::Addresses {
             settlement: config.contracts.gp_v2_settlement.map(Into::into),
@@ -261,5 +310,49 @@ pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
         },
         disable_access_list_simulation: config.disable_access_list_simulation,
         disable_gas_simulation: config.disable_gas_simulation.map(Into::into),
-    }
+        auth: config.auth.map(|auth| {
+            Arc::new(request_signing::Verifier::new(
+                auth.secrets,
+                Duration::from_secs(auth.max_age_secs),
+            ))
+        }),
+    })
+}
+
+async fn account(solver: &str, account: file::Account) -> Result<ethcontract::Account, Error> {
+    Ok(match account {
+        file::Account::PrivateKey(private_key) => ethcontract::Account::Offline(
+            ethcontract::PrivateKey::from_raw(private_key.0)
+                .map_err(|_| Error::PrivateKey(solver.to_owned()))?,
+            None,
+        ),
+        file::Account::Kms(key_id) => {
+            let config = ethcontract::aws_config::load_from_env().await;
+            let account = ethcontract::transaction::kms::Account::new((&config).into(), &key_id.0)
+                .await
+                .map_err(|_| Error::Kms(key_id.0.clone()))?;
+            ethcontract::Account::Kms(account, None)
+        }
+        file::Account::Address(address) => ethcontract::Account::Local(address, None),
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error: {0:?}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse TOML: {0}")]
+    Parse(String),
+    #[error("the configured chain ID does not match connected Ethereum node")]
+    ChainId,
+    #[error("solver {0} is configured more than once")]
+    DuplicateSolver(String),
+    #[error("invalid private key for solver {0}")]
+    PrivateKey(String),
+    #[error("unable to load KMS account {0:?}")]
+    Kms(String),
+    #[error("no {0} preset for current network")]
+    Preset(&'static str),
+    #[error("cannot configure both Tenderly and Enso")]
+    MultipleSimulators,
 }
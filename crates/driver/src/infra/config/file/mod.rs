@@ -3,12 +3,12 @@ use {
     reqwest::Url,
     serde::Deserialize,
     serde_with::serde_as,
-    solver::solver::Arn,
+    shared::{arguments::Arn, request_signing::Secret},
 };
 
 mod load;
 
-pub use load::load;
+pub use load::{load, try_load, Error};
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
@@ -50,6 +50,26 @@ struct Config {
 
     #[serde(default)]
     liquidity: LiquidityConfig,
+
+    /// Require requests to the driver API to be signed with a shared secret.
+    auth: Option<AuthConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct AuthConfig {
+    /// The secrets requests may be signed with. Configuring more than one
+    /// secret allows rotating them without downtime.
+    secrets: Vec<Secret>,
+
+    /// The maximum age of a signed request before it gets rejected. Specified
+    /// in seconds.
+    #[serde(default = "default_auth_max_age_secs")]
+    max_age_secs: u64,
+}
+
+fn default_auth_max_age_secs() -> u64 {
+    30
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -172,8 +192,86 @@ struct SolverConfig {
     #[serde(default)]
     skip_liquidity: bool,
 
+    /// Only send liquidity of these kinds to the solver. All kinds are sent
+    /// if not set.
+    liquidity_kinds: Option<Vec<LiquidityKind>>,
+
+    /// Only send liquidity of these venues to the solver, i.e. liquidity
+    /// using these router contracts for Uniswap-like pools and these vault
+    /// contracts for Balancer pools. All venues are sent if not set.
+    liquidity_venues: Option<Vec<eth::H160>>,
+
+    /// Only send liquidity to the solver which is part of a route from the
+    /// sell to the buy token of some order using at most this many liquidity
+    /// sources.
+    liquidity_max_hops: Option<usize>,
+
     /// The account which should be used to sign settlements for this solver.
     account: Account,
+
+    /// More accounts to submit settlements with, so that a settlement can be
+    /// submitted while the transaction of a previous one is still pending.
+    /// Like the main account, these need to be allowed to settle and funded.
+    #[serde(default)]
+    additional_accounts: Vec<Account>,
+
+    /// The secret used to sign requests to this solver. Requests are sent
+    /// unsigned if this is not set.
+    auth_secret: Option<Secret>,
+
+    /// Whether to request the solutions from the streaming endpoint of the
+    /// solver. The solver then sends its solutions as soon as it finds them,
+    /// allowing the driver to encode and simulate them while the solver is
+    /// still searching.
+    #[serde(default)]
+    stream_solutions: bool,
+
+    /// When to consider the solver unhealthy and stop sending it auctions.
+    #[serde(default)]
+    circuit_breaker: CircuitBreakerConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct CircuitBreakerConfig {
+    /// Open the circuit breaker after this many consecutive requests to the
+    /// solver failed or timed out. While the breaker is open, auctions are
+    /// rejected immediately without contacting the solver.
+    #[serde(default = "default_max_consecutive_failures")]
+    max_consecutive_failures: u32,
+
+    /// How often to probe the solver while the circuit breaker is open. The
+    /// breaker closes as soon as a probe succeeds. Specified in seconds.
+    #[serde(default = "default_probe_interval_secs")]
+    probe_interval_secs: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            max_consecutive_failures: default_max_consecutive_failures(),
+            probe_interval_secs: default_probe_interval_secs(),
+        }
+    }
+}
+
+fn default_max_consecutive_failures() -> u32 {
+    5
+}
+
+fn default_probe_interval_secs() -> u64 {
+    10
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum LiquidityKind {
+    UniswapV2,
+    UniswapV3,
+    BalancerV2Stable,
+    BalancerV2Weighted,
+    Swapr,
+    ZeroEx,
 }
 
 #[serde_as]
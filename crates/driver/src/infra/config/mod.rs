@@ -1,6 +1,10 @@
-use crate::{
-    domain::eth,
-    infra::{blockchain, liquidity, mempool, simulator, solver},
+use {
+    crate::{
+        domain::eth,
+        infra::{blockchain, liquidity, mempool, simulator, solver},
+    },
+    shared::request_signing,
+    std::sync::Arc,
 };
 
 pub mod file;
@@ -15,4 +19,6 @@ pub struct Config {
     pub simulator: Option<simulator::Config>,
     pub mempools: Vec<mempool::Config>,
     pub contracts: blockchain::contracts::Addresses,
+    /// Verifies that requests to the driver API are authenticated.
+    pub auth: Option<Arc<request_signing::Verifier>>,
 }
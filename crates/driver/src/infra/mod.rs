@@ -1,4 +1,5 @@
 pub mod api;
+pub mod archive;
 pub mod blockchain;
 pub mod cli;
 pub mod config;
@@ -14,6 +15,7 @@ pub mod tokens;
 pub use {
     self::solver::Solver,
     api::Api,
+    archive::Archive,
     blockchain::Ethereum,
     config::Config,
     mempool::Mempool,
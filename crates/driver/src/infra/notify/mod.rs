@@ -5,7 +5,7 @@ use {
 
 mod notification;
 
-pub use notification::{Kind, Notification, ScoreKind, Settlement};
+pub use notification::{Kind, Notification, ScoreKind, Settlement, SimulationFailure};
 
 use crate::domain::{competition::score, eth, mempools::Error};
 
@@ -70,7 +70,14 @@ pub fn encoding_failed(
         }
         solution::Error::Blockchain(_) => (),
         solution::Error::Boundary(_) => (),
-        solution::Error::Simulation(_) => (), // todo,
+        solution::Error::SimulationRevert(revert) => {
+            solver.notify(
+                auction_id,
+                solution_id,
+                notification::Kind::SimulationFailed(simulation_failure(revert)),
+            );
+        }
+        solution::Error::Simulation(_) => (),
         solution::Error::AssetFlow(_) => (),
         solution::Error::Execution(_) => (),
         solution::Error::FailingInternalization => (),
@@ -83,6 +90,7 @@ pub fn executed(
     auction_id: auction::Id,
     solution_id: Option<solution::Id>,
     res: &Result<eth::TxId, Error>,
+    revert: Option<&solution::Revert>,
 ) {
     if solution_id.is_none() {
         return;
@@ -91,8 +99,10 @@ pub fn executed(
     let kind = match res {
         Ok(hash) => notification::Settlement::Success(hash.clone()),
         Err(Error::Revert(hash)) => notification::Settlement::Revert(hash.clone()),
-        Err(Error::SimulationRevert) => notification::Settlement::SimulationRevert,
-        Err(Error::Other(_)) => notification::Settlement::Fail,
+        Err(Error::SimulationRevert) => {
+            notification::Settlement::SimulationRevert(revert.map(simulation_failure))
+        }
+        Err(Error::Other(_)) => notification::Settlement::Fail(revert.map(simulation_failure)),
     };
 
     solver.notify(
@@ -113,3 +123,14 @@ pub fn duplicated_solution_id(
         notification::Kind::DuplicatedSolutionId,
     );
 }
+
+fn simulation_failure(revert: &solution::Revert) -> notification::SimulationFailure {
+    let details = revert.error.revert();
+    notification::SimulationFailure {
+        block: details.block,
+        tx: revert.error.tx().clone(),
+        reason: details.reason(),
+        interaction: revert.interaction,
+        gas_used: details.gas_used,
+    }
+}
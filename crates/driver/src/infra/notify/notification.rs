@@ -1,7 +1,10 @@
 use {
-    crate::domain::{
-        competition::{auction, solution, ObjectiveValue, Score, SuccessProbability},
-        eth::{self, Ether, TokenAddress},
+    crate::{
+        domain::{
+            competition::{auction, solution, ObjectiveValue, Score, SuccessProbability},
+            eth::{self, Ether, TokenAddress},
+        },
+        infra::simulator,
     },
     std::collections::BTreeSet,
 };
@@ -30,6 +33,8 @@ pub enum Kind {
     NonBufferableTokensUsed(TokensUsed),
     /// Solver don't have enough balance to submit the solution onchain.
     SolverAccountInsufficientBalance(RequiredEther),
+    /// The settlement of the solution reverted in simulation.
+    SimulationFailed(SimulationFailure),
     /// Result of winning solver trying to settle the transaction onchain.
     Settled(Settlement),
 }
@@ -55,6 +60,22 @@ pub enum ScoreKind {
     ScoreHigherThanObjective(Score, ObjectiveValue),
 }
 
+/// Diagnostics of a reverted simulation. Only the details the simulator can
+/// provide are set.
+#[derive(Debug)]
+pub struct SimulationFailure {
+    /// The block the settlement was simulated at.
+    pub block: Option<eth::BlockNo>,
+    /// The simulated settlement transaction.
+    pub tx: eth::Tx,
+    /// The decoded revert reason or custom error.
+    pub reason: Option<simulator::revert::Reason>,
+    /// The index of the interaction of the solution which reverted.
+    pub interaction: Option<usize>,
+    /// The gas used until the settlement reverted.
+    pub gas_used: Option<eth::Gas>,
+}
+
 type TransactionHash = eth::TxId;
 
 #[derive(Debug)]
@@ -63,8 +84,10 @@ pub enum Settlement {
     Success(TransactionHash),
     /// Winning solver mined reverted transaction.
     Revert(TransactionHash),
-    /// Transaction started reverting during the submission.
-    SimulationRevert,
-    /// Winning solver failed to settle the transaction onchain.
-    Fail,
+    /// Transaction started reverting during the submission. Contains the
+    /// diagnostics if the settlement still reverts in simulation.
+    SimulationRevert(Option<SimulationFailure>),
+    /// Winning solver failed to settle the transaction onchain. Contains the
+    /// diagnostics if the settlement reverts in simulation.
+    Fail(Option<SimulationFailure>),
 }
@@ -16,6 +16,12 @@ pub struct Metrics {
     /// The results of the quoting process.
     #[metric(labels("solver", "result"))]
     pub quotes: prometheus::IntCounterVec,
+    /// The outcomes of the requests to the solver engines.
+    #[metric(labels("solver", "outcome"))]
+    pub solver_requests: prometheus::IntCounterVec,
+    /// Whether the circuit breaker of a solver engine is open.
+    #[metric(labels("solver"))]
+    pub solver_breaker_open: prometheus::IntGaugeVec,
 }
 
 /// Setup the metrics registry.
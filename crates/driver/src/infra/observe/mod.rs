@@ -21,19 +21,20 @@ use {
             quote::{self, Quote},
             Liquidity,
         },
-        infra::solver,
+        infra::{config, solver},
         util::http,
     },
     std::collections::HashMap,
+    tracing::level_filters::LevelFilter,
     url::Url,
 };
 
 mod metrics;
 
 /// Setup the observability. The log argument configures the tokio tracing
-/// framework.
-pub fn init(log: &str) {
-    observe::tracing::initialize_reentrant(log);
+/// framework, events up to the stderr threshold are logged to stderr.
+pub fn init(log: &str, stderr_threshold: LevelFilter) {
+    observe::tracing::initialize_reentrant_with_stderr_threshold(log, stderr_threshold);
     metrics::init();
 }
 
@@ -271,6 +272,23 @@ pub fn mounting_solver(solver: &solver::Name, path: &str) {
     tracing::debug!(%solver, path, "mounting solver");
 }
 
+/// Observe that the API routes for a solver were removed after reloading the
+/// config.
+pub fn unmounting_solver(solver: &solver::Name) {
+    tracing::info!(%solver, "unmounting solver");
+}
+
+/// Observe that the driver config was reloaded.
+pub fn reloaded_config<'a>(solvers: impl Iterator<Item = &'a solver::Name>) {
+    let solvers = solvers.map(ToString::to_string).collect::<Vec<_>>();
+    tracing::info!(?solvers, "reloaded config");
+}
+
+/// Observe that a changed driver config is invalid and was not reloaded.
+pub fn invalid_config(err: &config::file::Error) {
+    tracing::warn!(?err, "ignoring invalid config");
+}
+
 /// Observe that a request is about to be sent to the solver.
 pub fn solver_request(endpoint: &Url, req: &str) {
     tracing::trace!(%endpoint, %req, "sending request to solver");
@@ -288,6 +306,33 @@ pub fn solver_response(endpoint: &Url, res: Result<&str, &http::Error>) {
     }
 }
 
+/// Observe the outcome of a request to a solver and how it affected the
+/// circuit breaker of the solver.
+pub fn solver_health(
+    solver: &solver::Name,
+    outcome: solver::breaker::Outcome,
+    transition: Option<solver::breaker::Transition>,
+    health: solver::breaker::Health,
+) {
+    match transition {
+        Some(solver::breaker::Transition::Opened) => {
+            tracing::warn!(%solver, ?health, "solver is unhealthy, opened circuit breaker")
+        }
+        Some(solver::breaker::Transition::Closed) => {
+            tracing::info!(%solver, "solver recovered, closed circuit breaker")
+        }
+        None => tracing::trace!(%solver, ?outcome, ?health, "solver request finished"),
+    }
+    metrics::get()
+        .solver_requests
+        .with_label_values(&[solver.as_str(), outcome.as_str()])
+        .inc();
+    metrics::get()
+        .solver_breaker_open
+        .with_label_values(&[solver.as_str()])
+        .set(health.open.into());
+}
+
 /// Observe the result of mempool transaction execution.
 pub fn mempool_executed(
     mempool: &Mempool,
@@ -332,7 +377,9 @@ fn competition_error(err: &competition::Error) -> &'static str {
         competition::Error::Solver(solver::Error::Deserialize(_)) => "SolverDeserializeError",
         competition::Error::Solver(solver::Error::DuplicatedSolutionId) => "DuplicatedSolutionId",
         competition::Error::Solver(solver::Error::Dto(_)) => "SolverDtoError",
+        competition::Error::Solver(solver::Error::Unavailable) => "SolverUnavailable",
         competition::Error::SubmissionError => "SubmissionError",
+        competition::Error::NoAccountAvailable => "NoAccountAvailable",
     }
 }
 
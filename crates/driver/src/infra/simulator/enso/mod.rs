@@ -52,17 +52,26 @@ impl Enso {
 #[error("Enso tx simulation error")]
 pub enum Error {
     Http(#[from] reqwest::Error),
-    Revert(String),
+    Revert(String, super::Revert),
 }
 
 impl From<dto::Response> for Result<eth::Gas, Error> {
     fn from(response: dto::Response) -> Self {
         if !response.success {
-            return Err(Error::Revert(format!(
-                "{}: {}",
-                response.exit_reason,
-                hex::encode(&response.return_data)
-            )));
+            return Err(Error::Revert(
+                format!(
+                    "{}: {}",
+                    response.exit_reason,
+                    hex::encode(&response.return_data)
+                ),
+                super::Revert {
+                    block: Some(eth::BlockNo(response.block_number)),
+                    data: Some(response.return_data.into()),
+                    message: Some(response.exit_reason),
+                    failed_call: None,
+                    gas_used: Some(response.gas_used.into()),
+                },
+            ));
         }
         Ok(response.gas_used.into())
     }
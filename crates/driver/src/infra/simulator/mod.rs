@@ -8,8 +8,11 @@ use {
 };
 
 pub mod enso;
+pub mod revert;
 pub mod tenderly;
 
+pub use revert::Revert;
+
 /// Ethereum transaction simulator.
 #[derive(Debug, Clone)]
 pub struct Simulator {
@@ -80,17 +83,17 @@ impl Simulator {
                 tenderly
                     .simulate(tx.clone(), tenderly::GenerateAccessList::Yes)
                     .await
-                    .map_err(with_tx(tx.clone()))?
+                    .map_err(with_tx(tx.clone(), None))?
                     .access_list
             }
             Inner::Ethereum(ethereum) => ethereum
                 .create_access_list(tx.clone())
                 .await
-                .map_err(with_tx(tx.clone()))?,
+                .map_err(with_tx(tx.clone(), Some(current_block(ethereum))))?,
             Inner::Enso(_, ethereum) => ethereum
                 .create_access_list(tx.clone())
                 .await
-                .map_err(with_tx(tx.clone()))?,
+                .map_err(with_tx(tx.clone(), Some(current_block(ethereum))))?,
         };
         Ok(tx.access_list.merge(access_list))
     }
@@ -106,18 +109,18 @@ impl Simulator {
                     .simulate(tx.clone(), tenderly::GenerateAccessList::No)
                     .measure("tenderly_simulate_gas")
                     .await
-                    .map_err(with_tx(tx))?
+                    .map_err(with_tx(tx, None))?
                     .gas
             }
             Inner::Ethereum(ethereum) => ethereum
                 .estimate_gas(tx.clone())
                 .await
-                .map_err(with_tx(tx))?,
+                .map_err(with_tx(tx, Some(current_block(ethereum))))?,
             Inner::Enso(enso, _) => enso
                 .simulate(tx.clone())
                 .measure("enso_simulate_gas")
                 .await
-                .map_err(with_tx(tx))?,
+                .map_err(with_tx(tx, None))?,
         })
     }
 }
@@ -144,6 +147,19 @@ pub enum SimulatorError {
 pub struct WithTxError {
     err: SimulatorError,
     tx: eth::Tx,
+    revert: Revert,
+}
+
+impl WithTxError {
+    /// The transaction which reverted.
+    pub fn tx(&self) -> &eth::Tx {
+        &self.tx
+    }
+
+    /// Diagnostics about why the transaction reverted.
+    pub fn revert(&self) -> &Revert {
+        &self.revert
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -156,36 +172,52 @@ pub enum Error {
     WithTx(#[from] WithTxError),
 }
 
-fn with_tx<E>(tx: eth::Tx) -> impl FnOnce(E) -> Error
+/// The block the node simulates transactions at.
+fn current_block(eth: &Ethereum) -> eth::BlockNo {
+    eth::BlockNo(eth.current_block().borrow().number)
+}
+
+/// Attaches the transaction to the error if it reverted. `block` is the block
+/// the transaction was simulated at, for simulators which don't report it.
+fn with_tx<E>(tx: eth::Tx, block: Option<eth::BlockNo>) -> impl FnOnce(E) -> Error
 where
     E: Into<SimulatorError>,
 {
     move |err| {
         let err: SimulatorError = err.into();
-        let tx = match &err {
+        let revert = match &err {
             SimulatorError::Tenderly(tenderly::Error::Http(_)) => None,
-            SimulatorError::Tenderly(tenderly::Error::Revert(_)) => Some(tx),
-            SimulatorError::Blockchain(blockchain::Error::Method(error))
-                if matches!(error.inner, ExecutionError::Revert(_)) =>
-            {
-                Some(tx)
-            }
-            SimulatorError::Blockchain(blockchain::Error::Method(_)) => None,
+            SimulatorError::Tenderly(tenderly::Error::Revert(_, revert)) => Some(revert.clone()),
+            SimulatorError::Blockchain(blockchain::Error::Method(error)) => match &error.inner {
+                ExecutionError::Revert(message) => Some(Revert {
+                    message: message.clone(),
+                    ..Default::default()
+                }),
+                _ => None,
+            },
             SimulatorError::Blockchain(blockchain::Error::Web3(inner)) => {
-                let error = ExecutionError::from(inner.clone());
-                if matches!(error, ExecutionError::Revert(_)) {
-                    Some(tx)
-                } else {
-                    None
+                match ExecutionError::from(inner.clone()) {
+                    ExecutionError::Revert(message) => Some(Revert {
+                        message,
+                        ..Default::default()
+                    }),
+                    _ => None,
                 }
             }
             SimulatorError::Blockchain(blockchain::Error::Gas(_)) => None,
             SimulatorError::Blockchain(blockchain::Error::Response(_)) => None,
             SimulatorError::Enso(enso::Error::Http(_)) => None,
-            SimulatorError::Enso(enso::Error::Revert(_)) => Some(tx),
+            SimulatorError::Enso(enso::Error::Revert(_, revert)) => Some(revert.clone()),
         };
-        match tx {
-            Some(tx) => Error::WithTx(WithTxError { err, tx }),
+        match revert {
+            Some(revert) => Error::WithTx(WithTxError {
+                err,
+                tx,
+                revert: Revert {
+                    block: revert.block.or(block),
+                    ..revert
+                },
+            }),
             None => Error::Basic(err),
         }
     }
@@ -0,0 +1,133 @@
+use {
+    crate::{domain::eth, util::Bytes},
+    hex_literal::hex,
+};
+
+/// Selector of the `Error(string)` revert reason emitted by `require` and
+/// `revert` with a message.
+const ERROR_SELECTOR: [u8; 4] = hex!("08c379a0");
+/// Selector of the `Panic(uint256)` revert reason emitted by failed asserts,
+/// arithmetic overflows and the like.
+const PANIC_SELECTOR: [u8; 4] = hex!("4e487b71");
+
+/// Diagnostics about a reverted simulation. Which of these the simulator can
+/// provide depends on the simulator.
+#[derive(Debug, Clone, Default)]
+pub struct Revert {
+    /// The block the transaction was simulated at.
+    pub block: Option<eth::BlockNo>,
+    /// The data the transaction reverted with.
+    pub data: Option<Bytes<Vec<u8>>>,
+    /// The revert message as reported by the simulator, used if the revert
+    /// data is not available.
+    pub message: Option<String>,
+    /// The call made directly by the settlement contract which reverted.
+    pub failed_call: Option<Call>,
+    /// The gas used until the transaction reverted.
+    pub gas_used: Option<eth::Gas>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Call {
+    pub to: eth::Address,
+    pub input: Bytes<Vec<u8>>,
+}
+
+/// Why a transaction reverted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// `Error(string)` or a message reported by the simulator.
+    Message(String),
+    /// `Panic(uint256)` with the panic code.
+    Panic(eth::U256),
+    /// A custom Solidity error.
+    Custom {
+        selector: Bytes<[u8; 4]>,
+        data: Bytes<Vec<u8>>,
+    },
+}
+
+impl Revert {
+    /// Decodes the reason of the revert from the revert data, falling back to
+    /// the message reported by the simulator.
+    pub fn reason(&self) -> Option<Reason> {
+        self.data
+            .as_ref()
+            .and_then(|data| decode(&data.0))
+            .or_else(|| self.message.clone().map(Reason::Message))
+    }
+}
+
+fn decode(data: &[u8]) -> Option<Reason> {
+    if data.len() < 4 {
+        return None;
+    }
+    let (selector, params) = data.split_at(4);
+    let selector: [u8; 4] = selector.try_into().unwrap();
+    match selector {
+        ERROR_SELECTOR => {
+            let tokens = ethabi::decode(&[ethabi::ParamType::String], params).ok()?;
+            tokens
+                .into_iter()
+                .next()?
+                .into_string()
+                .map(Reason::Message)
+        }
+        PANIC_SELECTOR => {
+            let tokens = ethabi::decode(&[ethabi::ParamType::Uint(256)], params).ok()?;
+            tokens.into_iter().next()?.into_uint().map(Reason::Panic)
+        }
+        _ => Some(Reason::Custom {
+            selector: Bytes(selector),
+            data: Bytes(params.to_vec()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_revert_reasons() {
+        let message = [
+            ERROR_SELECTOR.to_vec(),
+            ethabi::encode(&[ethabi::Token::String(
+                "GPv2: limit price not respected".into(),
+            )]),
+        ]
+        .concat();
+        assert_eq!(
+            decode(&message),
+            Some(Reason::Message("GPv2: limit price not respected".into()))
+        );
+
+        let panic = [
+            PANIC_SELECTOR.to_vec(),
+            ethabi::encode(&[ethabi::Token::Uint(0x11.into())]),
+        ]
+        .concat();
+        assert_eq!(decode(&panic), Some(Reason::Panic(0x11.into())));
+
+        assert_eq!(
+            decode(&hex!("deadbeef0102")),
+            Some(Reason::Custom {
+                selector: Bytes(hex!("deadbeef")),
+                data: Bytes(vec![1, 2]),
+            })
+        );
+        assert_eq!(decode(&[]), None);
+    }
+
+    #[test]
+    fn falls_back_to_message() {
+        let revert = Revert {
+            message: Some("execution reverted".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            revert.reason(),
+            Some(Reason::Message("execution reverted".into()))
+        );
+    }
+}
@@ -34,6 +34,29 @@ pub struct Response {
 pub struct Transaction {
     pub status: bool,
     pub gas_used: u64,
+    pub block_number: Option<u64>,
+    pub error_message: Option<String>,
+    pub transaction_info: Option<TransactionInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransactionInfo {
+    pub call_trace: Option<CallTrace>,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+pub struct CallTrace {
+    pub to: eth::H160,
+    #[serde_as(as = "Option<serialize::Hex>")]
+    #[serde(default)]
+    pub input: Option<Vec<u8>>,
+    #[serde_as(as = "Option<serialize::Hex>")]
+    #[serde(default)]
+    pub output: Option<Vec<u8>>,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub calls: Vec<CallTrace>,
 }
 
 /// Tenderly requires access lists to be serialized with `snake_case` instead
@@ -114,7 +114,7 @@ pub(super) enum GenerateAccessList {
 #[error("tenderly error")]
 pub enum Error {
     Http(#[from] reqwest::Error),
-    Revert(SimulationId),
+    Revert(SimulationId, super::Revert),
 }
 
 impl From<dto::Response> for Result<Simulation, Error> {
@@ -127,7 +127,33 @@ impl From<dto::Response> for Result<Simulation, Error> {
                 access_list: res.generated_access_list.unwrap_or_default().into(),
             })
         } else {
-            Err(Error::Revert(id))
+            let trace = res
+                .transaction
+                .transaction_info
+                .and_then(|info| info.call_trace);
+            Err(Error::Revert(
+                id,
+                super::Revert {
+                    block: res.transaction.block_number.map(eth::BlockNo),
+                    data: trace
+                        .as_ref()
+                        .and_then(|trace| trace.output.clone())
+                        .map(Into::into),
+                    message: res.transaction.error_message,
+                    // The calls made directly by the settlement contract.
+                    failed_call: trace.and_then(|trace| {
+                        trace
+                            .calls
+                            .into_iter()
+                            .find(|call| call.error.is_some())
+                            .map(|call| super::revert::Call {
+                                to: call.to.into(),
+                                input: call.input.unwrap_or_default().into(),
+                            })
+                    }),
+                    gas_used: Some(res.transaction.gas_used.into()),
+                },
+            ))
         }
     }
 }
@@ -0,0 +1,257 @@
+use {
+    crate::{
+        domain::eth,
+        infra::blockchain::{self, Ethereum},
+    },
+    std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+            Mutex,
+        },
+    },
+};
+
+/// The accounts a solver submits settlements with. Every account has at most
+/// one settlement in flight, so that a transaction which is stuck in the
+/// mempool only blocks its own account and the solver can keep settling the
+/// auctions it wins with the other accounts.
+///
+/// The state of the accounts is tracked locally and refreshed from the node
+/// after every settlement, so reserving an account doesn't cause any requests.
+#[derive(Debug, Clone)]
+pub struct Accounts(Arc<Vec<Arc<Slot>>>);
+
+/// The state of all solver accounts by address. Solvers get recreated whenever
+/// the config is reloaded, but the state of their accounts is kept, so that an
+/// account with a settlement in flight doesn't get reserved a second time.
+#[derive(Debug, Default)]
+pub struct Slots(HashMap<eth::Address, Arc<Slot>>);
+
+#[derive(Debug)]
+struct Slot {
+    account: ethcontract::Account,
+    busy: AtomicBool,
+    /// Whether the account had transactions pending in the mempool when it
+    /// was last refreshed.
+    pending: AtomicBool,
+    /// The nonce following the last transaction of this account that is known
+    /// to be mined. The node might not have seen the block including it yet.
+    next_nonce: Mutex<eth::U256>,
+}
+
+impl Accounts {
+    /// Creates the accounts of a solver. The first account is the main
+    /// account of the solver. Accounts which are already known keep their
+    /// state.
+    pub fn new(
+        accounts: impl IntoIterator<Item = ethcontract::Account>,
+        slots: &mut Slots,
+    ) -> Self {
+        let slots = accounts
+            .into_iter()
+            .map(|account| {
+                slots
+                    .0
+                    .entry(account.address().into())
+                    .or_insert_with(|| {
+                        Arc::new(Slot {
+                            account,
+                            busy: AtomicBool::new(false),
+                            pending: AtomicBool::new(false),
+                            next_nonce: Default::default(),
+                        })
+                    })
+                    .clone()
+            })
+            .collect::<Vec<_>>();
+        assert!(!slots.is_empty(), "a solver needs at least one account");
+        Self(Arc::new(slots))
+    }
+
+    /// Reserves an account for submitting a settlement. Free accounts without
+    /// pending transactions are preferred, followed by free accounts whose
+    /// stuck transaction gets replaced by the settlement. Returns `None` if all
+    /// accounts have a settlement in flight, since sharing an account would
+    /// make both settlements compete for the same nonce.
+    pub fn acquire(&self) -> Option<Lease> {
+        let mut stuck = None;
+        for (index, slot) in self.0.iter().enumerate() {
+            if slot
+                .busy
+                .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+                .is_err()
+            {
+                continue;
+            }
+            let lease = Lease {
+                accounts: self.clone(),
+                index,
+            };
+            if slot.pending.load(Ordering::SeqCst) {
+                // Further stuck accounts are freed again by dropping their lease.
+                stuck.get_or_insert(lease);
+                continue;
+            }
+            return Some(lease);
+        }
+        if stuck.is_none() {
+            tracing::warn!("all solver accounts have a settlement in flight");
+        }
+        stuck
+    }
+}
+
+/// An account reserved for submitting a settlement. The account becomes free
+/// again when the lease is dropped.
+#[derive(Debug)]
+pub struct Lease {
+    accounts: Accounts,
+    index: usize,
+}
+
+impl Lease {
+    fn slot(&self) -> &Slot {
+        &self.accounts.0[self.index]
+    }
+
+    /// The account to sign the settlement transaction with.
+    pub fn account(&self) -> &ethcontract::Account {
+        &self.slot().account
+    }
+
+    pub fn address(&self) -> eth::Address {
+        self.account().address().into()
+    }
+
+    /// The nonce to submit the settlement with. Unless a transaction of the
+    /// account gets mined in the meantime, the settlement replaces any
+    /// transaction of the account which is stuck in the mempool.
+    pub async fn nonce(&self, eth: &Ethereum) -> Result<eth::U256, blockchain::Error> {
+        let mined = eth.nonce(self.address(), false).await?;
+        Ok(mined.max(*self.slot().next_nonce.lock().unwrap()))
+    }
+
+    /// Records that the transaction with the specified nonce got mined.
+    pub fn mined(&self, nonce: eth::U256) {
+        let mut next_nonce = self.slot().next_nonce.lock().unwrap();
+        *next_nonce = (*next_nonce).max(nonce + 1);
+    }
+
+    /// Fetches whether the account has transactions which are pending in the
+    /// mempool, which makes it less preferable for the next settlement.
+    pub async fn refresh(&self, eth: &Ethereum) {
+        let address = self.address();
+        match tokio::try_join!(eth.nonce(address, false), eth.nonce(address, true)) {
+            Ok((mined, pending)) => self.update(mined, pending),
+            Err(err) => tracing::warn!(?err, ?address, "failed to fetch solver account nonce"),
+        }
+    }
+
+    fn update(&self, mined: eth::U256, pending: eth::U256) {
+        let slot = self.slot();
+        let mut next_nonce = slot.next_nonce.lock().unwrap();
+        *next_nonce = (*next_nonce).max(mined);
+        slot.pending.store(pending > *next_nonce, Ordering::SeqCst);
+    }
+}
+
+impl Drop for Lease {
+    fn drop(&mut self) {
+        self.slot().busy.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, primitive_types::H160};
+
+    fn account(i: u64) -> ethcontract::Account {
+        ethcontract::Account::Local(H160::from_low_u64_be(i), None)
+    }
+
+    fn accounts(count: u64) -> Accounts {
+        Accounts::new((1..=count).map(account), &mut Slots::default())
+    }
+
+    fn address(i: u64) -> eth::Address {
+        H160::from_low_u64_be(i).into()
+    }
+
+    #[test]
+    fn reserves_free_accounts() {
+        let accounts = accounts(2);
+        let first = accounts.acquire().unwrap();
+        let second = accounts.acquire().unwrap();
+        assert_eq!(first.address(), address(1));
+        assert_eq!(second.address(), address(2));
+
+        // All accounts are busy and never get shared.
+        assert!(accounts.acquire().is_none());
+        assert!(accounts.0[0].busy.load(Ordering::SeqCst));
+
+        drop(first);
+        assert_eq!(accounts.acquire().unwrap().address(), address(1));
+    }
+
+    #[test]
+    fn prefers_accounts_without_pending_transactions() {
+        let accounts = accounts(3);
+        accounts.acquire().unwrap().update(5.into(), 6.into());
+        accounts.acquire().unwrap().update(5.into(), 5.into());
+
+        let lease = accounts.acquire().unwrap();
+        assert_eq!(lease.address(), address(2));
+        let lease_ = accounts.acquire().unwrap();
+        assert_eq!(lease_.address(), address(3));
+        // Only the account with a pending transaction is free.
+        let stuck = accounts.acquire().unwrap();
+        assert_eq!(stuck.address(), address(1));
+
+        // The pending transaction got mined.
+        stuck.update(6.into(), 6.into());
+        assert!(!accounts.0[0].pending.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn tracks_mined_nonces() {
+        let accounts = accounts(1);
+        let lease = accounts.acquire().unwrap();
+        lease.mined(7.into());
+        assert_eq!(*accounts.0[0].next_nonce.lock().unwrap(), 8.into());
+
+        // The node hasn't seen the mined transaction yet, so the transaction
+        // it reports as pending is the mined one.
+        lease.update(7.into(), 8.into());
+        assert_eq!(*accounts.0[0].next_nonce.lock().unwrap(), 8.into());
+        assert!(!accounts.0[0].pending.load(Ordering::SeqCst));
+
+        // A lower nonce never replaces a higher one.
+        lease.mined(3.into());
+        assert_eq!(*accounts.0[0].next_nonce.lock().unwrap(), 8.into());
+    }
+
+    #[test]
+    fn keeps_state_of_known_accounts() {
+        let mut slots = Slots::default();
+        let accounts = Accounts::new([account(1), account(2)], &mut slots);
+        let lease = accounts.acquire().unwrap();
+        lease.mined(7.into());
+
+        // The solver gets recreated with another additional account after the
+        // config got reloaded. The account with the settlement in flight stays
+        // reserved.
+        let reloaded = Accounts::new([account(1), account(3)], &mut slots);
+        let other = reloaded.acquire().unwrap();
+        assert_eq!(other.address(), address(3));
+        assert!(reloaded.acquire().is_none());
+
+        // Once the settlement is done, the account is free again and still
+        // knows its nonce.
+        drop(lease);
+        let lease = reloaded.acquire().unwrap();
+        assert_eq!(lease.address(), address(1));
+        assert_eq!(*reloaded.0[0].next_nonce.lock().unwrap(), 8.into());
+    }
+}
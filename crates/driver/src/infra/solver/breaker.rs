@@ -0,0 +1,204 @@
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// The number of most recent requests used to compute the failure and timeout
+/// rates of a solver.
+const WINDOW: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Open the breaker after this many consecutive requests failed or timed
+    /// out.
+    pub max_consecutive_failures: u32,
+    /// How often the solver gets probed while the breaker is open.
+    pub probe_interval: Duration,
+}
+
+/// The result of a request to a solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    Failure,
+    Timeout,
+}
+
+impl Outcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::Timeout => "timeout",
+        }
+    }
+}
+
+/// A circuit breaker tracking the health of a solver. After too many
+/// consecutive failed or timed out requests the breaker opens and requests to
+/// the solver fail fast, so that the driver doesn't wait for the whole solver
+/// timeout on every auction. While the breaker is open the solver is probed
+/// periodically and the breaker closes again as soon as a probe succeeds.
+#[derive(Debug)]
+pub struct Breaker {
+    config: Config,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct State {
+    /// When the breaker opened, `None` while it is closed.
+    opened: Option<Instant>,
+    consecutive_failures: u32,
+    /// The outcomes of the most recent requests, newest last.
+    outcomes: VecDeque<Outcome>,
+}
+
+/// A snapshot of the health of a solver.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Health {
+    /// Whether the breaker is open, i.e. the solver is considered unhealthy.
+    pub open: bool,
+    pub consecutive_failures: u32,
+    /// The share of the recent requests that failed.
+    pub failure_rate: f64,
+    /// The share of the recent requests that timed out.
+    pub timeout_rate: f64,
+}
+
+/// How the state of the breaker changed after recording an outcome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Opened,
+    Closed,
+}
+
+impl Breaker {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            state: Default::default(),
+        }
+    }
+
+    /// Creates a breaker with the specified config which continues from the
+    /// current state of this breaker.
+    pub fn reconfigure(&self, config: Config) -> Self {
+        Self {
+            config,
+            state: Mutex::new(self.state.lock().unwrap().clone()),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Whether requests to the solver should fail fast.
+    pub fn is_open(&self) -> bool {
+        self.state.lock().unwrap().opened.is_some()
+    }
+
+    /// Records the outcome of a request to the solver, including probes.
+    /// Returns the transition of the breaker, if any.
+    pub fn record(&self, outcome: Outcome) -> Option<Transition> {
+        let mut state = self.state.lock().unwrap();
+        if state.outcomes.len() == WINDOW {
+            state.outcomes.pop_front();
+        }
+        state.outcomes.push_back(outcome);
+
+        match outcome {
+            Outcome::Success => {
+                state.consecutive_failures = 0;
+                state.opened.take().map(|_| Transition::Closed)
+            }
+            Outcome::Failure | Outcome::Timeout => {
+                state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+                if state.opened.is_none()
+                    && state.consecutive_failures >= self.config.max_consecutive_failures
+                {
+                    state.opened = Some(Instant::now());
+                    Some(Transition::Opened)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    pub fn health(&self) -> Health {
+        let state = self.state.lock().unwrap();
+        let rate = |outcome| {
+            if state.outcomes.is_empty() {
+                return 0.;
+            }
+            let count = state.outcomes.iter().filter(|o| **o == outcome).count();
+            count as f64 / state.outcomes.len() as f64
+        };
+        Health {
+            open: state.opened.is_some(),
+            consecutive_failures: state.consecutive_failures,
+            failure_rate: rate(Outcome::Failure),
+            timeout_rate: rate(Outcome::Timeout),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> Breaker {
+        Breaker::new(Config {
+            max_consecutive_failures: 3,
+            probe_interval: Duration::from_secs(1),
+        })
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let breaker = breaker();
+        assert_eq!(breaker.record(Outcome::Failure), None);
+        assert_eq!(breaker.record(Outcome::Timeout), None);
+        assert_eq!(breaker.record(Outcome::Success), None);
+        assert_eq!(breaker.record(Outcome::Failure), None);
+        assert_eq!(breaker.record(Outcome::Failure), None);
+        assert!(!breaker.is_open());
+        assert_eq!(breaker.record(Outcome::Timeout), Some(Transition::Opened));
+        assert!(breaker.is_open());
+        assert_eq!(breaker.record(Outcome::Failure), None);
+        assert!(breaker.is_open());
+    }
+
+    #[test]
+    fn closes_after_success() {
+        let breaker = breaker();
+        for _ in 0..3 {
+            breaker.record(Outcome::Failure);
+        }
+        assert!(breaker.is_open());
+        assert_eq!(breaker.record(Outcome::Success), Some(Transition::Closed));
+        assert!(!breaker.is_open());
+        assert_eq!(breaker.health().consecutive_failures, 0);
+    }
+
+    #[test]
+    fn computes_rates_over_window() {
+        let breaker = breaker();
+        assert_eq!(breaker.health().failure_rate, 0.);
+        breaker.record(Outcome::Success);
+        breaker.record(Outcome::Failure);
+        breaker.record(Outcome::Timeout);
+        breaker.record(Outcome::Success);
+        let health = breaker.health();
+        assert_eq!(health.failure_rate, 0.25);
+        assert_eq!(health.timeout_rate, 0.25);
+
+        for _ in 0..WINDOW {
+            breaker.record(Outcome::Success);
+        }
+        assert_eq!(breaker.health().failure_rate, 0.);
+    }
+}
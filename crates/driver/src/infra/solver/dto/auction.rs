@@ -1,6 +1,7 @@
 use {
     crate::{
         domain::{competition, competition::order, eth, liquidity},
+        infra::solver,
         util::{
             conv::{rational_to_big_decimal, u256::U256Ext},
             serialize,
@@ -16,9 +17,12 @@ impl Auction {
     pub fn new(
         auction: &competition::Auction,
         liquidity: &[liquidity::Liquidity],
+        filter: &solver::LiquidityFilter,
         timeout: competition::SolverTimeout,
         weth: eth::WethAddress,
     ) -> Self {
+        let liquidity = filter.apply(auction, liquidity, weth);
+
         let mut tokens: HashMap<eth::H160, _> = auction
             .tokens()
             .iter()
@@ -31,6 +35,9 @@ impl Auction {
                         reference_price: token.price.map(Into::into),
                         available_balance: token.available_balance,
                         trusted: token.trusted,
+                        transfer_fee_bps: token
+                            .transfer_fee
+                            .map(competition::auction::TransferFee::bps),
                     },
                 )
             })
@@ -40,16 +47,7 @@ impl Auction {
         // which we are providing liquidity.
         for token in liquidity
             .iter()
-            .flat_map(|liquidity| match &liquidity.kind {
-                liquidity::Kind::UniswapV2(pool) => pool.reserves.iter().map(|r| r.token).collect(),
-                liquidity::Kind::UniswapV3(pool) => vec![pool.tokens.get().0, pool.tokens.get().1],
-                liquidity::Kind::BalancerV2Stable(pool) => pool.reserves.tokens().collect(),
-                liquidity::Kind::BalancerV2Weighted(pool) => pool.reserves.tokens().collect(),
-                liquidity::Kind::Swapr(pool) => {
-                    pool.base.reserves.iter().map(|r| r.token).collect()
-                }
-                liquidity::Kind::ZeroEx(_) => todo!(),
-            })
+            .flat_map(|liquidity| liquidity.kind.tokens())
         {
             tokens.entry(token.into()).or_insert_with(Default::default);
         }
@@ -263,6 +261,8 @@ struct Token {
     #[serde_as(as = "serialize::U256")]
     available_balance: eth::U256,
     trusted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transfer_fee_bps: Option<u32>,
 }
 
 // TODO Remove dead_code
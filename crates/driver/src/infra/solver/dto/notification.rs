@@ -4,7 +4,7 @@ use {
             competition::{auction, solution},
             eth,
         },
-        infra::notify,
+        infra::{notify, simulator},
         util::serialize,
     },
     serde::Serialize,
@@ -49,6 +49,9 @@ impl Notification {
                         required: required.0,
                     }
                 }
+                notify::Kind::SimulationFailed(failure) => {
+                    Kind::SimulationFailed(SimulationFailure::new(failure))
+                }
                 notify::Kind::DuplicatedSolutionId => Kind::DuplicatedSolutionId,
                 notify::Kind::Settled(kind) => Kind::Settled(match kind {
                     notify::Settlement::Success(hash) => Settlement::Success {
@@ -57,8 +60,12 @@ impl Notification {
                     notify::Settlement::Revert(hash) => Settlement::Revert {
                         transaction: hash.0,
                     },
-                    notify::Settlement::SimulationRevert => Settlement::SimulationRevert,
-                    notify::Settlement::Fail => Settlement::Fail,
+                    notify::Settlement::SimulationRevert(failure) => {
+                        Settlement::SimulationRevert(failure.map(SimulationFailure::new))
+                    }
+                    notify::Settlement::Fail(failure) => {
+                        Settlement::Fail(failure.map(SimulationFailure::new))
+                    }
                 }),
             },
         }
@@ -89,6 +96,76 @@ pub enum Kind {
         required: eth::U256,
     },
     Settled(Settlement),
+    SimulationFailed(SimulationFailure),
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationFailure {
+    block: Option<u64>,
+    tx: Tx,
+    revert_reason: Option<RevertReason>,
+    interaction: Option<usize>,
+    #[serde_as(as = "Option<serialize::U256>")]
+    gas_used: Option<eth::U256>,
+}
+
+impl SimulationFailure {
+    fn new(failure: notify::SimulationFailure) -> Self {
+        Self {
+            block: failure.block.map(|block| block.0),
+            tx: Tx {
+                from: failure.tx.from.into(),
+                to: failure.tx.to.into(),
+                input: failure.tx.input.into(),
+                value: failure.tx.value.into(),
+                access_list: failure.tx.access_list.into(),
+            },
+            revert_reason: failure.reason.map(|reason| match reason {
+                simulator::revert::Reason::Message(message) => RevertReason::Message { message },
+                simulator::revert::Reason::Panic(code) => RevertReason::Panic { code },
+                simulator::revert::Reason::Custom { selector, data } => RevertReason::Custom {
+                    selector: selector.0,
+                    data: data.into(),
+                },
+            }),
+            interaction: failure.interaction,
+            gas_used: failure.gas_used.map(|gas| gas.0),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tx {
+    from: eth::H160,
+    to: eth::H160,
+    #[serde_as(as = "serialize::Hex")]
+    input: Vec<u8>,
+    #[serde_as(as = "serialize::U256")]
+    value: eth::U256,
+    access_list: web3::types::AccessList,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum RevertReason {
+    Message {
+        message: String,
+    },
+    Panic {
+        #[serde_as(as = "serialize::U256")]
+        code: eth::U256,
+    },
+    Custom {
+        #[serde_as(as = "serialize::Hex")]
+        selector: [u8; 4],
+        #[serde_as(as = "serialize::Hex")]
+        data: Vec<u8>,
+    },
 }
 
 #[serde_as]
@@ -115,6 +192,6 @@ pub enum ScoreKind {
 pub enum Settlement {
     Success { transaction: eth::H256 },
     Revert { transaction: eth::H256 },
-    SimulationRevert,
-    Fail,
+    SimulationRevert(Option<SimulationFailure>),
+    Fail(Option<SimulationFailure>),
 }
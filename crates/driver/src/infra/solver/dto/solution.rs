@@ -11,6 +11,13 @@ use {
 };
 
 impl Solutions {
+    /// Combines the solutions of multiple lines of a streamed response.
+    pub fn concat(all: Vec<Self>) -> Self {
+        Self {
+            solutions: all.into_iter().flat_map(|s| s.solutions).collect(),
+        }
+    }
+
     pub fn into_domain(
         self,
         auction: &competition::Auction,
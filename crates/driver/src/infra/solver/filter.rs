@@ -0,0 +1,286 @@
+use {
+    crate::domain::{competition::Auction, eth, liquidity},
+    std::collections::{HashMap, HashSet, VecDeque},
+};
+
+/// The kinds of liquidity that can be sent to a solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiquidityKind {
+    UniswapV2,
+    UniswapV3,
+    BalancerV2Stable,
+    BalancerV2Weighted,
+    Swapr,
+    ZeroEx,
+}
+
+impl From<&liquidity::Kind> for LiquidityKind {
+    fn from(value: &liquidity::Kind) -> Self {
+        match value {
+            liquidity::Kind::UniswapV2(_) => Self::UniswapV2,
+            liquidity::Kind::UniswapV3(_) => Self::UniswapV3,
+            liquidity::Kind::BalancerV2Stable(_) => Self::BalancerV2Stable,
+            liquidity::Kind::BalancerV2Weighted(_) => Self::BalancerV2Weighted,
+            liquidity::Kind::Swapr(_) => Self::Swapr,
+            liquidity::Kind::ZeroEx(_) => Self::ZeroEx,
+        }
+    }
+}
+
+/// Restricts which of the fetched liquidity gets sent to a solver. The
+/// liquidity is fetched once and shared by all solvers of the driver, so this
+/// only reduces the size of the auctions the solvers receive.
+#[derive(Debug, Clone, Default)]
+pub struct LiquidityFilter {
+    /// Only send liquidity of these kinds. All kinds are sent if not set.
+    pub kinds: Option<HashSet<LiquidityKind>>,
+    /// Only send liquidity of these venues, see [`liquidity::Kind::venue`].
+    /// All venues are sent if not set.
+    pub venues: Option<HashSet<eth::ContractAddress>>,
+    /// Only send liquidity which is part of a route from the sell to the buy
+    /// token of some order using at most this many liquidity sources.
+    pub max_hops: Option<usize>,
+}
+
+impl LiquidityFilter {
+    /// Returns the liquidity that should be sent to the solver for the
+    /// specified auction.
+    pub fn apply<'a>(
+        &self,
+        auction: &Auction,
+        liquidity: &'a [liquidity::Liquidity],
+        weth: eth::WethAddress,
+    ) -> Vec<&'a liquidity::Liquidity> {
+        let liquidity = self.select(liquidity);
+        match self.max_hops {
+            Some(max_hops) => {
+                let pairs = auction
+                    .orders()
+                    .iter()
+                    .map(|order| (order.sell.token.wrap(weth), order.buy.token.wrap(weth)));
+                within_hops(pairs, liquidity, max_hops)
+            }
+            None => liquidity,
+        }
+    }
+
+    /// Returns the liquidity of the configured kinds and venues.
+    fn select<'a>(&self, liquidity: &'a [liquidity::Liquidity]) -> Vec<&'a liquidity::Liquidity> {
+        liquidity
+            .iter()
+            .filter(|liquidity| match &self.kinds {
+                Some(kinds) => kinds.contains(&(&liquidity.kind).into()),
+                None => true,
+            })
+            .filter(|liquidity| match (&self.venues, liquidity.kind.venue()) {
+                (Some(venues), Some(venue)) => venues.contains(&venue),
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .collect()
+    }
+}
+
+/// Keeps the liquidity that is part of a route with at most `max_hops`
+/// liquidity sources between the tokens of any of the specified pairs.
+fn within_hops<'a>(
+    pairs: impl Iterator<Item = (eth::TokenAddress, eth::TokenAddress)>,
+    liquidity: Vec<&'a liquidity::Liquidity>,
+    max_hops: usize,
+) -> Vec<&'a liquidity::Liquidity> {
+    let tokens = liquidity
+        .iter()
+        .map(|liquidity| liquidity.kind.tokens())
+        .collect::<Vec<_>>();
+    let mut graph = HashMap::<_, HashSet<_>>::new();
+    for tokens in &tokens {
+        for a in tokens {
+            graph
+                .entry(*a)
+                .or_default()
+                .extend(tokens.iter().filter(|b| *b != a));
+        }
+    }
+
+    // The distances from the sell token and to the buy token, only including
+    // tokens that are reachable within the maximum number of hops.
+    let mut distances = HashMap::new();
+    let mut distance = |token| {
+        distances
+            .entry(token)
+            .or_insert_with(|| hops(&graph, token, max_hops))
+            .clone()
+    };
+    let routes = pairs
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(|(sell, buy)| (distance(sell), distance(buy)))
+        .collect::<Vec<_>>();
+
+    liquidity
+        .into_iter()
+        .zip(tokens)
+        .filter(|(_, tokens)| {
+            routes.iter().any(|(from_sell, to_buy)| {
+                tokens.iter().any(|a| {
+                    tokens.iter().filter(|b| *b != a).any(|b| {
+                        match (from_sell.get(a), to_buy.get(b)) {
+                            (Some(x), Some(y)) => x + 1 + y <= max_hops,
+                            _ => false,
+                        }
+                    })
+                })
+            })
+        })
+        .map(|(liquidity, _)| liquidity)
+        .collect()
+}
+
+/// Breadth-first search for the number of hops to all tokens reachable from
+/// `start` within `max_hops`.
+fn hops(
+    graph: &HashMap<eth::TokenAddress, HashSet<eth::TokenAddress>>,
+    start: eth::TokenAddress,
+    max_hops: usize,
+) -> HashMap<eth::TokenAddress, usize> {
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(token) = queue.pop_front() {
+        let distance = distances[&token];
+        if distance >= max_hops {
+            continue;
+        }
+        for neighbour in graph.get(&token).into_iter().flatten() {
+            if !distances.contains_key(neighbour) {
+                distances.insert(*neighbour, distance + 1);
+                queue.push_back(*neighbour);
+            }
+        }
+    }
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, primitive_types::H160};
+
+    fn token(n: u8) -> eth::TokenAddress {
+        H160::from_low_u64_be(n.into()).into()
+    }
+
+    fn router(n: u8) -> eth::ContractAddress {
+        H160::repeat_byte(n).into()
+    }
+
+    fn liquidity(id: usize, kind: liquidity::Kind) -> liquidity::Liquidity {
+        liquidity::Liquidity {
+            id: id.into(),
+            gas: eth::Gas(100_000.into()),
+            kind,
+        }
+    }
+
+    fn uniswap(id: usize, router: eth::ContractAddress, a: u8, b: u8) -> liquidity::Liquidity {
+        let asset = |n| eth::Asset {
+            amount: eth::TokenAmount(1_000.into()),
+            token: token(n),
+        };
+        liquidity(
+            id,
+            liquidity::Kind::UniswapV2(liquidity::uniswap::v2::Pool {
+                address: H160::from_low_u64_be(id as u64).into(),
+                router,
+                reserves: liquidity::uniswap::v2::Reserves::new(asset(a), asset(b)).unwrap(),
+            }),
+        )
+    }
+
+    fn zeroex(id: usize, maker: u8, taker: u8) -> liquidity::Liquidity {
+        liquidity(
+            id,
+            liquidity::Kind::ZeroEx(liquidity::zeroex::LimitOrder {
+                maker_token: token(maker),
+                taker_token: token(taker),
+            }),
+        )
+    }
+
+    fn ids(liquidity: &[&liquidity::Liquidity]) -> Vec<usize> {
+        liquidity
+            .iter()
+            .map(|liquidity| liquidity.id.into())
+            .collect()
+    }
+
+    #[test]
+    fn filters_kinds() {
+        let liquidity = [uniswap(0, router(1), 1, 2), zeroex(1, 1, 2)];
+
+        let filter = LiquidityFilter::default();
+        assert_eq!(ids(&filter.select(&liquidity)), [0, 1]);
+
+        let filter = LiquidityFilter {
+            kinds: Some(HashSet::from([LiquidityKind::ZeroEx])),
+            ..Default::default()
+        };
+        assert_eq!(ids(&filter.select(&liquidity)), [1]);
+
+        let filter = LiquidityFilter {
+            kinds: Some(HashSet::from([LiquidityKind::UniswapV3])),
+            ..Default::default()
+        };
+        assert!(filter.select(&liquidity).is_empty());
+    }
+
+    #[test]
+    fn filters_venues() {
+        let liquidity = [
+            uniswap(0, router(1), 1, 2),
+            uniswap(1, router(2), 1, 2),
+            zeroex(2, 1, 2),
+        ];
+
+        // Liquidity without a venue only gets sent if venues are not
+        // restricted.
+        let filter = LiquidityFilter {
+            venues: Some(HashSet::from([router(2)])),
+            ..Default::default()
+        };
+        assert_eq!(ids(&filter.select(&liquidity)), [1]);
+
+        let filter = LiquidityFilter {
+            kinds: Some(HashSet::from([LiquidityKind::UniswapV2])),
+            venues: Some(HashSet::from([router(1), router(2)])),
+            ..Default::default()
+        };
+        assert_eq!(ids(&filter.select(&liquidity)), [0, 1]);
+    }
+
+    #[test]
+    fn keeps_liquidity_within_hops() {
+        // 1 - 2 - 3 - 4 and an unrelated 5 - 6 pool.
+        let liquidity = [
+            uniswap(0, router(1), 1, 2),
+            uniswap(1, router(1), 2, 3),
+            zeroex(2, 3, 4),
+            uniswap(3, router(1), 5, 6),
+            uniswap(4, router(1), 1, 3),
+        ];
+        let liquidity = liquidity.iter().collect::<Vec<_>>();
+        let within_hops = |pairs: &[(u8, u8)], max_hops| {
+            ids(&within_hops(
+                pairs.iter().map(|(sell, buy)| (token(*sell), token(*buy))),
+                liquidity.clone(),
+                max_hops,
+            ))
+        };
+
+        assert_eq!(within_hops(&[(1, 3)], 1), [4]);
+        assert_eq!(within_hops(&[(1, 3)], 2), [0, 1, 4]);
+        assert_eq!(within_hops(&[(1, 4)], 2), [2, 4]);
+        assert_eq!(within_hops(&[(1, 4)], 3), [0, 1, 2, 4]);
+        assert_eq!(within_hops(&[(1, 4), (6, 5)], 1), [3]);
+        assert!(within_hops(&[(1, 6)], 3).is_empty());
+        assert!(within_hops(&[], 3).is_empty());
+    }
+}
@@ -10,24 +10,35 @@ use {
             eth,
             liquidity,
         },
-        infra::blockchain::Ethereum,
+        infra::{self, blockchain::Ethereum},
         util,
     },
+    shared::request_signing,
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    },
     tap::TapFallible,
     thiserror::Error,
     tracing::Instrument,
 };
 
+mod accounts;
+pub mod breaker;
 pub mod dto;
+mod filter;
+
+pub use {
+    accounts::{Accounts, Lease, Slots},
+    filter::{LiquidityFilter, LiquidityKind},
+};
 
 const SOLVER_RESPONSE_MAX_BYTES: usize = 10_000_000;
 
-// TODO At some point I should be checking that the names are unique, I don't
-// think I'm doing that.
 /// The solver name. The user can configure this to be anything that they like.
 /// The name uniquely identifies each solver in case there's more than one of
 /// them.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Name(pub String);
 
 impl Name {
@@ -72,6 +83,8 @@ pub struct Solver {
     client: reqwest::Client,
     config: Config,
     eth: Ethereum,
+    breaker: Arc<breaker::Breaker>,
+    accounts: Accounts,
 }
 
 #[derive(Debug, Clone)]
@@ -83,8 +96,52 @@ pub struct Config {
     pub slippage: Slippage,
     /// Whether or not liquidity is used by this solver.
     pub liquidity: Liquidity,
+    /// Which of the fetched liquidity gets sent to this solver.
+    pub liquidity_filter: LiquidityFilter,
     /// The private key of this solver, used for settlement submission.
     pub account: ethcontract::Account,
+    /// More accounts to submit settlements with while a transaction of the
+    /// main account is still pending.
+    pub additional_accounts: Vec<ethcontract::Account>,
+    /// Signs the requests sent to this solver, if configured.
+    pub signer: Option<request_signing::Signer>,
+    /// Whether the solutions are requested from the streaming endpoint of the
+    /// solver.
+    pub stream: bool,
+    /// When to consider the solver unhealthy and stop sending it requests.
+    pub breaker: breaker::Config,
+}
+
+/// The state of the solvers which outlives config reloads. Solvers get
+/// recreated from every reloaded config, but they keep the health tracked by
+/// their circuit breaker and the settlements in flight of their accounts.
+#[derive(Debug, Clone, Default)]
+pub struct Registry(Arc<Mutex<RegistryState>>);
+
+#[derive(Debug, Default)]
+struct RegistryState {
+    breakers: HashMap<Name, Arc<breaker::Breaker>>,
+    accounts: Slots,
+}
+
+impl Registry {
+    /// Returns the circuit breaker of the solver. A changed breaker config
+    /// takes effect without resetting the health of the solver.
+    fn breaker(&self, name: &Name, config: &breaker::Config) -> Arc<breaker::Breaker> {
+        let mut state = self.0.lock().unwrap();
+        let breaker = state
+            .breakers
+            .entry(name.clone())
+            .or_insert_with(|| Arc::new(breaker::Breaker::new(config.clone())));
+        if breaker.config() != config {
+            *breaker = Arc::new(breaker.reconfigure(config.clone()));
+        }
+        breaker.clone()
+    }
+
+    fn accounts(&self, accounts: impl IntoIterator<Item = ethcontract::Account>) -> Accounts {
+        Accounts::new(accounts, &mut self.0.lock().unwrap().accounts)
+    }
 }
 
 impl Solver {
@@ -92,19 +149,31 @@ impl Solver {
         chrono::Duration::milliseconds(500)
     }
 
-    pub fn new(config: Config, eth: Ethereum) -> Self {
+    /// The deadline of the auctions sent to probe the solver while its circuit
+    /// breaker is open.
+    pub fn probe_deadline() -> chrono::Duration {
+        chrono::Duration::seconds(5)
+    }
+
+    /// Creates a solver which continues from the state the registry holds for
+    /// it, if any.
+    pub fn new(config: Config, eth: Ethereum, registry: &Registry) -> Self {
         let mut headers = reqwest::header::HeaderMap::new();
         headers.insert(
             reqwest::header::CONTENT_TYPE,
             "application/json".parse().unwrap(),
         );
         headers.insert(reqwest::header::ACCEPT, "application/json".parse().unwrap());
-        // TODO(#907) Also add an auth header
         Self {
             client: reqwest::ClientBuilder::new()
                 .default_headers(headers)
                 .build()
                 .unwrap(),
+            breaker: registry.breaker(&config.name, &config.breaker),
+            accounts: registry.accounts(
+                std::iter::once(config.account.clone())
+                    .chain(config.additional_accounts.iter().cloned()),
+            ),
             config,
             eth,
         }
@@ -124,14 +193,36 @@ impl Solver {
         self.config.liquidity
     }
 
-    /// The blockchain address of this solver.
+    /// Which of the fetched liquidity gets sent to this solver.
+    pub fn liquidity_filter(&self) -> &LiquidityFilter {
+        &self.config.liquidity_filter
+    }
+
+    /// Whether this solver streams its solutions, see
+    /// [`Solver::exchange_stream`].
+    pub fn streams(&self) -> bool {
+        self.config.stream
+    }
+
+    /// The health of this solver as tracked by its circuit breaker.
+    pub fn health(&self) -> breaker::Health {
+        self.breaker.health()
+    }
+
+    /// Whether auctions can be sent to this solver, i.e. its circuit breaker
+    /// is closed.
+    pub fn is_available(&self) -> bool {
+        !self.breaker.is_open()
+    }
+
+    /// The blockchain address of the main account of this solver.
     pub fn address(&self) -> eth::Address {
         self.config.account.address().into()
     }
 
-    /// The account which should be used to sign settlements for this solver.
-    pub fn account(&self) -> ethcontract::Account {
-        self.config.account.clone()
+    /// The accounts which should be used to sign settlements for this solver.
+    pub fn accounts(&self) -> &Accounts {
+        &self.accounts
     }
 
     /// Make a POST request instructing the solver to solve an auction.
@@ -142,32 +233,111 @@ impl Solver {
         liquidity: &[liquidity::Liquidity],
         timeout: SolverTimeout,
     ) -> Result<Vec<Solution>, Error> {
-        // Fetch the solutions from the solver.
+        let response = self.exchange(auction, liquidity, timeout).await?;
+        self.solutions(&response, auction, liquidity)
+    }
+
+    /// Make a POST request instructing the solver to solve an auction like
+    /// [`Solver::solve`] but return the raw response body.
+    pub async fn exchange(
+        &self,
+        auction: &Auction,
+        liquidity: &[liquidity::Liquidity],
+        timeout: SolverTimeout,
+    ) -> Result<String, Error> {
+        if !self.is_available() {
+            return Err(Error::Unavailable);
+        }
+        let (url, req) = self.request("solve", auction, liquidity, timeout);
+        let res = util::http::send(SOLVER_RESPONSE_MAX_BYTES, req).await;
+        super::observe::solver_response(&url, res.as_deref());
+        self.record(&res);
+        Ok(res?)
+    }
+
+    /// Make a POST request instructing the solver to solve an auction using
+    /// its streaming endpoint. The solver responds with one JSON object per
+    /// line, each containing the solutions it found since the previous line.
+    /// Returns the lines of the response, which can be read as the solver
+    /// sends them.
+    pub async fn exchange_stream(
+        &self,
+        auction: &Auction,
+        liquidity: &[liquidity::Liquidity],
+        timeout: SolverTimeout,
+    ) -> Result<Lines, Error> {
+        if !self.is_available() {
+            return Err(Error::Unavailable);
+        }
+        let (url, req) = self.request("solve/stream", auction, liquidity, timeout);
+        let res = req
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(util::http::Error::from);
+        self.record(&res);
+        let res = res?;
+        Ok(Lines {
+            url,
+            res,
+            buffer: Vec::new(),
+            received: 0,
+        })
+    }
+
+    /// Builds the request instructing the solver to solve an auction. Returns
+    /// the URL of the request along with the request itself.
+    fn request(
+        &self,
+        path: &str,
+        auction: &Auction,
+        liquidity: &[liquidity::Liquidity],
+        timeout: SolverTimeout,
+    ) -> (url::Url, reqwest::RequestBuilder) {
         let weth = self.eth.contracts().weth_address();
         let body = serde_json::to_string(&dto::Auction::new(
             auction,
             liquidity,
+            &self.config.liquidity_filter,
             // Reduce the timeout by a small buffer to account for network latency. Otherwise the
             // HTTP timeout might happen before the solver times out its search algorithm.
             timeout.reduce(Self::http_time_buffer()),
             weth,
         ))
         .unwrap();
-        let url = shared::url::join(&self.config.endpoint, "solve");
+        let url = shared::url::join(&self.config.endpoint, path);
         super::observe::solver_request(&url, &body);
         let mut req = self
-            .client
-            .post(url.clone())
+            .sign(self.client.post(url.clone()), &url, &body)
             .body(body)
             .timeout(timeout.duration().to_std().unwrap());
         if let Some(id) = observe::request_id::get_task_local_storage() {
             req = req.header("X-REQUEST-ID", id);
         }
-        let res = util::http::send(SOLVER_RESPONSE_MAX_BYTES, req).await;
-        super::observe::solver_response(&url, res.as_deref());
-        let res = res?;
-        let res: dto::Solutions = serde_json::from_str(&res)
-            .tap_err(|err| tracing::warn!(res, ?err, "failed to parse solver response"))?;
+        (url, req)
+    }
+
+    /// Parse the solutions from the raw response of the solver. For streaming
+    /// solvers the response can consist of multiple lines, in which case the
+    /// solutions of all lines are returned.
+    pub fn solutions(
+        &self,
+        response: &str,
+        auction: &Auction,
+        liquidity: &[liquidity::Liquidity],
+    ) -> Result<Vec<Solution>, Error> {
+        let weth = self.eth.contracts().weth_address();
+        let res = if self.config.stream {
+            response
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str::<dto::Solutions>)
+                .collect::<Result<Vec<_>, _>>()
+                .map(dto::Solutions::concat)
+        } else {
+            serde_json::from_str::<dto::Solutions>(response)
+        }
+        .tap_err(|err| tracing::warn!(response, ?err, "failed to parse solver response"))?;
         let solutions = res.into_domain(auction, liquidity, weth, self.clone())?;
 
         super::observe::solutions(&solutions);
@@ -185,7 +355,9 @@ impl Solver {
             serde_json::to_string(&dto::Notification::new(auction_id, solution_id, kind)).unwrap();
         let url = shared::url::join(&self.config.endpoint, "notify");
         super::observe::solver_request(&url, &body);
-        let mut req = self.client.post(url).body(body);
+        let mut req = self
+            .sign(self.client.post(url.clone()), &url, &body)
+            .body(body);
         if let Some(id) = observe::request_id::get_task_local_storage() {
             req = req.header("X-REQUEST-ID", id);
         }
@@ -194,6 +366,131 @@ impl Solver {
         };
         tokio::task::spawn(future.in_current_span());
     }
+
+    /// Periodically probes the solver while its circuit breaker is open, so
+    /// that the breaker closes again once the solver is reachable.
+    pub async fn probe_while_unhealthy(self) {
+        loop {
+            tokio::time::sleep(self.breaker.config().probe_interval).await;
+            if self.breaker.is_open() {
+                self.probe().await;
+            }
+        }
+    }
+
+    /// Sends an auction without any orders to the solver to check whether it
+    /// responds in time. Streaming solvers are probed on their streaming
+    /// endpoint.
+    async fn probe(&self) {
+        let deadline = infra::time::now() + Self::probe_deadline();
+        let auction = match Auction::new(
+            None,
+            Vec::new(),
+            std::iter::empty(),
+            deadline.into(),
+            &self.eth,
+            Default::default(),
+        )
+        .await
+        {
+            Ok(auction) => auction,
+            Err(err) => {
+                tracing::warn!(?err, "failed to create probe auction");
+                return;
+            }
+        };
+        let Ok(timeout) = auction.deadline().timeout() else {
+            return;
+        };
+        // Probe the endpoint auctions get sent to.
+        let path = if self.streams() {
+            "solve/stream"
+        } else {
+            "solve"
+        };
+        let (url, req) = self.request(path, &auction, &[], timeout);
+        let res = util::http::send(SOLVER_RESPONSE_MAX_BYTES, req).await;
+        super::observe::solver_response(&url, res.as_deref());
+        self.record(&res);
+    }
+
+    /// Updates the circuit breaker with the result of a request to the solver.
+    fn record<T>(&self, res: &Result<T, util::http::Error>) {
+        let outcome = match res {
+            Ok(_) => breaker::Outcome::Success,
+            Err(util::http::Error::Response(err)) if err.is_timeout() => breaker::Outcome::Timeout,
+            Err(_) => breaker::Outcome::Failure,
+        };
+        let transition = self.breaker.record(outcome);
+        super::observe::solver_health(self.name(), outcome, transition, self.breaker.health());
+    }
+
+    /// Adds the authentication headers for the given POST request to the
+    /// request if a signer is configured for this solver.
+    fn sign(
+        &self,
+        mut req: reqwest::RequestBuilder,
+        url: &reqwest::Url,
+        body: &str,
+    ) -> reqwest::RequestBuilder {
+        if let Some(signer) = &self.config.signer {
+            for (name, value) in signer.headers("POST", url, body.as_bytes()) {
+                req = req.header(name, value);
+            }
+        }
+        req
+    }
+}
+
+/// The lines of a streamed solver response.
+#[derive(Debug)]
+pub struct Lines {
+    url: url::Url,
+    res: reqwest::Response,
+    /// Received data which does not form a complete line yet.
+    buffer: Vec<u8>,
+    /// The total number of bytes received so far.
+    received: usize,
+}
+
+impl Lines {
+    /// Waits for the next non-empty line of the response. Returns `None` once
+    /// the solver closed the response.
+    pub async fn next(&mut self) -> Option<Result<String, Error>> {
+        let line = self.read().await;
+        match &line {
+            Ok(Some(line)) => super::observe::solver_response(&self.url, Ok(line)),
+            Ok(None) => (),
+            Err(err) => super::observe::solver_response(&self.url, Err(err)),
+        }
+        line.map_err(Into::into).transpose()
+    }
+
+    async fn read(&mut self) -> Result<Option<String>, util::http::Error> {
+        loop {
+            if let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+                let line = String::from_utf8(self.buffer.drain(..=end).collect())?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Ok(Some(line));
+            }
+            match self.res.chunk().await? {
+                Some(chunk) => {
+                    self.received += chunk.len();
+                    if self.received > SOLVER_RESPONSE_MAX_BYTES {
+                        return Err(util::http::Error::ResponseTooLarge {
+                            limit_bytes: SOLVER_RESPONSE_MAX_BYTES,
+                        });
+                    }
+                    self.buffer.extend_from_slice(&chunk);
+                }
+                // The last line does not have to be terminated by a newline.
+                None if !self.buffer.is_empty() => self.buffer.push(b'\n'),
+                None => return Ok(None),
+            }
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -206,4 +503,50 @@ pub enum Error {
     DuplicatedSolutionId,
     #[error("solver dto error: {0}")]
     Dto(#[from] dto::Error),
+    #[error("the solver is unhealthy, its circuit breaker is open")]
+    Unavailable,
+}
+
+impl Error {
+    /// Whether the solver didn't respond in time.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::Http(util::http::Error::Response(err)) if err.is_timeout())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, primitive_types::H160, std::time::Duration};
+
+    #[test]
+    fn registry_keeps_state_across_reloads() {
+        let registry = Registry::default();
+        let name = Name::from("solver".to_owned());
+        let config = breaker::Config {
+            max_consecutive_failures: 1,
+            probe_interval: Duration::from_secs(1),
+        };
+        registry
+            .breaker(&name, &config)
+            .record(breaker::Outcome::Failure);
+        assert!(registry.breaker(&name, &config).is_open());
+
+        // A changed config doesn't reset the health of the solver.
+        let reconfigured = breaker::Config {
+            max_consecutive_failures: 2,
+            ..config.clone()
+        };
+        let breaker = registry.breaker(&name, &reconfigured);
+        assert!(breaker.is_open());
+        assert_eq!(breaker.config(), &reconfigured);
+
+        // Other solvers have their own breaker.
+        let other = Name::from("other".to_owned());
+        assert!(!registry.breaker(&other, &config).is_open());
+
+        // Accounts with a settlement in flight stay reserved.
+        let account = ethcontract::Account::Local(H160::from_low_u64_be(1), None);
+        let _lease = registry.accounts([account.clone()]).acquire().unwrap();
+        assert!(registry.accounts([account]).acquire().is_none());
+    }
 }
@@ -6,6 +6,7 @@
 pub mod boundary;
 pub mod domain;
 pub mod infra;
+mod replay;
 mod run;
 pub mod util;
 
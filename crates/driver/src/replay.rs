@@ -0,0 +1,184 @@
+//! Replays a `/solve` round archived by [`infra::archive`] and reports how the
+//! solutions of the solver got encoded, merged and scored. The solutions use
+//! the archived liquidity, but the settlements are simulated against the
+//! configured node, so to reproduce the original conditions it should be a
+//! fork of the chain at the archived block.
+
+use {
+    crate::{
+        domain::{competition, Mempools},
+        infra::{self, archive, solver::Solver, tokens, Ethereum, Simulator},
+    },
+    anyhow::{Context, Result},
+    serde::Serialize,
+    std::path::Path,
+};
+
+/// The components of the driver needed to replay a round.
+pub struct Replay {
+    pub solvers: Vec<Solver>,
+    pub eth: Ethereum,
+    pub simulator: Simulator,
+    pub mempools: Mempools,
+}
+
+impl Replay {
+    /// Replays the round archived in the directory `dir`.
+    pub async fn run(&self, dir: &Path) -> Result<Report> {
+        let round = archive::Round::load(dir)
+            .await
+            .with_context(|| format!("failed to load round from {dir:?}"))?;
+        let name = dir
+            .parent()
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .context("round directory is not of the form <archive dir>/<solver>/<auction id>")?;
+        let solver = self
+            .solvers
+            .iter()
+            .find(|solver| solver.name().as_str() == name)
+            .with_context(|| format!("solver {name} is not configured"))?;
+
+        let auction = infra::api::archived_auction(
+            round.auction,
+            &self.eth,
+            &tokens::Fetcher::new(self.eth.clone()),
+        )
+        .await
+        .context("invalid archived auction")?;
+        let mut report = Report {
+            auction: auction.id().map(|id| id.0),
+            solver: name.to_owned(),
+            orders: auction.orders().len(),
+            block: round.block,
+            liquidity: round.liquidity.len(),
+            outcome: Outcome::Solved,
+            solutions: Default::default(),
+            archived_merges: round
+                .merging
+                .as_ref()
+                .map(|merging| merging.attempts.clone()),
+            merges: Default::default(),
+            settlements: Default::default(),
+        };
+
+        let response = match round.outcome {
+            archive::Outcome::Solved { response } => response,
+            archive::Outcome::TimedOut { response } => {
+                report.outcome = Outcome::TimedOut {
+                    partial: !response.is_empty(),
+                };
+                if response.is_empty() {
+                    return Ok(report);
+                }
+                response
+            }
+            archive::Outcome::Failed { error } => {
+                report.outcome = Outcome::Failed { error };
+                return Ok(report);
+            }
+        };
+
+        let solutions = solver
+            .solutions(&response, &auction, &round.liquidity)
+            .context("invalid solver response")?;
+        let mut settlements = Vec::new();
+        for solution in solutions {
+            let id = solution.id().0;
+            if solution.is_empty() {
+                report.solutions.push(Solution::Empty { id });
+                continue;
+            }
+            match solution.encode(&auction, &self.eth, &self.simulator).await {
+                Ok(settlement) => {
+                    report.solutions.push(Solution::Encoded { id });
+                    settlements.push(settlement);
+                }
+                Err(err) => report.solutions.push(Solution::EncodingFailed {
+                    id,
+                    error: format!("{err:?}"),
+                }),
+            }
+        }
+
+        // Merge in the archived order so that the merges can be compared with
+        // the ones of the original round. Settlements which weren't encoded
+        // originally get merged last.
+        if let Some(merging) = &round.merging {
+            settlements.sort_by_key(|settlement| {
+                archive::solutions(settlement)
+                    .first()
+                    .and_then(|id| merging.order.iter().position(|other| other == id))
+            });
+        }
+        let (merged, merges) = competition::merge(settlements, &self.eth, &self.simulator).await;
+        report.merges = merges;
+
+        for settlement in merged {
+            let solutions = archive::solutions(&settlement);
+            report.settlements.push(
+                match settlement.score(&self.eth, &auction, &self.mempools.revert_protection()) {
+                    Ok(score) => Settlement::Scored {
+                        solutions,
+                        score: score.0.to_string(),
+                    },
+                    Err(err) => Settlement::ScoringFailed {
+                        solutions,
+                        error: format!("{err:?}"),
+                    },
+                },
+            );
+        }
+        Ok(report)
+    }
+}
+
+/// The outcome of replaying a round.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub auction: Option<i64>,
+    pub solver: String,
+    pub orders: usize,
+    /// The block at which the liquidity was fetched.
+    pub block: Option<u64>,
+    /// The number of liquidity sources.
+    pub liquidity: usize,
+    pub outcome: Outcome,
+    pub solutions: Vec<Solution>,
+    /// The merges attempted in the original round, if they were archived.
+    pub archived_merges: Option<Vec<archive::Merge>>,
+    /// The merges attempted when replaying the round.
+    pub merges: Vec<archive::Merge>,
+    pub settlements: Vec<Settlement>,
+}
+
+/// How the communication with the solver engine ended in the original round.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Outcome {
+    Solved,
+    /// The solver didn't respond in time. Only a partial response of a
+    /// streaming solver can be replayed.
+    TimedOut {
+        partial: bool,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Solution {
+    Empty { id: u64 },
+    Encoded { id: u64 },
+    EncodingFailed { id: u64, error: String },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Settlement {
+    Scored { solutions: Vec<u64>, score: String },
+    ScoringFailed { solutions: Vec<u64>, error: String },
+}
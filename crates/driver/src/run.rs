@@ -1,23 +1,33 @@
 use {
     crate::{
-        domain::Mempools,
+        domain::{mempools::NoMempools, Mempools},
         infra::{
             self,
+            api,
+            archive,
             blockchain::{self, Ethereum},
             cli,
             config,
             liquidity,
             mempool,
             simulator::{self, Simulator},
-            solver::Solver,
+            solver::{self, Solver},
             Api,
+            Archive,
             Mempool,
         },
+        replay::Replay,
     },
     clap::Parser,
     futures::future::join_all,
-    std::{net::SocketAddr, sync::Arc, time::Duration},
-    tokio::sync::oneshot,
+    std::{
+        net::SocketAddr,
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::{Duration, SystemTime},
+    },
+    tokio::sync::{mpsc, oneshot},
+    tracing::level_filters::LevelFilter,
 };
 
 /// The driver entry-point. This function exists in order to be able to run the
@@ -44,33 +54,68 @@ pub async fn run(
 /// Run the driver. This function exists to avoid multiple monomorphizations of
 /// the `run` code, which bloats the binaries and increases compile times.
 async fn run_with(args: cli::Args, addr_sender: Option<oneshot::Sender<SocketAddr>>) {
-    crate::infra::observe::init(&args.log);
+    // Commands log everything to stderr, keeping stdout for their output.
+    let stderr_threshold = match args.command {
+        Some(_) => LevelFilter::TRACE,
+        None => LevelFilter::ERROR,
+    };
+    crate::infra::observe::init(&args.log, stderr_threshold);
 
     let ethrpc = ethrpc(&args).await;
     let config = config::file::load(ethrpc.network(), &args.config).await;
     tracing::info!("running driver with {config:#?}");
 
-    let (shutdown_sender, shutdown_receiver) = tokio::sync::oneshot::channel();
     let eth = ethereum(&config, ethrpc).await;
+    // Shared by the mempools of all reloaded configs so that transactions
+    // submitted before a reload are still replaced and cancelled correctly.
     let tx_pool = mempool::GlobalTxPool::default();
+    // Likewise, the reloaded solvers keep their health and the settlements in
+    // flight of their accounts.
+    let registry = solver::Registry::default();
+    if let Some(cli::Command::Replay { round }) = &args.command {
+        let replay = Replay {
+            solvers: solvers(&config, &eth, &registry),
+            simulator: simulator(&config, &eth),
+            mempools: mempools(&config, &eth, &tx_pool).await.unwrap(),
+            eth,
+        };
+        match replay.run(round).await {
+            Ok(report) => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+            Err(err) => {
+                tracing::error!(?err, ?round, "replay failed");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let (reload_sender, reload_receiver) = mpsc::unbounded_channel();
+    tokio::task::spawn(reload(
+        args.config.clone(),
+        eth.clone(),
+        tx_pool.clone(),
+        registry.clone(),
+        reload_sender,
+    ));
+
+    let (shutdown_sender, shutdown_receiver) = tokio::sync::oneshot::channel();
     let serve = Api {
-        solvers: solvers(&config, &eth),
+        solvers: solvers(&config, &eth, &registry),
         liquidity: liquidity(&config, &eth).await,
         simulator: simulator(&config, &eth),
-        mempools: Mempools::new(
-            join_all(
-                config
-                    .mempools
-                    .iter()
-                    .map(|mempool| Mempool::new(mempool.to_owned(), eth.clone(), tx_pool.clone())),
-            )
-            .await
-            .into_iter()
-            .flatten()
-            .collect(),
-        )
-        .unwrap(),
+        mempools: mempools(&config, &eth, &tx_pool).await.unwrap(),
         eth,
+        auth: config.auth.clone(),
+        archive: args.archive_dir.map(|dir| {
+            Archive::new(
+                dir,
+                archive::Retention {
+                    max_age: args.archive_max_age,
+                    max_rounds: args.archive_max_rounds,
+                },
+            )
+        }),
+        reloads: Some(reload_receiver),
         addr: args.addr,
         addr_sender,
     }
@@ -136,20 +181,129 @@ async fn ethereum(config: &infra::Config, ethrpc: blockchain::Rpc) -> Ethereum {
     Ethereum::new(ethrpc, config.contracts, gas).await
 }
 
-fn solvers(config: &config::Config, eth: &Ethereum) -> Vec<Solver> {
+fn solvers(config: &config::Config, eth: &Ethereum, registry: &solver::Registry) -> Vec<Solver> {
     config
         .solvers
         .iter()
-        .map(|config| Solver::new(config.clone(), eth.clone()))
+        .map(|config| Solver::new(config.clone(), eth.clone(), registry))
         .collect()
 }
 
+async fn mempools(
+    config: &config::Config,
+    eth: &Ethereum,
+    tx_pool: &mempool::GlobalTxPool,
+) -> Result<Mempools, NoMempools> {
+    Mempools::new(
+        join_all(
+            config
+                .mempools
+                .iter()
+                .map(|mempool| Mempool::new(mempool.to_owned(), eth.clone(), tx_pool.clone())),
+        )
+        .await
+        .into_iter()
+        .flatten()
+        .collect(),
+    )
+}
+
 async fn liquidity(config: &config::Config, eth: &Ethereum) -> liquidity::Fetcher {
     liquidity::Fetcher::new(eth, &config.liquidity)
         .await
         .expect("initialize liquidity fetcher")
 }
 
+/// How often the config file is checked for changes.
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Reloads the solvers and mempools whenever the config file changes or the
+/// driver receives SIGHUP. Changes to the other parts of the config, e.g. the
+/// liquidity sources, only take effect after a restart. Invalid configs are
+/// ignored.
+async fn reload(
+    path: PathBuf,
+    eth: Ethereum,
+    tx_pool: mempool::GlobalTxPool,
+    registry: solver::Registry,
+    reloads: mpsc::UnboundedSender<api::Reload>,
+) {
+    let mut modified = modified(&path).await;
+    let mut hangups = Hangups::new();
+    loop {
+        tokio::select! {
+            _ = hangups.recv() => (),
+            _ = tokio::time::sleep(CONFIG_POLL_INTERVAL) => {
+                let current = modified(&path).await;
+                if current == modified {
+                    continue;
+                }
+                modified = current;
+            }
+        }
+
+        let config = match config::file::try_load(eth.network(), &path).await {
+            Ok(config) => config,
+            Err(err) => {
+                infra::observe::invalid_config(&err);
+                continue;
+            }
+        };
+        let mempools = match mempools(&config, &eth, &tx_pool).await {
+            Ok(mempools) => mempools,
+            Err(err) => {
+                tracing::warn!(?err, "ignoring config without working mempools");
+                continue;
+            }
+        };
+        let reload = api::Reload {
+            solvers: solvers(&config, &eth, &registry),
+            mempools,
+        };
+        if reloads.send(reload).is_err() {
+            return;
+        }
+    }
+}
+
+async fn modified(path: &Path) -> Option<SystemTime> {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// The SIGHUP signals received by the driver. Registered once so that no
+/// signals get lost between reloads.
+#[cfg(unix)]
+struct Hangups(tokio::signal::unix::Signal);
+
+#[cfg(unix)]
+impl Hangups {
+    fn new() -> Self {
+        Self(tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()).unwrap())
+    }
+
+    async fn recv(&mut self) {
+        self.0.recv().await;
+    }
+}
+
+#[cfg(windows)]
+struct Hangups;
+
+#[cfg(windows)]
+impl Hangups {
+    fn new() -> Self {
+        Self
+    }
+
+    async fn recv(&mut self) {
+        // No support for signal handling on Windows.
+        std::future::pending().await
+    }
+}
+
 #[cfg(unix)]
 async fn shutdown_signal() {
     // Intercept signals for graceful shutdown. Kubernetes sends sigterm, Ctrl-C
@@ -47,3 +47,32 @@ async fn solution_not_available() {
 
     test.settle().await.err().kind("SolutionNotAvailable");
 }
+
+/// Checks that the settlement gets submitted from the account revealed to the
+/// autopilot, which rejects settlements from any other account.
+#[tokio::test]
+#[ignore]
+async fn submits_from_revealed_account() {
+    let test = tests::setup()
+        .name("submits from revealed account")
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    test.solve().await.ok();
+    let account = test
+        .reveal()
+        .await
+        .ok()
+        .orders(&[ab_order().name])
+        .account();
+    test.settle()
+        .await
+        .ok()
+        .await
+        .submitted_by(account)
+        .ab_order_executed()
+        .await;
+}
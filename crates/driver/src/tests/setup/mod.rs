@@ -910,7 +910,7 @@ impl RevealOk<'_> {
             .collect_vec();
         let result: serde_json::Value = serde_json::from_str(&self.body).unwrap();
         assert!(result.is_object());
-        assert_eq!(result.as_object().unwrap().len(), 2);
+        assert_eq!(result.as_object().unwrap().len(), 3);
         assert!(result.get("orders").is_some());
         let order_uids = result
             .get("orders")
@@ -924,6 +924,12 @@ impl RevealOk<'_> {
         assert_eq!(order_uids, expected_order_uids);
         self
     }
+
+    /// The account the settlement will be submitted from.
+    pub fn account(&self) -> eth::H160 {
+        let result: serde_json::Value = serde_json::from_str(&self.body).unwrap();
+        serde_json::from_value(result.get("account").unwrap().clone()).unwrap()
+    }
 }
 
 /// A /quote response.
@@ -1014,6 +1020,8 @@ pub struct Settle<'a> {
 pub struct SettleOk<'a> {
     test: &'a Test,
     old_balances: HashMap<&'static str, eth::U256>,
+    /// The account which submitted the settlement transaction.
+    submitter: eth::H160,
 }
 
 pub struct SettleErr {
@@ -1027,7 +1035,7 @@ impl<'a> Settle<'a> {
         assert_eq!(self.status, hyper::StatusCode::OK);
         let result: serde_json::Value = serde_json::from_str(&self.body).unwrap();
         assert!(result.is_object());
-        assert_eq!(result.as_object().unwrap().len(), 2);
+        assert_eq!(result.as_object().unwrap().len(), 3);
         assert!(!result
             .get("calldata")
             .unwrap()
@@ -1085,9 +1093,15 @@ impl<'a> Settle<'a> {
             format!("0x{}", hex::encode(&input).to_lowercase())
         );
 
+        // Ensure that the settlement was submitted from the reported account.
+        let account =
+            serde_json::from_value::<eth::H160>(result.get("account").unwrap().clone()).unwrap();
+        assert_eq!(tx.from, Some(account));
+
         SettleOk {
             test: self.test,
             old_balances: self.old_balances,
+            submitter: account,
         }
     }
 
@@ -1099,6 +1113,12 @@ impl<'a> Settle<'a> {
 }
 
 impl<'a> SettleOk<'a> {
+    /// Check that the settlement was submitted from the specified account.
+    pub fn submitted_by(self, account: eth::H160) -> SettleOk<'a> {
+        assert_eq!(self.submitter, account);
+        self
+    }
+
     /// Check that the user balance changed.
     pub async fn balance(self, token: &'static str, balance: Balance) -> SettleOk<'a> {
         let new_balances = self.test.balances().await;
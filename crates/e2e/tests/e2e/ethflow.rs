@@ -387,6 +387,7 @@ async fn test_order_parameters(
         Some(EthflowData {
             user_valid_to: order.0.valid_to as i64,
             refund_tx_hash: None,
+            refund_status: None,
         })
     );
     assert_eq!(
@@ -4,7 +4,10 @@ use {
     e2e::{nodes::local_node::TestNodeApi, setup::*},
     ethcontract::{H160, U256},
     ethrpc::{current_block::timestamp_of_current_block_in_seconds, Web3},
-    model::quote::{OrderQuoteRequest, OrderQuoteSide, QuoteSigningScheme, Validity},
+    model::{
+        order::RefundStatus,
+        quote::{OrderQuoteRequest, OrderQuoteSide, QuoteSigningScheme, Validity},
+    },
     number::nonzero::U256 as NonZeroU256,
     refunder::refund_service::RefundService,
     sqlx::PgPool,
@@ -90,10 +93,12 @@ async fn refunder_tx(web3: Web3) {
     let mut refunder = RefundService::new(
         pg_pool,
         web3,
-        onchain.contracts().ethflow.clone(),
+        vec![onchain.contracts().ethflow.clone()],
         validity_duration as i64 / 2,
         10u64,
         refunder.account().clone(),
+        800_000_000_000,
+        Default::default(),
     );
 
     assert_ne!(
@@ -121,4 +126,13 @@ async fn refunder_tx(web3: Web3) {
             .is_some()
     };
     wait_for_condition(TIMEOUT, has_tx_hash).await.unwrap();
+
+    let ethflow_data = services
+        .get_order(&order_id)
+        .await
+        .unwrap()
+        .metadata
+        .ethflow_data
+        .unwrap();
+    assert_eq!(ethflow_data.refund_status, Some(RefundStatus::Refunded));
 }
@@ -149,6 +149,9 @@ pub trait BlockRetrieving: Debug + Send + Sync + 'static {
     async fn current_block(&self) -> Result<BlockInfo>;
     async fn block(&self, number: u64) -> Result<BlockNumberHash>;
     async fn blocks(&self, range: RangeInclusive<u64>) -> Result<Vec<BlockNumberHash>>;
+    /// The latest block the node considers final, i.e. which can no longer be
+    /// reorged.
+    async fn finalized_block(&self) -> Result<BlockNumberHash>;
 }
 
 #[async_trait::async_trait]
@@ -198,6 +201,11 @@ impl BlockRetrieving for Web3 {
             })
             .collect()
     }
+
+    async fn finalized_block(&self) -> Result<BlockNumberHash> {
+        let block = get_block_info_at_id(self, BlockNumber::Finalized.into()).await?;
+        Ok((block.number, block.hash))
+    }
 }
 
 async fn get_block_info_at_id(web3: &Web3, id: BlockId) -> Result<BlockInfo> {
@@ -90,6 +90,10 @@ impl BlockRetrieving for BlockRetriever {
     async fn blocks(&self, range: RangeInclusive<u64>) -> Result<Vec<BlockNumberHash>> {
         self.0.blocks(range).await
     }
+
+    async fn finalized_block(&self) -> Result<BlockNumberHash> {
+        self.0.finalized_block().await
+    }
 }
 
 /// Decodes the return data from the `FetchBlock` contract.
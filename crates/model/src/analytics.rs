@@ -0,0 +1,105 @@
+//! Types of the analytics endpoints that sum up trades and settlements over
+//! time.
+
+use {
+    chrono::NaiveDate,
+    number::serialization::HexOrDecimalU256,
+    primitive_types::U256,
+    serde::{Deserialize, Serialize},
+    serde_with::serde_as,
+};
+
+/// Length of the periods trades and settlements get summed up over. Weeks
+/// start on monday and all periods refer to UTC days.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Interval {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+/// Additionally breaks down the trades of every period by this key.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TradeGrouping {
+    SellToken,
+    BuyToken,
+    AppCode,
+}
+
+/// Trades of a single period. All amounts are denominated in wei of the native
+/// token using the native prices of the auctions the trades were settled in.
+#[serde_as]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeBucket {
+    /// First day of the period.
+    pub period: NaiveDate,
+    /// Token address or app code depending on the requested grouping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub trades: u64,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub volume: U256,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub surplus: U256,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub fee: U256,
+}
+
+/// Settlements of a single period. Amounts other than the used gas are
+/// denominated in wei of the native token.
+#[serde_as]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementBucket {
+    /// First day of the period.
+    pub period: NaiveDate,
+    pub settlements: u64,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub gas_used: U256,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub gas_cost: U256,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub surplus: U256,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub fee: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn serialization() {
+        let bucket = TradeBucket {
+            period: NaiveDate::from_ymd_opt(2023, 5, 1).unwrap(),
+            key: None,
+            trades: 2,
+            volume: 3.into(),
+            surplus: 4.into(),
+            fee: 5.into(),
+        };
+        assert_eq!(
+            serde_json::to_value(&bucket).unwrap(),
+            json!({
+                "period": "2023-05-01",
+                "trades": 2,
+                "volume": "3",
+                "surplus": "4",
+                "fee": "5",
+            })
+        );
+
+        let grouped = TradeBucket {
+            key: Some("CoW Swap".to_string()),
+            ..bucket
+        };
+        assert_eq!(
+            serde_json::to_value(&grouped).unwrap()["key"],
+            json!("CoW Swap")
+        );
+    }
+}
@@ -1,16 +1,21 @@
 //! Contains models that are shared between the orderbook and the solver.
 
+pub mod analytics;
 pub mod app_data;
 pub mod auction;
 pub mod bytes_hex;
 pub mod interaction;
+pub mod market;
 pub mod order;
 pub mod quote;
+pub mod quote_accuracy;
 pub mod ratio_as_decimal;
 pub mod signature;
 pub mod solver_competition;
+pub mod solver_rewards;
 pub mod time;
 pub mod trade;
+pub mod webhook;
 
 use {
     hex::{FromHex, FromHexError},
@@ -0,0 +1,72 @@
+//! Open liquidity of the limit orders in the current auction for a single
+//! token pair.
+
+use {
+    crate::auction::AuctionId,
+    num::BigRational,
+    number::serialization::HexOrDecimalU256,
+    primitive_types::{H160, U256},
+    serde::{Deserialize, Serialize},
+    serde_with::serde_as,
+};
+
+/// The order book of a market. Prices are amounts of the buy token per amount
+/// of the sell token of the market in atoms.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderbookDepth {
+    /// The auction the order book was computed from.
+    pub auction_id: AuctionId,
+    pub sell_token: H160,
+    pub buy_token: H160,
+    /// Orders selling the sell token for the buy token, best (lowest) price
+    /// first.
+    pub asks: Vec<PriceLevel>,
+    /// Orders selling the buy token for the sell token, best (highest) price
+    /// first.
+    pub bids: Vec<PriceLevel>,
+}
+
+/// The remaining amounts of all orders whose limit price falls into a price
+/// level.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceLevel {
+    /// The worst limit price of the level. All orders of the level accept this
+    /// price.
+    #[serde(with = "crate::ratio_as_decimal")]
+    pub price: BigRational,
+    /// Remaining amount of the sell token of the market that the orders sell
+    /// (asks) or buy (bids).
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub sell_token_amount: U256,
+    /// Remaining amount of the buy token of the market that the orders buy
+    /// (asks) or sell (bids).
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub buy_token_amount: U256,
+    pub orders: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn serialization() {
+        let level = PriceLevel {
+            price: BigRational::new(3.into(), 2.into()),
+            sell_token_amount: 2.into(),
+            buy_token_amount: 3.into(),
+            orders: 1,
+        };
+        let value = json!({
+            "price": "1.5",
+            "sellTokenAmount": "2",
+            "buyTokenAmount": "3",
+            "orders": 1,
+        });
+        assert_eq!(serde_json::to_value(&level).unwrap(), value);
+        assert_eq!(serde_json::from_value::<PriceLevel>(value).unwrap(), level);
+    }
+}
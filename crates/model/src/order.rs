@@ -579,6 +579,76 @@ impl SignedOrderCancellations {
     }
 }
 
+/// Cancellation of all off-chain orders of the signer that were created before
+/// a timestamp, optionally restricted to orders selling and/or buying a
+/// specific token.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllOrdersCancellation {
+    /// Unix timestamp in seconds. Orders created at or after this time are not
+    /// affected.
+    pub cancelled_before: u32,
+    #[serde(default)]
+    pub sell_token: Option<H160>,
+    #[serde(default)]
+    pub buy_token: Option<H160>,
+}
+
+impl AllOrdersCancellation {
+    /// The EIP-712 type hash for cancelling all orders. Computed with:
+    /// `keccak256("AllOrdersCancellation(uint32 cancelledBefore,address
+    /// sellToken,address buyToken)")`.
+    const TYPE_HASH: [u8; 32] =
+        hex!("925f5b972e8c4fbafaa6a224baf74ee6ec9f0fc8612296d04a992f0af87f6cc7");
+
+    /// Token filters are encoded as the zero address when they are not set.
+    pub fn hash_struct(&self) -> [u8; 32] {
+        let mut hash_data = [0u8; 128];
+        hash_data[0..32].copy_from_slice(&Self::TYPE_HASH);
+        hash_data[60..64].copy_from_slice(&self.cancelled_before.to_be_bytes());
+        hash_data[76..96].copy_from_slice(self.sell_token.unwrap_or_default().as_bytes());
+        hash_data[108..128].copy_from_slice(self.buy_token.unwrap_or_default().as_bytes());
+        signing::keccak256(&hash_data)
+    }
+
+    /// Whether the order is cancelled by this message, assuming the message was
+    /// signed by the order owner. Only orders with an ECDSA signature can be
+    /// cancelled off-chain.
+    pub fn cancels(&self, order: &Order) -> bool {
+        order.signature.scheme().is_ecdsa_scheme()
+            && order.metadata.creation_date.timestamp() < i64::from(self.cancelled_before)
+            && self
+                .sell_token
+                .map_or(true, |token| token == order.data.sell_token)
+            && self
+                .buy_token
+                .map_or(true, |token| token == order.data.buy_token)
+    }
+}
+
+/// Signed cancellation of all orders of an owner.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedAllOrdersCancellation {
+    #[serde(flatten)]
+    pub data: AllOrdersCancellation,
+    pub signature: EcdsaSignature,
+    pub signing_scheme: EcdsaSigningScheme,
+}
+
+impl SignedAllOrdersCancellation {
+    pub fn validate(&self, domain_separator: &DomainSeparator) -> Result<H160> {
+        Ok(self
+            .signature
+            .recover(
+                self.signing_scheme,
+                domain_separator,
+                &self.data.hash_struct(),
+            )?
+            .signer)
+    }
+}
+
 /// An order cancellation as provided to the orderbook by the frontend.
 #[derive(Eq, PartialEq, Clone, Copy, Debug)]
 pub struct OrderCancellation {
@@ -655,6 +725,40 @@ pub struct CancellationPayload {
 pub struct EthflowData {
     pub user_valid_to: i64,
     pub refund_tx_hash: Option<H256>,
+    /// How far the refund of the order progressed. `None` if the refunder did
+    /// not consider refunding the order yet.
+    #[serde(default)]
+    pub refund_status: Option<RefundStatus>,
+}
+
+/// Progress of refunding an expired ethflow order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RefundStatus {
+    /// The refunder is about to refund the order.
+    Pending,
+    /// The refunder submitted a transaction refunding the order.
+    Submitted,
+    /// Submitting the refund transaction failed. The refunder will retry.
+    Failed,
+    /// The order got refunded on-chain.
+    Refunded,
+}
+
+/// Refund of an ethflow order of a user.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthflowRefund {
+    pub order_uid: OrderUid,
+    /// The ethflow contract the order was placed in.
+    pub ethflow_contract: H160,
+    pub creation_date: DateTime<Utc>,
+    pub user_valid_to: i64,
+    pub status: RefundStatus,
+    /// When the status last changed. `None` if the refund was only indexed
+    /// on-chain.
+    pub updated_at: Option<DateTime<Utc>>,
+    pub tx_hash: Option<H256>,
 }
 
 // We still want to have the `is_refunded` field in the JSON response to stay
@@ -672,12 +776,14 @@ impl ::serde::Serialize for EthflowData {
             user_valid_to: i64,
             refund_tx_hash: Option<H256>,
             is_refunded: bool,
+            refund_status: Option<RefundStatus>,
         }
 
         let ext = Extended {
             user_valid_to: self.user_valid_to,
             refund_tx_hash: self.refund_tx_hash,
             is_refunded: self.refund_tx_hash.is_some(),
+            refund_status: self.refund_status,
         };
 
         ext.serialize(serializer)
@@ -1075,6 +1181,36 @@ mod tests {
         web3::signing::keccak256,
     };
 
+    #[test]
+    fn ethflow_data_serialization() {
+        let data = EthflowData {
+            user_valid_to: 1,
+            refund_tx_hash: Some(H256([2; 32])),
+            refund_status: Some(RefundStatus::Refunded),
+        };
+        let value = json!({
+            "userValidTo": 1,
+            "refundTxHash": "0x0202020202020202020202020202020202020202020202020202020202020202",
+            "isRefunded": true,
+            "refundStatus": "refunded",
+        });
+        assert_eq!(serde_json::to_value(&data).unwrap(), value);
+        assert_eq!(serde_json::from_value::<EthflowData>(value).unwrap(), data);
+
+        let legacy = json!({
+            "userValidTo": 1,
+            "refundTxHash": null,
+            "isRefunded": false,
+        });
+        assert_eq!(
+            serde_json::from_value::<EthflowData>(legacy).unwrap(),
+            EthflowData {
+                user_valid_to: 1,
+                ..Default::default()
+            }
+        );
+    }
+
     #[test]
     fn deserialization_and_back() {
         let value = json!(
@@ -1485,4 +1621,99 @@ mod tests {
             assert_eq!(cancellations.hash_struct(), struct_hash);
         }
     }
+
+    #[test]
+    fn all_orders_cancellation_struct_hash() {
+        for (cancellation, struct_hash) in [
+            (
+                AllOrdersCancellation::default(),
+                hex!("ccc010010f247167b3711839de9f45c56faf73ebb7e6d3546afd4f75df720ed2"),
+            ),
+            (
+                AllOrdersCancellation {
+                    cancelled_before: 1_700_000_000,
+                    sell_token: Some(H160([0x11; 20])),
+                    buy_token: None,
+                },
+                hex!("6e730b10f50f1e282a319c12177535ed4c50ec275dd26d973b325b921f1548d4"),
+            ),
+            (
+                AllOrdersCancellation {
+                    cancelled_before: 1_700_000_000,
+                    sell_token: Some(H160([0x11; 20])),
+                    buy_token: Some(H160([0x22; 20])),
+                },
+                hex!("9cb11523c44729c19ac13e190936de3c2bae5ab500d57280060c8e90b2d93fbf"),
+            ),
+        ] {
+            assert_eq!(cancellation.hash_struct(), struct_hash);
+        }
+    }
+
+    #[test]
+    fn all_orders_cancellation_signature() {
+        let domain_separator = DomainSeparator([0x2a; 32]);
+        let key = SecretKey::from_slice(&[1; 32]).unwrap();
+        let owner = SecretKeyRef::new(&key).address();
+
+        let data = AllOrdersCancellation {
+            cancelled_before: 1_700_000_000,
+            sell_token: Some(H160([0x11; 20])),
+            buy_token: None,
+        };
+        for signing_scheme in [EcdsaSigningScheme::Eip712, EcdsaSigningScheme::EthSign] {
+            let signed = SignedAllOrdersCancellation {
+                data,
+                signature: EcdsaSignature::sign(
+                    signing_scheme,
+                    &domain_separator,
+                    &data.hash_struct(),
+                    SecretKeyRef::new(&key),
+                ),
+                signing_scheme,
+            };
+            assert_eq!(signed.validate(&domain_separator).unwrap(), owner);
+
+            let tampered = SignedAllOrdersCancellation {
+                data: AllOrdersCancellation {
+                    sell_token: None,
+                    ..data
+                },
+                ..signed
+            };
+            assert_ne!(tampered.validate(&domain_separator).unwrap(), owner);
+        }
+    }
+
+    #[test]
+    fn all_orders_cancellation_cancels_matching_orders() {
+        let cancellation = AllOrdersCancellation {
+            cancelled_before: 100,
+            sell_token: Some(H160([0x11; 20])),
+            buy_token: None,
+        };
+        let order = |created: i64, sell_token: u8| {
+            OrderBuilder::default()
+                .with_creation_date(Utc.timestamp_opt(created, 0).unwrap())
+                .with_sell_token(H160([sell_token; 20]))
+                .with_buy_token(H160([0x33; 20]))
+                .build()
+        };
+
+        assert!(cancellation.cancels(&order(99, 0x11)));
+        // Orders created afterwards are not affected.
+        assert!(!cancellation.cancels(&order(100, 0x11)));
+        // Orders of other token pairs are not affected.
+        assert!(!cancellation.cancels(&order(99, 0x22)));
+        // On-chain orders have to be cancelled on-chain.
+        let mut presign = order(99, 0x11);
+        presign.signature = Signature::PreSign;
+        assert!(!cancellation.cancels(&presign));
+        // Without filters all orders of the owner are cancelled.
+        assert!(AllOrdersCancellation {
+            cancelled_before: 100,
+            ..Default::default()
+        }
+        .cancels(&order(99, 0x22)));
+    }
 }
@@ -302,6 +302,50 @@ pub struct OrderQuoteResponse {
     pub from: H160,
     pub expiration: DateTime<Utc>,
     pub id: Option<QuoteId>,
+    /// Explains how the quote was computed. Only included for verbose quote
+    /// requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<QuoteDetails>,
+}
+
+/// Detailed information about how a quote was computed.
+#[serde_as]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteDetails {
+    /// Name of the price estimator that provided the winning estimate.
+    pub winner: Option<String>,
+    /// The results of all price estimators that competed for this quote.
+    pub estimates: Vec<QuoteEstimate>,
+    /// Whether the winning estimate was verified by simulating the trade.
+    pub verified: bool,
+    /// Gas units used for computing the fee, including any additional gas
+    /// for signature verification and hooks.
+    pub gas_amount: u64,
+    /// Gas price in wei used for computing the fee.
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub gas_price: U256,
+    /// The fee amount before applying any subsidies. The subsidy applied to
+    /// the quote is the difference to the quoted `feeAmount`.
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub full_fee_amount: U256,
+}
+
+/// The result of a single price estimator competing for a quote.
+#[serde_as]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteEstimate {
+    pub estimator: String,
+    #[serde_as(as = "Option<HexOrDecimalU256>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub out_amount: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas: Option<u64>,
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 impl OrderQuoteRequest {
@@ -0,0 +1,112 @@
+//! Accuracy of the price estimators measured by comparing the quotes that
+//! orders were created with to the prices the orders got executed at.
+
+use {
+    chrono::{DateTime, Utc},
+    primitive_types::H160,
+    serde::{Deserialize, Serialize},
+    std::collections::BTreeMap,
+};
+
+/// Accuracy of every price estimator over the market orders created since
+/// `since` that got executed.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub since: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub estimators: Vec<EstimatorAccuracy>,
+    pub pairs: Vec<PairAccuracy>,
+    /// The weights the price estimators apply to the estimates of every
+    /// estimator. Empty if the weights are not derived from the accuracy.
+    pub weights: BTreeMap<String, f64>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimatorAccuracy {
+    pub estimator: String,
+    #[serde(flatten)]
+    pub accuracy: Accuracy,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PairAccuracy {
+    pub estimator: String,
+    pub sell_token: H160,
+    pub buy_token: H160,
+    #[serde(flatten)]
+    pub accuracy: Accuracy,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Accuracy {
+    pub samples: u64,
+    /// Relative deviation of the executed price from the quoted price. Positive
+    /// values mean that orders got executed at better prices than quoted.
+    pub deviation: Distribution,
+    /// How long the estimator took to respond in milliseconds. Missing if the
+    /// latency of none of the samples is known.
+    pub latency_ms: Option<Distribution>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Distribution {
+    pub mean: f64,
+    pub p10: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, chrono::TimeZone, serde_json::json};
+
+    #[test]
+    fn serialization() {
+        let report = Report {
+            since: Utc.timestamp_opt(0, 0).unwrap(),
+            updated_at: Utc.timestamp_opt(60, 0).unwrap(),
+            estimators: vec![EstimatorAccuracy {
+                estimator: "Baseline".to_string(),
+                accuracy: Accuracy {
+                    samples: 2,
+                    deviation: Distribution {
+                        mean: -0.5,
+                        ..Default::default()
+                    },
+                    latency_ms: None,
+                },
+            }],
+            pairs: Default::default(),
+            weights: [("Baseline".to_string(), 0.9)].into_iter().collect(),
+        };
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "since": "1970-01-01T00:00:00Z",
+                "updatedAt": "1970-01-01T00:01:00Z",
+                "estimators": [{
+                    "estimator": "Baseline",
+                    "samples": 2,
+                    "deviation": {
+                        "mean": -0.5,
+                        "p10": 0.0,
+                        "p50": 0.0,
+                        "p90": 0.0,
+                        "p99": 0.0,
+                    },
+                    "latencyMs": null,
+                }],
+                "pairs": [],
+                "weights": {
+                    "Baseline": 0.9,
+                },
+            })
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use {
+    crate::auction::AuctionId,
+    num::BigInt,
+    number::serialization::HexOrDecimalU256,
+    primitive_types::{H160, H256, U256},
+    serde::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+};
+
+/// Returned by the `/solver_rewards` endpoint. Contains the rewards of all
+/// auctions whose block deadline lies in the accounting period
+/// `[from_block, to_block)` and the resulting totals per solver.
+///
+/// The report only depends on the settlement scores and observations that are
+/// stored in the database, so requesting it again for a period whose
+/// settlements have all been observed yields the same report.
+#[serde_as]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub from_block: u64,
+    pub to_block: u64,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub upper_cap: U256,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub lower_cap: U256,
+    pub auctions: Vec<AuctionReward>,
+    pub solvers: Vec<SolverReward>,
+}
+
+/// The reward of the winner of a single auction.
+#[serde_as]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuctionReward {
+    pub auction_id: AuctionId,
+    pub solver: H160,
+    pub outcome: Outcome,
+    pub transaction_hash: Option<H256>,
+    pub settlement_block: Option<u64>,
+    pub block_deadline: u64,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub winning_score: U256,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub reference_score: U256,
+    /// The score observed on chain, i.e. surplus plus fees of the settlement.
+    #[serde_as(as = "Option<HexOrDecimalU256>")]
+    pub observed_score: Option<U256>,
+    /// The gas costs the solver paid for executing the settlement.
+    #[serde_as(as = "Option<HexOrDecimalU256>")]
+    pub execution_cost: Option<U256>,
+    /// The payment to the solver in wei. Negative payments are penalties.
+    #[serde_as(as = "DisplayFromStr")]
+    pub payment: BigInt,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Outcome {
+    /// The auction was settled on time and the settlement has been observed.
+    Settled,
+    /// The auction was settled after its block deadline.
+    Late,
+    /// The auction was not settled before its block deadline became final.
+    #[default]
+    Failed,
+    /// The auction was settled on time but the settlement has not been
+    /// observed yet, or it was not settled but its block deadline is not final
+    /// yet. Its payment is only known later.
+    Pending,
+}
+
+/// The totals of a solver over the accounting period.
+#[serde_as]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SolverReward {
+    pub solver: H160,
+    pub participated_auctions: u64,
+    pub won_auctions: u64,
+    /// Won auctions that were settled late or not at all.
+    pub failed_auctions: u64,
+    pub pending_auctions: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub payment: BigInt,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub execution_costs: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn serialize() {
+        let report = Report {
+            from_block: 1,
+            to_block: 2,
+            upper_cap: 10.into(),
+            lower_cap: 5.into(),
+            auctions: vec![AuctionReward {
+                auction_id: 3,
+                solver: H160([1; 20]),
+                outcome: Outcome::Late,
+                transaction_hash: Some(H256([2; 32])),
+                settlement_block: Some(2),
+                block_deadline: 1,
+                winning_score: 4.into(),
+                reference_score: 3.into(),
+                observed_score: Some(4.into()),
+                execution_cost: Some(1.into()),
+                payment: BigInt::from(-3),
+            }],
+            solvers: vec![SolverReward {
+                solver: H160([1; 20]),
+                participated_auctions: 1,
+                won_auctions: 1,
+                failed_auctions: 1,
+                pending_auctions: 0,
+                payment: BigInt::from(-3),
+                execution_costs: 1.into(),
+            }],
+        };
+        let expected = json!({
+            "fromBlock": 1,
+            "toBlock": 2,
+            "upperCap": "10",
+            "lowerCap": "5",
+            "auctions": [{
+                "auctionId": 3,
+                "solver": "0x0101010101010101010101010101010101010101",
+                "outcome": "late",
+                "transactionHash": "0x0202020202020202020202020202020202020202020202020202020202020202",
+                "settlementBlock": 2,
+                "blockDeadline": 1,
+                "winningScore": "4",
+                "referenceScore": "3",
+                "observedScore": "4",
+                "executionCost": "1",
+                "payment": "-3",
+            }],
+            "solvers": [{
+                "solver": "0x0101010101010101010101010101010101010101",
+                "participatedAuctions": 1,
+                "wonAuctions": 1,
+                "failedAuctions": 1,
+                "pendingAuctions": 0,
+                "payment": "-3",
+                "executionCosts": "1",
+            }],
+        });
+        assert_eq!(serde_json::to_value(&report).unwrap(), expected);
+        assert_eq!(serde_json::from_value::<Report>(expected).unwrap(), report);
+    }
+}
@@ -0,0 +1,122 @@
+//! Webhooks that notify integrators about what happens to orders.
+
+use {
+    crate::order::OrderUid,
+    chrono::{DateTime, Utc},
+    primitive_types::H160,
+    serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookEvent {
+    /// The order got completely filled.
+    Traded,
+    /// A partially fillable order got filled without being completely filled.
+    PartiallyFilled,
+    /// The order expired without being completely filled.
+    Expired,
+    /// The order was filtered from the auction.
+    Filtered,
+}
+
+/// Request to register a webhook.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookRegistration {
+    pub url: String,
+    /// Only notify about orders placed by this owner.
+    pub owner: Option<H160>,
+    /// Only notify about orders whose app data specifies this app code.
+    pub app_code: Option<String>,
+}
+
+/// Response to registering a webhook.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredWebhook {
+    pub id: i64,
+    /// Secret the deliveries get signed with. It is only returned once.
+    pub secret: String,
+}
+
+/// A registered webhook. The secret is never returned.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub id: i64,
+    pub url: String,
+    pub owner: Option<H160>,
+    pub app_code: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The JSON body that gets posted to webhooks.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    pub order_uid: OrderUid,
+    /// The user that placed the order. For on-chain orders this is the sender
+    /// of the order placement.
+    pub owner: H160,
+    pub app_code: Option<String>,
+    /// When the event happened. For expiries this is the `validTo` of the
+    /// order.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A delivery that ran out of attempts.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event: WebhookEvent,
+    pub order_uid: OrderUid,
+    pub event_timestamp: DateTime<Utc>,
+    pub attempts: u32,
+    pub last_attempt: DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, chrono::TimeZone, serde_json::json};
+
+    #[test]
+    fn payload_serialization() {
+        let payload = WebhookPayload {
+            event: WebhookEvent::PartiallyFilled,
+            order_uid: OrderUid([1; 56]),
+            owner: H160([2; 20]),
+            app_code: Some("CoW Swap".to_string()),
+            timestamp: Utc.timestamp_opt(0, 0).unwrap(),
+        };
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            json!({
+                "event": "partiallyFilled",
+                "orderUid": format!("0x{}", "01".repeat(56)),
+                "owner": "0x0202020202020202020202020202020202020202",
+                "appCode": "CoW Swap",
+                "timestamp": "1970-01-01T00:00:00Z",
+            })
+        );
+    }
+
+    #[test]
+    fn registration_defaults() {
+        let registration: WebhookRegistration = serde_json::from_value(json!({
+            "url": "https://example.com",
+        }))
+        .unwrap();
+        assert_eq!(
+            registration,
+            WebhookRegistration {
+                url: "https://example.com".to_string(),
+                ..Default::default()
+            }
+        );
+    }
+}
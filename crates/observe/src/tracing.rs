@@ -18,10 +18,16 @@ pub fn initialize(env_filter: &str, stderr_threshold: LevelFilter) {
 ///
 /// Useful for tests.
 pub fn initialize_reentrant(env_filter: &str) {
+    initialize_reentrant_with_stderr_threshold(env_filter, LevelFilter::ERROR)
+}
+
+/// Like [`initialize_reentrant`], but logs events up to `stderr_threshold` to
+/// stderr instead of stdout.
+pub fn initialize_reentrant_with_stderr_threshold(env_filter: &str, stderr_threshold: LevelFilter) {
     // The tracing subscriber below is global object so initializing it again in the
     // same process by a different thread would fail.
     static ONCE: Once = Once::new();
-    ONCE.call_once(|| set_tracing_subscriber(env_filter, LevelFilter::ERROR));
+    ONCE.call_once(|| set_tracing_subscriber(env_filter, stderr_threshold));
 }
 
 fn set_tracing_subscriber(env_filter: &str, stderr_threshold: LevelFilter) {
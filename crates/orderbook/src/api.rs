@@ -1,32 +1,55 @@
 use {
-    crate::{app_data, database::Postgres, orderbook::Orderbook},
+    crate::{
+        app_data,
+        database::Postgres,
+        market_depth::MarketDepth,
+        orderbook::Orderbook,
+        quote_accuracy::QuoteAccuracy,
+        solver_rewards,
+    },
     shared::{
         api::{box_filter, error, finalize_router, ApiReply},
         order_quoting::QuoteHandler,
         price_estimation::native::NativePriceEstimating,
+        request_signing::Secret,
     },
     std::sync::Arc,
     warp::{Filter, Rejection, Reply},
 };
 
+mod cancel_all_orders;
 mod cancel_order;
 mod cancel_orders;
+mod delete_webhook;
 mod get_app_data;
 mod get_auction;
+mod get_dead_webhook_deliveries;
+mod get_market_orderbook;
 mod get_native_price;
 mod get_order_by_uid;
 mod get_orders_by_tx;
+mod get_quote_accuracy;
+mod get_settlement_analytics;
 mod get_solver_competition;
+mod get_solver_rewards;
+mod get_token_quality;
 mod get_total_surplus;
+mod get_trade_analytics;
 mod get_trades;
 mod get_user_orders;
+mod get_user_refunds;
+mod get_webhooks;
 mod post_order;
+mod post_order_batch;
 mod post_quote;
 mod post_solver_competition;
+mod post_webhook;
 mod put_app_data;
 mod replace_order;
+mod retry_webhook_delivery;
 mod version;
 
+#[allow(clippy::too_many_arguments)]
 pub fn handle_all_routes(
     database: Postgres,
     orderbook: Arc<Orderbook>,
@@ -34,6 +57,13 @@ pub fn handle_all_routes(
     app_data: Arc<app_data::Registry>,
     solver_competition_auth: Option<String>,
     native_price_estimator: Arc<dyn NativePriceEstimating>,
+    solver_rewards: solver_rewards::Config,
+    max_orders_per_batch: usize,
+    quote_accuracy: Arc<QuoteAccuracy>,
+    market_depth: Arc<MarketDepth>,
+    allow_taxed_tokens: bool,
+    webhook_admin_auth: Option<String>,
+    webhook_signing_key: Option<Secret>,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
     // Note that we add a string with endpoint's name to all responses.
     // This string will be used later to report metrics.
@@ -44,6 +74,13 @@ pub fn handle_all_routes(
             "v1/create_order",
             box_filter(post_order::post_order(orderbook.clone())),
         ),
+        (
+            "v1/create_order_batch",
+            box_filter(post_order_batch::filter(
+                orderbook.clone(),
+                max_orders_per_batch,
+            )),
+        ),
         (
             "v1/get_order",
             box_filter(get_order_by_uid::get_order_by_uid(orderbook.clone())),
@@ -60,6 +97,10 @@ pub fn handle_all_routes(
             "v1/cancel_orders",
             box_filter(cancel_orders::filter(orderbook.clone())),
         ),
+        (
+            "v1/cancel_all_orders",
+            box_filter(cancel_all_orders::filter(orderbook.clone())),
+        ),
         (
             "v1/replace_order",
             box_filter(replace_order::filter(orderbook.clone())),
@@ -68,6 +109,10 @@ pub fn handle_all_routes(
             "v1/get_user_orders",
             box_filter(get_user_orders::get_user_orders(orderbook.clone())),
         ),
+        (
+            "v1/get_user_refunds",
+            box_filter(get_user_refunds::get(database.clone())),
+        ),
         (
             "v1/get_orders_by_tx",
             box_filter(get_orders_by_tx::get_orders_by_tx(orderbook.clone())),
@@ -77,6 +122,10 @@ pub fn handle_all_routes(
             "v1/auction",
             box_filter(get_auction::get_auction(orderbook)),
         ),
+        (
+            "v1/get_market_orderbook",
+            box_filter(get_market_orderbook::get(market_depth)),
+        ),
         (
             "v1/solver_competition",
             box_filter(get_solver_competition::get(Arc::new(database.clone()))),
@@ -85,7 +134,7 @@ pub fn handle_all_routes(
             "v1/solver_competition",
             box_filter(post_solver_competition::post(
                 Arc::new(database.clone()),
-                solver_competition_auth,
+                solver_competition_auth.clone(),
             )),
         ),
         ("v1/version", box_filter(version::version())),
@@ -103,7 +152,63 @@ pub fn handle_all_routes(
         ),
         (
             "v1/get_total_surplus",
-            box_filter(get_total_surplus::get(database)),
+            box_filter(get_total_surplus::get(database.clone())),
+        ),
+        (
+            "v1/get_solver_rewards",
+            box_filter(get_solver_rewards::get(database.clone(), solver_rewards)),
+        ),
+        (
+            "v1/get_trade_analytics",
+            box_filter(get_trade_analytics::get(database.clone())),
+        ),
+        (
+            "v1/get_settlement_analytics",
+            box_filter(get_settlement_analytics::get(database.clone())),
+        ),
+        (
+            "v1/get_token_quality",
+            get_token_quality::get(database.clone(), allow_taxed_tokens).boxed(),
+        ),
+        (
+            "v1/post_webhook",
+            box_filter(post_webhook::post(
+                database.clone(),
+                webhook_admin_auth.clone(),
+                webhook_signing_key,
+            )),
+        ),
+        (
+            "v1/get_webhooks",
+            box_filter(get_webhooks::get(
+                database.clone(),
+                webhook_admin_auth.clone(),
+            )),
+        ),
+        (
+            "v1/delete_webhook",
+            box_filter(delete_webhook::delete(
+                database.clone(),
+                webhook_admin_auth.clone(),
+            )),
+        ),
+        (
+            "v1/get_dead_webhook_deliveries",
+            box_filter(get_dead_webhook_deliveries::get(
+                database.clone(),
+                webhook_admin_auth.clone(),
+            )),
+        ),
+        (
+            "v1/retry_webhook_delivery",
+            box_filter(retry_webhook_delivery::post(database, webhook_admin_auth)),
+        ),
+        (
+            "v1/get_quote_accuracy",
+            box_filter(get_quote_accuracy::get(
+                quote_accuracy,
+                solver_competition_auth,
+            )),
         ),
     ];
 
@@ -0,0 +1,68 @@
+use {
+    crate::orderbook::{OrderCancellationError, Orderbook},
+    anyhow::Result,
+    model::order::SignedAllOrdersCancellation,
+    primitive_types::H160,
+    shared::api::{convert_json_response, extract_payload},
+    std::{convert::Infallible, sync::Arc},
+    warp::{Filter, Rejection},
+};
+
+pub fn request(
+) -> impl Filter<Extract = (H160, SignedAllOrdersCancellation), Error = Rejection> + Clone {
+    warp::path!("v1" / "account" / H160 / "orders")
+        .and(warp::delete())
+        .and(extract_payload())
+}
+
+pub fn response(result: Result<(), OrderCancellationError>) -> super::ApiReply {
+    convert_json_response(result.map(|_| "Cancelled"))
+}
+
+pub fn filter(
+    orderbook: Arc<Orderbook>,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |owner, cancellation| {
+        let orderbook = orderbook.clone();
+        async move {
+            let result = orderbook.cancel_all_orders(owner, cancellation).await;
+            Result::<_, Infallible>::Ok(response(result))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        model::{order::AllOrdersCancellation, signature::EcdsaSigningScheme},
+        serde_json::json,
+        warp::test::request,
+    };
+
+    #[tokio::test]
+    async fn cancel_all_orders_request_ok() {
+        let filter = super::request();
+        let request = request()
+            .path("/v1/account/0x0101010101010101010101010101010101010101/orders")
+            .method("DELETE")
+            .header("content-type", "application/json")
+            .json(&json!({
+                "cancelledBefore": 1_700_000_000,
+                "sellToken": "0x0202020202020202020202020202020202020202",
+                "signature": format!("0x{}1b", "03".repeat(64)),
+                "signingScheme": "eip712",
+            }));
+        let (owner, cancellation) = request.filter(&filter).await.unwrap();
+        assert_eq!(owner, H160([0x01; 20]));
+        assert_eq!(
+            cancellation.data,
+            AllOrdersCancellation {
+                cancelled_before: 1_700_000_000,
+                sell_token: Some(H160([0x02; 20])),
+                buy_token: None,
+            }
+        );
+        assert_eq!(cancellation.signing_scheme, EcdsaSigningScheme::Eip712);
+    }
+}
@@ -53,6 +53,13 @@ impl IntoWarpReply for OrderCancellationError {
                 super::error("OnChainOrder", "On-chain orders must be cancelled on-chain"),
                 StatusCode::BAD_REQUEST,
             ),
+            Self::CancellationInFuture => with_status(
+                super::error(
+                    "CancellationInFuture",
+                    "Cancellation timestamp must not be in the future",
+                ),
+                StatusCode::BAD_REQUEST,
+            ),
             Self::Other(err) => {
                 tracing::error!(?err, "cancel_order");
                 shared::api::internal_error_reply()
@@ -0,0 +1,73 @@
+//! This is a private, undocumented api to remove a webhook together with its
+//! pending deliveries.
+
+use {
+    crate::database::Postgres,
+    reqwest::StatusCode,
+    std::convert::Infallible,
+    warp::{reply::with_status, Filter, Rejection},
+};
+
+fn request() -> impl Filter<Extract = (i64, Option<String>), Error = Rejection> + Clone {
+    warp::path!("v1" / "admin" / "webhooks" / i64)
+        .and(warp::delete())
+        .and(warp::header::optional::<String>("Authorization"))
+}
+
+pub fn delete(
+    db: Postgres,
+    expected_auth: Option<String>,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |id: i64, auth| {
+        let db = db.clone();
+        let expected_auth = expected_auth.clone();
+        async move {
+            if expected_auth.is_none() || expected_auth != auth {
+                return Result::<_, Infallible>::Ok(with_status(
+                    super::error("Unauthorized", ""),
+                    StatusCode::UNAUTHORIZED,
+                ));
+            }
+
+            Ok(match db.delete_webhook(id).await {
+                Ok(true) => with_status(warp::reply::json(&()), StatusCode::OK),
+                Ok(false) => with_status(
+                    super::error("NotFound", "no webhook with this id"),
+                    StatusCode::NOT_FOUND,
+                ),
+                Err(err) => {
+                    tracing::error!(?err, id, "delete_webhook");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, warp::test::request};
+
+    #[tokio::test]
+    async fn request_() {
+        let result = request()
+            .path("/v1/admin/webhooks/7")
+            .method("DELETE")
+            .header("authorization", "auth")
+            .filter(&super::request())
+            .await
+            .unwrap();
+        assert_eq!(result, (7, Some("auth".to_string())));
+    }
+
+    #[tokio::test]
+    async fn requires_auth() {
+        let filter = delete(Postgres::new("postgresql://").unwrap(), None);
+        let response = request()
+            .path("/v1/admin/webhooks/7")
+            .method("DELETE")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
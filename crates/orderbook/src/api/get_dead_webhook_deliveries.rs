@@ -0,0 +1,102 @@
+//! This is a private, undocumented api to inspect the webhook deliveries that
+//! ran out of attempts.
+
+use {
+    crate::database::Postgres,
+    reqwest::StatusCode,
+    serde::Deserialize,
+    std::convert::Infallible,
+    warp::{reply::with_status, Filter, Rejection},
+};
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Query {
+    webhook_id: Option<i64>,
+    offset: Option<u64>,
+    limit: Option<u64>,
+}
+
+fn request() -> impl Filter<Extract = (Option<String>, Query), Error = Rejection> + Clone {
+    warp::path!("v1" / "admin" / "webhooks" / "dead_letters")
+        .and(warp::get())
+        .and(warp::header::optional::<String>("Authorization"))
+        .and(warp::query::<Query>())
+}
+
+pub fn get(
+    db: Postgres,
+    expected_auth: Option<String>,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |auth, query: Query| {
+        let db = db.clone();
+        let expected_auth = expected_auth.clone();
+        async move {
+            if expected_auth.is_none() || expected_auth != auth {
+                return Result::<_, Infallible>::Ok(with_status(
+                    super::error("Unauthorized", ""),
+                    StatusCode::UNAUTHORIZED,
+                ));
+            }
+
+            const DEFAULT_OFFSET: u64 = 0;
+            const DEFAULT_LIMIT: u64 = 10;
+            const MIN_LIMIT: u64 = 1;
+            const MAX_LIMIT: u64 = 1000;
+            let offset = query.offset.unwrap_or(DEFAULT_OFFSET);
+            let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+            if !(MIN_LIMIT..=MAX_LIMIT).contains(&limit) {
+                return Ok(with_status(
+                    super::error(
+                        "LIMIT_OUT_OF_BOUNDS",
+                        format!("The pagination limit is [{MIN_LIMIT},{MAX_LIMIT}]."),
+                    ),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+            let result = db
+                .dead_webhook_deliveries(query.webhook_id, offset, limit)
+                .await;
+            Ok(match result {
+                Ok(deliveries) => with_status(warp::reply::json(&deliveries), StatusCode::OK),
+                Err(err) => {
+                    tracing::error!(?err, ?query, "get_dead_webhook_deliveries");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, warp::test::request};
+
+    #[tokio::test]
+    async fn request_() {
+        let (_, query) = request()
+            .path("/v1/admin/webhooks/dead_letters?webhookId=3&limit=2")
+            .method("GET")
+            .filter(&super::request())
+            .await
+            .unwrap();
+        assert_eq!(query.webhook_id, Some(3));
+        assert_eq!(query.offset, None);
+        assert_eq!(query.limit, Some(2));
+    }
+
+    #[tokio::test]
+    async fn limit_out_of_bounds() {
+        let filter = get(
+            Postgres::new("postgresql://").unwrap(),
+            Some("auth".to_string()),
+        );
+        let response = request()
+            .path("/v1/admin/webhooks/dead_letters?limit=1001")
+            .method("GET")
+            .header("authorization", "auth")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
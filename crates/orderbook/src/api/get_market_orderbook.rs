@@ -0,0 +1,163 @@
+use {
+    crate::market_depth::MarketDepth,
+    anyhow::{Context, Result},
+    model::ratio_as_decimal::DecimalBigRational,
+    num::{BigRational, Zero},
+    primitive_types::H160,
+    reqwest::StatusCode,
+    serde::Deserialize,
+    serde_with::serde_as,
+    std::{convert::Infallible, str::FromStr, sync::Arc},
+    warp::{reply::with_status, Filter, Rejection},
+};
+
+/// A market in the form `{sellToken}-{buyToken}`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Market {
+    sell_token: H160,
+    buy_token: H160,
+}
+
+impl FromStr for Market {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (sell_token, buy_token) = s.split_once('-').context("missing separator")?;
+        Ok(Self {
+            sell_token: sell_token.parse().context("invalid sell token")?,
+            buy_token: buy_token.parse().context("invalid buy token")?,
+        })
+    }
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize)]
+struct Query {
+    /// Prices get grouped into multiples of the tick.
+    #[serde_as(as = "Option<DecimalBigRational>")]
+    #[serde(default)]
+    tick: Option<BigRational>,
+    /// Maximum number of price levels per side.
+    limit: Option<u64>,
+}
+
+fn request() -> impl Filter<Extract = (Market, Query), Error = Rejection> + Clone {
+    warp::path!("v1" / "markets" / Market / "orderbook")
+        .and(warp::get())
+        .and(warp::query::<Query>())
+}
+
+pub fn get(
+    market_depth: Arc<MarketDepth>,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |market: Market, query: Query| {
+        let market_depth = market_depth.clone();
+        async move {
+            const DEFAULT_LIMIT: u64 = 100;
+            const MIN_LIMIT: u64 = 1;
+            const MAX_LIMIT: u64 = 1000;
+            let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+            if !(MIN_LIMIT..=MAX_LIMIT).contains(&limit) {
+                return Result::<_, Infallible>::Ok(with_status(
+                    super::error(
+                        "LIMIT_OUT_OF_BOUNDS",
+                        format!("The pagination limit is [{MIN_LIMIT},{MAX_LIMIT}]."),
+                    ),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+            if matches!(&query.tick, Some(tick) if tick <= &BigRational::zero()) {
+                return Ok(with_status(
+                    super::error("InvalidTick", "tick must be positive"),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+            if market.sell_token == market.buy_token {
+                return Ok(with_status(
+                    super::error("SameBuyAndSellToken", "buy and sell token are the same"),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+
+            let result = market_depth
+                .orderbook(
+                    market.sell_token,
+                    market.buy_token,
+                    query.tick.as_ref(),
+                    limit as usize,
+                )
+                .await;
+            Ok(match result {
+                Ok(Some(orderbook)) => with_status(warp::reply::json(&orderbook), StatusCode::OK),
+                Ok(None) => with_status(
+                    super::error("NotFound", "There is no active auction"),
+                    StatusCode::NOT_FOUND,
+                ),
+                Err(err) => {
+                    tracing::error!(?err, ?market, "failed to get market orderbook");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::database::Postgres, shared::addr, warp::test::request};
+
+    const PATH: &str =
+        "/v1/markets/\
+         0x0000000000000000000000000000000000000001-0x0000000000000000000000000000000000000002/\
+         orderbook";
+
+    #[tokio::test]
+    async fn request_() {
+        let (market, query) = request()
+            .path(PATH)
+            .method("GET")
+            .filter(&super::request())
+            .await
+            .unwrap();
+        assert_eq!(
+            market,
+            Market {
+                sell_token: addr!("0000000000000000000000000000000000000001"),
+                buy_token: addr!("0000000000000000000000000000000000000002"),
+            }
+        );
+        assert_eq!(query.tick, None);
+        assert_eq!(query.limit, None);
+
+        let (_, query) = request()
+            .path(&format!("{PATH}?tick=0.5&limit=10"))
+            .method("GET")
+            .filter(&super::request())
+            .await
+            .unwrap();
+        assert_eq!(query.tick, Some(BigRational::new(1.into(), 2.into())));
+        assert_eq!(query.limit, Some(10));
+
+        let result = request()
+            .path("/v1/markets/0x0000000000000000000000000000000000000001/orderbook")
+            .method("GET")
+            .filter(&super::request())
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_query() {
+        let filter = get(Arc::new(MarketDepth::new(
+            Postgres::new("postgresql://").unwrap(),
+        )));
+        for query in ["limit=0", "limit=1001", "tick=0", "tick=-1"] {
+            let response = request()
+                .path(&format!("{PATH}?{query}"))
+                .method("GET")
+                .reply(&filter)
+                .await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{query}");
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! This is a private, undocumented api to inspect how accurately the price
+//! estimators quote.
+
+use {
+    crate::quote_accuracy::QuoteAccuracy,
+    reqwest::StatusCode,
+    std::{convert::Infallible, sync::Arc},
+    warp::{reply::with_status, Filter, Rejection},
+};
+
+fn request() -> impl Filter<Extract = (Option<String>,), Error = Rejection> + Clone {
+    warp::path!("v1" / "admin" / "quote_accuracy")
+        .and(warp::get())
+        .and(warp::header::optional::<String>("Authorization"))
+}
+
+pub fn get(
+    quote_accuracy: Arc<QuoteAccuracy>,
+    expected_auth: Option<String>,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |auth| {
+        let quote_accuracy = quote_accuracy.clone();
+        let expected_auth = expected_auth.clone();
+        async move {
+            if expected_auth.is_some() && expected_auth != auth {
+                return Result::<_, Infallible>::Ok(with_status(
+                    super::error("Unauthorized", ""),
+                    StatusCode::UNAUTHORIZED,
+                ));
+            }
+
+            Ok(match quote_accuracy.report() {
+                Some(report) => with_status(warp::reply::json(&report), StatusCode::OK),
+                None => with_status(
+                    super::error("NotReady", "quote accuracy has not been computed yet"),
+                    StatusCode::SERVICE_UNAVAILABLE,
+                ),
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::database::Postgres, std::time::Duration, warp::test::request};
+
+    fn quote_accuracy() -> Arc<QuoteAccuracy> {
+        Arc::new(QuoteAccuracy::new(
+            Postgres::new("postgresql://").unwrap(),
+            Duration::from_secs(60),
+            None,
+        ))
+    }
+
+    #[tokio::test]
+    async fn not_ready() {
+        let filter = get(quote_accuracy(), None);
+        let response = request()
+            .path("/v1/admin/quote_accuracy")
+            .method("GET")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn requires_auth() {
+        let filter = get(quote_accuracy(), Some("auth".to_string()));
+        let response = request()
+            .path("/v1/admin/quote_accuracy")
+            .method("GET")
+            .header("authorization", "wrong")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = request()
+            .path("/v1/admin/quote_accuracy")
+            .method("GET")
+            .header("authorization", "auth")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
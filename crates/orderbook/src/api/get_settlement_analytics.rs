@@ -0,0 +1,106 @@
+use {
+    crate::database::{analytics::SettlementFilter, Postgres},
+    chrono::NaiveDate,
+    model::analytics::Interval,
+    primitive_types::H160,
+    serde::Deserialize,
+    std::convert::Infallible,
+    warp::{http::StatusCode, reply::with_status, Filter, Rejection},
+};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Query {
+    #[serde(default)]
+    interval: Interval,
+    solver: Option<H160>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    offset: Option<u64>,
+    limit: Option<u64>,
+}
+
+fn request() -> impl Filter<Extract = (Query,), Error = Rejection> + Clone {
+    warp::path!("v1" / "analytics" / "settlements")
+        .and(warp::get())
+        .and(warp::query::<Query>())
+}
+
+pub fn get(db: Postgres) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |query: Query| {
+        let db = db.clone();
+        async move {
+            const DEFAULT_OFFSET: u64 = 0;
+            const DEFAULT_LIMIT: u64 = 100;
+            const MIN_LIMIT: u64 = 1;
+            const MAX_LIMIT: u64 = 1000;
+            let offset = query.offset.unwrap_or(DEFAULT_OFFSET);
+            let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+            if !(MIN_LIMIT..=MAX_LIMIT).contains(&limit) {
+                return Result::<_, Infallible>::Ok(with_status(
+                    super::error(
+                        "LIMIT_OUT_OF_BOUNDS",
+                        format!("The pagination limit is [{MIN_LIMIT},{MAX_LIMIT}]."),
+                    ),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+            if let (Some(from), Some(to)) = (query.from, query.to) {
+                if from >= to {
+                    return Ok(with_status(
+                        super::error("InvalidDateRange", "from must be before to"),
+                        StatusCode::BAD_REQUEST,
+                    ));
+                }
+            }
+
+            let filter = SettlementFilter {
+                solver: query.solver,
+                from: query.from,
+                to: query.to,
+            };
+            let buckets = db
+                .settlement_analytics(query.interval, filter, offset, limit)
+                .await;
+            Ok(match buckets {
+                Ok(buckets) => with_status(warp::reply::json(&buckets), StatusCode::OK),
+                Err(err) => {
+                    tracing::error!(?err, ?query, "failed to get settlement analytics");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, shared::addr};
+
+    #[tokio::test]
+    async fn parses_query() {
+        let query = warp::test::request()
+            .path(
+                "/v1/analytics/settlements?interval=month&\
+                 solver=0x0000000000000000000000000000000000000001&from=2023-05-01",
+            )
+            .filter(&request())
+            .await
+            .unwrap();
+        assert_eq!(
+            query,
+            Query {
+                interval: Interval::Month,
+                solver: Some(addr!("0000000000000000000000000000000000000001")),
+                from: NaiveDate::from_ymd_opt(2023, 5, 1),
+                ..Default::default()
+            }
+        );
+
+        let result = warp::test::request()
+            .path("/v1/analytics/settlements?from=yesterday")
+            .filter(&request())
+            .await;
+        assert!(result.is_err());
+    }
+}
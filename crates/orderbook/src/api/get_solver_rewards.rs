@@ -0,0 +1,100 @@
+use {
+    crate::{database::Postgres, solver_rewards::Config},
+    serde::Deserialize,
+    std::convert::Infallible,
+    warp::{http::StatusCode, reply::with_status, Filter, Rejection},
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Query {
+    from_block: u64,
+    to_block: u64,
+}
+
+fn request() -> impl Filter<Extract = (Query,), Error = Rejection> + Clone {
+    warp::path!("v1" / "solver_rewards")
+        .and(warp::get())
+        .and(warp::query::<Query>())
+}
+
+pub fn get(
+    db: Postgres,
+    config: Config,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |query: Query| {
+        let db = db.clone();
+        let config = config.clone();
+        async move {
+            // One week of mainnet blocks.
+            const MAX_BLOCK_RANGE: u64 = 50_400;
+            if query.from_block >= query.to_block {
+                return Result::<_, Infallible>::Ok(with_status(
+                    super::error(
+                        "InvalidBlockRange",
+                        "fromBlock must be smaller than toBlock",
+                    ),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+            if query.to_block - query.from_block > MAX_BLOCK_RANGE {
+                return Ok(with_status(
+                    super::error(
+                        "InvalidBlockRange",
+                        format!("The block range can span at most {MAX_BLOCK_RANGE} blocks."),
+                    ),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+            let report = db
+                .solver_rewards(query.from_block..query.to_block, &config)
+                .await;
+            Ok(match report {
+                Ok(report) => with_status(warp::reply::json(&report), StatusCode::OK),
+                Err(err) => {
+                    tracing::error!(?err, ?query, "failed to compute solver rewards");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn parses_query() {
+        let query = warp::test::request()
+            .path("/v1/solver_rewards?fromBlock=10&toBlock=20")
+            .filter(&request())
+            .await
+            .unwrap();
+        assert_eq!(
+            query,
+            Query {
+                from_block: 10,
+                to_block: 20,
+            }
+        );
+
+        let result = warp::test::request()
+            .path("/v1/solver_rewards?fromBlock=10")
+            .filter(&request())
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_block_range() {
+        let filter = get(Postgres::new("postgresql://").unwrap(), Caps::default());
+        for query in ["fromBlock=10&toBlock=10", "fromBlock=0&toBlock=50401"] {
+            let response = warp::test::request()
+                .path(&format!("/v1/solver_rewards?{query}"))
+                .reply(&filter)
+                .await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{query}");
+        }
+    }
+}
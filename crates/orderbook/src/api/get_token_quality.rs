@@ -0,0 +1,114 @@
+use {
+    crate::database::Postgres,
+    chrono::{DateTime, Utc},
+    primitive_types::H160,
+    serde::Serialize,
+    shared::bad_token::{
+        persisted::{StoredTokenQuality, TokenQualityStoring},
+        TokenQuality,
+    },
+    std::convert::Infallible,
+    warp::{hyper::StatusCode, reply, Filter, Rejection, Reply},
+};
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenQualityResponse {
+    good: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transfer_fee_bps: Option<u32>,
+    expiration: DateTime<Utc>,
+}
+
+impl From<StoredTokenQuality> for TokenQualityResponse {
+    fn from(stored: StoredTokenQuality) -> Self {
+        let (good, reason, transfer_fee_bps) = match stored.quality {
+            TokenQuality::Good => (true, None, None),
+            TokenQuality::Taxed { transfer_fee_bps } => (true, None, Some(transfer_fee_bps)),
+            TokenQuality::Bad { reason } => (false, Some(reason), None),
+        };
+        Self {
+            good,
+            reason,
+            transfer_fee_bps,
+            expiration: stored.expiration,
+        }
+    }
+}
+
+fn request() -> impl Filter<Extract = (H160,), Error = Rejection> + Clone {
+    warp::path!("v1" / "token" / H160 / "quality").and(warp::get())
+}
+
+/// Returns the stored verdict of the mode the orderbook detects tokens in.
+pub fn get(
+    database: Postgres,
+    allow_taxed_tokens: bool,
+) -> impl Filter<Extract = (Box<dyn Reply>,), Error = Rejection> + Clone {
+    request().and_then(move |token: H160| {
+        let database = database.clone();
+        async move {
+            let result = database.get(token, allow_taxed_tokens, Utc::now()).await;
+            Result::<_, Infallible>::Ok(match result {
+                Ok(Some(stored)) => Box::new(reply::with_status(
+                    reply::json(&TokenQualityResponse::from(stored)),
+                    StatusCode::OK,
+                )) as Box<dyn Reply>,
+                Ok(None) => Box::new(reply::with_status(
+                    "token quality not found",
+                    StatusCode::NOT_FOUND,
+                )),
+                Err(err) => {
+                    tracing::error!(?err, ?token, "get_token_quality");
+                    Box::new(shared::api::internal_error_reply())
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        chrono::TimeZone,
+        futures::FutureExt,
+        hex_literal::hex,
+        serde_json::json,
+        warp::test::request,
+    };
+
+    #[test]
+    fn token_quality_request() {
+        let path = "/v1/token/0xdac17f958d2ee523a2206206994597c13d831ec7/quality";
+        let result = request()
+            .path(path)
+            .method("GET")
+            .filter(&super::request())
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            result,
+            H160(hex!("dac17f958d2ee523a2206206994597c13d831ec7"))
+        );
+    }
+
+    #[test]
+    fn serializes_response() {
+        let response = TokenQualityResponse::from(StoredTokenQuality {
+            quality: TokenQuality::bad("transfer fails"),
+            expiration: Utc.timestamp_opt(0, 0).unwrap(),
+        });
+        assert_eq!(
+            serde_json::to_value(response).unwrap(),
+            json!({
+                "good": false,
+                "reason": "transfer fails",
+                "expiration": "1970-01-01T00:00:00Z",
+            })
+        );
+    }
+}
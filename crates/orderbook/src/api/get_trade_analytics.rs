@@ -0,0 +1,124 @@
+use {
+    crate::database::{analytics::TradeFilter, Postgres},
+    chrono::NaiveDate,
+    model::analytics::{Interval, TradeGrouping},
+    primitive_types::H160,
+    serde::Deserialize,
+    std::convert::Infallible,
+    warp::{http::StatusCode, reply::with_status, Filter, Rejection},
+};
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Query {
+    #[serde(default)]
+    interval: Interval,
+    group_by: Option<TradeGrouping>,
+    owner: Option<H160>,
+    token: Option<H160>,
+    app_code: Option<String>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    offset: Option<u64>,
+    limit: Option<u64>,
+}
+
+fn request() -> impl Filter<Extract = (Query,), Error = Rejection> + Clone {
+    warp::path!("v1" / "analytics" / "trades")
+        .and(warp::get())
+        .and(warp::query::<Query>())
+}
+
+pub fn get(db: Postgres) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |query: Query| {
+        let db = db.clone();
+        async move {
+            const DEFAULT_OFFSET: u64 = 0;
+            const DEFAULT_LIMIT: u64 = 100;
+            const MIN_LIMIT: u64 = 1;
+            const MAX_LIMIT: u64 = 1000;
+            let offset = query.offset.unwrap_or(DEFAULT_OFFSET);
+            let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+            if !(MIN_LIMIT..=MAX_LIMIT).contains(&limit) {
+                return Result::<_, Infallible>::Ok(with_status(
+                    super::error(
+                        "LIMIT_OUT_OF_BOUNDS",
+                        format!("The pagination limit is [{MIN_LIMIT},{MAX_LIMIT}]."),
+                    ),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+            if let (Some(from), Some(to)) = (query.from, query.to) {
+                if from >= to {
+                    return Ok(with_status(
+                        super::error("InvalidDateRange", "from must be before to"),
+                        StatusCode::BAD_REQUEST,
+                    ));
+                }
+            }
+
+            let filter = TradeFilter {
+                owner: query.owner,
+                token: query.token,
+                app_code: query.app_code.clone(),
+                from: query.from,
+                to: query.to,
+            };
+            let buckets = db
+                .trade_analytics(query.interval, query.group_by, filter, offset, limit)
+                .await;
+            Ok(match buckets {
+                Ok(buckets) => with_status(warp::reply::json(&buckets), StatusCode::OK),
+                Err(err) => {
+                    tracing::error!(?err, ?query, "failed to get trade analytics");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, shared::addr};
+
+    #[tokio::test]
+    async fn parses_query() {
+        let query = warp::test::request()
+            .path("/v1/analytics/trades")
+            .filter(&request())
+            .await
+            .unwrap();
+        assert_eq!(query, Query::default());
+
+        let query = warp::test::request()
+            .path(
+                "/v1/analytics/trades?interval=week&groupBy=appCode&\
+                 owner=0x0000000000000000000000000000000000000001&appCode=CoW%20Swap&\
+                 from=2023-05-01&to=2023-06-01&offset=1&limit=2",
+            )
+            .filter(&request())
+            .await
+            .unwrap();
+        assert_eq!(
+            query,
+            Query {
+                interval: Interval::Week,
+                group_by: Some(TradeGrouping::AppCode),
+                owner: Some(addr!("0000000000000000000000000000000000000001")),
+                token: None,
+                app_code: Some("CoW Swap".to_string()),
+                from: NaiveDate::from_ymd_opt(2023, 5, 1),
+                to: NaiveDate::from_ymd_opt(2023, 6, 1),
+                offset: Some(1),
+                limit: Some(2),
+            }
+        );
+
+        let result = warp::test::request()
+            .path("/v1/analytics/trades?interval=year")
+            .filter(&request())
+            .await;
+        assert!(result.is_err());
+    }
+}
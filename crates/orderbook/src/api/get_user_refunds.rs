@@ -0,0 +1,91 @@
+use {
+    crate::database::Postgres,
+    primitive_types::H160,
+    serde::Deserialize,
+    std::convert::Infallible,
+    warp::{hyper::StatusCode, reply::with_status, Filter, Rejection},
+};
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+struct Query {
+    offset: Option<u64>,
+    limit: Option<u64>,
+}
+
+fn request() -> impl Filter<Extract = (H160, Query), Error = Rejection> + Clone {
+    warp::path!("v1" / "account" / H160 / "refunds")
+        .and(warp::get())
+        .and(warp::query::<Query>())
+}
+
+pub fn get(db: Postgres) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |user: H160, query: Query| {
+        let db = db.clone();
+        async move {
+            const DEFAULT_OFFSET: u64 = 0;
+            const DEFAULT_LIMIT: u64 = 10;
+            const MIN_LIMIT: u64 = 1;
+            const MAX_LIMIT: u64 = 1000;
+            let offset = query.offset.unwrap_or(DEFAULT_OFFSET);
+            let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+            if !(MIN_LIMIT..=MAX_LIMIT).contains(&limit) {
+                return Result::<_, Infallible>::Ok(with_status(
+                    super::error(
+                        "LIMIT_OUT_OF_BOUNDS",
+                        format!("The pagination limit is [{MIN_LIMIT},{MAX_LIMIT}]."),
+                    ),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+            let result = db.user_refunds(&user, offset, limit).await;
+            Ok(match result {
+                Ok(refunds) => with_status(warp::reply::json(&refunds), StatusCode::OK),
+                Err(err) => {
+                    tracing::error!(?err, ?user, "failed to get user refunds");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, shared::addr};
+
+    #[tokio::test]
+    async fn request_() {
+        let path = "/v1/account/0x0000000000000000000000000000000000000001/refunds";
+        let result = warp::test::request()
+            .path(path)
+            .method("GET")
+            .filter(&request())
+            .await
+            .unwrap();
+        assert_eq!(result.0, addr!("0000000000000000000000000000000000000001"));
+        assert_eq!(result.1.offset, None);
+        assert_eq!(result.1.limit, None);
+
+        let path =
+            "/v1/account/0x0000000000000000000000000000000000000001/refunds?offset=1&limit=2";
+        let result = warp::test::request()
+            .path(path)
+            .method("GET")
+            .filter(&request())
+            .await
+            .unwrap();
+        assert_eq!(result.1.offset, Some(1));
+        assert_eq!(result.1.limit, Some(2));
+    }
+
+    #[tokio::test]
+    async fn limit_out_of_bounds() {
+        let filter = get(Postgres::new("postgresql://").unwrap());
+        let response = warp::test::request()
+            .path("/v1/account/0x0000000000000000000000000000000000000001/refunds?limit=0")
+            .method("GET")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,60 @@
+//! This is a private, undocumented api to list the registered webhooks.
+
+use {
+    crate::database::Postgres,
+    reqwest::StatusCode,
+    std::convert::Infallible,
+    warp::{reply::with_status, Filter, Rejection},
+};
+
+fn request() -> impl Filter<Extract = (Option<String>,), Error = Rejection> + Clone {
+    warp::path!("v1" / "admin" / "webhooks")
+        .and(warp::get())
+        .and(warp::header::optional::<String>("Authorization"))
+}
+
+pub fn get(
+    db: Postgres,
+    expected_auth: Option<String>,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |auth| {
+        let db = db.clone();
+        let expected_auth = expected_auth.clone();
+        async move {
+            if expected_auth.is_none() || expected_auth != auth {
+                return Result::<_, Infallible>::Ok(with_status(
+                    super::error("Unauthorized", ""),
+                    StatusCode::UNAUTHORIZED,
+                ));
+            }
+
+            Ok(match db.webhooks().await {
+                Ok(webhooks) => with_status(warp::reply::json(&webhooks), StatusCode::OK),
+                Err(err) => {
+                    tracing::error!(?err, "get_webhooks");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, warp::test::request};
+
+    #[tokio::test]
+    async fn requires_auth() {
+        let filter = get(
+            Postgres::new("postgresql://").unwrap(),
+            Some("auth".to_string()),
+        );
+        let response = request()
+            .path("/v1/admin/webhooks")
+            .method("GET")
+            .header("authorization", "wrong")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
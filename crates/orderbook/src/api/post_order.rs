@@ -255,6 +255,13 @@ impl IntoWarpReply for AddOrderError {
                 error("DuplicatedOrder", "order already exists"),
                 StatusCode::BAD_REQUEST,
             ),
+            Self::BatchRejected => with_status(
+                error(
+                    "BatchRejected",
+                    "order was not added because another order of the batch was rejected",
+                ),
+                StatusCode::BAD_REQUEST,
+            ),
             Self::Database(err) => {
                 tracing::error!(?err, "AddOrderError");
                 shared::api::internal_error_reply()
@@ -0,0 +1,172 @@
+use {
+    crate::orderbook::{AddOrderError, Orderbook},
+    anyhow::Result,
+    model::{
+        order::{OrderCreation, OrderUid},
+        quote::QuoteId,
+    },
+    serde::{Deserialize, Serialize},
+    shared::api::{error, response_body, ApiReply, IntoWarpReply},
+    std::{convert::Infallible, sync::Arc},
+    warp::{hyper::StatusCode, reply::with_status, Filter, Rejection, Reply},
+};
+
+/// The maximum payload size of a single order in a batch.
+const MAX_ORDER_PAYLOAD: u64 = 1024 * 16;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderBatch {
+    pub orders: Vec<OrderCreation>,
+    /// Either add all orders or none of them.
+    #[serde(default)]
+    pub atomic: bool,
+}
+
+/// The result of adding a single order of a batch.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+enum OrderResult {
+    Created {
+        uid: OrderUid,
+        #[serde(rename = "quoteId")]
+        quote_id: Option<QuoteId>,
+    },
+    Rejected {
+        /// The status code the error has when placing the order on its own.
+        status: u16,
+        error: serde_json::Value,
+    },
+}
+
+impl OrderResult {
+    async fn new(result: Result<(OrderUid, Option<QuoteId>), AddOrderError>) -> Self {
+        match result {
+            Ok((uid, quote_id)) => Self::Created { uid, quote_id },
+            Err(err) => {
+                let response = err.into_warp_reply().into_response();
+                let status = response.status().as_u16();
+                let body = response_body(response).await;
+                Self::Rejected {
+                    status,
+                    error: serde_json::from_slice(&body).unwrap_or_default(),
+                }
+            }
+        }
+    }
+}
+
+fn request(max_orders: usize) -> impl Filter<Extract = (OrderBatch,), Error = Rejection> + Clone {
+    warp::path!("v1" / "orders" / "batch")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(
+            MAX_ORDER_PAYLOAD * max_orders as u64,
+        ))
+        .and(warp::body::json())
+}
+
+async fn response(results: Vec<Result<(OrderUid, Option<QuoteId>), AddOrderError>>) -> ApiReply {
+    let mut body = Vec::with_capacity(results.len());
+    for result in results {
+        body.push(OrderResult::new(result).await);
+    }
+    with_status(warp::reply::json(&body), StatusCode::OK)
+}
+
+pub fn filter(
+    orderbook: Arc<Orderbook>,
+    max_orders: usize,
+) -> impl Filter<Extract = (ApiReply,), Error = Rejection> + Clone {
+    request(max_orders).and_then(move |batch: OrderBatch| {
+        let orderbook = orderbook.clone();
+        async move {
+            if batch.orders.len() > max_orders {
+                return Result::<_, Infallible>::Ok(with_status(
+                    error(
+                        "TooManyOrders",
+                        format!("a batch can contain at most {max_orders} orders"),
+                    ),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+
+            let results = orderbook.add_orders(batch.orders, batch.atomic).await;
+            for result in &results {
+                match result {
+                    Ok((order_uid, quote_id)) => {
+                        tracing::debug!(%order_uid, ?quote_id, "order created")
+                    }
+                    Err(err) => tracing::debug!(?err, "error creating order"),
+                }
+            }
+            Result::<_, Infallible>::Ok(response(results).await)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json, warp::test::request};
+
+    #[tokio::test]
+    async fn request_ok() {
+        let filter = super::request(2);
+        let batch = json!({
+            "orders": [OrderCreation::default(), OrderCreation::default()],
+            "atomic": true,
+        });
+        let result = request()
+            .path("/v1/orders/batch")
+            .method("POST")
+            .header("content-type", "application/json")
+            .json(&batch)
+            .filter(&filter)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            OrderBatch {
+                orders: vec![OrderCreation::default(), OrderCreation::default()],
+                atomic: true,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn per_order_results() {
+        let response = response(vec![
+            Ok((OrderUid([1; 56]), Some(42))),
+            Err(AddOrderError::DuplicatedOrder),
+            Err(AddOrderError::BatchRejected),
+        ])
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value =
+            serde_json::from_slice(&response_body(response).await).unwrap();
+        assert_eq!(
+            body,
+            json!([
+                {
+                    "uid": OrderUid([1; 56]),
+                    "quoteId": 42,
+                },
+                {
+                    "status": 400,
+                    "error": {
+                        "errorType": "DuplicatedOrder",
+                        "description": "order already exists",
+                    },
+                },
+                {
+                    "status": 400,
+                    "error": {
+                        "errorType": "BatchRejected",
+                        "description": "order was not added because another order of the \
+                                        batch was rejected",
+                    },
+                },
+            ])
+        );
+    }
+}
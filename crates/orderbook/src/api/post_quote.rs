@@ -3,6 +3,7 @@ use {
     anyhow::Result,
     model::quote::OrderQuoteRequest,
     reqwest::StatusCode,
+    serde::Deserialize,
     shared::{
         api::{self, convert_json_response, rich_error, ApiReply, IntoWarpReply},
         order_quoting::{CalculateQuoteError, OrderQuoteError, QuoteHandler},
@@ -11,22 +12,33 @@ use {
     warp::{Filter, Rejection},
 };
 
-fn post_quote_request() -> impl Filter<Extract = (OrderQuoteRequest,), Error = Rejection> + Clone {
+#[derive(Debug, Default, Deserialize, Eq, PartialEq)]
+struct Query {
+    /// Include details about how the quote was computed in the response.
+    #[serde(default)]
+    verbose: bool,
+}
+
+fn post_quote_request(
+) -> impl Filter<Extract = (Query, OrderQuoteRequest), Error = Rejection> + Clone {
     warp::path!("v1" / "quote")
         .and(warp::post())
+        .and(warp::query::<Query>())
         .and(api::extract_payload())
 }
 
 pub fn post_quote(
     quotes: Arc<QuoteHandler>,
 ) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
-    post_quote_request().and_then(move |request: OrderQuoteRequest| {
+    post_quote_request().and_then(move |query: Query, request: OrderQuoteRequest| {
         let quotes = quotes.clone();
         async move {
-            let result = quotes
-                .calculate_quote(&request)
-                .await
-                .map_err(OrderQuoteErrorWrapper);
+            let result = if query.verbose {
+                quotes.calculate_verbose_quote(&request).await
+            } else {
+                quotes.calculate_quote(&request).await
+            }
+            .map_err(OrderQuoteErrorWrapper);
             if let Err(err) = &result {
                 tracing::warn!(?err, ?request, "post_quote error");
             }
@@ -244,7 +256,20 @@ mod tests {
             .header("content-type", "application/json")
             .json(&request_payload);
         let result = request.filter(&filter).await.unwrap();
-        assert_eq!(result, request_payload);
+        assert_eq!(result, (Query::default(), request_payload));
+    }
+
+    #[tokio::test]
+    async fn post_quote_request_verbose() {
+        let filter = post_quote_request();
+        let request_payload = OrderQuoteRequest::default();
+        let request = request()
+            .path("/v1/quote?verbose=true")
+            .method("POST")
+            .header("content-type", "application/json")
+            .json(&request_payload);
+        let result = request.filter(&filter).await.unwrap();
+        assert_eq!(result, (Query { verbose: true }, request_payload));
     }
 
     #[tokio::test]
@@ -282,6 +307,7 @@ mod tests {
             from: H160::zero(),
             expiration: Utc.timestamp_millis_opt(0).unwrap(),
             id: Some(0),
+            details: None,
         };
         let response = convert_json_response::<OrderQuoteResponse, OrderQuoteErrorWrapper>(Ok(
             order_quote_response.clone(),
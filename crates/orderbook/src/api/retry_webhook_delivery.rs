@@ -0,0 +1,61 @@
+//! This is a private, undocumented api to move a dead webhook delivery back
+//! into the queue.
+
+use {
+    crate::database::Postgres,
+    reqwest::StatusCode,
+    std::convert::Infallible,
+    warp::{reply::with_status, Filter, Rejection},
+};
+
+fn request() -> impl Filter<Extract = (i64, Option<String>), Error = Rejection> + Clone {
+    warp::path!("v1" / "admin" / "webhooks" / "deliveries" / i64 / "retry")
+        .and(warp::post())
+        .and(warp::header::optional::<String>("Authorization"))
+}
+
+pub fn post(
+    db: Postgres,
+    expected_auth: Option<String>,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |id: i64, auth| {
+        let db = db.clone();
+        let expected_auth = expected_auth.clone();
+        async move {
+            if expected_auth.is_none() || expected_auth != auth {
+                return Result::<_, Infallible>::Ok(with_status(
+                    super::error("Unauthorized", ""),
+                    StatusCode::UNAUTHORIZED,
+                ));
+            }
+
+            Ok(match db.retry_webhook_delivery(id).await {
+                Ok(true) => with_status(warp::reply::json(&()), StatusCode::OK),
+                Ok(false) => with_status(
+                    super::error("NotFound", "no dead delivery with this id"),
+                    StatusCode::NOT_FOUND,
+                ),
+                Err(err) => {
+                    tracing::error!(?err, id, "retry_webhook_delivery");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, warp::test::request};
+
+    #[tokio::test]
+    async fn request_() {
+        let result = request()
+            .path("/v1/admin/webhooks/deliveries/5/retry")
+            .method("POST")
+            .filter(&super::request())
+            .await
+            .unwrap();
+        assert_eq!(result, (5, None));
+    }
+}
@@ -1,7 +1,8 @@
 use {
-    primitive_types::H160,
+    primitive_types::{H160, U256},
     reqwest::Url,
     shared::{
+        address_list::AddressListSource,
         arguments::{display_option, display_secret_option},
         bad_token::token_owner_finder,
         http_client,
@@ -80,6 +81,22 @@ pub struct Arguments {
     )]
     pub token_quality_cache_expiry: Duration,
 
+    /// The amount of time in seconds a classification of a token into good or
+    /// bad is stored in the database for. Stored classifications are shared
+    /// between services and survive restarts.
+    #[clap(
+        long,
+        env,
+        default_value = "86400",
+        value_parser = shared::arguments::duration_from_seconds,
+    )]
+    pub token_quality_db_expiry: Duration,
+
+    /// Support tokens that take a fee on transfer instead of treating them as
+    /// bad tokens. Their transfer fee gets communicated to the solvers.
+    #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub allow_taxed_tokens: bool,
+
     /// List of token addresses to be ignored throughout service
     #[clap(long, env, use_value_delimiter = true)]
     pub unsupported_tokens: Vec<H160>,
@@ -88,6 +105,27 @@ pub struct Arguments {
     #[clap(long, env, use_value_delimiter = true)]
     pub banned_users: Vec<H160>,
 
+    /// Where to periodically load additional unsupported tokens from. Either
+    /// `database` (the `deny_lists` table), a path to a local file or an
+    /// http(s) URL serving a JSON array of token addresses.
+    #[clap(long, env)]
+    pub unsupported_tokens_source: Option<AddressListSource>,
+
+    /// Where to periodically load additional banned users from. Accepts the
+    /// same values as `unsupported_tokens_source`.
+    #[clap(long, env)]
+    pub banned_users_source: Option<AddressListSource>,
+
+    /// How often in seconds the unsupported tokens and banned users get
+    /// reloaded from their sources.
+    #[clap(
+        long,
+        env,
+        default_value = "60",
+        value_parser = shared::arguments::duration_from_seconds,
+    )]
+    pub deny_list_update_interval: Duration,
+
     /// Which estimators to use to estimate token prices in terms of the chain's
     /// native token.
     #[clap(long, env, default_value_t)]
@@ -145,6 +183,12 @@ pub struct Arguments {
     #[clap(long, env, default_value = "10")]
     pub max_limit_orders_per_user: u64,
 
+    /// Max number of orders that can be placed with a single batch request.
+    /// This is independent of `max_limit_orders_per_user` which still applies
+    /// to the orders of a batch.
+    #[clap(long, env, default_value = "100")]
+    pub max_orders_per_batch: usize,
+
     /// Enable buy ETH orders paying to smart contract wallets.
     #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
     pub enable_eth_smart_contract_payments: bool,
@@ -171,6 +215,41 @@ pub struct Arguments {
     /// Set the maximum size in bytes of order app data.
     #[clap(long, env, default_value = "8192")]
     pub app_data_size_limit: usize,
+
+    /// The maximum reward a solver receives for settling a single auction.
+    /// Defaults to 0.01 ETH.
+    #[clap(long, env, default_value = "0.01", value_parser = shared::arguments::wei_from_ether)]
+    pub solver_rewards_upper_cap: U256,
+
+    /// The maximum penalty a solver receives for a single auction. Defaults to
+    /// 0.01 ETH.
+    #[clap(long, env, default_value = "0.01", value_parser = shared::arguments::wei_from_ether)]
+    pub solver_rewards_lower_cap: U256,
+
+    /// How often the accuracy of the quotes of every price estimator gets
+    /// recomputed.
+    #[clap(long, env, default_value = "300", value_parser = shared::arguments::duration_from_seconds)]
+    pub quote_accuracy_update_interval: Duration,
+
+    /// Only orders created within this period contribute to the quote
+    /// accuracy. Defaults to one week.
+    #[clap(long, env, default_value = "604800", value_parser = shared::arguments::duration_from_seconds)]
+    pub quote_accuracy_window: Duration,
+
+    /// Derive the weights the price estimators apply to the estimates of
+    /// every estimator from its quote accuracy.
+    #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub quote_accuracy_weights: bool,
+
+    /// The number of executed orders an estimator needs to have quoted before
+    /// its weight gets derived from its accuracy.
+    #[clap(long, env, default_value = "100")]
+    pub quote_accuracy_min_samples: u64,
+
+    /// Value of the authorization header for the private webhook endpoints.
+    /// The endpoints are disabled if this is not set.
+    #[clap(long, env)]
+    pub webhook_admin_auth: Option<String>,
 }
 
 impl std::fmt::Display for Arguments {
@@ -203,8 +282,25 @@ impl std::fmt::Display for Arguments {
             "token_quality_cache_expiry: {:?}",
             self.token_quality_cache_expiry
         )?;
+        writeln!(
+            f,
+            "token_quality_db_expiry: {:?}",
+            self.token_quality_db_expiry
+        )?;
+        writeln!(f, "allow_taxed_tokens: {}", self.allow_taxed_tokens)?;
         writeln!(f, "unsupported_tokens: {:?}", self.unsupported_tokens)?;
         writeln!(f, "banned_users: {:?}", self.banned_users)?;
+        writeln!(
+            f,
+            "unsupported_tokens_source: {:?}",
+            self.unsupported_tokens_source
+        )?;
+        writeln!(f, "banned_users_source: {:?}", self.banned_users_source)?;
+        writeln!(
+            f,
+            "deny_list_update_interval: {:?}",
+            self.deny_list_update_interval
+        )?;
         writeln!(f, "allowed_tokens: {:?}", self.allowed_tokens)?;
         writeln!(f, "pool_cache_lru_size: {}", self.pool_cache_lru_size)?;
         writeln!(f, "enable_eip1271_orders: {}", self.enable_eip1271_orders)?;
@@ -244,6 +340,7 @@ impl std::fmt::Display for Arguments {
             "max_limit_orders_per_user: {}",
             self.max_limit_orders_per_user
         )?;
+        writeln!(f, "max_orders_per_batch: {}", self.max_orders_per_batch)?;
         writeln!(
             f,
             "enable_custom_interactions: {:?}",
@@ -257,7 +354,30 @@ impl std::fmt::Display for Arguments {
             &self.hooks_contract_address.map(|a| format!("{a:?}")),
         )?;
         writeln!(f, "app_data_size_limit: {}", self.app_data_size_limit)?;
+        writeln!(
+            f,
+            "solver_rewards_upper_cap: {}",
+            self.solver_rewards_upper_cap
+        )?;
+        writeln!(
+            f,
+            "solver_rewards_lower_cap: {}",
+            self.solver_rewards_lower_cap
+        )?;
+        writeln!(
+            f,
+            "quote_accuracy_update_interval: {:?}",
+            self.quote_accuracy_update_interval
+        )?;
+        writeln!(f, "quote_accuracy_window: {:?}", self.quote_accuracy_window)?;
+        writeln!(f, "quote_accuracy_weights: {}", self.quote_accuracy_weights)?;
+        writeln!(
+            f,
+            "quote_accuracy_min_samples: {}",
+            self.quote_accuracy_min_samples
+        )?;
 
+        display_secret_option(f, "webhook_admin_auth", &self.webhook_admin_auth)?;
         Ok(())
     }
 }
@@ -1,10 +1,17 @@
+pub mod analytics;
 pub mod app_data;
 pub mod auctions;
+pub mod deny_lists;
 pub mod orders;
+pub mod quote_accuracy;
 pub mod quotes;
+pub mod refunds;
 pub mod solver_competition;
+pub mod solver_rewards;
+pub mod token_quality;
 pub mod total_surplus;
 pub mod trades;
+pub mod webhooks;
 
 use {anyhow::Result, sqlx::PgPool};
 
@@ -0,0 +1,146 @@
+use {
+    anyhow::{Context, Result},
+    bigdecimal::BigDecimal,
+    chrono::NaiveDate,
+    database::{analytics, byte_array::ByteArray},
+    model::analytics::{Interval, SettlementBucket, TradeBucket, TradeGrouping},
+    number::conversions::big_decimal_to_u256,
+    primitive_types::{H160, U256},
+};
+
+/// Restricts which trades are summed up. Unset fields match all trades.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TradeFilter {
+    pub owner: Option<H160>,
+    /// Matches trades that sell or buy the token.
+    pub token: Option<H160>,
+    pub app_code: Option<String>,
+    /// First day (inclusive).
+    pub from: Option<NaiveDate>,
+    /// Last day (exclusive).
+    pub to: Option<NaiveDate>,
+}
+
+/// Restricts which settlements are summed up. Unset fields match all
+/// settlements.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SettlementFilter {
+    pub solver: Option<H160>,
+    /// First day (inclusive).
+    pub from: Option<NaiveDate>,
+    /// Last day (exclusive).
+    pub to: Option<NaiveDate>,
+}
+
+impl super::Postgres {
+    /// Sums up the trades per period, latest period first.
+    pub async fn trade_analytics(
+        &self,
+        interval: Interval,
+        grouping: Option<TradeGrouping>,
+        filter: TradeFilter,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<TradeBucket>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["trade_analytics"])
+            .start_timer();
+
+        let filter = analytics::TradeFilter {
+            owner: filter.owner.map(|owner| ByteArray(owner.0)),
+            token: filter.token.map(|token| ByteArray(token.0)),
+            app_code: filter.app_code,
+            from: filter.from,
+            to: filter.to,
+        };
+        let mut ex = self.pool.acquire().await?;
+        let buckets = analytics::trade_buckets(
+            &mut ex,
+            interval_(interval),
+            grouping.map(grouping_),
+            &filter,
+            offset.try_into().context("convert offset")?,
+            limit.try_into().context("convert limit")?,
+        )
+        .await?;
+        buckets
+            .into_iter()
+            .map(|bucket| {
+                Ok(TradeBucket {
+                    period: bucket.period,
+                    key: bucket.key,
+                    trades: bucket.trades.try_into().context("convert trades")?,
+                    volume: u256(&bucket.volume)?,
+                    surplus: u256(&bucket.surplus)?,
+                    fee: u256(&bucket.fee)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Sums up the settlements per period, latest period first.
+    pub async fn settlement_analytics(
+        &self,
+        interval: Interval,
+        filter: SettlementFilter,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<SettlementBucket>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["settlement_analytics"])
+            .start_timer();
+
+        let filter = analytics::SettlementFilter {
+            solver: filter.solver.map(|solver| ByteArray(solver.0)),
+            from: filter.from,
+            to: filter.to,
+        };
+        let mut ex = self.pool.acquire().await?;
+        let buckets = analytics::settlement_buckets(
+            &mut ex,
+            interval_(interval),
+            &filter,
+            offset.try_into().context("convert offset")?,
+            limit.try_into().context("convert limit")?,
+        )
+        .await?;
+        buckets
+            .into_iter()
+            .map(|bucket| {
+                Ok(SettlementBucket {
+                    period: bucket.period,
+                    settlements: bucket
+                        .settlements
+                        .try_into()
+                        .context("convert settlements")?,
+                    gas_used: u256(&bucket.gas_used)?,
+                    gas_cost: u256(&bucket.gas_cost)?,
+                    surplus: u256(&bucket.surplus)?,
+                    fee: u256(&bucket.fee)?,
+                })
+            })
+            .collect()
+    }
+}
+
+fn interval_(interval: Interval) -> analytics::Interval {
+    match interval {
+        Interval::Day => analytics::Interval::Day,
+        Interval::Week => analytics::Interval::Week,
+        Interval::Month => analytics::Interval::Month,
+    }
+}
+
+fn grouping_(grouping: TradeGrouping) -> analytics::TradeGrouping {
+    match grouping {
+        TradeGrouping::SellToken => analytics::TradeGrouping::SellToken,
+        TradeGrouping::BuyToken => analytics::TradeGrouping::BuyToken,
+        TradeGrouping::AppCode => analytics::TradeGrouping::AppCode,
+    }
+}
+
+fn u256(value: &BigDecimal) -> Result<U256> {
+    big_decimal_to_u256(value).context("convert to U256")
+}
@@ -1,6 +1,6 @@
 use {
     anyhow::Result,
-    model::auction::{Auction, AuctionWithId},
+    model::auction::{Auction, AuctionId, AuctionWithId},
 };
 
 impl super::Postgres {
@@ -19,4 +19,14 @@ impl super::Postgres {
         let auction = AuctionWithId { id, auction };
         Ok(Some(auction))
     }
+
+    pub async fn most_recent_auction_id(&self) -> Result<Option<AuctionId>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["load_most_recent_auction_id"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        Ok(database::auction::load_most_recent_id(&mut ex).await?)
+    }
 }
@@ -2,17 +2,19 @@ use {
     super::Postgres,
     anyhow::{Context as _, Result},
     async_trait::async_trait,
-    chrono::{DateTime, Utc},
+    chrono::{DateTime, TimeZone, Utc},
     database::{
         byte_array::ByteArray,
         order_events::{insert_order_event, OrderEvent, OrderEventLabel},
         orders::{FullOrder, OrderKind as DbOrderKind},
+        owner_order_cancellations::OwnerOrderCancellation,
     },
     ethcontract::H256,
     futures::{stream::TryStreamExt, FutureExt, StreamExt},
     model::{
         app_data::AppDataHash,
         order::{
+            AllOrdersCancellation,
             EthflowData,
             Interactions,
             OnchainOrderData,
@@ -39,6 +41,7 @@ use {
             order_class_into,
             order_kind_from,
             order_kind_into,
+            refund_status_from,
             sell_token_source_from,
             sell_token_source_into,
             signing_scheme_from,
@@ -56,8 +59,23 @@ use {
 pub trait OrderStoring: Send + Sync {
     async fn insert_order(&self, order: &Order, quote: Option<Quote>)
         -> Result<(), InsertionError>;
+    /// Inserts all orders in a single transaction. If inserting any of them
+    /// fails, none of the orders are stored and the index of the failing order
+    /// is returned with the error.
+    async fn insert_orders(
+        &self,
+        orders: &[(Order, Option<Quote>)],
+    ) -> Result<(), (usize, InsertionError)>;
     async fn cancel_orders(&self, order_uids: Vec<OrderUid>, now: DateTime<Utc>) -> Result<()>;
     async fn cancel_order(&self, order_uid: &OrderUid, now: DateTime<Utc>) -> Result<()>;
+    /// Stores the cancellation of all orders of the owner and cancels the
+    /// matching orders. Returns the UIDs of the orders that got cancelled.
+    async fn cancel_all_orders(
+        &self,
+        owner: H160,
+        cancellation: &AllOrdersCancellation,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<OrderUid>>;
     async fn replace_order(
         &self,
         old_order: &OrderUid,
@@ -194,6 +212,30 @@ async fn insert_order(order: &Order, ex: &mut PgConnection) -> Result<(), Insert
     Ok(())
 }
 
+/// Inserts the order together with its quote and full app data.
+async fn insert_full_order(
+    order: &Order,
+    quote: Option<&Quote>,
+    ex: &mut PgConnection,
+) -> Result<(), InsertionError> {
+    insert_order(order, ex).await?;
+    if let Some(quote) = quote {
+        insert_quote(&order.metadata.uid, quote, ex).await?;
+    }
+    if let Some(full_app_data) = &order.metadata.full_app_data {
+        let contract_app_data = &ByteArray(order.data.app_data.0);
+        let full_app_data = full_app_data.as_bytes();
+        if let Some(existing) =
+            database::app_data::insert(ex, contract_app_data, full_app_data).await?
+        {
+            if full_app_data != existing {
+                return Err(InsertionError::AppDataMismatch(existing));
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn insert_quote(
     uid: &OrderUid,
     quote: &Quote,
@@ -207,6 +249,16 @@ async fn insert_quote(
         sell_amount: u256_to_big_decimal(&quote.sell_amount),
         buy_amount: u256_to_big_decimal(&quote.buy_amount),
         solver: ByteArray(quote.data.solver.0),
+        estimator: quote
+            .data
+            .estimator
+            .as_ref()
+            .map(|estimator| estimator.name.clone()),
+        estimator_latency_ms: quote
+            .data
+            .estimator
+            .as_ref()
+            .map(|estimator| estimator.latency.as_millis() as i64),
     };
     database::orders::insert_quote(ex, &quote)
         .await
@@ -226,27 +278,37 @@ impl OrderStoring for Postgres {
             .with_label_values(&["insert_order"])
             .start_timer();
 
-        let order = order.clone();
         let mut connection = self.pool.acquire().await?;
         let mut ex = connection.begin().await?;
+        insert_full_order(order, quote.as_ref(), &mut ex).await?;
+        ex.commit().await?;
+        Ok(())
+    }
 
-        insert_order(&order, &mut ex).await?;
-        if let Some(quote) = quote {
-            insert_quote(&order.metadata.uid, &quote, &mut ex).await?;
-        }
-        if let Some(full_app_data) = order.metadata.full_app_data {
-            let contract_app_data = &ByteArray(order.data.app_data.0);
-            let full_app_data = full_app_data.as_bytes();
-            if let Some(existing) =
-                database::app_data::insert(&mut ex, contract_app_data, full_app_data).await?
-            {
-                if full_app_data != existing {
-                    return Err(InsertionError::AppDataMismatch(existing));
-                }
-            }
-        }
+    async fn insert_orders(
+        &self,
+        orders: &[(Order, Option<Quote>)],
+    ) -> Result<(), (usize, InsertionError)> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["insert_orders"])
+            .start_timer();
 
-        ex.commit().await?;
+        // Errors that are not caused by a specific order are attributed to the
+        // first one.
+        let mut ex = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| (0, InsertionError::DbError(err)))?;
+        for (i, (order, quote)) in orders.iter().enumerate() {
+            insert_full_order(order, quote.as_ref(), &mut ex)
+                .await
+                .map_err(|err| (i, err))?;
+        }
+        ex.commit()
+            .await
+            .map_err(|err| (0, InsertionError::DbError(err)))?;
         Ok(())
     }
 
@@ -277,6 +339,48 @@ impl OrderStoring for Postgres {
         ex.commit().await.context("commit cancel single order")
     }
 
+    async fn cancel_all_orders(
+        &self,
+        owner: H160,
+        cancellation: &AllOrdersCancellation,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<OrderUid>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["cancel_all_orders"])
+            .start_timer();
+
+        let cancellation = OwnerOrderCancellation {
+            owner: ByteArray(owner.0),
+            cancelled_before: Utc
+                .timestamp_opt(cancellation.cancelled_before.into(), 0)
+                .unwrap(),
+            sell_token: cancellation.sell_token.map(|token| ByteArray(token.0)),
+            buy_token: cancellation.buy_token.map(|token| ByteArray(token.0)),
+        };
+        let mut ex = self.pool.begin().await?;
+        database::owner_order_cancellations::upsert(&mut ex, &cancellation, now).await?;
+        let cancelled =
+            database::owner_order_cancellations::cancel_orders(&mut ex, &cancellation, now).await?;
+        for order_uid in &cancelled {
+            insert_order_event(
+                &mut ex,
+                &OrderEvent {
+                    order_uid: *order_uid,
+                    timestamp: now,
+                    label: OrderEventLabel::Cancelled,
+                },
+            )
+            .await?;
+        }
+        ex.commit().await.context("commit cancel all orders")?;
+
+        Ok(cancelled
+            .into_iter()
+            .map(|order_uid| OrderUid(order_uid.0))
+            .collect())
+    }
+
     async fn replace_order(
         &self,
         old_order: &model::order::OrderUid,
@@ -418,6 +522,7 @@ fn full_order_into_model_order(order: FullOrder) -> Result<Order> {
         Some(EthflowData {
             user_valid_to,
             refund_tx_hash: refund_tx.map(|hash| H256(hash.0)),
+            refund_status: refund_status_from(refund_tx.is_some(), order.refund_event),
         })
     } else {
         None
@@ -566,6 +671,7 @@ mod tests {
             pre_interactions: Vec::new(),
             post_interactions: Vec::new(),
             ethflow_data: None,
+            refund_event: None,
             onchain_user: None,
             onchain_placement_error: None,
             executed_surplus_fee: Default::default(),
@@ -0,0 +1,42 @@
+use {
+    crate::quote_accuracy::ExecutedQuote,
+    anyhow::{Context, Result},
+    chrono::{DateTime, Utc},
+    database::quote_accuracy,
+    number::conversions::big_decimal_to_u256,
+    primitive_types::H160,
+    std::time::Duration,
+};
+
+impl super::Postgres {
+    pub async fn executed_quotes(&self, since: DateTime<Utc>) -> Result<Vec<ExecutedQuote>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["executed_quotes"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let quotes = quote_accuracy::executed_quotes(&mut ex, since).await?;
+        quotes
+            .into_iter()
+            .map(|quote| {
+                Ok(ExecutedQuote {
+                    estimator: quote.estimator,
+                    latency: quote
+                        .estimator_latency_ms
+                        .map(|ms| Duration::from_millis(ms.max(0) as u64)),
+                    sell_token: H160(quote.sell_token.0),
+                    buy_token: H160(quote.buy_token.0),
+                    quoted_sell_amount: big_decimal_to_u256(&quote.quoted_sell_amount)
+                        .context("quoted sell amount is not a valid U256")?,
+                    quoted_buy_amount: big_decimal_to_u256(&quote.quoted_buy_amount)
+                        .context("quoted buy amount is not a valid U256")?,
+                    executed_sell_amount: big_decimal_to_u256(&quote.executed_sell_amount)
+                        .context("executed sell amount is not a valid U256")?,
+                    executed_buy_amount: big_decimal_to_u256(&quote.executed_buy_amount)
+                        .context("executed buy amount is not a valid U256")?,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,45 @@
+use {
+    anyhow::{Context, Result},
+    database::{byte_array::ByteArray, ethflow_orders},
+    model::order::{EthflowRefund, OrderUid},
+    primitive_types::{H160, H256},
+    shared::db_order_conversions::refund_status_from,
+};
+
+impl super::Postgres {
+    pub async fn user_refunds(
+        &self,
+        user: &H160,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<EthflowRefund>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["user_refunds"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let refunds = ethflow_orders::user_refunds(
+            &mut ex,
+            &ByteArray(user.0),
+            offset.try_into().context("offset overflow")?,
+            limit.try_into().context("limit overflow")?,
+        )
+        .await?;
+        refunds
+            .into_iter()
+            .map(|refund| {
+                Ok(EthflowRefund {
+                    order_uid: OrderUid(refund.uid.0),
+                    ethflow_contract: H160(refund.owner.0),
+                    creation_date: refund.creation_timestamp,
+                    user_valid_to: refund.valid_to,
+                    status: refund_status_from(refund.tx_hash.is_some(), refund.event)
+                        .context("refund without refund status")?,
+                    updated_at: refund.event_timestamp,
+                    tx_hash: refund.tx_hash.map(|hash| H256(hash.0)),
+                })
+            })
+            .collect()
+    }
+}
@@ -69,6 +69,8 @@ impl SolverCompetitionStoring for Postgres {
                     .competition_simulation_block
                     .try_into()
                     .context("convert simulation block")?,
+                // Solvers without a driver submit their settlements themselves.
+                submission_address: ByteArray(request.scores.winner.0),
             },
         )
         .await
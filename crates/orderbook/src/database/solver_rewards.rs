@@ -0,0 +1,110 @@
+use {
+    crate::solver_rewards::{self, Auction, Config, Observation, Settlement},
+    anyhow::{Context, Result},
+    bigdecimal::BigDecimal,
+    database::solver_rewards::AuctionOutcome,
+    model::solver_rewards::Report,
+    number::conversions::big_decimal_to_u256,
+    primitive_types::{H160, H256, U256},
+    shared::event_handling::{Finality, MAX_REORG_BLOCK_COUNT},
+    std::ops::{DerefMut, Range},
+};
+
+impl super::Postgres {
+    /// Computes the solver rewards of all auctions whose block deadline lies
+    /// in the specified block range.
+    pub async fn solver_rewards(&self, period: Range<u64>, config: &Config) -> Result<Report> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["solver_rewards"])
+            .start_timer();
+
+        let block_deadlines = i64::try_from(period.start).context("convert from block")?
+            ..i64::try_from(period.end).context("convert to block")?;
+        let mut ex = self.pool.begin().await?;
+        // Both queries need to see the same database snapshot for the report to
+        // be consistent.
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .execute(ex.deref_mut())
+            .await?;
+        let outcomes =
+            database::solver_rewards::fetch_auction_outcomes(&mut ex, block_deadlines.clone())
+                .await
+                .context("fetch_auction_outcomes")?;
+        let participation = database::solver_rewards::fetch_participation(&mut ex, block_deadlines)
+            .await
+            .context("fetch_participation")?;
+        let last_block = database::events::last_block(&mut ex)
+            .await
+            .context("last_block")?;
+        ex.commit().await?;
+        // Indexed events can still get reorged out until they are final.
+        let last_block = u64::try_from(last_block).context("convert last block")?;
+        let final_block = match config.finality {
+            Finality::ReorgDepth => last_block.saturating_sub(MAX_REORG_BLOCK_COUNT),
+            Finality::Finalized => config
+                .finality
+                .last_final_block(&config.web3, last_block)
+                .await
+                .context("last final block")?
+                .min(last_block),
+        };
+
+        let auctions = outcomes
+            .into_iter()
+            .map(auction)
+            .collect::<Result<Vec<_>>>()?;
+        let participation = participation
+            .into_iter()
+            .map(|row| {
+                Ok((
+                    H160(row.participant.0),
+                    u64::try_from(row.auctions).context("convert auction count")?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(solver_rewards::report(
+            period,
+            final_block,
+            config.caps,
+            auctions,
+            participation,
+        ))
+    }
+}
+
+fn auction(row: AuctionOutcome) -> Result<Auction> {
+    let settlement = match (row.tx_hash, row.settlement_block) {
+        (Some(tx_hash), Some(block)) => Some(Settlement {
+            tx_hash: H256(tx_hash.0),
+            block: block.try_into().context("convert settlement block")?,
+            observation: match (row.surplus, row.fee, row.gas_used, row.effective_gas_price) {
+                (Some(surplus), Some(fee), Some(gas_used), Some(effective_gas_price)) => {
+                    Some(Observation {
+                        surplus: u256(&surplus)?,
+                        fee: u256(&fee)?,
+                        gas_used: u256(&gas_used)?,
+                        effective_gas_price: u256(&effective_gas_price)?,
+                    })
+                }
+                _ => None,
+            },
+        }),
+        _ => None,
+    };
+    Ok(Auction {
+        id: row.auction_id,
+        winner: H160(row.winner.0),
+        winning_score: u256(&row.winning_score)?,
+        reference_score: u256(&row.reference_score)?,
+        block_deadline: row
+            .block_deadline
+            .try_into()
+            .context("convert block deadline")?,
+        settlement,
+    })
+}
+
+fn u256(value: &BigDecimal) -> Result<U256> {
+    big_decimal_to_u256(value).context("convert to U256")
+}
@@ -0,0 +1,113 @@
+use {
+    anyhow::{Context, Result},
+    chrono::Utc,
+    database::{byte_array::ByteArray, webhooks},
+    model::{
+        order::OrderUid,
+        webhook::{DeadDelivery, Webhook, WebhookEvent, WebhookRegistration},
+    },
+    primitive_types::H160,
+};
+
+impl super::Postgres {
+    pub async fn insert_webhook(&self, registration: &WebhookRegistration) -> Result<i64> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["insert_webhook"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let webhook = webhooks::Webhook {
+            id: Default::default(),
+            url: registration.url.clone(),
+            owner: registration.owner.map(|owner| ByteArray(owner.0)),
+            app_code: registration.app_code.clone(),
+            created_at: Utc::now(),
+        };
+        Ok(webhooks::insert_webhook(&mut ex, &webhook).await?)
+    }
+
+    pub async fn webhooks(&self) -> Result<Vec<Webhook>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["webhooks"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let webhooks = webhooks::webhooks(&mut ex).await?;
+        Ok(webhooks
+            .into_iter()
+            .map(|webhook| Webhook {
+                id: webhook.id,
+                url: webhook.url,
+                owner: webhook.owner.map(|owner| H160(owner.0)),
+                app_code: webhook.app_code,
+                created_at: webhook.created_at,
+            })
+            .collect())
+    }
+
+    pub async fn delete_webhook(&self, id: i64) -> Result<bool> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["delete_webhook"])
+            .start_timer();
+
+        let mut ex = self.pool.begin().await?;
+        let deleted = webhooks::delete_webhook(&mut ex, id).await?;
+        ex.commit().await?;
+        Ok(deleted)
+    }
+
+    pub async fn dead_webhook_deliveries(
+        &self,
+        webhook_id: Option<i64>,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<DeadDelivery>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["dead_webhook_deliveries"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let deliveries = webhooks::dead_deliveries(
+            &mut ex,
+            webhook_id,
+            offset.try_into().context("offset overflow")?,
+            limit.try_into().context("limit overflow")?,
+        )
+        .await?;
+        deliveries
+            .into_iter()
+            .map(|delivery| {
+                Ok(DeadDelivery {
+                    id: delivery.id,
+                    webhook_id: delivery.webhook_id,
+                    event: match delivery.event {
+                        webhooks::WebhookEvent::Traded => WebhookEvent::Traded,
+                        webhooks::WebhookEvent::PartiallyFilled => WebhookEvent::PartiallyFilled,
+                        webhooks::WebhookEvent::Expired => WebhookEvent::Expired,
+                        webhooks::WebhookEvent::Filtered => WebhookEvent::Filtered,
+                    },
+                    order_uid: OrderUid(delivery.order_uid.0),
+                    event_timestamp: delivery.event_timestamp,
+                    attempts: delivery.attempts.try_into().context("negative attempts")?,
+                    // Dead deliveries keep the time their last attempt was due.
+                    last_attempt: delivery.next_attempt,
+                    last_error: delivery.last_error,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn retry_webhook_delivery(&self, id: i64) -> Result<bool> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["retry_webhook_delivery"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        Ok(webhooks::retry_delivery(&mut ex, id, Utc::now()).await?)
+    }
+}
@@ -4,8 +4,11 @@ pub mod arguments;
 pub mod database;
 mod ipfs;
 mod ipfs_app_data;
+pub mod market_depth;
 pub mod orderbook;
+pub mod quote_accuracy;
 pub mod run;
 pub mod solver_competition;
+pub mod solver_rewards;
 
 pub use self::run::{run, start};
@@ -0,0 +1,331 @@
+//! Order books of single markets built from the open limit orders of the most
+//! recent auction.
+//!
+//! The auction only contains orders that are solvable: they are valid, not
+//! completely filled and their owners have sufficient balance and allowance.
+//! The remaining amounts of all these orders get computed once per auction and
+//! are then grouped into price levels for every request.
+//!
+//! The orderbook reads the auction the autopilot stored in the database rather
+//! than using the `SolvableOrdersCache`, which lives in the autopilot. This
+//! way the depth matches the auction the solvers were given and the orderbook
+//! doesn't need to fetch balances itself.
+
+use {
+    crate::database::Postgres,
+    anyhow::Result,
+    model::{
+        auction::{AuctionId, AuctionWithId},
+        market::{OrderbookDepth, PriceLevel},
+    },
+    num::BigRational,
+    number::conversions::u256_to_big_rational,
+    primitive_types::{H160, U256},
+    shared::remaining_amounts,
+    std::{
+        collections::{BTreeMap, HashMap},
+        sync::Arc,
+        time::{Duration, Instant},
+    },
+    tokio::sync::Mutex,
+};
+
+/// How long a book gets served before checking whether there is a newer
+/// auction.
+const MAX_BOOK_AGE: Duration = Duration::from_secs(1);
+
+pub struct MarketDepth {
+    database: Postgres,
+    book: Mutex<Option<CachedBook>>,
+}
+
+struct CachedBook {
+    book: Arc<Book>,
+    /// When the auction of the book was last checked to be the most recent.
+    checked: Instant,
+}
+
+impl MarketDepth {
+    pub fn new(database: Postgres) -> Self {
+        Self {
+            database,
+            book: Default::default(),
+        }
+    }
+
+    /// Returns the order book of the market selling `sell_token` for
+    /// `buy_token` with at most `limit` levels per side. Prices get grouped
+    /// into multiples of `tick` if specified.
+    ///
+    /// Returns `None` if there is no auction yet.
+    pub async fn orderbook(
+        &self,
+        sell_token: H160,
+        buy_token: H160,
+        tick: Option<&BigRational>,
+        limit: usize,
+    ) -> Result<Option<OrderbookDepth>> {
+        let Some(book) = self.book().await? else {
+            return Ok(None);
+        };
+        let offers = |sell_token: H160, buy_token: H160| {
+            book.offers
+                .get(&(sell_token, buy_token))
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+        };
+        Ok(Some(OrderbookDepth {
+            auction_id: book.auction_id,
+            sell_token,
+            buy_token,
+            asks: levels(offers(sell_token, buy_token), Side::Ask, tick, limit),
+            bids: levels(offers(buy_token, sell_token), Side::Bid, tick, limit),
+        }))
+    }
+
+    /// Returns the book of the most recent auction, building it if the auction
+    /// changed since it was last checked.
+    async fn book(&self) -> Result<Option<Arc<Book>>> {
+        // Concurrent requests wait for the one checking the auction instead of
+        // all querying the database and building the same book.
+        let mut cached = self.book.lock().await;
+        if let Some(cached) = cached
+            .as_ref()
+            .filter(|cached| cached.checked.elapsed() < MAX_BOOK_AGE)
+        {
+            return Ok(Some(cached.book.clone()));
+        }
+
+        let Some(auction_id) = self.database.most_recent_auction_id().await? else {
+            return Ok(None);
+        };
+        if let Some(cached) = cached
+            .as_mut()
+            .filter(|cached| cached.book.auction_id == auction_id)
+        {
+            cached.checked = Instant::now();
+            return Ok(Some(cached.book.clone()));
+        }
+
+        let Some(auction) = self.database.most_recent_auction().await? else {
+            return Ok(None);
+        };
+        let book = Arc::new(Book::new(&auction));
+        *cached = Some(CachedBook {
+            book: book.clone(),
+            checked: Instant::now(),
+        });
+        Ok(Some(book))
+    }
+}
+
+/// The remaining amounts of the limit orders of an auction.
+#[derive(Debug)]
+struct Book {
+    auction_id: AuctionId,
+    /// Indexed by the sell and buy token of the orders.
+    offers: HashMap<(H160, H160), Vec<Offer>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Offer {
+    sell_amount: U256,
+    buy_amount: U256,
+}
+
+impl Book {
+    fn new(auction: &AuctionWithId) -> Self {
+        let mut offers = HashMap::<_, Vec<_>>::new();
+        for order in auction
+            .auction
+            .orders
+            .iter()
+            .filter(|order| order.is_limit_order())
+        {
+            let Ok(remaining) = remaining_amounts::Remaining::from_order(&order.into()) else {
+                continue;
+            };
+            let (Ok(sell_amount), Ok(buy_amount)) = (
+                remaining.remaining(order.data.sell_amount),
+                remaining.remaining(order.data.buy_amount),
+            ) else {
+                continue;
+            };
+            if sell_amount.is_zero() || buy_amount.is_zero() {
+                continue;
+            }
+            offers
+                .entry((order.data.sell_token, order.data.buy_token))
+                .or_default()
+                .push(Offer {
+                    sell_amount,
+                    buy_amount,
+                });
+        }
+        Self {
+            auction_id: auction.id,
+            offers,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Side {
+    /// Offers selling the sell token of the market.
+    Ask,
+    /// Offers selling the buy token of the market.
+    Bid,
+}
+
+/// Groups the offers into price levels, best price first.
+fn levels(
+    offers: &[Offer],
+    side: Side,
+    tick: Option<&BigRational>,
+    limit: usize,
+) -> Vec<PriceLevel> {
+    let mut levels = BTreeMap::<BigRational, PriceLevel>::new();
+    for offer in offers {
+        // Amounts of the sell and buy token of the market.
+        let (sell_amount, buy_amount) = match side {
+            Side::Ask => (offer.sell_amount, offer.buy_amount),
+            Side::Bid => (offer.buy_amount, offer.sell_amount),
+        };
+        let price = u256_to_big_rational(&buy_amount) / u256_to_big_rational(&sell_amount);
+        // Round towards the worse price so that every order of the level accepts
+        // the price of the level.
+        let price = match (tick, side) {
+            (None, _) => price,
+            (Some(tick), Side::Ask) => (price / tick).ceil() * tick,
+            (Some(tick), Side::Bid) => (price / tick).floor() * tick,
+        };
+        let level = levels.entry(price.clone()).or_insert_with(|| PriceLevel {
+            price,
+            sell_token_amount: U256::zero(),
+            buy_token_amount: U256::zero(),
+            orders: 0,
+        });
+        level.sell_token_amount = level.sell_token_amount.saturating_add(sell_amount);
+        level.buy_token_amount = level.buy_token_amount.saturating_add(buy_amount);
+        level.orders += 1;
+    }
+    let levels = levels.into_values();
+    match side {
+        Side::Ask => levels.take(limit).collect(),
+        Side::Bid => levels.rev().take(limit).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        model::{
+            auction::Auction,
+            order::{LimitOrderClass, Order, OrderClass, OrderData, OrderKind, OrderMetadata},
+        },
+    };
+
+    fn offer(sell_amount: u64, buy_amount: u64) -> Offer {
+        Offer {
+            sell_amount: sell_amount.into(),
+            buy_amount: buy_amount.into(),
+        }
+    }
+
+    fn level(
+        price: (i64, i64),
+        sell_token_amount: u64,
+        buy_token_amount: u64,
+        orders: u64,
+    ) -> PriceLevel {
+        PriceLevel {
+            price: BigRational::new(price.0.into(), price.1.into()),
+            sell_token_amount: sell_token_amount.into(),
+            buy_token_amount: buy_token_amount.into(),
+            orders,
+        }
+    }
+
+    #[test]
+    fn builds_book_from_limit_orders() {
+        let order = |class, partially_fillable, executed: u64| Order {
+            data: OrderData {
+                sell_token: H160([1; 20]),
+                buy_token: H160([2; 20]),
+                sell_amount: 100.into(),
+                buy_amount: 200.into(),
+                kind: OrderKind::Sell,
+                partially_fillable,
+                ..Default::default()
+            },
+            metadata: OrderMetadata {
+                class,
+                executed_sell_amount_before_fees: executed.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let limit = OrderClass::Limit(LimitOrderClass::default());
+        let auction = AuctionWithId {
+            id: 7,
+            auction: Auction {
+                orders: vec![
+                    order(limit, false, 0),
+                    order(limit, true, 25),
+                    // Completely filled.
+                    order(limit, true, 100),
+                    order(OrderClass::Market, false, 0),
+                    order(OrderClass::Liquidity, false, 0),
+                ],
+                ..Default::default()
+            },
+        };
+
+        let book = Book::new(&auction);
+        assert_eq!(book.auction_id, 7);
+        assert_eq!(book.offers.len(), 1);
+        assert_eq!(
+            book.offers[&(H160([1; 20]), H160([2; 20]))],
+            vec![offer(100, 200), offer(75, 150)]
+        );
+    }
+
+    #[test]
+    fn groups_asks() {
+        let offers = [offer(10, 20), offer(10, 21), offer(5, 10), offer(10, 30)];
+        assert_eq!(
+            levels(&offers, Side::Ask, None, 10),
+            vec![
+                level((2, 1), 15, 30, 2),
+                level((21, 10), 10, 21, 1),
+                level((3, 1), 10, 30, 1),
+            ]
+        );
+
+        let tick = BigRational::new(1.into(), 2.into());
+        assert_eq!(
+            levels(&offers, Side::Ask, Some(&tick), 10),
+            vec![
+                level((2, 1), 15, 30, 2),
+                level((5, 2), 10, 21, 1),
+                level((3, 1), 10, 30, 1)
+            ]
+        );
+        assert_eq!(
+            levels(&offers, Side::Ask, Some(&tick), 1),
+            vec![level((2, 1), 15, 30, 2)]
+        );
+    }
+
+    #[test]
+    fn groups_bids() {
+        // Sell the buy token of the market for the sell token of the market.
+        let offers = [offer(20, 10), offer(21, 10), offer(30, 10)];
+        let tick = BigRational::new(1.into(), 1.into());
+        assert_eq!(
+            levels(&offers, Side::Bid, Some(&tick), 10),
+            vec![level((3, 1), 10, 30, 1), level((2, 1), 20, 41, 2)]
+        );
+    }
+}
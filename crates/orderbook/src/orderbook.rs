@@ -17,6 +17,7 @@ use {
             OrderCreationAppData,
             OrderStatus,
             OrderUid,
+            SignedAllOrdersCancellation,
             SignedOrderCancellations,
         },
         quote::QuoteId,
@@ -97,6 +98,8 @@ pub enum AddOrderError {
     OrderValidation(ValidationError),
     #[error("database error: {0}")]
     Database(#[from] anyhow::Error),
+    #[error("not added because another order of the batch was rejected")]
+    BatchRejected,
     #[error(
         "contract app data {contract_app_data:?} is associated with full app data {existing:?} \
          which is different from the provided {provided:?}"
@@ -130,6 +133,14 @@ impl AddOrderError {
     }
 }
 
+/// Fails all orders of an atomic batch that didn't fail on their own.
+fn reject_batch<T>(results: Vec<Result<T, AddOrderError>>) -> Vec<Result<T, AddOrderError>> {
+    results
+        .into_iter()
+        .map(|result| result.and(Err(AddOrderError::BatchRejected)))
+        .collect()
+}
+
 // This requires a manual implementation because the `#[from]` attribute from
 // `thiserror` implies `#[source]` which requires `ValidationError: Error`,
 // which it currently does not!
@@ -155,6 +166,8 @@ pub enum OrderCancellationError {
     OrderExpired,
     #[error("on-chain orders cannot be cancelled with off-chain signature")]
     OnChainOrder,
+    #[error("cancellation timestamp is in the future")]
+    CancellationInFuture,
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -260,6 +273,88 @@ impl Orderbook {
         Ok((order.metadata.uid, quote_id))
     }
 
+    /// Adds a batch of orders. The orders are validated together sharing
+    /// token quality and balance lookups. Results are returned in the order of
+    /// the input.
+    ///
+    /// With `atomic`, either all orders are added in a single database
+    /// transaction or none of them are, in which case the orders that were not
+    /// at fault fail with [`AddOrderError::BatchRejected`].
+    pub async fn add_orders(
+        &self,
+        payloads: Vec<OrderCreation>,
+        atomic: bool,
+    ) -> Vec<Result<(OrderUid, Option<QuoteId>), AddOrderError>> {
+        let mut results = Vec::<Result<_, AddOrderError>>::with_capacity(payloads.len());
+        let mut to_validate = Vec::with_capacity(payloads.len());
+        for payload in payloads {
+            let full_app_data_override = match payload.app_data {
+                OrderCreationAppData::Hash { hash } => match self.find_full_app_data(&hash).await {
+                    Ok(full_app_data) => full_app_data,
+                    Err(err) => {
+                        results.push(Err(err.into()));
+                        continue;
+                    }
+                },
+                _ => None,
+            };
+            results.push(Ok(Default::default()));
+            to_validate.push((payload, full_app_data_override));
+        }
+
+        let mut validated = self
+            .order_validator
+            .validate_and_construct_orders(
+                to_validate,
+                &self.domain_separator,
+                self.settlement_contract,
+            )
+            .await
+            .into_iter();
+        let mut orders = Vec::with_capacity(results.len());
+        for (i, result) in results.iter_mut().enumerate() {
+            if result.is_err() {
+                continue;
+            }
+            match validated.next().expect("one validation result per order") {
+                Ok(order) => orders.push((i, order)),
+                Err(err) => *result = Err(err.into()),
+            }
+        }
+
+        if atomic {
+            if results.iter().any(Result::is_err) {
+                return reject_batch(results);
+            }
+            let (indices, orders): (Vec<_>, Vec<_>) = orders.into_iter().unzip();
+            if let Err((i, err)) = self.database.insert_orders(&orders).await {
+                let (order, _) = &orders[i];
+                results[indices[i]] = Err(AddOrderError::from_insertion(err, order));
+                return reject_batch(results);
+            }
+            for (i, (order, quote)) in indices.into_iter().zip(&orders) {
+                Metrics::on_order_operation(order, OrderOperation::Created);
+                results[i] = Ok((
+                    order.metadata.uid,
+                    quote.as_ref().and_then(|quote| quote.id),
+                ));
+            }
+        } else {
+            for (i, (order, quote)) in orders {
+                let quote_id = quote.as_ref().and_then(|quote| quote.id);
+                results[i] = match self.database.insert_order(&order, quote).await {
+                    Ok(()) => {
+                        Metrics::on_order_operation(&order, OrderOperation::Created);
+                        Ok((order.metadata.uid, quote_id))
+                    }
+                    Err(err) => Err(AddOrderError::from_insertion(err, &order)),
+                };
+            }
+        }
+
+        results
+    }
+
     /// Finds an order for cancellation.
     ///
     /// Returns an error if the order cannot be found or cannot be cancelled.
@@ -318,6 +413,43 @@ impl Orderbook {
         Ok(())
     }
 
+    /// Cancels all off-chain orders of the owner that were created before the
+    /// signed timestamp. Orders created afterwards are unaffected which is why
+    /// timestamps in the future are rejected.
+    pub async fn cancel_all_orders(
+        &self,
+        owner: H160,
+        cancellation: SignedAllOrdersCancellation,
+    ) -> Result<(), OrderCancellationError> {
+        let signer = cancellation
+            .validate(&self.domain_separator)
+            .map_err(|_| OrderCancellationError::InvalidSignature)?;
+        if signer != owner {
+            return Err(OrderCancellationError::WrongOwner);
+        }
+
+        let now = Utc::now();
+        if i64::from(cancellation.data.cancelled_before) > now.timestamp() {
+            return Err(OrderCancellationError::CancellationInFuture);
+        }
+
+        let cancelled = self
+            .database
+            .cancel_all_orders(owner, &cancellation.data, now)
+            .await?;
+
+        tracing::debug!(
+            ?owner,
+            cancelled_before = cancellation.data.cancelled_before,
+            sell_token = ?cancellation.data.sell_token,
+            buy_token = ?cancellation.data.buy_token,
+            ?cancelled,
+            "all orders cancelled"
+        );
+
+        Ok(())
+    }
+
     pub async fn cancel_order(
         &self,
         cancellation: OrderCancellation,
@@ -0,0 +1,395 @@
+//! Tracks how accurately every price estimator quotes by comparing the quotes
+//! that market orders were created with to the prices the orders got executed
+//! at.
+//!
+//! The deviation of a quote is the executed price divided by the quoted price
+//! minus 1, where prices are buy amounts per sell amount excluding fees. This
+//! makes the deviation independent of the order kind: a positive deviation
+//! means that the order got executed at a better price than quoted and a
+//! negative deviation that the estimator promised more than the order got.
+
+use {
+    crate::database::Postgres,
+    chrono::Utc,
+    model::quote_accuracy::{Accuracy, Distribution, EstimatorAccuracy, PairAccuracy, Report},
+    num::ToPrimitive,
+    number::conversions::u256_to_big_rational,
+    primitive_types::{H160, U256},
+    shared::price_estimation::competition::EstimatorWeights,
+    std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+    tracing::Instrument,
+};
+
+/// Weights never adjust estimates by more than this fraction.
+const MAX_WEIGHT_ADJUSTMENT: f64 = 0.1;
+
+/// A quote of an executed market order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutedQuote {
+    pub estimator: String,
+    pub latency: Option<Duration>,
+    pub sell_token: H160,
+    pub buy_token: H160,
+    pub quoted_sell_amount: U256,
+    pub quoted_buy_amount: U256,
+    pub executed_sell_amount: U256,
+    pub executed_buy_amount: U256,
+}
+
+impl ExecutedQuote {
+    /// Returns `None` if any of the amounts is zero.
+    fn deviation(&self) -> Option<f64> {
+        if [
+            self.quoted_sell_amount,
+            self.quoted_buy_amount,
+            self.executed_sell_amount,
+            self.executed_buy_amount,
+        ]
+        .iter()
+        .any(U256::is_zero)
+        {
+            return None;
+        }
+        let executed_price = u256_to_big_rational(&self.executed_buy_amount)
+            / u256_to_big_rational(&self.executed_sell_amount);
+        let quoted_price = u256_to_big_rational(&self.quoted_buy_amount)
+            / u256_to_big_rational(&self.quoted_sell_amount);
+        Some((executed_price / quoted_price).to_f64()? - 1.)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Samples {
+    deviations: Vec<f64>,
+    latencies_ms: Vec<f64>,
+}
+
+impl Samples {
+    fn add(&mut self, deviation: f64, latency: Option<Duration>) {
+        self.deviations.push(deviation);
+        if let Some(latency) = latency {
+            self.latencies_ms.push(latency.as_secs_f64() * 1000.);
+        }
+    }
+
+    fn accuracy(mut self) -> Accuracy {
+        Accuracy {
+            samples: self.deviations.len() as u64,
+            deviation: distribution(&mut self.deviations).unwrap_or_default(),
+            latency_ms: distribution(&mut self.latencies_ms),
+        }
+    }
+}
+
+/// Computes the mean and quantiles using the nearest rank method.
+fn distribution(values: &mut [f64]) -> Option<Distribution> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let quantile = |q: f64| {
+        let rank = (q * values.len() as f64).ceil() as usize;
+        values[rank.clamp(1, values.len()) - 1]
+    };
+    Some(Distribution {
+        mean: values.iter().sum::<f64>() / values.len() as f64,
+        p10: quantile(0.1),
+        p50: quantile(0.5),
+        p90: quantile(0.9),
+        p99: quantile(0.99),
+    })
+}
+
+/// Computes the accuracy per estimator and per estimator and token pair.
+/// Results are sorted by estimator and token pair.
+pub fn accuracy(quotes: &[ExecutedQuote]) -> (Vec<EstimatorAccuracy>, Vec<PairAccuracy>) {
+    let mut estimators = BTreeMap::<String, Samples>::new();
+    let mut pairs = BTreeMap::<(String, H160, H160), Samples>::new();
+    for quote in quotes {
+        let Some(deviation) = quote.deviation() else {
+            continue;
+        };
+        estimators
+            .entry(quote.estimator.clone())
+            .or_default()
+            .add(deviation, quote.latency);
+        pairs
+            .entry((quote.estimator.clone(), quote.sell_token, quote.buy_token))
+            .or_default()
+            .add(deviation, quote.latency);
+    }
+
+    let estimators = estimators
+        .into_iter()
+        .map(|(estimator, samples)| EstimatorAccuracy {
+            estimator,
+            accuracy: samples.accuracy(),
+        })
+        .collect();
+    let pairs = pairs
+        .into_iter()
+        .map(
+            |((estimator, sell_token, buy_token), samples)| PairAccuracy {
+                estimator,
+                sell_token,
+                buy_token,
+                accuracy: samples.accuracy(),
+            },
+        )
+        .collect();
+    (estimators, pairs)
+}
+
+/// Derives the weight of every estimator with enough samples from its median
+/// deviation. An estimator whose quotes typically promised 2% more than the
+/// orders got executed for gets a weight of 0.98.
+pub fn weights(estimators: &[EstimatorAccuracy], min_samples: u64) -> BTreeMap<String, f64> {
+    estimators
+        .iter()
+        .filter(|estimator| estimator.accuracy.samples >= min_samples)
+        .map(|estimator| {
+            let weight = (1. + estimator.accuracy.deviation.p50)
+                .clamp(1. - MAX_WEIGHT_ADJUSTMENT, 1. + MAX_WEIGHT_ADJUSTMENT);
+            (estimator.estimator.clone(), weight)
+        })
+        .collect()
+}
+
+/// Weights that get derived from the accuracy and applied to the price
+/// estimators.
+pub struct Weights {
+    pub weights: Arc<EstimatorWeights>,
+    pub min_samples: u64,
+}
+
+/// Periodically recomputes the quote accuracy and keeps the latest report.
+pub struct QuoteAccuracy {
+    db: Postgres,
+    window: Duration,
+    weights: Option<Weights>,
+    report: Mutex<Option<Report>>,
+}
+
+impl QuoteAccuracy {
+    pub fn new(db: Postgres, window: Duration, weights: Option<Weights>) -> Self {
+        Self {
+            db,
+            window,
+            weights,
+            report: Default::default(),
+        }
+    }
+
+    /// The latest report. `None` until the accuracy has been computed once.
+    pub fn report(&self) -> Option<Report> {
+        self.report.lock().unwrap().clone()
+    }
+
+    pub fn spawn(self: Arc<Self>, update_interval: Duration) {
+        let updater = async move {
+            loop {
+                if let Err(err) = self.update().await {
+                    tracing::error!(?err, "failed to update quote accuracy");
+                }
+                tokio::time::sleep(update_interval).await;
+            }
+        };
+        tokio::task::spawn(updater.instrument(tracing::info_span!("quote_accuracy")));
+    }
+
+    async fn update(&self) -> anyhow::Result<()> {
+        let now = Utc::now();
+        let since = now - chrono::Duration::from_std(self.window)?;
+        let quotes = self.db.executed_quotes(since).await?;
+        let (estimators, pairs) = accuracy(&quotes);
+
+        let weights = match &self.weights {
+            Some(config) => {
+                let weights = weights(&estimators, config.min_samples);
+                config.weights.set(weights.clone().into_iter().collect());
+                weights
+            }
+            None => Default::default(),
+        };
+
+        let metrics = Metrics::get();
+        metrics.deviation.reset();
+        metrics.latency.reset();
+        metrics.samples.reset();
+        metrics.weight.reset();
+        for estimator in &estimators {
+            let name = estimator.estimator.as_str();
+            let accuracy = &estimator.accuracy;
+            metrics
+                .samples
+                .with_label_values(&[name])
+                .set(accuracy.samples as i64);
+            for (quantile, value) in quantiles(&accuracy.deviation) {
+                metrics
+                    .deviation
+                    .with_label_values(&[name, quantile])
+                    .set(value);
+            }
+            for (quantile, value) in accuracy.latency_ms.iter().flat_map(quantiles) {
+                metrics
+                    .latency
+                    .with_label_values(&[name, quantile])
+                    .set(value / 1000.);
+            }
+        }
+        for (name, weight) in &weights {
+            metrics
+                .weight
+                .with_label_values(&[name.as_str()])
+                .set(*weight);
+        }
+
+        tracing::debug!(samples = quotes.len(), "updated quote accuracy");
+        *self.report.lock().unwrap() = Some(Report {
+            since,
+            updated_at: now,
+            estimators,
+            pairs,
+            weights,
+        });
+        Ok(())
+    }
+}
+
+fn quantiles(distribution: &Distribution) -> [(&'static str, f64); 4] {
+    [
+        ("0.1", distribution.p10),
+        ("0.5", distribution.p50),
+        ("0.9", distribution.p90),
+        ("0.99", distribution.p99),
+    ]
+}
+
+#[derive(prometheus_metric_storage::MetricStorage)]
+#[metric(subsystem = "quote_accuracy")]
+struct Metrics {
+    /// Relative deviation of executed prices from quoted prices per estimator.
+    #[metric(labels("estimator", "quantile"))]
+    deviation: prometheus::GaugeVec,
+
+    /// Time it took the estimators to provide their winning quotes.
+    #[metric(labels("estimator", "quantile"))]
+    latency: prometheus::GaugeVec,
+
+    /// Number of executed orders quoted by every estimator.
+    #[metric(labels("estimator"))]
+    samples: prometheus::IntGaugeVec,
+
+    /// Weights applied to the estimates of every estimator.
+    #[metric(labels("estimator"))]
+    weight: prometheus::GaugeVec,
+}
+
+impl Metrics {
+    fn get() -> &'static Self {
+        Metrics::instance(observe::metrics::get_storage_registry()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, shared::addr};
+
+    fn quote(estimator: &str, executed_buy_amount: u64, latency_ms: u64) -> ExecutedQuote {
+        ExecutedQuote {
+            estimator: estimator.to_string(),
+            latency: Some(Duration::from_millis(latency_ms)),
+            sell_token: addr!("0000000000000000000000000000000000000001"),
+            buy_token: addr!("0000000000000000000000000000000000000002"),
+            quoted_sell_amount: 100.into(),
+            quoted_buy_amount: 200.into(),
+            executed_sell_amount: 50.into(),
+            executed_buy_amount: executed_buy_amount.into(),
+        }
+    }
+
+    #[test]
+    fn computes_deviation() {
+        let deviation = |executed_buy_amount| quote("a", executed_buy_amount, 0).deviation();
+        assert_eq!(deviation(100), Some(0.));
+        assert!((deviation(110).unwrap() - 0.1).abs() < 1e-9);
+        assert!((deviation(90).unwrap() + 0.1).abs() < 1e-9);
+        assert_eq!(deviation(0), None);
+    }
+
+    #[test]
+    fn computes_distribution() {
+        let mut values = (1..=10).rev().map(f64::from).collect::<Vec<_>>();
+        assert_eq!(
+            distribution(&mut values),
+            Some(Distribution {
+                mean: 5.5,
+                p10: 1.,
+                p50: 5.,
+                p90: 9.,
+                p99: 10.,
+            })
+        );
+        assert_eq!(distribution(&mut []), None);
+    }
+
+    #[test]
+    fn groups_by_estimator_and_pair() {
+        let other_pair = ExecutedQuote {
+            buy_token: addr!("0000000000000000000000000000000000000003"),
+            latency: None,
+            ..quote("a", 100, 0)
+        };
+        let quotes = [
+            quote("b", 90, 20),
+            quote("a", 110, 10),
+            other_pair,
+            quote("a", 0, 10),
+        ];
+        let (estimators, pairs) = accuracy(&quotes);
+        assert_eq!(
+            estimators
+                .iter()
+                .map(|e| (e.estimator.as_str(), e.accuracy.samples))
+                .collect::<Vec<_>>(),
+            vec![("a", 2), ("b", 1)]
+        );
+        assert_eq!(estimators[0].accuracy.latency_ms.unwrap().p50, 10.);
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs[0].accuracy.samples, 1);
+        assert_eq!(pairs[1].accuracy.latency_ms, None);
+    }
+
+    #[test]
+    fn derives_weights() {
+        let estimator = |name: &str, samples: u64, p50: f64| EstimatorAccuracy {
+            estimator: name.to_string(),
+            accuracy: Accuracy {
+                samples,
+                deviation: Distribution {
+                    p50,
+                    ..Default::default()
+                },
+                latency_ms: None,
+            },
+        };
+        let weights = weights(
+            &[
+                estimator("a", 10, -0.0625),
+                estimator("b", 10, 0.5),
+                estimator("c", 9, -0.5),
+            ],
+            10,
+        );
+        assert_eq!(
+            weights,
+            [("a".to_string(), 0.9375), ("b".to_string(), 1.1)]
+                .into_iter()
+                .collect()
+        );
+    }
+}
@@ -3,23 +3,29 @@ use {
         api,
         app_data,
         arguments::Arguments,
-        database::Postgres,
+        database::{deny_lists::DenyList, Postgres},
         ipfs::Ipfs,
         ipfs_app_data::IpfsAppData,
+        market_depth::MarketDepth,
         orderbook::Orderbook,
+        quote_accuracy::{self, QuoteAccuracy},
+        solver_rewards,
     },
     anyhow::{anyhow, Context, Result},
     clap::Parser,
     contracts::{BalancerV2Vault, GPv2Settlement, HooksTrampoline, IUniswapV3Factory, WETH9},
+    database::deny_lists::DenyListKind,
     ethcontract::errors::DeployError,
     futures::{FutureExt, StreamExt},
     model::{order::BUY_ETH_ADDRESS, DomainSeparator},
     shared::{
         account_balances,
+        address_list::{AddressListConfiguration, AutoUpdatingAddressList},
         bad_token::{
             cache::CachingDetector,
             instrumented::InstrumentedBadTokenDetectorExt,
             list_based::{ListBasedDetector, UnknownTokenStrategy},
+            persisted::PersistedDetector,
             token_owner_finder,
             trace_call::TraceCallDetector,
         },
@@ -40,6 +46,7 @@ use {
             PriceEstimating,
         },
         recent_block_cache::CacheConfig,
+        request_signing::Secret,
         signature_validator,
         sources::{
             self,
@@ -209,7 +216,33 @@ pub async fn run(args: Arguments) {
     let mut allowed_tokens = args.allowed_tokens.clone();
     allowed_tokens.extend(base_tokens.tokens().iter().copied());
     allowed_tokens.push(BUY_ETH_ADDRESS);
-    let unsupported_tokens = args.unsupported_tokens.clone();
+    let deny_list = |name, hardcoded, source, kind| {
+        let db = postgres.clone();
+        AutoUpdatingAddressList::from_configuration(
+            AddressListConfiguration {
+                name,
+                hardcoded,
+                source,
+                client: http_factory.create(),
+                update_interval: args.deny_list_update_interval,
+            },
+            move || Arc::new(DenyList { db, kind }),
+        )
+    };
+    let unsupported_tokens = deny_list(
+        "unsupported_tokens",
+        args.unsupported_tokens.clone(),
+        args.unsupported_tokens_source.clone(),
+        DenyListKind::UnsupportedToken,
+    )
+    .await;
+    let banned_users = deny_list(
+        "banned_users",
+        args.banned_users.clone(),
+        args.banned_users_source.clone(),
+        DenyListKind::BannedUser,
+    )
+    .await;
 
     let uniswapv3_factory = match IUniswapV3Factory::deployed(&web3).await {
         Err(DeployError::NotFound(_)) => None,
@@ -231,16 +264,22 @@ pub async fn run(args: Arguments) {
 
     let trace_call_detector = args.tracing_node_url.as_ref().map(|tracing_node_url| {
         Box::new(CachingDetector::new(
-            Box::new(TraceCallDetector {
-                web3: shared::ethrpc::web3(
-                    &args.shared.ethrpc,
-                    &http_factory,
-                    tracing_node_url,
-                    "trace",
-                ),
-                finder,
-                settlement_contract: settlement_contract.address(),
-            }),
+            Box::new(PersistedDetector::new(
+                Box::new(TraceCallDetector {
+                    web3: shared::ethrpc::web3(
+                        &args.shared.ethrpc,
+                        &http_factory,
+                        tracing_node_url,
+                        "trace",
+                    ),
+                    finder,
+                    settlement_contract: settlement_contract.address(),
+                    allow_taxed_tokens: args.allow_taxed_tokens,
+                }),
+                Arc::new(postgres.clone()),
+                args.token_quality_db_expiry,
+                args.allow_taxed_tokens,
+            )),
             args.token_quality_cache_expiry,
         ))
     });
@@ -393,6 +432,19 @@ pub async fn run(args: Arguments) {
     )
     .expect("failed to initialize price estimator factory");
 
+    let quote_accuracy = Arc::new(QuoteAccuracy::new(
+        postgres.clone(),
+        args.quote_accuracy_window,
+        args.quote_accuracy_weights
+            .then(|| quote_accuracy::Weights {
+                weights: price_estimator_factory.weights(),
+                min_samples: args.quote_accuracy_min_samples,
+            }),
+    ));
+    quote_accuracy
+        .clone()
+        .spawn(args.quote_accuracy_update_interval);
+
     let price_estimator = price_estimator_factory
         .price_estimator(&PriceEstimatorSource::for_args(
             args.order_quoting.price_estimators.as_slice(),
@@ -475,7 +527,7 @@ pub async fn run(args: Arguments) {
     let order_validator = Arc::new(
         OrderValidator::new(
             native_token.clone(),
-            args.banned_users.iter().copied().collect(),
+            banned_users,
             args.order_quoting
                 .liquidity_order_owners
                 .iter()
@@ -534,6 +586,8 @@ pub async fn run(args: Arguments) {
         postgres.clone(),
     ));
 
+    let market_depth = Arc::new(MarketDepth::new(postgres.clone()));
+
     let (shutdown_sender, shutdown_receiver) = tokio::sync::oneshot::channel();
     let serve_api = serve_api(
         postgres,
@@ -546,6 +600,20 @@ pub async fn run(args: Arguments) {
         },
         args.shared.solver_competition_auth,
         native_price_estimator,
+        solver_rewards::Config {
+            caps: solver_rewards::Caps {
+                upper: args.solver_rewards_upper_cap,
+                lower: args.solver_rewards_lower_cap,
+            },
+            finality: args.shared.finality,
+            web3: web3.clone(),
+        },
+        args.max_orders_per_batch,
+        quote_accuracy,
+        market_depth,
+        args.allow_taxed_tokens,
+        args.webhook_admin_auth,
+        args.shared.webhook_signing_key,
     );
 
     let service_maintainer = ServiceMaintenance::new(maintainers);
@@ -616,6 +684,13 @@ fn serve_api(
     shutdown_receiver: impl Future<Output = ()> + Send + 'static,
     solver_competition_auth: Option<String>,
     native_price_estimator: Arc<dyn NativePriceEstimating>,
+    solver_rewards: solver_rewards::Config,
+    max_orders_per_batch: usize,
+    quote_accuracy: Arc<QuoteAccuracy>,
+    market_depth: Arc<MarketDepth>,
+    allow_taxed_tokens: bool,
+    webhook_admin_auth: Option<String>,
+    webhook_signing_key: Option<Secret>,
 ) -> JoinHandle<()> {
     let filter = api::handle_all_routes(
         database,
@@ -624,6 +699,13 @@ fn serve_api(
         app_data,
         solver_competition_auth,
         native_price_estimator,
+        solver_rewards,
+        max_orders_per_batch,
+        quote_accuracy,
+        market_depth,
+        allow_taxed_tokens,
+        webhook_admin_auth,
+        webhook_signing_key,
     )
     .boxed();
     tracing::info!(%address, "serving order book");
@@ -0,0 +1,308 @@
+//! Solver rewards accounting based on the settlement scores that solvers
+//! commit to during the competition and the settlements that are observed on
+//! chain.
+//!
+//! The winner of an auction is paid the difference between the score that was
+//! observed on chain and the reference score (the score of the runner-up),
+//! capped from above and below. Auctions that are not settled, or settled after
+//! their block deadline, are penalised with the reference score, also capped.
+//! An auction only counts as not settled once its block deadline is final in
+//! the indexed events, as its settlement might not have been indexed yet.
+
+use {
+    model::solver_rewards::{AuctionReward, Outcome, Report, SolverReward},
+    num::BigInt,
+    number::conversions::u256_to_big_int,
+    primitive_types::{H160, H256, U256},
+    shared::{ethrpc::Web3, event_handling::Finality},
+    std::{collections::BTreeMap, ops::Range},
+};
+
+/// How the solver rewards get computed.
+#[derive(Clone)]
+pub struct Config {
+    pub caps: Caps,
+    /// The finality the settlement events get indexed with. Determines up to
+    /// which block all settlements are known.
+    pub finality: Finality,
+    pub web3: Web3,
+}
+
+/// The maximum reward and penalty a solver can receive for a single auction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Caps {
+    pub upper: U256,
+    pub lower: U256,
+}
+
+impl Caps {
+    fn clamp(&self, payment: BigInt) -> BigInt {
+        payment.clamp(-u256_to_big_int(&self.lower), u256_to_big_int(&self.upper))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Auction {
+    pub id: i64,
+    pub winner: H160,
+    pub winning_score: U256,
+    pub reference_score: U256,
+    /// The last block the auction may be settled in.
+    pub block_deadline: u64,
+    pub settlement: Option<Settlement>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Settlement {
+    pub tx_hash: H256,
+    pub block: u64,
+    pub observation: Option<Observation>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Observation {
+    pub surplus: U256,
+    pub fee: U256,
+    pub gas_used: U256,
+    pub effective_gas_price: U256,
+}
+
+impl Auction {
+    fn outcome(&self, final_block: u64) -> Outcome {
+        match &self.settlement {
+            None if self.block_deadline < final_block => Outcome::Failed,
+            None => Outcome::Pending,
+            Some(settlement) if settlement.block > self.block_deadline => Outcome::Late,
+            Some(Settlement {
+                observation: None, ..
+            }) => Outcome::Pending,
+            Some(_) => Outcome::Settled,
+        }
+    }
+
+    fn observation(&self) -> Option<&Observation> {
+        self.settlement.as_ref()?.observation.as_ref()
+    }
+
+    fn reward(&self, caps: &Caps, final_block: u64) -> AuctionReward {
+        let outcome = self.outcome(final_block);
+        let observed_score = self
+            .observation()
+            .map(|observation| observation.surplus.saturating_add(observation.fee));
+        let execution_cost = self.observation().map(|observation| {
+            observation
+                .gas_used
+                .saturating_mul(observation.effective_gas_price)
+        });
+        let reference_score = u256_to_big_int(&self.reference_score);
+        let payment = match outcome {
+            Outcome::Settled => {
+                caps.clamp(u256_to_big_int(&observed_score.unwrap_or_default()) - reference_score)
+            }
+            Outcome::Late | Outcome::Failed => caps.clamp(-reference_score),
+            Outcome::Pending => BigInt::default(),
+        };
+        AuctionReward {
+            auction_id: self.id,
+            solver: self.winner,
+            outcome,
+            transaction_hash: self.settlement.as_ref().map(|s| s.tx_hash),
+            settlement_block: self.settlement.as_ref().map(|s| s.block),
+            block_deadline: self.block_deadline,
+            winning_score: self.winning_score,
+            reference_score: self.reference_score,
+            observed_score,
+            execution_cost,
+            payment,
+        }
+    }
+}
+
+/// Computes the rewards of all auctions whose block deadline lies in the
+/// accounting period. `final_block` is the latest block up to which all
+/// settlements are known to be indexed. `participation` contains the number of
+/// auctions every solver participated in during that period.
+///
+/// Auctions and solvers are sorted by ID and address respectively so that the
+/// same inputs always result in the same report.
+pub fn report(
+    period: Range<u64>,
+    final_block: u64,
+    caps: Caps,
+    mut auctions: Vec<Auction>,
+    participation: impl IntoIterator<Item = (H160, u64)>,
+) -> Report {
+    auctions.sort_by_key(|auction| auction.id);
+    let auctions = auctions
+        .iter()
+        .map(|auction| auction.reward(&caps, final_block))
+        .collect::<Vec<_>>();
+
+    let mut solvers = BTreeMap::<H160, SolverReward>::new();
+    for (solver, auctions) in participation {
+        solvers.entry(solver).or_default().participated_auctions += auctions;
+    }
+    for auction in &auctions {
+        let solver = solvers.entry(auction.solver).or_default();
+        solver.won_auctions += 1;
+        match auction.outcome {
+            Outcome::Late | Outcome::Failed => solver.failed_auctions += 1,
+            Outcome::Pending => solver.pending_auctions += 1,
+            Outcome::Settled => (),
+        }
+        solver.payment += &auction.payment;
+        solver.execution_costs = solver
+            .execution_costs
+            .saturating_add(auction.execution_cost.unwrap_or_default());
+    }
+    let solvers = solvers
+        .into_iter()
+        .map(|(solver, reward)| SolverReward { solver, ..reward })
+        .collect();
+
+    Report {
+        from_block: period.start,
+        to_block: period.end,
+        upper_cap: caps.upper,
+        lower_cap: caps.lower,
+        auctions,
+        solvers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPS: Caps = Caps {
+        upper: U256([100, 0, 0, 0]),
+        lower: U256([50, 0, 0, 0]),
+    };
+
+    fn settlement(block: u64, observation: Option<(u64, u64)>) -> Option<Settlement> {
+        Some(Settlement {
+            tx_hash: H256::from_low_u64_be(block),
+            block,
+            observation: observation.map(|(surplus, fee)| Observation {
+                surplus: surplus.into(),
+                fee: fee.into(),
+                gas_used: 2.into(),
+                effective_gas_price: 3.into(),
+            }),
+        })
+    }
+
+    fn auction(id: i64, winner: u64, reference: u64, settlement: Option<Settlement>) -> Auction {
+        Auction {
+            id,
+            winner: H160::from_low_u64_be(winner),
+            winning_score: 1000.into(),
+            reference_score: reference.into(),
+            block_deadline: 10,
+            settlement,
+        }
+    }
+
+    #[test]
+    fn payments() {
+        let payment = |auction: Auction| {
+            let reward = auction.reward(&CAPS, 20);
+            (reward.outcome, reward.payment)
+        };
+
+        // Observed score minus reference score.
+        assert_eq!(
+            payment(auction(1, 1, 40, settlement(10, Some((60, 10))))),
+            (Outcome::Settled, 30.into())
+        );
+        // Capped from above.
+        assert_eq!(
+            payment(auction(1, 1, 40, settlement(9, Some((600, 10))))),
+            (Outcome::Settled, 100.into())
+        );
+        // A settlement scoring less than the reference is a penalty.
+        assert_eq!(
+            payment(auction(1, 1, 40, settlement(9, Some((10, 10))))),
+            (Outcome::Settled, (-20).into())
+        );
+        // Failed and late settlements are penalised with the capped reference
+        // score.
+        assert_eq!(
+            payment(auction(1, 1, 40, None)),
+            (Outcome::Failed, (-40).into())
+        );
+        assert_eq!(
+            payment(auction(1, 1, 400, settlement(11, Some((600, 10))))),
+            (Outcome::Late, (-50).into())
+        );
+        // Not observed yet.
+        assert_eq!(
+            payment(auction(1, 1, 40, settlement(10, None))),
+            (Outcome::Pending, 0.into())
+        );
+    }
+
+    #[test]
+    fn fails_only_once_deadline_is_final() {
+        let outcome = |final_block| auction(1, 1, 40, None).outcome(final_block);
+        assert_eq!(outcome(5), Outcome::Pending);
+        assert_eq!(outcome(10), Outcome::Pending);
+        assert_eq!(outcome(11), Outcome::Failed);
+    }
+
+    #[test]
+    fn solver_totals() {
+        let report = report(
+            10..20,
+            20,
+            CAPS,
+            vec![
+                auction(3, 2, 40, None),
+                auction(1, 1, 40, settlement(10, Some((60, 10)))),
+                auction(2, 1, 40, settlement(10, None)),
+            ],
+            [
+                (H160::from_low_u64_be(2), 3),
+                (H160::from_low_u64_be(1), 2),
+                (H160::from_low_u64_be(3), 3),
+            ],
+        );
+
+        assert_eq!(
+            report
+                .auctions
+                .iter()
+                .map(|auction| auction.auction_id)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            report.solvers,
+            vec![
+                SolverReward {
+                    solver: H160::from_low_u64_be(1),
+                    participated_auctions: 2,
+                    won_auctions: 2,
+                    failed_auctions: 0,
+                    pending_auctions: 1,
+                    payment: 30.into(),
+                    execution_costs: 6.into(),
+                },
+                SolverReward {
+                    solver: H160::from_low_u64_be(2),
+                    participated_auctions: 3,
+                    won_auctions: 1,
+                    failed_auctions: 1,
+                    pending_auctions: 0,
+                    payment: (-40).into(),
+                    execution_costs: 0.into(),
+                },
+                SolverReward {
+                    solver: H160::from_low_u64_be(3),
+                    participated_auctions: 3,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+}
@@ -1,7 +1,12 @@
 use {
     clap::Parser,
     ethcontract::H160,
-    shared::{arguments::display_option, ethrpc, http_client, logging_args_with_default_filter},
+    shared::{
+        arguments::{display_option, AccountArg},
+        ethrpc,
+        http_client,
+        logging_args_with_default_filter,
+    },
     std::time::Duration,
     tracing::level_filters::LevelFilter,
     url::Url,
@@ -52,12 +57,38 @@ pub struct Arguments {
     #[clap(long, env)]
     pub chain_id: Option<u64>,
 
-    /// Address of the ethflow contract
-    #[clap(long, env)]
-    pub ethflow_contract: H160,
+    /// Addresses of the ethflow contracts whose orders get refunded.
+    #[clap(long, env, alias = "ethflow-contract", use_value_delimiter = true)]
+    pub ethflow_contracts: Vec<H160>,
+
+    /// The account sending the refund transactions. Either a private key, an
+    /// AWS KMS key ARN or an address of an account managed by the node.
+    #[clap(long, env, alias = "refunder-pk", hide_env_values = true)]
+    pub refunder_account: AccountArg,
+
+    /// The maximum gas price in wei the refunder is willing to pay.
+    #[clap(long, env, default_value = "800000000000")]
+    pub max_gas_price: u64,
+
+    /// The estimated amount of gas it takes to refund a single order.
+    #[clap(long, env, default_value = "30000")]
+    pub gas_per_refund: u64,
+
+    /// The gas budget of a single refund transaction. Determines how many
+    /// orders get refunded together.
+    #[clap(long, env, default_value = "930000")]
+    pub max_gas_per_tx: u64,
+
+    /// The gas budget of all refund transactions sent out in a single loop.
+    /// The most valuable refunds relative to their gas get sent first.
+    #[clap(long, env, default_value = "930000")]
+    pub max_gas_per_loop: u64,
 
-    #[clap(long, env, hide_env_values = true)]
-    pub refunder_pk: String,
+    /// Orders whose refunded value is less than this multiple of the costs of
+    /// refunding them at the current gas price are not refunded until the gas
+    /// price drops. 0 refunds all orders regardless of the costs.
+    #[clap(long, env, default_value = "0")]
+    pub min_value_to_cost_ratio: f64,
 
     /// The port at which we serve our metrics
     #[clap(long, env, default_value = "9590")]
@@ -73,8 +104,17 @@ impl std::fmt::Display for Arguments {
         writeln!(f, "db_url: SECRET")?;
         writeln!(f, "node_url: {}", self.node_url)?;
         display_option(f, "chain_id", &self.chain_id)?;
-        writeln!(f, "ethflow_contract: {:?}", self.ethflow_contract)?;
-        writeln!(f, "refunder_pk: SECRET")?;
+        writeln!(f, "ethflow_contracts: {:?}", self.ethflow_contracts)?;
+        writeln!(f, "refunder_account: {:?}", self.refunder_account)?;
+        writeln!(f, "max_gas_price: {}", self.max_gas_price)?;
+        writeln!(f, "gas_per_refund: {}", self.gas_per_refund)?;
+        writeln!(f, "max_gas_per_tx: {}", self.max_gas_per_tx)?;
+        writeln!(f, "max_gas_per_loop: {}", self.max_gas_per_loop)?;
+        writeln!(
+            f,
+            "min_value_to_cost_ratio: {}",
+            self.min_value_to_cost_ratio
+        )?;
         writeln!(f, "metrics_port: {}", self.metrics_port)?;
         Ok(())
     }
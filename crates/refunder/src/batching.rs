@@ -0,0 +1,207 @@
+//! Groups refundable orders into refund transactions.
+//!
+//! Every transaction refunds orders of a single ethflow contract and stays
+//! within the gas budget of a transaction. The batches that refund the most
+//! value per gas get sent first until the gas budget of the loop is used up.
+
+use {
+    crate::ethflow_order::EncodedEthflowOrder,
+    database::OrderUid,
+    ethcontract::{H160, U256},
+    std::collections::BTreeMap,
+};
+
+/// Gas of a refund transaction that doesn't depend on the number of refunded
+/// orders.
+const TX_BASE_GAS: u64 = 30_000;
+
+/// Gas budgets of the refund transactions.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// Estimated gas it takes to refund a single order.
+    pub gas_per_refund: u64,
+    pub max_gas_per_tx: u64,
+    pub max_gas_per_loop: u64,
+    /// Orders refunding less than this multiple of their refund costs get
+    /// skipped.
+    pub min_value_to_cost_ratio: f64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            gas_per_refund: 30_000,
+            max_gas_per_tx: 930_000,
+            max_gas_per_loop: 930_000,
+            min_value_to_cost_ratio: 0.,
+        }
+    }
+}
+
+impl Limits {
+    fn refunds_per_tx(&self) -> usize {
+        let refunds = self.max_gas_per_tx.saturating_sub(TX_BASE_GAS) / self.gas_per_refund.max(1);
+        (refunds as usize).max(1)
+    }
+
+    fn gas(&self, refunds: usize) -> u64 {
+        TX_BASE_GAS + self.gas_per_refund * refunds as u64
+    }
+}
+
+/// An order that can be refunded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Refund {
+    pub uid: OrderUid,
+    /// The ethflow contract that owns the order.
+    pub contract: H160,
+    /// The amount of ETH that gets refunded.
+    pub value: U256,
+    pub order: EncodedEthflowOrder,
+}
+
+/// Refunds that get sent out in a single transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Batch {
+    pub contract: H160,
+    pub refunds: Vec<Refund>,
+    pub gas: u64,
+}
+
+impl Batch {
+    fn value_per_gas(&self) -> f64 {
+        let value = self
+            .refunds
+            .iter()
+            .fold(U256::zero(), |sum, refund| sum.saturating_add(refund.value));
+        value.to_f64_lossy() / self.gas as f64
+    }
+}
+
+/// Splits the refunds into the batches that should be sent out in the order in
+/// which they should be sent out.
+pub fn batches(refunds: Vec<Refund>, limits: &Limits, gas_price: f64) -> Vec<Batch> {
+    let cost_per_refund = limits.gas_per_refund as f64 * gas_price;
+    let mut per_contract = BTreeMap::<H160, Vec<Refund>>::new();
+    for refund in refunds {
+        if refund.value.to_f64_lossy() < cost_per_refund * limits.min_value_to_cost_ratio {
+            tracing::debug!(uid = ?refund.uid, value = %refund.value, "refund too costly");
+            continue;
+        }
+        per_contract
+            .entry(refund.contract)
+            .or_default()
+            .push(refund);
+    }
+
+    let mut batches = Vec::new();
+    for (contract, mut refunds) in per_contract {
+        refunds.sort_by(|a, b| b.value.cmp(&a.value));
+        for chunk in refunds.chunks(limits.refunds_per_tx()) {
+            batches.push(Batch {
+                contract,
+                refunds: chunk.to_vec(),
+                gas: limits.gas(chunk.len()),
+            });
+        }
+    }
+    batches.sort_by(|a, b| b.value_per_gas().total_cmp(&a.value_per_gas()));
+
+    let mut gas = 0;
+    batches
+        .into_iter()
+        .enumerate()
+        .take_while(|(i, batch)| {
+            gas += batch.gas;
+            *i == 0 || gas <= limits.max_gas_per_loop
+        })
+        .map(|(_, batch)| batch)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, database::byte_array::ByteArray, ethcontract::Bytes};
+
+    fn refund(uid: u8, contract: u8, value: u64) -> Refund {
+        Refund {
+            uid: ByteArray([uid; 56]),
+            contract: H160([contract; 20]),
+            value: value.into(),
+            order: (
+                H160::zero(),
+                H160::zero(),
+                value.into(),
+                U256::zero(),
+                Bytes([0; 32]),
+                U256::zero(),
+                0,
+                false,
+                0,
+            ),
+        }
+    }
+
+    fn uids(batch: &Batch) -> Vec<u8> {
+        batch.refunds.iter().map(|refund| refund.uid.0[0]).collect()
+    }
+
+    #[test]
+    fn batches_by_contract_and_value() {
+        let limits = Limits {
+            gas_per_refund: 10_000,
+            max_gas_per_tx: 50_000,
+            max_gas_per_loop: 90_000,
+            min_value_to_cost_ratio: 0.,
+        };
+        let refunds = vec![
+            refund(1, 1, 100),
+            refund(2, 1, 300),
+            refund(3, 1, 200),
+            refund(4, 2, 1_000),
+        ];
+        let batches = batches(refunds, &limits, 1.);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].contract, H160([2; 20]));
+        assert_eq!(uids(&batches[0]), vec![4]);
+        assert_eq!(batches[0].gas, 40_000);
+        assert_eq!(batches[1].contract, H160([1; 20]));
+        assert_eq!(uids(&batches[1]), vec![2, 3]);
+        assert_eq!(batches[1].gas, 50_000);
+    }
+
+    #[test]
+    fn respects_loop_budget() {
+        let limits = Limits {
+            gas_per_refund: 10_000,
+            max_gas_per_tx: 40_000,
+            max_gas_per_loop: 50_000,
+            min_value_to_cost_ratio: 0.,
+        };
+        let refunds = vec![refund(1, 1, 100), refund(2, 1, 200), refund(3, 1, 300)];
+        let batches = batches(refunds, &limits, 1.);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(uids(&batches[0]), vec![3]);
+
+        // The most valuable batch gets sent even if it exceeds the budget.
+        let limits = Limits {
+            max_gas_per_loop: 0,
+            ..limits
+        };
+        let batches = super::batches(vec![refund(1, 1, 100)], &limits, 1.);
+        assert_eq!(batches.len(), 1);
+    }
+
+    #[test]
+    fn skips_costly_refunds() {
+        let limits = Limits {
+            gas_per_refund: 10,
+            min_value_to_cost_ratio: 2.,
+            ..Default::default()
+        };
+        let refunds = vec![refund(1, 1, 199), refund(2, 1, 200)];
+        let batches = batches(refunds, &limits, 10.);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(uids(&batches[0]), vec![2]);
+    }
+}
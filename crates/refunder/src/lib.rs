@@ -1,4 +1,5 @@
 pub mod arguments;
+pub mod batching;
 pub mod ethflow_order;
 pub mod refund_service;
 pub mod submitter;
@@ -7,7 +8,6 @@ use {
     crate::arguments::Arguments,
     clap::Parser,
     contracts::CoWSwapEthFlow,
-    ethcontract::{Account, PrivateKey},
     refund_service::RefundService,
     shared::{http_client::HttpClientFactory, metrics::LivenessChecking},
     sqlx::PgPool,
@@ -35,13 +35,13 @@ pub async fn start(args: impl Iterator<Item = String>) {
 pub async fn run(args: arguments::Arguments) {
     let http_factory = HttpClientFactory::new(&args.http_client);
     let web3 = shared::ethrpc::web3(&args.ethrpc, &http_factory, &args.node_url, "base");
+    let chain_id = web3
+        .eth()
+        .chain_id()
+        .await
+        .expect("Could not get chainId")
+        .as_u64();
     if let Some(expected_chain_id) = args.chain_id {
-        let chain_id = web3
-            .eth()
-            .chain_id()
-            .await
-            .expect("Could not get chainId")
-            .as_u64();
         assert_eq!(
             chain_id, expected_chain_id,
             "connected to node with incorrect chain ID",
@@ -56,15 +56,34 @@ pub async fn run(args: arguments::Arguments) {
     });
     shared::metrics::serve_metrics(liveness.clone(), ([0, 0, 0, 0], args.metrics_port).into());
 
-    let ethflow_contract = CoWSwapEthFlow::at(&web3, args.ethflow_contract);
-    let refunder_account = Account::Offline(args.refunder_pk.parse::<PrivateKey>().unwrap(), None);
+    assert!(
+        !args.ethflow_contracts.is_empty(),
+        "at least one ethflow contract needs to be configured"
+    );
+    let ethflow_contracts = args
+        .ethflow_contracts
+        .iter()
+        .map(|address| CoWSwapEthFlow::at(&web3, *address))
+        .collect();
+    let refunder_account = args
+        .refunder_account
+        .into_account(chain_id)
+        .await
+        .expect("failed to load refunder account");
     let mut refunder = RefundService::new(
         pg_pool,
         web3,
-        ethflow_contract,
+        ethflow_contracts,
         args.min_validity_duration.as_secs() as i64,
         args.min_slippage_bps,
         refunder_account,
+        args.max_gas_price,
+        batching::Limits {
+            gas_per_refund: args.gas_per_refund,
+            max_gas_per_tx: args.max_gas_per_tx,
+            max_gas_per_loop: args.max_gas_per_loop,
+            min_value_to_cost_ratio: args.min_value_to_cost_ratio,
+        },
     );
     loop {
         tracing::info!("Staring a new refunding loop");
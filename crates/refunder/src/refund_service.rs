@@ -1,10 +1,15 @@
 use {
-    super::ethflow_order::{order_to_ethflow_data, EncodedEthflowOrder, EthflowOrder},
-    crate::submitter::Submitter,
+    super::ethflow_order::order_to_ethflow_data,
+    crate::{
+        batching::{self, Limits, Refund},
+        submitter::{Submission, Submitter},
+    },
     anyhow::{anyhow, Context, Result},
+    chrono::{DateTime, Utc},
     contracts::CoWSwapEthFlow,
     database::{
         ethflow_orders::{read_order, refundable_orders, EthOrderPlacement},
+        order_events::{insert_refund_event, latest_refund_event, OrderEvent, OrderEventLabel},
         orders::read_order as read_db_order,
         OrderUid,
     },
@@ -16,19 +21,20 @@ use {
         MAX_BATCH_SIZE,
     },
     futures::{stream, StreamExt},
+    gas_estimation::GasPriceEstimating,
     sqlx::PgPool,
 };
 
 pub const NO_OWNER: H160 = H160([0u8; 20]);
 pub const INVALIDATED_OWNER: H160 = H160([255u8; 20]);
-const MAX_NUMBER_OF_UIDS_PER_REFUND_TX: usize = 30;
 
 pub struct RefundService {
     pub db: PgPool,
     pub web3: Web3,
-    pub ethflow_contract: CoWSwapEthFlow,
+    pub ethflow_contracts: Vec<CoWSwapEthFlow>,
     pub min_validity_duration: i64,
     pub min_slippage: f64,
+    pub limits: Limits,
     pub submitter: Submitter,
 }
 
@@ -40,27 +46,32 @@ enum RefundStatus {
 }
 
 impl RefundService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: PgPool,
         web3: Web3,
-        ethflow_contract: CoWSwapEthFlow,
+        ethflow_contracts: Vec<CoWSwapEthFlow>,
         min_validity_duration: i64,
         min_slippage_bps: u64,
         account: Account,
+        max_gas_price: u64,
+        limits: Limits,
     ) -> Self {
         RefundService {
             db,
             web3: web3.clone(),
-            ethflow_contract: ethflow_contract.clone(),
+            ethflow_contracts,
             min_validity_duration,
             min_slippage: min_slippage_bps as f64 / 10000f64,
+            limits,
             submitter: Submitter {
                 web3: web3.clone(),
-                ethflow_contract,
                 account,
                 gas_estimator: Box::new(web3),
                 gas_parameters_of_last_tx: None,
                 nonce_of_last_submission: None,
+                next_nonce: None,
+                max_gas_price,
             },
         }
     }
@@ -68,11 +79,11 @@ impl RefundService {
     pub async fn try_to_refund_all_eligble_orders(&mut self) -> Result<()> {
         let refundable_order_uids = self.get_refundable_ethflow_orders_from_db().await?;
 
-        let to_be_refunded_uids = self
-            .identify_uids_refunding_status_via_web3_calls(refundable_order_uids)
-            .await?;
+        let refunds = self.get_refunds_from_db(refundable_order_uids).await;
+
+        let to_be_refunded = self.identify_refunds_status_via_web3_calls(refunds).await?;
 
-        self.send_out_refunding_tx(to_be_refunded_uids).await?;
+        self.send_out_refunding_txs(to_be_refunded).await?;
         Ok(())
     }
 
@@ -95,22 +106,88 @@ impl RefundService {
         })
     }
 
-    async fn identify_uids_refunding_status_via_web3_calls(
+    fn ethflow_contract(&self, address: H160) -> Option<&CoWSwapEthFlow> {
+        self.ethflow_contracts
+            .iter()
+            .find(|contract| contract.address() == address)
+    }
+
+    /// Loads the data needed to refund the orders. Orders of unknown ethflow
+    /// contracts are skipped.
+    async fn get_refunds_from_db(&self, orders: Vec<EthOrderPlacement>) -> Vec<Refund> {
+        let futures = orders.iter().map(|order| {
+            let uid = order.uid;
+            async move {
+                self.get_refund_from_db(&uid)
+                    .await
+                    .context(format!("uid {uid:?}"))
+            }
+        });
+        let refunds: Vec<Refund> = stream::iter(futures)
+            .buffer_unordered(10)
+            .filter_map(|result| async {
+                match result {
+                    Ok(refund) => Some(refund),
+                    Err(err) => {
+                        tracing::error!(?err, "failed to get data from db");
+                        None
+                    }
+                }
+            })
+            .collect()
+            .await;
+
+        let (known, unknown): (Vec<_>, Vec<_>) = refunds
+            .into_iter()
+            .partition(|refund| self.ethflow_contract(refund.contract).is_some());
+        if !unknown.is_empty() {
+            tracing::warn!(
+                "Skipping orders of unknown ethflow contracts. Uids: {:?}",
+                unknown.iter().map(|refund| refund.uid).collect::<Vec<_>>()
+            );
+        }
+        known
+    }
+
+    async fn get_refund_from_db(&self, uid: &OrderUid) -> Result<Refund> {
+        let mut ex = self.db.acquire().await.context("acquire")?;
+        let order = read_db_order(&mut ex, uid)
+            .await
+            .context("read order")?
+            .context("missing order")?;
+        let ethflow_order = read_order(&mut ex, uid)
+            .await
+            .context("read ethflow order")?
+            .context("missing ethflow order")?;
+        // Ethflow orders are owned by the ethflow contract they were placed in.
+        let contract = H160(order.owner.0);
+        let ethflow_order = order_to_ethflow_data(order, ethflow_order);
+        Ok(Refund {
+            uid: *uid,
+            contract,
+            value: ethflow_order
+                .sell_amount
+                .saturating_add(ethflow_order.fee_amount),
+            order: ethflow_order.encode(),
+        })
+    }
+
+    async fn identify_refunds_status_via_web3_calls(
         &self,
-        refundable_order_uids: Vec<EthOrderPlacement>,
-    ) -> Result<Vec<OrderUid>> {
+        refunds: Vec<Refund>,
+    ) -> Result<Vec<Refund>> {
         let mut batch = Web3CallBatch::new(self.web3.transport().clone());
-        let futures = refundable_order_uids
-            .iter()
-            .map(|eth_order_placement| {
-                let order_hash: [u8; 32] = eth_order_placement.uid.0[0..32]
+        let futures = refunds
+            .into_iter()
+            .filter_map(|refund| {
+                let order_hash: [u8; 32] = refund.uid.0[0..32]
                     .try_into()
                     .expect("order_uid slice with incorrect length");
                 let order = self
-                    .ethflow_contract
+                    .ethflow_contract(refund.contract)?
                     .orders(ethcontract::tokens::Bytes(order_hash))
                     .batch_call(&mut batch);
-                async move {
+                Some(async move {
                     let order_owner = match order.await {
                         Ok(order) => Some(order.0),
                         Err(err) => {
@@ -129,20 +206,21 @@ impl RefundService {
                         // any other owner
                         _ => RefundStatus::NotYetRefunded,
                     };
-                    Some((eth_order_placement.uid, refund_status))
-                }
+                    Some((refund, refund_status))
+                })
             })
             .collect::<Vec<_>>();
 
         batch.execute_all(MAX_BATCH_SIZE).await;
-        let uid_with_latest_refundablility = futures::future::join_all(futures).await;
-        let mut to_be_refunded_uids = Vec::new();
+        let refunds_with_latest_refundablility = futures::future::join_all(futures).await;
+        let mut to_be_refunded = Vec::new();
+        let mut refunded_uids = Vec::new();
         let mut invalid_uids = Vec::new();
-        for (uid, refund_status) in uid_with_latest_refundablility.into_iter().flatten() {
+        for (refund, refund_status) in refunds_with_latest_refundablility.into_iter().flatten() {
             match refund_status {
-                RefundStatus::Refunded => (),
-                RefundStatus::Invalid => invalid_uids.push(uid),
-                RefundStatus::NotYetRefunded => to_be_refunded_uids.push(uid),
+                RefundStatus::Refunded => refunded_uids.push(refund.uid),
+                RefundStatus::Invalid => invalid_uids.push(refund.uid),
+                RefundStatus::NotYetRefunded => to_be_refunded.push(refund),
             }
         }
         if !invalid_uids.is_empty() {
@@ -154,59 +232,93 @@ impl RefundService {
                 invalid_uids
             );
         }
-        Ok(to_be_refunded_uids)
+        // The refund transaction might not be indexed yet.
+        self.store_refund_events(&refunded_uids, OrderEventLabel::Refunded)
+            .await;
+        Ok(to_be_refunded)
     }
 
-    async fn get_ethflow_data_from_db(&self, uid: &OrderUid) -> Result<EthflowOrder> {
-        let mut ex = self.db.acquire().await.context("acquire")?;
-        let order = read_db_order(&mut ex, uid)
-            .await
-            .context("read order")?
-            .context("missing order")?;
-        let ethflow_order = read_order(&mut ex, uid)
-            .await
-            .context("read ethflow order")?
-            .context("missing ethflow order")?;
-        Ok(order_to_ethflow_data(order, ethflow_order))
+    /// Records the progress of refunding the orders. Failing to do so is only
+    /// logged since the events are purely informational.
+    async fn store_refund_events(&self, uids: &[OrderUid], label: OrderEventLabel) {
+        if uids.is_empty() {
+            return;
+        }
+        if let Err(err) = store_refund_events(&self.db, uids, label, Utc::now()).await {
+            tracing::warn!(?err, ?label, "failed to insert refund events");
+        }
     }
 
-    async fn send_out_refunding_tx(&mut self, uids: Vec<OrderUid>) -> Result<()> {
-        if uids.is_empty() {
+    async fn send_out_refunding_txs(&mut self, refunds: Vec<Refund>) -> Result<()> {
+        if refunds.is_empty() {
             return Ok(());
         }
-        // only try to refund MAX_NUMBER_OF_UIDS_PER_REFUND_TX uids, in order to fit
-        // into gas limit
-        let uids: Vec<OrderUid> = uids
-            .into_iter()
-            .take(MAX_NUMBER_OF_UIDS_PER_REFUND_TX)
-            .collect();
-
-        tracing::debug!("Trying to refund the following uids: {:?}", uids);
+        let gas_price = self
+            .submitter
+            .gas_estimator
+            .estimate()
+            .await?
+            .effective_gas_price();
 
-        let futures = uids.iter().map(|uid| {
-            let (uid, self_) = (*uid, &self);
-            async move {
-                self_
-                    .get_ethflow_data_from_db(&uid)
-                    .await
-                    .context(format!("uid {uid:?}"))
+        for batch in batching::batches(refunds, &self.limits, gas_price) {
+            let contract = self
+                .ethflow_contract(batch.contract)
+                .context("unknown ethflow contract")?
+                .clone();
+            let (uids, encoded_ethflow_orders): (Vec<_>, Vec<_>) = batch
+                .refunds
+                .into_iter()
+                .map(|refund| (refund.uid, refund.order))
+                .unzip();
+            tracing::debug!(
+                contract = ?batch.contract,
+                "Trying to refund the following uids: {:?}",
+                uids
+            );
+            self.store_refund_events(&uids, OrderEventLabel::RefundPending)
+                .await;
+            let submission = self
+                .submitter
+                .submit(&contract, &uids, encoded_ethflow_orders)
+                .await?;
+            let label = match submission {
+                Submission::Confirmed | Submission::Unconfirmed => OrderEventLabel::RefundSubmitted,
+                Submission::Failed => OrderEventLabel::RefundFailed,
+            };
+            self.store_refund_events(&uids, label).await;
+            if submission == Submission::Unconfirmed {
+                // The next batch would use the nonce of the pending transaction and replace
+                // it, so wait for the transaction to get mined or bump its gas price instead.
+                tracing::debug!("stopping refunds until the pending transaction is mined");
+                break;
             }
-        });
-        let encoded_ethflow_orders: Vec<EncodedEthflowOrder> = stream::iter(futures)
-            .buffer_unordered(10)
-            .filter_map(|result| async {
-                match result {
-                    Ok(order) => Some(order.encode()),
-                    Err(err) => {
-                        tracing::error!(?err, "failed to get data from db");
-                        None
-                    }
-                }
-            })
-            .collect()
-            .await;
-
-        self.submitter.submit(uids, encoded_ethflow_orders).await?;
+        }
         Ok(())
     }
 }
+
+async fn store_refund_events(
+    db: &PgPool,
+    uids: &[OrderUid],
+    label: OrderEventLabel,
+    timestamp: DateTime<Utc>,
+) -> Result<()> {
+    let mut ex = db.begin().await.context("begin transaction")?;
+    for uid in uids {
+        // Orders only become pending once. Afterwards their status describes
+        // the latest refund attempt.
+        if label == OrderEventLabel::RefundPending
+            && latest_refund_event(&mut ex, uid).await?.is_some()
+        {
+            continue;
+        }
+        let event = OrderEvent {
+            order_uid: *uid,
+            timestamp,
+            label,
+        };
+        insert_refund_event(&mut ex, &event).await?;
+    }
+    ex.commit().await?;
+    Ok(())
+}
@@ -2,11 +2,12 @@
 // It tries to submit a tx - as EIP1559 - with a small tx tip,
 // but a quite high max_fee_per_gas such that it's likely being mined quickly
 //
-// Then it waits for 5 blocks. If the tx is not mined, it will return an error
-// and it needs to be called again. If the last submission was not successful,
-// this submitter stores the last gas_price in order to submit the new tx with
-// a higher gas price, in order to avoid: ErrReplaceUnderpriced erros
-// In the re-newed attempt for submission the same nonce is used as before.
+// Then it waits for 5 blocks. If the tx is not mined, it is reported as
+// unconfirmed and it needs to be called again. If the last submission was not
+// successful, this submitter stores the last gas_price in order to submit the
+// new tx with a higher gas price, in order to avoid: ErrReplaceUnderpriced
+// erros In the re-newed attempt for submission the same nonce is used as
+// before.
 
 use {
     super::ethflow_order::EncodedEthflowOrder,
@@ -14,6 +15,7 @@ use {
     contracts::CoWSwapEthFlow,
     database::OrderUid,
     ethcontract::{
+        errors::ExecutionError,
         transaction::{confirm::ConfirmParams, ResolveCondition},
         Account,
         U256,
@@ -26,9 +28,6 @@ use {
     },
 };
 
-// Max gas price used for submitting transactions
-const MAX_GAS_PRICE: u64 = 800_000_000_000;
-
 // The gas price buffer determines the gas price buffer used to
 // send out EIP1559 txs.
 // Example: If the prevailing gas is 10Gwei and the buffer factor is 1.20
@@ -43,29 +42,51 @@ const GAS_PRICE_BUMP: f64 = 1.125;
 
 pub struct Submitter {
     pub web3: Web3,
-    pub ethflow_contract: CoWSwapEthFlow,
     pub account: Account,
     pub gas_estimator: Box<dyn GasPriceEstimating>,
     pub gas_parameters_of_last_tx: Option<GasPrice1559>,
     pub nonce_of_last_submission: Option<U256>,
+    /// The nonce following the last refund transaction known to be mined. The
+    /// node might not have processed the block including it yet.
+    pub next_nonce: Option<U256>,
+    /// Max gas price used for submitting transactions.
+    pub max_gas_price: u64,
+}
+
+/// The outcome of submitting a refund transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Submission {
+    /// The transaction got mined.
+    Confirmed,
+    /// The transaction didn't get mined in time. It is still pending and gets
+    /// replaced by the next submission.
+    Unconfirmed,
+    /// The node rejected the transaction.
+    Failed,
 }
 
 impl Submitter {
     async fn get_submission_nonce(&self) -> Result<U256> {
         // this command returns the tx count ever mined at the latest block
-        // Mempool tx are not considered.
-        self.web3
+        // Mempool tx are not considered, so that a pending refund tx gets replaced.
+        let mined = self
+            .web3
             .eth()
             .transaction_count(self.account.address(), None)
             .await
-            .map_err(|err| anyhow!("Could not get latest nonce due to err: {err}"))
+            .map_err(|err| anyhow!("Could not get latest nonce due to err: {err}"))?;
+        Ok(mined.max(self.next_nonce.unwrap_or_default()))
     }
 
+    /// Submits a transaction refunding the given orders. Failing to submit the
+    /// transaction is not an error since the orders will be refunded in the
+    /// next attempt.
     pub async fn submit(
         &mut self,
-        uids: Vec<OrderUid>,
+        ethflow_contract: &CoWSwapEthFlow,
+        uids: &[OrderUid],
         encoded_ethflow_orders: Vec<EncodedEthflowOrder>,
-    ) -> Result<()> {
+    ) -> Result<Submission> {
         let confirm_params = ConfirmParams {
             block_timeout: Some(5),
             ..Default::default()
@@ -78,12 +99,12 @@ impl Submitter {
             gas_price_estimation,
             nonce,
             self.nonce_of_last_submission,
+            self.max_gas_price,
         )?;
 
         self.gas_parameters_of_last_tx = Some(gas_price);
         self.nonce_of_last_submission = Some(nonce);
-        let tx_result = self
-            .ethflow_contract
+        let tx_result = ethflow_contract
             .invalidate_orders_ignoring_not_allowed(encoded_ethflow_orders)
             .gas_price(into_gas_price(&gas_price))
             .from(self.account.clone())
@@ -92,34 +113,45 @@ impl Submitter {
             .resolve(resolve_conditions)
             .send()
             .await;
-        match tx_result {
+        let submission = match tx_result {
             Ok(handle) => {
                 tracing::debug!(
                     "Tx to refund the orderuids {:?} yielded following result {:?}",
                     uids,
                     handle
                 );
+                self.next_nonce = Some(nonce + 1);
+                Submission::Confirmed
+            }
+            Err(ExecutionError::ConfirmTimeout(_)) => {
+                tracing::debug!(?uids, "refund transaction not mined in time");
+                Submission::Unconfirmed
             }
             Err(err) => {
                 let err = err.to_string();
                 if TX_ALREADY_MINED.iter().any(|msg| err.contains(msg)) {
                     // It could happen that the previous tx got mined right before the tx was
-                    // send.
+                    // send. The orders get refunded with the next nonce unless the previous tx
+                    // already refunded them.
                     tracing::debug!(?err, "transaction already mined");
+                    self.next_nonce = Some(nonce + 1);
+                    Submission::Unconfirmed
                 } else if TX_ALREADY_KNOWN.iter().any(|msg| err.contains(msg)) {
                     // This case means that the node is already aware of the tx
-                    // This can only happen after restarts, or close to the MAX_GAS_PRICE
+                    // This can only happen after restarts, or close to the max gas price
                     // as usually we would always increase the gas tip compared to previous tx.
                     // Hence, we irgnore the warning and just retry.
                     tracing::debug!(?err, "transaction already known");
+                    Submission::Unconfirmed
                 } else {
                     // Todo: Handle the error "replacement transaction underpriced"
-                    // This could happen after restarts or close to the MAX_GAS_PRICE
+                    // This could happen after restarts or close to the max gas price
                     tracing::warn!(?err, "submission failed");
+                    Submission::Failed
                 }
             }
-        }
-        Ok(())
+        };
+        Ok(submission)
     }
 }
 
@@ -128,6 +160,7 @@ fn calculate_submission_gas_price(
     web3_gas_estimation: GasPrice1559,
     newest_nonce: U256,
     nonce_of_last_submission: Option<U256>,
+    max_gas_price: u64,
 ) -> Result<GasPrice1559> {
     // The gas price of the refund tx is the current prevailing gas price
     // of the web3 gas estimation plus a buffer.
@@ -151,15 +184,15 @@ fn calculate_submission_gas_price(
         }
     }
 
-    if new_gas_price.max_fee_per_gas > MAX_GAS_PRICE as f64 {
+    if new_gas_price.max_fee_per_gas > max_gas_price as f64 {
         tracing::warn!(
             "Refunding txs are likely not mined in time, as the current gas price {:?} is higher \
-             than MAX_GAS_PRICE specified {:?}",
+             than the max gas price specified {:?}",
             new_gas_price.max_fee_per_gas,
-            MAX_GAS_PRICE
+            max_gas_price
         );
         new_gas_price.max_fee_per_gas =
-            f64::min(MAX_GAS_PRICE as f64, new_gas_price.max_fee_per_gas);
+            f64::min(max_gas_price as f64, new_gas_price.max_fee_per_gas);
     }
     new_gas_price.max_priority_fee_per_gas = f64::min(
         new_gas_price.max_priority_fee_per_gas,
@@ -172,6 +205,8 @@ fn calculate_submission_gas_price(
 mod tests {
     use super::*;
 
+    const MAX_GAS_PRICE: u64 = 800_000_000_000;
+
     #[test]
     fn test_calculate_submission_gas_price() {
         // First case: previous tx was successful
@@ -189,6 +224,7 @@ mod tests {
             web3_gas_estimation,
             newest_nonce,
             nonce_of_last_submission,
+            MAX_GAS_PRICE,
         )
         .unwrap();
         let expected_result = GasPrice1559 {
@@ -210,6 +246,7 @@ mod tests {
             web3_gas_estimation,
             newest_nonce,
             nonce_of_last_submission,
+            MAX_GAS_PRICE,
         )
         .unwrap();
         let expected_result = GasPrice1559 {
@@ -232,6 +269,7 @@ mod tests {
             web3_gas_estimation,
             newest_nonce,
             nonce_of_last_submission,
+            MAX_GAS_PRICE,
         )
         .unwrap();
         let expected_result = GasPrice1559 {
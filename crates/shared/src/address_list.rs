@@ -0,0 +1,284 @@
+//! Sets of addresses (e.g. unsupported tokens or banned users) that can be
+//! changed at runtime by periodically reloading them from an external source.
+
+use {
+    anyhow::{Context, Result},
+    ethcontract::H160,
+    reqwest::{Client, Url},
+    std::{collections::HashSet, path::PathBuf, str::FromStr, sync::Arc, time::Duration},
+    tokio::sync::watch,
+    tracing::Instrument,
+};
+
+/// Where an address list gets loaded from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AddressListSource {
+    /// Local file containing a JSON array of addresses.
+    File(PathBuf),
+    /// URL serving a JSON array of addresses.
+    Url(Url),
+    /// The `deny_lists` table of the database.
+    Database,
+}
+
+impl FromStr for AddressListSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "database" {
+            return Ok(Self::Database);
+        }
+        if let Some(path) = s.strip_prefix("file://") {
+            return Ok(Self::File(path.into()));
+        }
+        if s.starts_with("http://") || s.starts_with("https://") {
+            return Ok(Self::Url(s.parse()?));
+        }
+        Ok(Self::File(s.into()))
+    }
+}
+
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait AddressListFetching: Send + Sync {
+    async fn fetch(&self) -> Result<HashSet<H160>>;
+}
+
+/// Reads a JSON array of addresses from a local file.
+pub struct FileAddressList(pub PathBuf);
+
+#[async_trait::async_trait]
+impl AddressListFetching for FileAddressList {
+    async fn fetch(&self) -> Result<HashSet<H160>> {
+        let content = tokio::fs::read(&self.0)
+            .await
+            .with_context(|| format!("failed to read {}", self.0.display()))?;
+        Ok(serde_json::from_slice(&content)?)
+    }
+}
+
+/// Fetches a JSON array of addresses from a URL.
+pub struct UrlAddressList {
+    pub client: Client,
+    pub url: Url,
+}
+
+#[async_trait::async_trait]
+impl AddressListFetching for UrlAddressList {
+    async fn fetch(&self) -> Result<HashSet<H160>> {
+        Ok(self
+            .client
+            .get(self.url.clone())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AddressListConfiguration {
+    /// Used to identify the list in logs.
+    pub name: &'static str,
+    /// Addresses that are always part of the list.
+    pub hardcoded: Vec<H160>,
+    /// Where additional addresses get loaded from.
+    pub source: Option<AddressListSource>,
+    pub client: Client,
+    pub update_interval: Duration,
+}
+
+/// A set of addresses that gets periodically refreshed in the background.
+/// Every change to the set gets logged to keep an audit trail of when
+/// addresses got added or removed.
+#[derive(Clone, Debug)]
+pub struct AutoUpdatingAddressList {
+    addresses: Arc<watch::Sender<HashSet<H160>>>,
+}
+
+impl AutoUpdatingAddressList {
+    /// Creates a list according to the configuration. Since the database
+    /// schema is only known to the individual services they have to provide
+    /// the fetcher used for [`AddressListSource::Database`].
+    pub async fn from_configuration(
+        configuration: AddressListConfiguration,
+        database: impl FnOnce() -> Arc<dyn AddressListFetching>,
+    ) -> Self {
+        let fetcher: Arc<dyn AddressListFetching> = match configuration.source {
+            None => return Self::from(configuration.hardcoded),
+            Some(AddressListSource::File(path)) => Arc::new(FileAddressList(path)),
+            Some(AddressListSource::Url(url)) => Arc::new(UrlAddressList {
+                client: configuration.client,
+                url,
+            }),
+            Some(AddressListSource::Database) => database(),
+        };
+        Self::from_fetcher(
+            configuration.name,
+            configuration.hardcoded,
+            fetcher,
+            configuration.update_interval,
+        )
+        .await
+    }
+
+    /// Creates a list that consists of the `hardcoded` addresses and whatever
+    /// `fetcher` returns. The list gets refreshed every `update_interval`. If
+    /// fetching fails the previous addresses are kept.
+    pub async fn from_fetcher(
+        name: &'static str,
+        hardcoded: Vec<H160>,
+        fetcher: Arc<dyn AddressListFetching>,
+        update_interval: Duration,
+    ) -> Self {
+        let list = Self::new(hardcoded.iter().copied().collect());
+        let update = {
+            let list = list.clone();
+            move || {
+                let list = list.clone();
+                let hardcoded = hardcoded.clone();
+                let fetcher = fetcher.clone();
+                async move {
+                    match fetcher.fetch().await {
+                        Ok(addresses) => {
+                            list.replace(addresses.into_iter().chain(hardcoded).collect())
+                        }
+                        Err(err) => tracing::error!(?err, "failed to update address list"),
+                    }
+                }
+            }
+        };
+
+        update()
+            .instrument(tracing::info_span!("auto_updating_address_list", name))
+            .await;
+
+        // spawn a background task to regularly update the address list
+        let updater = async move {
+            loop {
+                tokio::time::sleep(update_interval).await;
+                update().await;
+            }
+        };
+        tokio::task::spawn(
+            updater.instrument(tracing::info_span!("auto_updating_address_list", name)),
+        );
+
+        list
+    }
+
+    pub fn new(addresses: HashSet<H160>) -> Self {
+        Self {
+            addresses: Arc::new(watch::channel(addresses).0),
+        }
+    }
+
+    pub fn contains(&self, address: &H160) -> bool {
+        self.addresses.borrow().contains(address)
+    }
+
+    pub fn all(&self) -> HashSet<H160> {
+        self.addresses.borrow().clone()
+    }
+
+    /// Returns a receiver which gets notified whenever the list got refreshed,
+    /// even if the addresses didn't change.
+    pub fn subscribe(&self) -> watch::Receiver<HashSet<H160>> {
+        self.addresses.subscribe()
+    }
+
+    fn replace(&self, addresses: HashSet<H160>) {
+        self.addresses.send_modify(|current| {
+            let added: Vec<_> = addresses.difference(current).collect();
+            let removed: Vec<_> = current.difference(&addresses).collect();
+            if !added.is_empty() || !removed.is_empty() {
+                tracing::info!(?added, ?removed, "address list changed");
+            }
+            *current = addresses;
+        });
+    }
+}
+
+impl Default for AutoUpdatingAddressList {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl From<Vec<H160>> for AutoUpdatingAddressList {
+    fn from(addresses: Vec<H160>) -> Self {
+        Self::new(addresses.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, maplit::hashset};
+
+    #[test]
+    fn parses_source() {
+        assert_eq!(
+            "database".parse::<AddressListSource>().unwrap(),
+            AddressListSource::Database
+        );
+        assert_eq!(
+            "file:///etc/deny.json"
+                .parse::<AddressListSource>()
+                .unwrap(),
+            AddressListSource::File("/etc/deny.json".into())
+        );
+        assert_eq!(
+            "deny.json".parse::<AddressListSource>().unwrap(),
+            AddressListSource::File("deny.json".into())
+        );
+        assert_eq!(
+            "https://example.com/deny.json"
+                .parse::<AddressListSource>()
+                .unwrap(),
+            AddressListSource::Url("https://example.com/deny.json".parse().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn combines_hardcoded_and_fetched_addresses() {
+        let mut fetcher = MockAddressListFetching::new();
+        fetcher
+            .expect_fetch()
+            .returning(|| Ok(hashset![H160([2; 20])]));
+
+        let list = AutoUpdatingAddressList::from_fetcher(
+            "test",
+            vec![H160([1; 20])],
+            Arc::new(fetcher),
+            Duration::from_secs(3600),
+        )
+        .await;
+        assert_eq!(list.all(), hashset![H160([1; 20]), H160([2; 20])]);
+    }
+
+    #[tokio::test]
+    async fn keeps_addresses_if_update_fails() {
+        let mut fetcher = MockAddressListFetching::new();
+        fetcher
+            .expect_fetch()
+            .returning(|| Err(anyhow::anyhow!("unavailable")));
+
+        let list = AutoUpdatingAddressList::from_fetcher(
+            "test",
+            vec![H160([1; 20])],
+            Arc::new(fetcher),
+            Duration::from_secs(3600),
+        )
+        .await;
+        assert_eq!(list.all(), hashset![H160([1; 20])]);
+    }
+
+    #[test]
+    fn replaces_addresses() {
+        let list = AutoUpdatingAddressList::from(vec![H160([1; 20])]);
+        list.replace(hashset![H160([2; 20])]);
+        assert!(!list.contains(&H160([1; 20])));
+        assert!(list.contains(&H160([2; 20])));
+    }
+}
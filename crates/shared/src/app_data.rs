@@ -71,6 +71,19 @@ impl Validator {
     }
 }
 
+/// Returns the `appCode` of the full app data or `None` if the app data is
+/// invalid or doesn't specify one. Unlike [`Validator::validate`] this is
+/// lenient because it is used for app data that has already been accepted.
+pub fn app_code(full_app_data: &[u8]) -> Option<String> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Root {
+        app_code: Option<String>,
+    }
+
+    serde_json::from_slice::<Root>(full_app_data).ok()?.app_code
+}
+
 /// The root app data JSON object.
 ///
 /// App data JSON is organised in an object of the form
@@ -355,4 +368,15 @@ mod tests {
         let err = validator.validate(size_limit).unwrap_err();
         dbg!(err);
     }
+
+    #[test]
+    fn app_code_() {
+        assert_eq!(
+            app_code(br#"{"appCode":"CoW Swap","version":"0.9.0"}"#),
+            Some("CoW Swap".to_string())
+        );
+        assert_eq!(app_code(EMPTY.as_bytes()), None);
+        assert_eq!(app_code(br#"{"appCode":1}"#), None);
+        assert_eq!(app_code(b"not json"), None);
+    }
 }
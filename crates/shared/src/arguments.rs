@@ -3,9 +3,11 @@
 
 use {
     crate::{
+        event_handling::Finality,
         gas_price_estimation::GasEstimatorType,
         price_estimation::PriceEstimators,
         rate_limiter::RateLimitingStrategy,
+        request_signing::Secret,
         sources::{
             balancer_v2::BalancerFactoryKind,
             uniswap_v2::UniV2BaselineSourceParameters,
@@ -13,9 +15,9 @@ use {
         },
         tenderly_api,
     },
-    anyhow::{ensure, Context, Result},
+    anyhow::{anyhow, ensure, Context, Result},
     bigdecimal::BigDecimal,
-    ethcontract::{H160, H256, U256},
+    ethcontract::{transaction::kms, Account, PrivateKey, H160, H256, U256},
     std::{
         fmt::{self, Display, Formatter},
         num::{NonZeroU64, ParseFloatError},
@@ -288,6 +290,26 @@ pub struct Arguments {
     #[clap(long, env)]
     pub solver_competition_auth: Option<String>,
 
+    /// How to determine which blocks can no longer get reorged. Settlement
+    /// observations are only computed for final blocks and, with `finalized`,
+    /// reorgs deeper than the indexing history are rolled back to the node's
+    /// finalized block. Solver rewards only treat auctions as failed once
+    /// their deadline is final.
+    #[clap(long, env, value_enum, default_value = "reorg-depth")]
+    pub finality: Finality,
+
+    /// The secret used to sign requests to the drivers, both for solving
+    /// auctions and for quoting. Requests are sent unsigned if this is not set.
+    #[clap(long, env)]
+    pub driver_auth_secret: Option<Secret>,
+
+    /// The key the secrets of webhooks are derived from. The orderbook hands
+    /// out the derived secret when a webhook gets registered and the autopilot
+    /// signs the deliveries with it, so both need the same key. Webhooks are
+    /// disabled if this is not set.
+    #[clap(long, env)]
+    pub webhook_signing_key: Option<Secret>,
+
     /// If liquidity pool fetcher has caching mechanism, this argument defines
     /// how old pool data is allowed to be before updating
     #[clap(
@@ -461,6 +483,9 @@ impl Display for Arguments {
             self.balancer_pool_deny_list
         )?;
         display_secret_option(f, "solver_competition_auth", &self.solver_competition_auth)?;
+        writeln!(f, "finality: {:?}", self.finality)?;
+        display_secret_option(f, "driver_auth_secret", &self.driver_auth_secret)?;
+        display_secret_option(f, "webhook_signing_key", &self.webhook_signing_key)?;
         display_option(
             f,
             "network_block_interval",
@@ -588,6 +613,85 @@ impl FromStr for RateLimitingStrategy {
     }
 }
 
+/// An account to sign transactions with.
+#[derive(Clone)]
+pub enum AccountArg {
+    PrivateKey(PrivateKey),
+    Kms(Arn),
+    Address(H160),
+}
+
+impl fmt::Debug for AccountArg {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AccountArg::PrivateKey(k) => write!(f, "PrivateKey({:?})", k.public_address()),
+            AccountArg::Kms(key_id) => write!(f, "KMS({key_id:?})"),
+            AccountArg::Address(a) => write!(f, "Address({a:?})"),
+        }
+    }
+}
+
+impl AccountArg {
+    pub async fn into_account(self, chain_id: u64) -> Result<Account> {
+        Ok(match self {
+            AccountArg::PrivateKey(key) => Account::Offline(key, Some(chain_id)),
+            AccountArg::Kms(key_id) => {
+                let config = ethcontract::aws_config::load_from_env().await;
+                let account = kms::Account::new((&config).into(), &key_id.0)
+                    .await
+                    .with_context(|| format!("unable to load KMS account {key_id:?}"))?;
+                Account::Kms(account, Some(chain_id))
+            }
+            AccountArg::Address(address) => Account::Local(address, None),
+        })
+    }
+}
+
+impl FromStr for AccountArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.parse::<PrivateKey>()
+            .map(AccountArg::PrivateKey)
+            .map_err(|pk_err| anyhow!("could not parse as private key: {}", pk_err))
+            .or_else(|error_chain| {
+                Ok(AccountArg::Address(s.parse().map_err(|addr_err| {
+                    error_chain.context(anyhow!("could not parse as address: {}", addr_err))
+                })?))
+            })
+            .or_else(|error_chain: Self::Err| {
+                let key_id = Arn::from_str(s).map_err(|arn_err| {
+                    error_chain.context(anyhow!("could not parse as AWS ARN: {}", arn_err))
+                })?;
+                Ok(AccountArg::Kms(key_id))
+            })
+            .map_err(|err: Self::Err| {
+                err.context(
+                    "invalid account, it is neither a private key, an Ethereum address, nor a KMS \
+                     key",
+                )
+            })
+    }
+}
+
+// Wrapper type for AWS ARN identifiers
+#[derive(Debug, Clone)]
+pub struct Arn(pub String);
+
+impl FromStr for Arn {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // Could be more strict here, but this should suffice to catch unintended
+        // configuration mistakes
+        if s.starts_with("arn:aws:kms:") {
+            Ok(Self(s.to_string()))
+        } else {
+            Err(anyhow!("Invalid ARN identifier: {}", s))
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -668,4 +772,35 @@ mod test {
         )
         .is_err());
     }
+
+    #[test]
+    fn parses_account_arg() {
+        assert!(matches!(
+            "0x4242424242424242424242424242424242424242424242424242424242424242"
+                .parse::<AccountArg>()
+                .unwrap(),
+            AccountArg::PrivateKey(key)
+                if key.public_address() == PrivateKey::from_raw([0x42; 32]).unwrap().public_address()
+        ));
+        assert!(matches!(
+            "0x4242424242424242424242424242424242424242"
+                .parse::<AccountArg>()
+                .unwrap(),
+            AccountArg::Address(address) if address == H160([0x42; 20])
+        ));
+        assert!(matches!(
+            "arn:aws:kms:eu-central-1:42:key/00000000-0000-0000-0000-00000000"
+                .parse::<AccountArg>()
+                .unwrap(),
+            AccountArg::Kms(_)
+        ));
+    }
+
+    #[test]
+    fn errors_on_invalid_account_arg() {
+        assert!("0x010203040506070809101112131415161718192021"
+            .parse::<AccountArg>()
+            .is_err());
+        assert!("not an account".parse::<AccountArg>().is_err());
+    }
 }
@@ -1,7 +1,9 @@
 use {
     super::{BadTokenDetecting, TokenQuality},
+    crate::address_list::AutoUpdatingAddressList,
     anyhow::Result,
     primitive_types::H160,
+    std::collections::HashSet,
 };
 
 /// If a token is neither in the allow nor the deny list treat it this way.
@@ -11,24 +13,34 @@ pub enum UnknownTokenStrategy {
     Forward(Box<dyn BadTokenDetecting>),
 }
 
-/// Classify tokens with explicit allow and deny lists.
+/// Classify tokens with explicit allow and deny lists. The deny list can change
+/// at runtime. Tokens that are on both lists are considered allowed.
 pub struct ListBasedDetector {
     allow_list: Vec<H160>,
-    deny_list: Vec<H160>,
+    deny_list: AutoUpdatingAddressList,
     strategy: UnknownTokenStrategy,
 }
 
 impl ListBasedDetector {
-    /// Panics if same token is both allowed and denied.
+    /// Tokens on both lists are most likely misconfigured, so they get logged
+    /// initially and whenever the deny list gets refreshed.
     pub fn new(
         allow_list: Vec<H160>,
-        deny_list: Vec<H160>,
+        deny_list: AutoUpdatingAddressList,
         strategy: UnknownTokenStrategy,
     ) -> Self {
-        assert!(
-            allow_list.iter().all(|token| !deny_list.contains(token)),
-            "token is allowed and denied"
-        );
+        if !allow_list.is_empty() {
+            let allowed = allow_list.clone();
+            let mut refreshes = deny_list.subscribe();
+            tokio::task::spawn(async move {
+                loop {
+                    warn_about_overlap(&allowed, &refreshes.borrow_and_update());
+                    if refreshes.changed().await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
         Self {
             allow_list,
             deny_list,
@@ -39,12 +51,25 @@ impl ListBasedDetector {
     pub fn deny_list(list: Vec<H160>) -> Self {
         Self {
             allow_list: Vec::new(),
-            deny_list: list,
+            deny_list: list.into(),
             strategy: UnknownTokenStrategy::Allow,
         }
     }
 }
 
+fn warn_about_overlap(allow_list: &[H160], deny_list: &HashSet<H160>) {
+    let overlap: Vec<_> = allow_list
+        .iter()
+        .filter(|token| deny_list.contains(token))
+        .collect();
+    if !overlap.is_empty() {
+        tracing::warn!(
+            ?overlap,
+            "tokens are allowed and denied, the allow list takes precedence"
+        );
+    }
+}
+
 #[async_trait::async_trait]
 impl BadTokenDetecting for ListBasedDetector {
     async fn detect(&self, token: ethcontract::H160) -> Result<TokenQuality> {
@@ -78,7 +103,7 @@ mod tests {
         let inner = MockBadTokenDetecting::new();
         let detector = ListBasedDetector {
             allow_list: vec![H160::from_low_u64_le(0)],
-            deny_list: vec![H160::from_low_u64_le(1)],
+            deny_list: vec![H160::from_low_u64_le(1)].into(),
             strategy: UnknownTokenStrategy::Forward(Box::new(inner)),
         };
 
@@ -95,11 +120,19 @@ mod tests {
         assert!(!result.unwrap().is_good());
     }
 
+    #[tokio::test]
+    async fn allow_list_takes_precedence() {
+        let token = H160::from_low_u64_le(0);
+        let detector =
+            ListBasedDetector::new(vec![token], vec![token].into(), UnknownTokenStrategy::Deny);
+        assert!(detector.detect(token).await.unwrap().is_good());
+    }
+
     #[test]
     fn not_in_list_default() {
         let detector = ListBasedDetector {
             allow_list: Vec::new(),
-            deny_list: Vec::new(),
+            deny_list: Default::default(),
             strategy: UnknownTokenStrategy::Allow,
         };
         let result = detector
@@ -110,7 +143,7 @@ mod tests {
 
         let detector = ListBasedDetector {
             allow_list: Vec::new(),
-            deny_list: Vec::new(),
+            deny_list: Default::default(),
             strategy: UnknownTokenStrategy::Deny,
         };
         let result = detector
@@ -130,7 +163,7 @@ mod tests {
 
         let detector = ListBasedDetector {
             allow_list: Vec::new(),
-            deny_list: Vec::new(),
+            deny_list: Default::default(),
             strategy: UnknownTokenStrategy::Forward(Box::new(inner)),
         };
 
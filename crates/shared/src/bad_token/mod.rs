@@ -1,6 +1,7 @@
 pub mod cache;
 pub mod instrumented;
 pub mod list_based;
+pub mod persisted;
 pub mod token_owner_finder;
 pub mod trace_call;
 
@@ -10,12 +11,29 @@ use {anyhow::Result, primitive_types::H160};
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum TokenQuality {
     Good,
-    Bad { reason: String },
+    /// The token can be traded but the token contract takes a fee on every
+    /// transfer.
+    Taxed {
+        /// The fee in basis points of the transferred amount.
+        transfer_fee_bps: u32,
+    },
+    Bad {
+        reason: String,
+    },
 }
 
 impl TokenQuality {
+    /// Whether the token can be traded. This includes taxed tokens.
     pub fn is_good(&self) -> bool {
-        matches!(self, Self::Good { .. })
+        matches!(self, Self::Good { .. } | Self::Taxed { .. })
+    }
+
+    /// The transfer fee in basis points if the token is taxed.
+    pub fn transfer_fee_bps(&self) -> Option<u32> {
+        match self {
+            Self::Taxed { transfer_fee_bps } => Some(*transfer_fee_bps),
+            _ => None,
+        }
     }
 
     pub fn bad(reason: impl ToString) -> Self {
@@ -0,0 +1,298 @@
+use {
+    super::{BadTokenDetecting, TokenQuality},
+    anyhow::{Context, Result},
+    chrono::{DateTime, Utc},
+    database::byte_array::ByteArray,
+    primitive_types::H160,
+    std::{sync::Arc, time::Duration},
+};
+
+/// A token quality verdict together with the time until which it is valid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredTokenQuality {
+    pub quality: TokenQuality,
+    pub expiration: DateTime<Utc>,
+}
+
+impl StoredTokenQuality {
+    /// Converts a row of the `token_quality` table.
+    pub fn from_row(row: database::token_quality::TokenQuality) -> Result<Self> {
+        let quality = match (row.good, row.transfer_fee_bps) {
+            (true, None) => TokenQuality::Good,
+            (true, Some(fee)) => TokenQuality::Taxed {
+                transfer_fee_bps: fee.try_into().context("invalid transfer fee")?,
+            },
+            (false, _) => TokenQuality::Bad {
+                reason: row.reason.unwrap_or_default(),
+            },
+        };
+        Ok(Self {
+            quality,
+            expiration: row.expiration,
+        })
+    }
+
+    /// Converts the verdict for the token into a row of the `token_quality`
+    /// table.
+    pub fn into_row(
+        self,
+        token: H160,
+        allow_taxed_tokens: bool,
+    ) -> Result<database::token_quality::TokenQuality> {
+        let (good, reason, transfer_fee_bps) = match self.quality {
+            TokenQuality::Good => (true, None, None),
+            TokenQuality::Taxed { transfer_fee_bps } => (
+                true,
+                None,
+                Some(
+                    transfer_fee_bps
+                        .try_into()
+                        .context("invalid transfer fee")?,
+                ),
+            ),
+            TokenQuality::Bad { reason } => (false, Some(reason), None),
+        };
+        Ok(database::token_quality::TokenQuality {
+            token: ByteArray(token.0),
+            good,
+            reason,
+            expiration: self.expiration,
+            transfer_fee_bps,
+            allow_taxed_tokens,
+        })
+    }
+}
+
+/// Storage for token quality verdicts that is shared between services.
+///
+/// Verdicts are stored separately for detectors that allow taxed tokens and
+/// those that don't because the same token can be taxed for the former and bad
+/// for the latter.
+#[mockall::automock]
+#[async_trait::async_trait]
+pub trait TokenQualityStoring: Send + Sync {
+    /// Returns the stored verdict for the token if it has not expired yet.
+    async fn get(
+        &self,
+        token: H160,
+        allow_taxed_tokens: bool,
+        now: DateTime<Utc>,
+    ) -> Result<Option<StoredTokenQuality>>;
+
+    async fn save(
+        &self,
+        token: H160,
+        allow_taxed_tokens: bool,
+        quality: StoredTokenQuality,
+    ) -> Result<()>;
+}
+
+/// Persists the verdicts of the inner detector so they survive restarts and
+/// can be reused by other services.
+pub struct PersistedDetector {
+    inner: Box<dyn BadTokenDetecting>,
+    storage: Arc<dyn TokenQualityStoring>,
+    expiry: chrono::Duration,
+    /// Whether the inner detector allows taxed tokens.
+    allow_taxed_tokens: bool,
+}
+
+impl PersistedDetector {
+    /// Creates a detector storing verdicts for the specified time. Expiries
+    /// too large to represent are clamped.
+    pub fn new(
+        inner: Box<dyn BadTokenDetecting>,
+        storage: Arc<dyn TokenQualityStoring>,
+        expiry: Duration,
+        allow_taxed_tokens: bool,
+    ) -> Self {
+        Self {
+            inner,
+            storage,
+            expiry: chrono::Duration::from_std(expiry)
+                .unwrap_or_else(|_| chrono::Duration::max_value()),
+            allow_taxed_tokens,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BadTokenDetecting for PersistedDetector {
+    async fn detect(&self, token: H160) -> Result<TokenQuality> {
+        let now = Utc::now();
+        // Failing to access the storage should not prevent us from detecting
+        // the token quality.
+        match self.storage.get(token, self.allow_taxed_tokens, now).await {
+            Ok(Some(stored)) => return Ok(stored.quality),
+            Ok(None) => (),
+            Err(err) => tracing::warn!(?err, ?token, "failed to load token quality"),
+        }
+
+        let quality = self.inner.detect(token).await?;
+        let stored = StoredTokenQuality {
+            quality: quality.clone(),
+            expiration: now
+                .checked_add_signed(self.expiry)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        };
+        if let Err(err) = self
+            .storage
+            .save(token, self.allow_taxed_tokens, stored)
+            .await
+        {
+            tracing::warn!(?err, ?token, "failed to store token quality");
+        }
+        Ok(quality)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::bad_token::MockBadTokenDetecting, futures::FutureExt};
+
+    #[test]
+    fn uses_stored_quality() {
+        // Would panic if called.
+        let inner = MockBadTokenDetecting::new();
+        let mut storage = MockTokenQualityStoring::new();
+        storage.expect_get().returning(|_, _, now| {
+            Ok(Some(StoredTokenQuality {
+                quality: TokenQuality::bad("stored"),
+                expiration: now + chrono::Duration::seconds(1),
+            }))
+        });
+
+        let detector = PersistedDetector::new(
+            Box::new(inner),
+            Arc::new(storage),
+            Duration::from_secs(1),
+            true,
+        );
+        let result = detector
+            .detect(H160::from_low_u64_le(0))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.unwrap(), TokenQuality::bad("stored"));
+    }
+
+    #[test]
+    fn stores_detected_quality() {
+        let mut inner = MockBadTokenDetecting::new();
+        inner
+            .expect_detect()
+            .times(1)
+            .returning(|_| Ok(TokenQuality::Good));
+        let mut storage = MockTokenQualityStoring::new();
+        storage
+            .expect_get()
+            .withf(|_, allow_taxed_tokens, _| *allow_taxed_tokens)
+            .returning(|_, _, _| Ok(None));
+        storage
+            .expect_save()
+            .times(1)
+            .withf(|token, allow_taxed_tokens, stored| {
+                *token == H160::from_low_u64_le(0)
+                    && *allow_taxed_tokens
+                    && stored.quality == TokenQuality::Good
+            })
+            .returning(|_, _, _| Ok(()));
+
+        let detector = PersistedDetector::new(
+            Box::new(inner),
+            Arc::new(storage),
+            Duration::from_secs(1),
+            true,
+        );
+        let result = detector
+            .detect(H160::from_low_u64_le(0))
+            .now_or_never()
+            .unwrap();
+        assert!(result.unwrap().is_good());
+    }
+
+    #[test]
+    fn detects_quality_if_storage_fails() {
+        let mut inner = MockBadTokenDetecting::new();
+        inner
+            .expect_detect()
+            .times(1)
+            .returning(|_| Ok(TokenQuality::Good));
+        let mut storage = MockTokenQualityStoring::new();
+        storage
+            .expect_get()
+            .returning(|_, _, _| Err(anyhow::anyhow!("unavailable")));
+        storage
+            .expect_save()
+            .returning(|_, _, _| Err(anyhow::anyhow!("unavailable")));
+
+        let detector = PersistedDetector::new(
+            Box::new(inner),
+            Arc::new(storage),
+            Duration::from_secs(1),
+            true,
+        );
+        let result = detector
+            .detect(H160::from_low_u64_le(0))
+            .now_or_never()
+            .unwrap();
+        assert!(result.unwrap().is_good());
+    }
+
+    #[test]
+    fn stores_verdicts_for_huge_expiries() {
+        let mut inner = MockBadTokenDetecting::new();
+        inner.expect_detect().returning(|_| Ok(TokenQuality::Good));
+        let mut storage = MockTokenQualityStoring::new();
+        storage.expect_get().returning(|_, _, _| Ok(None));
+        storage
+            .expect_save()
+            .times(1)
+            .withf(|_, _, stored| stored.expiration == DateTime::<Utc>::MAX_UTC)
+            .returning(|_, _, _| Ok(()));
+
+        let detector =
+            PersistedDetector::new(Box::new(inner), Arc::new(storage), Duration::MAX, false);
+        let result = detector
+            .detect(H160::from_low_u64_le(0))
+            .now_or_never()
+            .unwrap();
+        assert!(result.unwrap().is_good());
+    }
+
+    #[test]
+    fn converts_rows() {
+        let expiration = Utc::now();
+        for quality in [
+            TokenQuality::Good,
+            TokenQuality::Taxed {
+                transfer_fee_bps: 100,
+            },
+            TokenQuality::bad("reason"),
+        ] {
+            let stored = StoredTokenQuality {
+                quality,
+                expiration,
+            };
+            let row = stored
+                .clone()
+                .into_row(H160::from_low_u64_le(1), true)
+                .unwrap();
+            assert_eq!(row.token, ByteArray(H160::from_low_u64_le(1).0));
+            assert!(row.allow_taxed_tokens);
+            assert_eq!(StoredTokenQuality::from_row(row).unwrap(), stored);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_transfer_fees() {
+        let row = database::token_quality::TokenQuality {
+            token: ByteArray([1; 20]),
+            good: true,
+            reason: None,
+            expiration: Utc::now(),
+            transfer_fee_bps: Some(-1),
+            allow_taxed_tokens: true,
+        };
+        assert!(StoredTokenQuality::from_row(row).is_err());
+    }
+}
@@ -4,7 +4,7 @@ use {
     anyhow::{bail, ensure, Context, Result},
     contracts::ERC20,
     ethcontract::{dyns::DynTransport, transaction::TransactionBuilder, PrivateKey},
-    primitive_types::{H160, U256},
+    primitive_types::{H160, U256, U512},
     std::{cmp, sync::Arc},
     web3::{
         signing::keccak256,
@@ -18,11 +18,14 @@ use {
 /// Tokens are bad if:
 /// - we cannot find an amm pool of the token to one of the base tokens
 /// - transfer into the settlement contract or back out fails
-/// - a transfer loses total balance
+/// - a transfer loses total balance (unless taxed tokens are allowed)
 pub struct TraceCallDetector {
     pub web3: Web3,
     pub finder: Arc<dyn TokenOwnerFinding>,
     pub settlement_contract: H160,
+    /// Classify tokens that take a fee on transfer as
+    /// [`TokenQuality::Taxed`] instead of rejecting them.
+    pub allow_taxed_tokens: bool,
 }
 
 #[async_trait::async_trait]
@@ -72,11 +75,23 @@ impl TraceCallDetector {
         // Note that gas use can depend on the recipient because for the standard
         // implementation sending to an address that does not have any balance
         // yet (implicitly 0) causes an allocation.
-        let request = self.create_trace_request(token, amount, take_from);
+        // Taxed tokens credit less than the transferred amount to the settlement
+        // contract so we can't transfer the full amount back out.
+        let amount_out = match self.allow_taxed_tokens {
+            true => amount / 2,
+            false => amount,
+        };
+        let request = self.create_trace_request(token, amount, amount_out, take_from);
         let traces = trace_many::trace_many(request, &self.web3)
             .await
             .context("trace_many")?;
-        Self::handle_response(&traces, amount, take_from)
+        Self::handle_response(
+            &traces,
+            amount,
+            amount_out,
+            take_from,
+            self.allow_taxed_tokens,
+        )
     }
 
     // For the out transfer we use an arbitrary address without balance to detect
@@ -88,7 +103,13 @@ impl TraceCallDetector {
             .public_address()
     }
 
-    fn create_trace_request(&self, token: H160, amount: U256, take_from: H160) -> Vec<CallRequest> {
+    fn create_trace_request(
+        &self,
+        token: H160,
+        amount: U256,
+        amount_out: U256,
+        take_from: H160,
+    ) -> Vec<CallRequest> {
         let instance = ERC20::at(&self.web3, token);
 
         let mut requests = Vec::new();
@@ -107,7 +128,7 @@ impl TraceCallDetector {
         let tx = instance.balance_of(recipient).m.tx;
         requests.push(call_request(None, token, tx));
         // 4
-        let tx = instance.transfer(recipient, amount).tx;
+        let tx = instance.transfer(recipient, amount_out).tx;
         requests.push(call_request(Some(self.settlement_contract), token, tx));
         // 5
         let tx = instance.balance_of(self.settlement_contract).m.tx;
@@ -126,7 +147,9 @@ impl TraceCallDetector {
     fn handle_response(
         traces: &[BlockTrace],
         amount: U256,
+        amount_out: U256,
         take_from: H160,
+        allow_taxed_tokens: bool,
     ) -> Result<TokenQuality> {
         ensure!(traces.len() == 8, "unexpected number of traces");
 
@@ -194,38 +217,56 @@ impl TraceCallDetector {
                 )))
             }
         };
-        if balance_after_in != computed_balance_after_in {
-            return Ok(TokenQuality::bad(format!(
-                "Transferring {amount} into settlement contract was expected to result in a \
-                 balance of {computed_balance_after_in} but actually resulted in \
-                 {balance_after_in}. A common cause for this is that the token takes a fee on \
-                 transfer."
-            )));
-        }
-        if balance_after_out != balance_before_in {
+        let fee_in = match transfer_fee(
+            balance_before_in,
+            balance_after_in,
+            computed_balance_after_in,
+            allow_taxed_tokens,
+        ) {
+            Some(fee) => fee,
+            None => {
+                return Ok(TokenQuality::bad(format!(
+                    "Transferring {amount} into settlement contract was expected to result in a \
+                     balance of {computed_balance_after_in} but actually resulted in \
+                     {balance_after_in}. A common cause for this is that the token takes a fee on \
+                     transfer."
+                )))
+            }
+        };
+        let computed_balance_after_out = balance_after_in.checked_sub(amount_out);
+        if computed_balance_after_out != Some(balance_after_out) {
             return Ok(TokenQuality::bad(format!(
-                "Transferring {amount} out of settlement contract was expected to result in the \
-                 original balance of {balance_before_in} but actually resulted in \
+                "Transferring {amount_out} out of settlement contract was expected to result in a \
+                 balance of {computed_balance_after_out:?} but actually resulted in \
                  {balance_after_out}."
             )));
         }
-        let computed_balance_recipient_after = match balance_recipient_before.checked_add(amount) {
-            Some(amount) => amount,
+        let computed_balance_recipient_after =
+            match balance_recipient_before.checked_add(amount_out) {
+                Some(amount) => amount,
+                None => {
+                    return Ok(TokenQuality::bad(format!(
+                        "Transferring {amount_out} into arbitrary recipient {arbitrary:?} would \
+                         overflow its balance."
+                    )))
+                }
+            };
+        let fee_out = match transfer_fee(
+            balance_recipient_before,
+            balance_recipient_after,
+            computed_balance_recipient_after,
+            allow_taxed_tokens,
+        ) {
+            Some(fee) => fee,
             None => {
                 return Ok(TokenQuality::bad(format!(
-                    "Transferring {amount} into arbitrary recipient {arbitrary:?} would overflow \
-                     its balance."
+                    "Transferring {amount_out} into arbitrary recipient {arbitrary:?} was \
+                     expected to result in a balance of {computed_balance_recipient_after} but \
+                     actually resulted in {balance_recipient_after}. A common cause for this is \
+                     that the token takes a fee on transfer."
                 )))
             }
         };
-        if computed_balance_recipient_after != balance_recipient_after {
-            return Ok(TokenQuality::bad(format!(
-                "Transferring {amount} into arbitrary recipient {arbitrary:?} was expected to \
-                 result in a balance of {computed_balance_recipient_after} but actually resulted \
-                 in {balance_recipient_after}. A common cause for this is that the token takes a \
-                 fee on transfer."
-            )));
-        }
 
         if let Err(err) = ensure_transaction_ok_and_get_gas(&traces[7])? {
             return Ok(TokenQuality::bad(format!(
@@ -234,10 +275,46 @@ impl TraceCallDetector {
         }
 
         let _gas_per_transfer = (gas_in + gas_out) / 2;
+        let transfer_fee_bps = cmp::max(fee_bps(fee_in, amount), fee_bps(fee_out, amount_out));
+        if transfer_fee_bps > 0 {
+            return Ok(TokenQuality::Taxed { transfer_fee_bps });
+        }
         Ok(TokenQuality::Good)
     }
 }
 
+/// Returns how much of a transfer was lost to fees given the balances of the
+/// recipient before and after the transfer. Returns `None` if the balance
+/// change is unexpected, which includes any loss if taxed tokens are not
+/// allowed.
+fn transfer_fee(
+    balance_before: U256,
+    balance_after: U256,
+    expected_balance_after: U256,
+    allow_taxed_tokens: bool,
+) -> Option<U256> {
+    if balance_after == expected_balance_after {
+        return Some(U256::zero());
+    }
+    if allow_taxed_tokens
+        && balance_before < balance_after
+        && balance_after < expected_balance_after
+    {
+        return Some(expected_balance_after - balance_after);
+    }
+    None
+}
+
+/// The fee relative to the transferred amount in basis points, rounded up.
+fn fee_bps(fee: U256, amount: U256) -> u32 {
+    if fee.is_zero() {
+        return 0;
+    }
+    let amount = U512::from(amount);
+    let bps = (fee.full_mul(10_000.into()) + amount - 1) / amount;
+    bps.min(10_000.into()).low_u32()
+}
+
 fn call_request(
     from: Option<H160>,
     to: H160,
@@ -433,11 +510,93 @@ mod tests {
             },
         ];
 
-        let result = TraceCallDetector::handle_response(traces, 1.into(), H160::zero()).unwrap();
+        let result =
+            TraceCallDetector::handle_response(traces, 1.into(), 1.into(), H160::zero(), false)
+                .unwrap();
         let expected = TokenQuality::Good;
         assert_eq!(result, expected);
     }
 
+    fn balance_trace(balance: u64) -> BlockTrace {
+        BlockTrace {
+            output: encode_u256(balance.into()),
+            trace: None,
+            vm_trace: None,
+            state_diff: None,
+            transaction_hash: None,
+        }
+    }
+
+    fn transaction_trace() -> BlockTrace {
+        BlockTrace {
+            output: Default::default(),
+            trace: Some(vec![TransactionTrace {
+                trace_address: Vec::new(),
+                subtraces: 0,
+                action: Action::Call(Call {
+                    from: H160::zero(),
+                    to: H160::zero(),
+                    value: 0.into(),
+                    gas: 0.into(),
+                    input: Bytes(Vec::new()),
+                    call_type: CallType::None,
+                }),
+                action_type: ActionType::Call,
+                result: Some(Res::Call(CallResult {
+                    gas_used: 1.into(),
+                    output: Bytes(Vec::new()),
+                })),
+                error: None,
+            }]),
+            vm_trace: None,
+            state_diff: None,
+            transaction_hash: None,
+        }
+    }
+
+    #[test]
+    fn handle_response_taxed() {
+        // 1% of every transfer is taken as a fee.
+        let traces = &[
+            balance_trace(0),
+            transaction_trace(),
+            balance_trace(990),
+            balance_trace(0),
+            transaction_trace(),
+            balance_trace(490),
+            balance_trace(495),
+            transaction_trace(),
+        ];
+
+        let result =
+            TraceCallDetector::handle_response(traces, 1000.into(), 500.into(), H160::zero(), true)
+                .unwrap();
+        assert_eq!(
+            result,
+            TokenQuality::Taxed {
+                transfer_fee_bps: 100
+            }
+        );
+
+        let result = TraceCallDetector::handle_response(
+            traces,
+            1000.into(),
+            500.into(),
+            H160::zero(),
+            false,
+        )
+        .unwrap();
+        assert!(!result.is_good());
+    }
+
+    #[test]
+    fn fee_bps_rounds_up() {
+        assert_eq!(fee_bps(0.into(), 1000.into()), 0);
+        assert_eq!(fee_bps(10.into(), 1000.into()), 100);
+        assert_eq!(fee_bps(1.into(), 1_000_000.into()), 1);
+        assert_eq!(fee_bps(U256::MAX, U256::MAX), 10_000);
+    }
+
     #[test]
     fn arbitrary_recipient_() {
         println!("{:?}", TraceCallDetector::arbitrary_recipient());
@@ -674,6 +833,7 @@ mod tests {
             web3,
             finder,
             settlement_contract: settlement.address(),
+            allow_taxed_tokens: false,
         };
 
         println!("testing good tokens");
@@ -711,6 +871,7 @@ mod tests {
             web3,
             finder,
             settlement_contract: settlement.address(),
+            allow_taxed_tokens: false,
         };
 
         let result = token_cache.detect(testlib::tokens::USDC).await;
@@ -836,6 +997,7 @@ mod tests {
             web3,
             finder,
             settlement_contract: settlement.address(),
+            allow_taxed_tokens: false,
         };
 
         for token in tokens {
@@ -2,6 +2,7 @@ use {
     anyhow::{Context, Result},
     database::{
         onchain_broadcasted_orders::OnchainOrderPlacementError as DbOnchainOrderPlacementError,
+        order_events::OrderEventLabel as DbOrderEventLabel,
         orders::{
             BuyTokenDestination as DbBuyTokenDestination,
             ExecutionTime,
@@ -30,6 +31,7 @@ use {
             OrderMetadata,
             OrderStatus,
             OrderUid,
+            RefundStatus,
             SellTokenSource,
         },
         signature::{Signature, SigningScheme},
@@ -45,6 +47,7 @@ pub fn full_order_into_model_order(order: database::orders::FullOrder) -> Result
         Some(EthflowData {
             user_valid_to,
             refund_tx_hash: refund_tx.map(|hash| H256::from(hash.0)),
+            refund_status: refund_status_from(refund_tx.is_some(), order.refund_event),
         })
     } else {
         None
@@ -203,6 +206,24 @@ pub fn onchain_order_placement_error_from(
     }
 }
 
+/// Derives the refund status of an ethflow order from whether its refund
+/// transaction got indexed and the latest event the refunder recorded for it.
+pub fn refund_status_from(
+    refund_indexed: bool,
+    event: Option<DbOrderEventLabel>,
+) -> Option<RefundStatus> {
+    if refund_indexed {
+        return Some(RefundStatus::Refunded);
+    }
+    match event? {
+        DbOrderEventLabel::RefundPending => Some(RefundStatus::Pending),
+        DbOrderEventLabel::RefundSubmitted => Some(RefundStatus::Submitted),
+        DbOrderEventLabel::RefundFailed => Some(RefundStatus::Failed),
+        DbOrderEventLabel::Refunded => Some(RefundStatus::Refunded),
+        _ => None,
+    }
+}
+
 pub fn order_class_from(order: &FullOrderDb) -> OrderClass {
     match order.class {
         DbOrderClass::Market => OrderClass::Market,
@@ -49,6 +49,48 @@ where
     contract: C,
     store: S,
     last_handled_blocks: Vec<BlockNumberHash>,
+    finality: Finality,
+}
+
+/// How to determine which blocks can no longer get reorged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Finality {
+    /// Blocks are final once they are `MAX_REORG_BLOCK_COUNT` blocks deep.
+    #[default]
+    ReorgDepth,
+    /// Blocks are final once the node reports them as `finalized`.
+    Finalized,
+}
+
+impl Finality {
+    /// Returns the latest block that can no longer get reorged.
+    pub async fn last_final_block(
+        &self,
+        block_retriever: &dyn BlockRetrieving,
+        current_block: u64,
+    ) -> Result<u64> {
+        match self {
+            Self::ReorgDepth => current_block
+                .checked_sub(MAX_REORG_BLOCK_COUNT)
+                .context("no reorg safe block"),
+            Self::Finalized => Ok(block_retriever
+                .finalized_block()
+                .await
+                .context("finalized block")?
+                .0),
+        }
+    }
+}
+
+/// A reorg that caused stored events to be replaced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reorg {
+    /// The oldest handled block that is no longer part of the canonical chain.
+    pub reorged_block: BlockNumberHash,
+    /// Events of this block and all following blocks were replaced.
+    pub replaced_from: u64,
+    /// The canonical head at the time the reorg was handled.
+    pub current_block: BlockNumberHash,
 }
 
 /// `EventStoring` is used by `EventHandler` for the purpose of giving the user
@@ -64,10 +106,14 @@ pub trait EventStoring<T>: Send + Sync {
     /// # Arguments
     /// * `events` the contract events to be replaced by the implementer
     /// * `range` indicates a particular range of blocks on which to operate.
+    /// * `reorg` the reorg that caused the events to be replaced, if any.
+    ///   Implementers keeping an audit trail of reorgs should record it
+    ///   atomically with the replacement.
     async fn replace_events(
         &mut self,
         events: Vec<EthcontractEvent<T>>,
         range: RangeInclusive<u64>,
+        reorg: Option<Reorg>,
     ) -> Result<()>;
 
     /// Returns ok, on successful execution, otherwise an appropriate error
@@ -92,6 +138,8 @@ struct EventRange {
     latest_blocks: Vec<BlockNumberHash>,
     /// Defines if reorg happened for reorg unsafe blocks
     is_reorg: bool,
+    /// The detected reorg, if any
+    reorg: Option<Reorg>,
 }
 
 impl<C, S> EventHandler<C, S>
@@ -115,9 +163,18 @@ where
                     None => vec![],
                 }
             },
+            finality: Finality::default(),
         }
     }
 
+    /// Configures how the handler determines which blocks are final. With
+    /// [`Finality::Finalized`] reorgs that are deeper than the handled block
+    /// history are rolled back to the node's finalized block.
+    pub fn with_finality(mut self, finality: Finality) -> Self {
+        self.finality = finality;
+        self
+    }
+
     /// Creates a new instance of the event handler that does not index events
     /// appearing in blocks before the specified input date. Note that this
     /// is a different behavior compared to [`Self::new()`]: that function
@@ -187,6 +244,7 @@ where
                 history_range: None,
                 latest_blocks: vec![(current_block_number, current_block_hash)],
                 is_reorg: false,
+                reorg: None,
             });
         }
 
@@ -200,6 +258,7 @@ where
                 history_range: None,
                 latest_blocks: vec![],
                 is_reorg: false,
+                reorg: None,
             });
         }
 
@@ -209,13 +268,14 @@ where
             current_block_number,
         )?;
 
-        let (history_range, latest_range) = split_range(block_range);
+        let (mut history_range, latest_range) = split_range(block_range);
         tracing::debug!(
             "history range {:?}, latest_range {:?}",
             history_range,
             latest_range
         );
 
+        let latest_start = *latest_range.start();
         let latest_blocks = self.block_retriever.blocks(latest_range).await?;
         tracing::debug!(
             "latest blocks: {:?} - {:?}",
@@ -223,6 +283,30 @@ where
             latest_blocks.last(),
         );
 
+        let reorged_block = first_reorged_block(&handled_blocks, &latest_blocks);
+        if history_range.is_none()
+            && reorged_block.is_some()
+            && reorged_block == handled_blocks.first().copied()
+            && self.finality == Finality::Finalized
+        {
+            // Even the oldest handled block got reorged so we don't know the
+            // common ancestor. Replace everything after the finalized block.
+            let finalized = self
+                .block_retriever
+                .finalized_block()
+                .await
+                .context("finalized block")?
+                .0;
+            if finalized + 1 < latest_start {
+                history_range = Some(RangeInclusive::try_new(finalized + 1, latest_start - 1)?);
+            }
+            tracing::warn!(
+                ?reorged_block,
+                finalized,
+                "reorg deeper than handled blocks, replacing events since finalized block"
+            );
+        }
+
         // do not try to shorten the latest_blocks list if history range exists
         // if history range exists then we want to update for the full range of blocks,
         // otherwise history_blocks update would erase all subsequent blocks and we
@@ -234,6 +318,21 @@ where
                 (latest_blocks.to_vec(), is_reorg)
             }
         };
+        let replaced_from = match &history_range {
+            Some(range) => Some(*range.start()),
+            None => latest_blocks.first().map(|block| block.0),
+        };
+        let reorg = match (reorged_block, replaced_from) {
+            (Some(reorged_block), Some(replaced_from)) if is_reorg => Some(Reorg {
+                reorged_block,
+                replaced_from: replaced_from.min(reorged_block.0),
+                current_block: (current_block_number, current_block_hash),
+            }),
+            _ => None,
+        };
+        if let Some(reorg) = &reorg {
+            tracing::info!(?reorg, "detected reorg");
+        }
 
         tracing::debug!(
             "final latest blocks {:?} - {:?}, is reorg: {}",
@@ -246,24 +345,35 @@ where
             history_range,
             latest_blocks,
             is_reorg,
+            reorg,
         })
     }
 
     /// Get new events from the contract and insert them into the database.
     pub async fn update_events(&mut self) -> Result<()> {
         let event_range = self.event_block_range().await?;
+        let mut reorg = event_range.reorg;
 
         if let Some(range) = event_range.history_range {
-            self.update_events_from_old_blocks(range).await?;
+            self.update_events_from_old_blocks(range, &mut reorg)
+                .await?;
         }
         if !event_range.latest_blocks.is_empty() {
-            self.update_events_from_latest_blocks(&event_range.latest_blocks, event_range.is_reorg)
-                .await?;
+            self.update_events_from_latest_blocks(
+                &event_range.latest_blocks,
+                event_range.is_reorg,
+                &mut reorg,
+            )
+            .await?;
         }
         Ok(())
     }
 
-    async fn update_events_from_old_blocks(&mut self, range: RangeInclusive<u64>) -> Result<()> {
+    async fn update_events_from_old_blocks(
+        &mut self,
+        range: RangeInclusive<u64>,
+        reorg: &mut Option<Reorg>,
+    ) -> Result<()> {
         // first get the blocks needed to update `last_handled_blocks` because if it
         // fails, it's safer to fail at the beginning of the function before we
         // update Storage
@@ -315,7 +425,7 @@ where
             let unwrapped_events = events_chunk.context("failed to get next chunk of events")?;
             if !have_deleted_old_events {
                 self.store
-                    .replace_events(unwrapped_events, range.clone())
+                    .replace_events(unwrapped_events, range.clone(), reorg.take())
                     .await?;
                 have_deleted_old_events = true;
             } else {
@@ -326,7 +436,9 @@ where
         // completely empty. However we do want to delete old events in this
         // case as a rerorg might have removed events without adding new ones.
         if !have_deleted_old_events {
-            self.store.replace_events(Vec::new(), range.clone()).await?;
+            self.store
+                .replace_events(Vec::new(), range.clone(), reorg.take())
+                .await?;
         }
 
         self.update_last_handled_blocks(&blocks);
@@ -337,6 +449,7 @@ where
         &mut self,
         latest_blocks: &[BlockNumberHash],
         is_reorg: bool,
+        reorg: &mut Option<Reorg>,
     ) -> Result<()> {
         debug_assert!(
             !latest_blocks.is_empty(),
@@ -353,7 +466,9 @@ where
         // update storage regardless if it's a full update or partial update
         let range = RangeInclusive::try_new(blocks.first().unwrap().0, blocks.last().unwrap().0)?;
         if is_reorg {
-            self.store.replace_events(events, range.clone()).await?;
+            self.store
+                .replace_events(events, range.clone(), reorg.take())
+                .await?;
         } else {
             self.store.append_events(events).await?;
         }
@@ -471,6 +586,19 @@ fn detect_reorg_path<'a>(
     (latest_blocks, is_reorg)
 }
 
+/// Returns the oldest handled block that has a different hash than the block
+/// with the same number in `latest_blocks`, i.e. that got reorged.
+fn first_reorged_block(
+    handled_blocks: &[BlockNumberHash],
+    latest_blocks: &[BlockNumberHash],
+) -> Option<BlockNumberHash> {
+    handled_blocks.iter().copied().find(|handled| {
+        latest_blocks
+            .iter()
+            .any(|latest| latest.0 == handled.0 && latest.1 != handled.1)
+    })
+}
+
 /// Splits range into two disjuctive consecutive ranges, second one containing
 /// last (up to) MAX_BLOCKS_QUERIED elements, first one containing the rest (if
 /// any)
@@ -572,11 +700,11 @@ fn track_block_range(range: &str) {
 mod tests {
     use {
         super::*,
-        contracts::{gpv2_settlement, GPv2Settlement},
+        contracts::{dummy_contract, gpv2_settlement, GPv2Settlement},
         ethcontract::{BlockNumber, H256},
         ethrpc::{
             create_env_test_transport,
-            current_block::block_number_to_block_number_hash,
+            current_block::{block_number_to_block_number_hash, BlockInfo},
             Web3,
         },
         std::str::FromStr,
@@ -600,6 +728,7 @@ mod tests {
             &mut self,
             events: Vec<EthcontractEvent<T>>,
             range: RangeInclusive<u64>,
+            _: Option<Reorg>,
         ) -> Result<()> {
             self.events
                 .retain(|event| event.meta.clone().unwrap().block_number < *range.start());
@@ -693,6 +822,176 @@ mod tests {
         assert!(is_reorg);
     }
 
+    #[test]
+    fn first_reorged_block_test_no_reorg() {
+        let handled_blocks = vec![(1, H256::from_low_u64_be(1)), (2, H256::from_low_u64_be(2))];
+        let latest_blocks = vec![
+            (1, H256::from_low_u64_be(1)),
+            (2, H256::from_low_u64_be(2)),
+            (3, H256::from_low_u64_be(3)),
+        ];
+        assert_eq!(first_reorged_block(&handled_blocks, &latest_blocks), None);
+    }
+
+    #[test]
+    fn first_reorged_block_test_reorg() {
+        let handled_blocks = vec![
+            (1, H256::from_low_u64_be(1)),
+            (2, H256::from_low_u64_be(2)),
+            (3, H256::from_low_u64_be(3)),
+        ];
+        let latest_blocks = vec![
+            (1, H256::from_low_u64_be(1)),
+            (2, H256::from_low_u64_be(21)),
+            (3, H256::from_low_u64_be(31)),
+        ];
+        assert_eq!(
+            first_reorged_block(&handled_blocks, &latest_blocks),
+            Some((2, H256::from_low_u64_be(2)))
+        );
+    }
+
+    /// A chain whose blocks after `fork` got reorged.
+    #[derive(Debug)]
+    struct Chain {
+        head: u64,
+        fork: u64,
+        finalized: u64,
+    }
+
+    impl Chain {
+        fn hash(&self, number: u64) -> H256 {
+            if number > self.fork {
+                H256::from_low_u64_be(number + 1_000_000)
+            } else {
+                H256::from_low_u64_be(number)
+            }
+        }
+
+        /// Returns a handler that handled the blocks before the reorg up to
+        /// block 1063.
+        fn handler(
+            self,
+            finality: Finality,
+        ) -> EventHandler<GPv2SettlementContract, EventStorage<gpv2_settlement::Event>> {
+            let mut handler = EventHandler::new(
+                Arc::new(self),
+                GPv2SettlementContract::new(dummy_contract!(GPv2Settlement, [0x01; 20])),
+                EventStorage { events: vec![] },
+                None,
+            )
+            .with_finality(finality);
+            handler.last_handled_blocks = (1000..1064)
+                .map(|number| (number, H256::from_low_u64_be(number)))
+                .collect();
+            handler
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl BlockRetrieving for Chain {
+        async fn current_block(&self) -> Result<BlockInfo> {
+            Ok(BlockInfo {
+                number: self.head,
+                hash: self.hash(self.head),
+                parent_hash: self.hash(self.head - 1),
+            })
+        }
+
+        async fn block(&self, number: u64) -> Result<BlockNumberHash> {
+            Ok((number, self.hash(number)))
+        }
+
+        async fn blocks(&self, range: RangeInclusive<u64>) -> Result<Vec<BlockNumberHash>> {
+            let (start, end) = range.into_inner();
+            Ok((start..=end)
+                .map(|number| (number, self.hash(number)))
+                .collect())
+        }
+
+        async fn finalized_block(&self) -> Result<BlockNumberHash> {
+            Ok((self.finalized, self.hash(self.finalized)))
+        }
+    }
+
+    #[tokio::test]
+    async fn event_block_range_shallow_reorg() {
+        let chain = Chain {
+            head: 1070,
+            fork: 1050,
+            finalized: 950,
+        };
+        let current_block = (1070, chain.hash(1070));
+        let handler = chain.handler(Finality::Finalized);
+
+        let range = handler.event_block_range().await.unwrap();
+        assert_eq!(range.history_range, None);
+        assert_eq!(range.latest_blocks.first().unwrap().0, 1051);
+        assert_eq!(range.latest_blocks.last().unwrap().0, 1070);
+        assert!(range.is_reorg);
+        assert_eq!(
+            range.reorg,
+            Some(Reorg {
+                reorged_block: (1051, H256::from_low_u64_be(1051)),
+                replaced_from: 1051,
+                current_block,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn event_block_range_deep_reorg_falls_back_to_finalized_block() {
+        let chain = Chain {
+            head: 1070,
+            fork: 900,
+            finalized: 950,
+        };
+        let current_block = (1070, chain.hash(1070));
+        let handler = chain.handler(Finality::Finalized);
+
+        let range = handler.event_block_range().await.unwrap();
+        assert_eq!(
+            range.history_range,
+            Some(RangeInclusive::try_new(951, 998).unwrap())
+        );
+        assert_eq!(range.latest_blocks.first().unwrap().0, 999);
+        assert_eq!(range.latest_blocks.last().unwrap().0, 1070);
+        assert!(range.is_reorg);
+        // The recorded reorg goes past the handled blocks.
+        assert_eq!(
+            range.reorg,
+            Some(Reorg {
+                reorged_block: (1000, H256::from_low_u64_be(1000)),
+                replaced_from: 951,
+                current_block,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn event_block_range_deep_reorg_without_finalized_block() {
+        let chain = Chain {
+            head: 1070,
+            fork: 900,
+            finalized: 950,
+        };
+        let current_block = (1070, chain.hash(1070));
+        let handler = chain.handler(Finality::ReorgDepth);
+
+        let range = handler.event_block_range().await.unwrap();
+        assert_eq!(range.history_range, None);
+        assert_eq!(range.latest_blocks.first().unwrap().0, 999);
+        assert!(range.is_reorg);
+        assert_eq!(
+            range.reorg,
+            Some(Reorg {
+                reorged_block: (1000, H256::from_low_u64_be(1000)),
+                replaced_from: 999,
+                current_block,
+            })
+        );
+    }
+
     #[test]
     fn split_range_test_equal() {
         let range = RangeInclusive::try_new(0, 0).unwrap();
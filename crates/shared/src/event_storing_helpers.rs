@@ -25,6 +25,13 @@ pub fn create_quote_row(data: QuoteData) -> DbQuote {
         expiration_timestamp: data.expiration,
         quote_kind: data.quote_kind,
         solver: ByteArray(data.solver.0),
+        verified: data.verified,
+        estimator_latency_ms: data
+            .estimator
+            .as_ref()
+            .map(|estimator| estimator.latency.as_millis() as i64),
+        estimator: data.estimator.map(|estimator| estimator.name),
+        hooks_hash: data.hooks_hash.map(|hash| ByteArray(hash.0)),
     }
 }
 
@@ -41,5 +48,7 @@ pub fn create_db_search_parameters(
         kind: order_kind_into(params.kind),
         expiration,
         quote_kind: quote_kind_from_signing_scheme(&params.signing_scheme),
+        hooks_hash: params.hooks_hash().map(|hash| ByteArray(hash.0)),
+        hooks_gas: params.additional_gas.try_into().unwrap_or(i64::MAX),
     }
 }
@@ -510,11 +510,12 @@ pub struct TransactionWithError {
 #[derivative(Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SimulatedTransaction {
-    /// The simulation was done at the beginning of the block
-    pub block_number: u64,
+    /// The simulation was done at the beginning of the block. Contains `None`
+    /// if the simulator didn't report the block.
+    pub block_number: Option<u64>,
     /// Index of the transaction inside the block the transaction was simulated
-    /// on
-    pub tx_index: u64,
+    /// on. Contains `None` if the simulator didn't report the index.
+    pub tx_index: Option<u64>,
     /// Is transaction simulated with internalized interactions or without
     pub internalization: InternalizationStrategy,
     /// Which storage the settlement tries to access. Contains `None` if some
@@ -1174,8 +1175,8 @@ mod tests {
                     address: H160::from_low_u64_be(1),
                     storage_keys: vec![H256::from_low_u64_be(2)]
                 }]),
-                block_number: 15848799,
-                tx_index: 0,
+                block_number: Some(15848799),
+                tx_index: Some(0),
                 from: H160::from_str("0x9008D19f58AAbD9eD0D60971565AA8510560ab41").unwrap(),
                 to: H160::from_str("0x9008D19f58AAbD9eD0D60971565AA8510560ab41").unwrap(),
                 data: vec![19, 250, 73],
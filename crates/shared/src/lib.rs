@@ -2,6 +2,7 @@
 pub mod macros;
 
 pub mod account_balances;
+pub mod address_list;
 pub mod api;
 pub mod app_data;
 pub mod arguments;
@@ -38,6 +39,7 @@ pub mod rate_limiter;
 pub mod recent_block_cache;
 pub mod remaining_amounts;
 pub mod request_sharing;
+pub mod request_signing;
 pub mod signature_validator;
 pub mod sources;
 pub mod subgraph;
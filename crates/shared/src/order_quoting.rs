@@ -14,14 +14,17 @@ use {
             PartialValidationError,
             PreOrderData,
         },
-        price_estimation::Verification,
+        price_estimation::{
+            competition::{self, CompetitionTrace},
+            Verification,
+        },
         trade_finding,
     },
     anyhow::{Context, Result},
     chrono::{DateTime, Duration, TimeZone as _, Utc},
     database::quotes::{Quote as QuoteRow, QuoteKind},
-    ethcontract::{H160, U256},
-    futures::TryFutureExt as _,
+    ethcontract::{H160, H256, U256},
+    futures::{FutureExt as _, TryFutureExt as _},
     gas_estimation::GasPriceEstimating,
     model::{
         order::{OrderClass, OrderCreationAppData, OrderKind},
@@ -31,6 +34,8 @@ use {
             OrderQuoteResponse,
             OrderQuoteSide,
             PriceQuality,
+            QuoteDetails,
+            QuoteEstimate,
             QuoteId,
             QuoteSigningScheme,
             SellAmount,
@@ -68,7 +73,24 @@ impl QuoteHandler {
         &self,
         request: &OrderQuoteRequest,
     ) -> Result<OrderQuoteResponse, OrderQuoteError> {
-        tracing::debug!(?request, "calculating quote");
+        self.quote(request, false).await
+    }
+
+    /// Calculates a quote and includes details about how it was computed in
+    /// the response.
+    pub async fn calculate_verbose_quote(
+        &self,
+        request: &OrderQuoteRequest,
+    ) -> Result<OrderQuoteResponse, OrderQuoteError> {
+        self.quote(request, true).await
+    }
+
+    async fn quote(
+        &self,
+        request: &OrderQuoteRequest,
+        verbose: bool,
+    ) -> Result<OrderQuoteResponse, OrderQuoteError> {
+        tracing::debug!(?request, verbose, "calculating quote");
 
         let app_data = self
             .order_validator
@@ -101,16 +123,26 @@ impl QuoteHandler {
             }
         };
 
+        let quoter = match request.price_quality {
+            PriceQuality::Optimal | PriceQuality::Verified => &self.optimal_quoter,
+            PriceQuality::Fast => &self.fast_quoter,
+        };
+        let (quote, trace) = if verbose {
+            competition::traced(quoter.calculate_quote(params.clone())).await
+        } else {
+            (quoter.calculate_quote(params.clone()).await, None)
+        };
+        let quote = quote?;
+        let additional_gas = params.additional_cost(quote.data.verified);
+
         let quote = match request.price_quality {
-            PriceQuality::Optimal | PriceQuality::Verified => {
-                let quote = self.optimal_quoter.calculate_quote(params).await?;
-                self.optimal_quoter
-                    .store_quote(quote)
-                    .await
-                    .map_err(CalculateQuoteError::Other)?
-            }
+            PriceQuality::Optimal | PriceQuality::Verified => self
+                .optimal_quoter
+                .store_quote(quote)
+                .await
+                .map_err(CalculateQuoteError::Other)?,
             PriceQuality::Fast => {
-                let mut quote = self.fast_quoter.calculate_quote(params).await?;
+                let mut quote = quote;
                 // We maintain an API guarantee that fast quotes always have an expiry of zero,
                 // because they're not very accurate and can be considered to
                 // expire immediately.
@@ -144,6 +176,7 @@ impl QuoteHandler {
             from: request.from,
             expiration: quote.data.expiration,
             id: quote.id,
+            details: verbose.then(|| quote_details(&quote, additional_gas, trace)),
         };
 
         tracing::debug!(?response, "finished computing quote");
@@ -151,6 +184,41 @@ impl QuoteHandler {
     }
 }
 
+fn quote_details(
+    quote: &Quote,
+    additional_gas: u64,
+    trace: Option<CompetitionTrace>,
+) -> QuoteDetails {
+    let trace = trace.unwrap_or_default();
+    let estimates = trace
+        .estimates
+        .into_iter()
+        .map(|(estimator, result)| match result {
+            Ok(estimate) => QuoteEstimate {
+                estimator,
+                out_amount: Some(estimate.out_amount),
+                gas: Some(estimate.gas),
+                verified: estimate.verified,
+                error: None,
+            },
+            Err(err) => QuoteEstimate {
+                estimator,
+                error: Some(err.to_string()),
+                ..Default::default()
+            },
+        })
+        .collect();
+
+    QuoteDetails {
+        winner: (!trace.winner.is_empty()).then_some(trace.winner),
+        estimates,
+        verified: quote.data.verified,
+        gas_amount: (quote.data.fee_parameters.gas_amount as u64).saturating_add(additional_gas),
+        gas_price: U256::from_f64_lossy(quote.data.fee_parameters.gas_price),
+        full_fee_amount: quote.full_fee_amount,
+    }
+}
+
 /// Result from handling a quote request.
 #[derive(Debug, Error)]
 pub enum OrderQuoteError {
@@ -210,13 +278,29 @@ impl QuoteParameters {
         }
     }
 
-    fn additional_cost(&self) -> u64 {
-        self.signing_scheme
-            .additional_gas_amount()
-            .saturating_add(self.additional_gas)
+    /// Returns additional gas costs incurred by the quote.
+    fn additional_cost(&self, verified: bool) -> u64 {
+        additional_cost(&self.signing_scheme, self.additional_gas, verified)
     }
 }
 
+/// Returns the gas that needs to be added on top of the quoted gas amount.
+///
+/// Quotes verified with the order's hooks got simulated together with them, so
+/// their gas amount already covers executing them. Signatures are not checked
+/// during the simulation, though, so their verification cost has to be added
+/// regardless.
+fn additional_cost(
+    signing_scheme: &QuoteSigningScheme,
+    hooks_gas: u64,
+    verified_with_hooks: bool,
+) -> u64 {
+    let hooks_gas = if verified_with_hooks { 0 } else { hooks_gas };
+    signing_scheme
+        .additional_gas_amount()
+        .saturating_add(hooks_gas)
+}
+
 /// A calculated order quote.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Quote {
@@ -330,6 +414,22 @@ pub struct QuoteData {
     pub quote_kind: QuoteKind,
     /// The address of the solver that provided the quote.
     pub solver: H160,
+    /// Whether the quote was verified by simulating the trade.
+    pub verified: bool,
+    /// Hash of the hooks the trade was simulated with. Verified quotes only
+    /// cover the gas of these hooks.
+    pub hooks_hash: Option<H256>,
+    /// The price estimator that won the competition for this quote. Quotes
+    /// that weren't computed by a competition don't have one.
+    pub estimator: Option<QuoteEstimator>,
+}
+
+/// Identifies the price estimator that provided a quote's estimate.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct QuoteEstimator {
+    pub name: String,
+    /// How long the estimator took to provide its estimate.
+    pub latency: std::time::Duration,
 }
 
 impl TryFrom<QuoteRow> for QuoteData {
@@ -352,6 +452,14 @@ impl TryFrom<QuoteRow> for QuoteData {
             expiration: row.expiration_timestamp,
             quote_kind: row.quote_kind,
             solver: H160(row.solver.0),
+            verified: row.verified,
+            hooks_hash: row.hooks_hash.map(|hash| H256(hash.0)),
+            estimator: row.estimator.map(|name| QuoteEstimator {
+                name,
+                latency: std::time::Duration::from_millis(
+                    row.estimator_latency_ms.unwrap_or_default().max(0) as u64,
+                ),
+            }),
         })
     }
 }
@@ -437,11 +545,25 @@ impl QuoteSearchParameters {
                 == (data.sell_token, data.buy_token, data.kind)
     }
 
+    /// Returns the hash of the hooks the order gets executed with. This is
+    /// only known if the order requires verified quotes.
+    pub(crate) fn hooks_hash(&self) -> Option<H256> {
+        self.verification.as_ref().map(Verification::hooks_hash)
+    }
+
+    /// Returns true if the quote was verified with the same hooks as the order
+    /// and therefore already covers their gas.
+    fn verified_with_hooks(&self, data: &QuoteData) -> bool {
+        data.verified && data.hooks_hash.is_some() && data.hooks_hash == self.hooks_hash()
+    }
+
     /// Returns additional gas costs incurred by the quote.
-    fn additional_cost(&self) -> u64 {
-        self.signing_scheme
-            .additional_gas_amount()
-            .saturating_add(self.additional_gas)
+    fn additional_cost(&self, data: &QuoteData) -> u64 {
+        additional_cost(
+            &self.signing_scheme,
+            self.additional_gas,
+            self.verified_with_hooks(data),
+        )
     }
 }
 
@@ -549,11 +671,12 @@ impl OrderQuoter {
         };
 
         let trade_query = Arc::new(parameters.to_price_query());
-        let (gas_estimate, trade_estimate, sell_token_price, _) = futures::try_join!(
+        let (gas_estimate, (trade_estimate, trace), sell_token_price, _) = futures::try_join!(
             self.gas_estimator
                 .estimate()
                 .map_err(PriceEstimationError::ProtocolInternal),
-            self.price_estimator.estimate(trade_query.clone()),
+            competition::traced(self.price_estimator.estimate(trade_query.clone()))
+                .map(|(estimate, trace)| estimate.map(|estimate| (estimate, trace))),
             self.native_price_estimator
                 .estimate_native_price(parameters.sell_token),
             // We don't care about the native price of the buy_token for the quote but we need it
@@ -591,6 +714,15 @@ impl OrderQuoter {
             expiration,
             quote_kind,
             solver: trade_estimate.solver,
+            verified: trade_estimate.verified,
+            hooks_hash: parameters
+                .verification
+                .as_ref()
+                .map(Verification::hooks_hash),
+            estimator: trace.map(|trace| QuoteEstimator {
+                name: trace.winner,
+                latency: trace.winner_latency,
+            }),
         };
 
         Ok(quote)
@@ -616,8 +748,9 @@ impl OrderQuoting for OrderQuoter {
                 .map_err(From::from),
         )?;
 
+        let additional_cost = parameters.additional_cost(data.verified);
         let mut quote = Quote::new(Default::default(), data)
-            .with_subsidy_and_additional_cost(&subsidy, parameters.additional_cost());
+            .with_subsidy_and_additional_cost(&subsidy, additional_cost);
 
         // Make sure to scale the sell and buy amounts for quotes for sell
         // amounts before fees.
@@ -671,7 +804,6 @@ impl OrderQuoting for OrderQuoter {
         };
 
         let now = self.now.now();
-        let additional_cost = parameters.additional_cost();
         let quote = async {
             let (id, data) = match id {
                 Some(id) => {
@@ -692,7 +824,7 @@ impl OrderQuoting for OrderQuoter {
                 }
                 None => self
                     .storage
-                    .find(parameters, now)
+                    .find(parameters.clone(), now)
                     .await?
                     .ok_or(FindQuoteError::NotFound(None))?,
             };
@@ -706,6 +838,7 @@ impl OrderQuoting for OrderQuoter {
                 .map_err(FindQuoteError::from)
         )?;
 
+        let additional_cost = parameters.additional_cost(&quote.data);
         let quote = quote.with_subsidy_and_additional_cost(&subsidy, additional_cost);
         let quote = match scaled_sell_amount {
             Some(sell_amount) => quote.with_scaled_sell_amount(sell_amount),
@@ -838,6 +971,7 @@ mod tests {
                         out_amount: 42.into(),
                         gas: 3,
                         solver: H160([1; 20]),
+                        verified: false,
                     })
                 }
                 .boxed()
@@ -878,6 +1012,9 @@ mod tests {
                 expiration: now + Duration::seconds(60i64),
                 quote_kind: QuoteKind::Standard,
                 solver: H160([1; 20]),
+                verified: false,
+                hooks_hash: Some(Verification::default().hooks_hash()),
+                estimator: None,
             }))
             .returning(|_| Ok(1337));
 
@@ -912,6 +1049,9 @@ mod tests {
                     expiration: now + chrono::Duration::seconds(60i64),
                     quote_kind: QuoteKind::Standard,
                     solver: H160([1; 20]),
+                    verified: false,
+                    hooks_hash: Some(Verification::default().hooks_hash()),
+                    estimator: None,
                 },
                 sell_amount: 70.into(),
                 buy_amount: 29.into(),
@@ -970,6 +1110,7 @@ mod tests {
                         out_amount: 42.into(),
                         gas: 3,
                         solver: H160([1; 20]),
+                        verified: false,
                     })
                 }
                 .boxed()
@@ -1010,6 +1151,9 @@ mod tests {
                 expiration: now + chrono::Duration::seconds(60i64),
                 quote_kind: QuoteKind::Standard,
                 solver: H160([1; 20]),
+                verified: false,
+                hooks_hash: Some(Verification::default().hooks_hash()),
+                estimator: None,
             }))
             .returning(|_| Ok(1337));
 
@@ -1047,6 +1191,9 @@ mod tests {
                     expiration: now + chrono::Duration::seconds(60i64),
                     quote_kind: QuoteKind::Standard,
                     solver: H160([1; 20]),
+                    verified: false,
+                    hooks_hash: Some(Verification::default().hooks_hash()),
+                    estimator: None,
                 },
                 sell_amount: 100.into(),
                 buy_amount: 42.into(),
@@ -1100,6 +1247,7 @@ mod tests {
                         out_amount: 100.into(),
                         gas: 3,
                         solver: H160([1; 20]),
+                        verified: false,
                     })
                 }
                 .boxed()
@@ -1140,6 +1288,9 @@ mod tests {
                 expiration: now + chrono::Duration::seconds(60i64),
                 quote_kind: QuoteKind::Standard,
                 solver: H160([1; 20]),
+                verified: false,
+                hooks_hash: Some(Verification::default().hooks_hash()),
+                estimator: None,
             }))
             .returning(|_| Ok(1337));
 
@@ -1178,6 +1329,9 @@ mod tests {
                     expiration: now + chrono::Duration::seconds(60i64),
                     quote_kind: QuoteKind::Standard,
                     solver: H160([1; 20]),
+                    verified: false,
+                    hooks_hash: Some(Verification::default().hooks_hash()),
+                    estimator: None,
                 },
                 sell_amount: 100.into(),
                 buy_amount: 42.into(),
@@ -1217,6 +1371,7 @@ mod tests {
                     out_amount: 100.into(),
                     gas: 200,
                     solver: H160([1; 20]),
+                    verified: false,
                 })
             }
             .boxed()
@@ -1286,6 +1441,7 @@ mod tests {
                     out_amount: 100.into(),
                     gas: 200,
                     solver: H160([1; 20]),
+                    verified: false,
                 })
             }
             .boxed()
@@ -1360,6 +1516,9 @@ mod tests {
                 expiration: now + chrono::Duration::seconds(10),
                 quote_kind: QuoteKind::Standard,
                 solver: H160([1; 20]),
+                verified: false,
+                hooks_hash: None,
+                estimator: None,
             }))
         });
 
@@ -1394,6 +1553,9 @@ mod tests {
                     expiration: now + chrono::Duration::seconds(10),
                     quote_kind: QuoteKind::Standard,
                     solver: H160([1; 20]),
+                    verified: false,
+                    hooks_hash: None,
+                    estimator: None,
                 },
                 sell_amount: 85.into(),
                 // Allows for "out-of-price" buy amounts. This means that order
@@ -1410,6 +1572,99 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn verified_quotes_include_gas_of_their_hooks() {
+        let now = Utc::now();
+        let parameters = QuoteSearchParameters {
+            sell_token: H160([1; 20]),
+            buy_token: H160([2; 20]),
+            sell_amount: 100.into(),
+            buy_amount: 42.into(),
+            fee_amount: 6.into(),
+            kind: OrderKind::Sell,
+            signing_scheme: QuoteSigningScheme::Eip712,
+            additional_gas: 2,
+            verification: Some(Verification {
+                from: H160([3; 20]),
+                pre_interactions: vec![trade_finding::Interaction {
+                    target: H160([4; 20]),
+                    value: 0.into(),
+                    data: vec![1],
+                }],
+                ..Default::default()
+            }),
+        };
+        let data = QuoteData {
+            sell_token: H160([1; 20]),
+            buy_token: H160([2; 20]),
+            quoted_sell_amount: 100.into(),
+            quoted_buy_amount: 42.into(),
+            fee_parameters: FeeParameters {
+                gas_amount: 3.,
+                gas_price: 2.,
+                sell_token_price: 1.,
+            },
+            kind: OrderKind::Sell,
+            expiration: now + chrono::Duration::seconds(10),
+            quote_kind: QuoteKind::Standard,
+            solver: H160([1; 20]),
+            verified: false,
+            hooks_hash: None,
+            estimator: None,
+        };
+
+        let mut storage = MockQuoteStoring::new();
+        storage.expect_get().with(eq(1)).returning({
+            let data = data.clone();
+            move |_| Ok(Some(data.clone()))
+        });
+        storage.expect_get().with(eq(2)).returning({
+            let data = QuoteData {
+                verified: true,
+                hooks_hash: parameters.hooks_hash(),
+                ..data.clone()
+            };
+            move |_| Ok(Some(data.clone()))
+        });
+        storage.expect_get().with(eq(3)).returning({
+            let data = QuoteData {
+                verified: true,
+                hooks_hash: Some(Verification::default().hooks_hash()),
+                ..data.clone()
+            };
+            move |_| Ok(Some(data.clone()))
+        });
+
+        let quoter = OrderQuoter {
+            price_estimator: Arc::new(MockPriceEstimating::new()),
+            native_price_estimator: Arc::new(MockNativePriceEstimating::new()),
+            gas_estimator: Arc::new(FakeGasPriceEstimator::default()),
+            fee_subsidy: Arc::new(Subsidy::default()),
+            storage: Arc::new(storage),
+            now: Arc::new(now),
+            validity: Validity::default(),
+        };
+
+        // The hook gas gets added on top of unverified quotes.
+        let quote = quoter
+            .find_quote(Some(1), parameters.clone())
+            .await
+            .unwrap();
+        assert_eq!(quote.full_fee_amount, 10.into());
+
+        // Verified quotes were simulated with the hooks so their gas amount
+        // already accounts for them.
+        let quote = quoter
+            .find_quote(Some(2), parameters.clone())
+            .await
+            .unwrap();
+        assert_eq!(quote.full_fee_amount, 6.into());
+
+        // Quotes verified without the order's hooks don't cover their gas.
+        let quote = quoter.find_quote(Some(3), parameters).await.unwrap();
+        assert_eq!(quote.full_fee_amount, 10.into());
+    }
+
     #[tokio::test]
     async fn finds_quote_with_sell_amount_after_fee() {
         let now = Utc::now();
@@ -1445,6 +1700,9 @@ mod tests {
                 expiration: now + chrono::Duration::seconds(10),
                 quote_kind: QuoteKind::Standard,
                 solver: H160([1; 20]),
+                verified: false,
+                hooks_hash: None,
+                estimator: None,
             }))
         });
 
@@ -1476,6 +1734,9 @@ mod tests {
                     expiration: now + chrono::Duration::seconds(10),
                     quote_kind: QuoteKind::Standard,
                     solver: H160([1; 20]),
+                    verified: false,
+                    hooks_hash: None,
+                    estimator: None,
                 },
                 sell_amount: 100.into(),
                 buy_amount: 42.into(),
@@ -1524,6 +1785,9 @@ mod tests {
                         expiration: now + chrono::Duration::seconds(10),
                         quote_kind: QuoteKind::Standard,
                         solver: H160([1; 20]),
+                        verified: false,
+                        hooks_hash: None,
+                        estimator: None,
                     },
                 )))
             });
@@ -1556,6 +1820,9 @@ mod tests {
                     expiration: now + chrono::Duration::seconds(10),
                     quote_kind: QuoteKind::Standard,
                     solver: H160([1; 20]),
+                    verified: false,
+                    hooks_hash: None,
+                    estimator: None,
                 },
                 sell_amount: 100.into(),
                 buy_amount: 42.into(),
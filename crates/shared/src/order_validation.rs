@@ -1,6 +1,7 @@
 use {
     crate::{
         account_balances::{self, BalanceFetching, TransferSimulationError},
+        address_list::AutoUpdatingAddressList,
         app_data::{ProtocolAppData, ValidatedAppData},
         bad_token::{BadTokenDetecting, TokenQuality},
         code_fetching::CodeFetching,
@@ -46,7 +47,11 @@ use {
         time,
         DomainSeparator,
     },
-    std::{collections::HashSet, sync::Arc, time::Duration},
+    std::{
+        collections::{HashMap, HashSet},
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
 };
 
 #[mockall::automock]
@@ -100,6 +105,18 @@ pub trait OrderValidating: Send + Sync {
         settlement_contract: H160,
         full_app_data_override: Option<String>,
     ) -> Result<(Order, Option<Quote>), ValidationError>;
+
+    /// Fully validates a batch of orders, each with its optional
+    /// `full_app_data_override`. Token quality and transfer checks are shared
+    /// between the orders of the batch and limit orders that are accepted
+    /// earlier in the batch count towards the owner's limit order maximum of
+    /// later ones. Results are returned in the order of the input.
+    async fn validate_and_construct_orders(
+        &self,
+        orders: Vec<(OrderCreation, Option<String>)>,
+        domain_separator: &DomainSeparator,
+        settlement_contract: H160,
+    ) -> Vec<Result<(Order, Option<Quote>), ValidationError>>;
 }
 
 #[derive(Debug)]
@@ -240,7 +257,7 @@ pub struct OrderValidator {
     /// For Pre/Partial-Validation: performed during fee & quote phase
     /// when only part of the order data is available
     native_token: WETH9,
-    banned_users: HashSet<H160>,
+    banned_users: AutoUpdatingAddressList,
     liquidity_order_owners: HashSet<H160>,
     validity_configuration: OrderValidPeriodConfiguration,
     signature_configuration: SignatureConfiguration,
@@ -319,7 +336,7 @@ impl OrderValidator {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         native_token: WETH9,
-        banned_users: HashSet<H160>,
+        banned_users: AutoUpdatingAddressList,
         liquidity_order_owners: HashSet<H160>,
         validity_configuration: OrderValidPeriodConfiguration,
         signature_configuration: SignatureConfiguration,
@@ -774,6 +791,185 @@ impl OrderValidating for OrderValidator {
 
         Ok((order, quote))
     }
+
+    async fn validate_and_construct_orders(
+        &self,
+        orders: Vec<(OrderCreation, Option<String>)>,
+        domain_separator: &DomainSeparator,
+        settlement_contract: H160,
+    ) -> Vec<Result<(Order, Option<Quote>), ValidationError>> {
+        let limit_order_counter = Arc::new(BatchLimitOrderCounter::new(
+            self.limit_order_counter.clone(),
+        ));
+        let validator = Self {
+            bad_token_detector: Arc::new(BatchTokenQuality::new(self.bad_token_detector.clone())),
+            balance_fetcher: Arc::new(BatchTransferChecks::new(self.balance_fetcher.clone())),
+            limit_order_counter: limit_order_counter.clone(),
+            ..self.clone()
+        };
+
+        let mut results = Vec::with_capacity(orders.len());
+        for (order, full_app_data_override) in orders {
+            let result = validator
+                .validate_and_construct_order(
+                    order,
+                    domain_separator,
+                    settlement_contract,
+                    full_app_data_override,
+                )
+                .await;
+            if let Ok((order, _)) = &result {
+                if order.metadata.class.is_limit() {
+                    limit_order_counter.accept(order.metadata.owner);
+                }
+            }
+            results.push(result);
+        }
+        results
+    }
+}
+
+/// Token quality lookups shared between the orders of a batch.
+struct BatchTokenQuality {
+    inner: Arc<dyn BadTokenDetecting>,
+    cache: Mutex<HashMap<H160, TokenQuality>>,
+}
+
+impl BatchTokenQuality {
+    fn new(inner: Arc<dyn BadTokenDetecting>) -> Self {
+        Self {
+            inner,
+            cache: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl BadTokenDetecting for BatchTokenQuality {
+    async fn detect(&self, token: H160) -> Result<TokenQuality> {
+        if let Some(quality) = self.cache.lock().unwrap().get(&token) {
+            return Ok(quality.clone());
+        }
+        let quality = self.inner.detect(token).await?;
+        self.cache.lock().unwrap().insert(token, quality.clone());
+        Ok(quality)
+    }
+}
+
+/// What is known about transfers of a query within a batch.
+#[derive(Default)]
+struct TransferBounds {
+    /// The largest amount that could be transferred.
+    max_success: Option<U256>,
+    /// The smallest amount that could not be transferred because of missing
+    /// balance or allowance, and whether it was the allowance.
+    min_insufficient: Option<(U256, bool)>,
+}
+
+/// Transfer checks shared between the orders of a batch.
+///
+/// Market makers usually place many orders selling the same token, so instead
+/// of simulating a transfer for every order we reuse earlier results: if an
+/// amount can be transferred, so can every smaller amount, and if the balance
+/// or allowance is insufficient for an amount, it is for every larger amount
+/// too.
+struct BatchTransferChecks {
+    inner: Arc<dyn BalanceFetching>,
+    bounds: Mutex<HashMap<account_balances::Query, TransferBounds>>,
+}
+
+impl BatchTransferChecks {
+    fn new(inner: Arc<dyn BalanceFetching>) -> Self {
+        Self {
+            inner,
+            bounds: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl BalanceFetching for BatchTransferChecks {
+    async fn get_balances(&self, queries: &[account_balances::Query]) -> Vec<Result<U256>> {
+        self.inner.get_balances(queries).await
+    }
+
+    async fn can_transfer(
+        &self,
+        query: &account_balances::Query,
+        amount: U256,
+    ) -> Result<(), TransferSimulationError> {
+        if let Some(bounds) = self.bounds.lock().unwrap().get(query) {
+            if bounds.max_success.map_or(false, |max| amount <= max) {
+                return Ok(());
+            }
+            match bounds.min_insufficient {
+                Some((min, true)) if amount >= min => {
+                    return Err(TransferSimulationError::InsufficientAllowance)
+                }
+                Some((min, false)) if amount >= min => {
+                    return Err(TransferSimulationError::InsufficientBalance)
+                }
+                _ => (),
+            }
+        }
+
+        let result = self.inner.can_transfer(query, amount).await;
+        let mut bounds = self.bounds.lock().unwrap();
+        let bounds = bounds.entry(query.clone()).or_default();
+        let insufficient = match &result {
+            Ok(()) => {
+                bounds.max_success = Some(bounds.max_success.unwrap_or_default().max(amount));
+                None
+            }
+            Err(TransferSimulationError::InsufficientAllowance) => Some(true),
+            Err(TransferSimulationError::InsufficientBalance) => Some(false),
+            Err(_) => None,
+        };
+        if let Some(allowance) = insufficient {
+            if bounds
+                .min_insufficient
+                .map_or(true, |(min, _)| amount < min)
+            {
+                bounds.min_insufficient = Some((amount, allowance));
+            }
+        }
+        result
+    }
+}
+
+/// Counts the limit orders accepted earlier in a batch in addition to the
+/// ones that are already stored.
+struct BatchLimitOrderCounter {
+    inner: Arc<dyn LimitOrderCounting>,
+    accepted: Mutex<HashMap<H160, u64>>,
+}
+
+impl BatchLimitOrderCounter {
+    fn new(inner: Arc<dyn LimitOrderCounting>) -> Self {
+        Self {
+            inner,
+            accepted: Default::default(),
+        }
+    }
+
+    fn accept(&self, owner: H160) {
+        *self.accepted.lock().unwrap().entry(owner).or_default() += 1;
+    }
+}
+
+#[async_trait]
+impl LimitOrderCounting for BatchLimitOrderCounter {
+    async fn count(&self, owner: H160) -> Result<u64> {
+        let stored = self.inner.count(owner).await?;
+        let accepted = self
+            .accepted
+            .lock()
+            .unwrap()
+            .get(&owner)
+            .copied()
+            .unwrap_or_default();
+        Ok(stored + accepted)
+    }
 }
 
 /// Order validity period configuration.
@@ -1075,7 +1271,7 @@ mod tests {
             max_market: Duration::from_secs(100),
             max_limit: Duration::from_secs(200),
         };
-        let banned_users = hashset![H160::from_low_u64_be(1)];
+        let banned_users = AutoUpdatingAddressList::from(vec![H160::from_low_u64_be(1)]);
         let legit_valid_to =
             time::now_in_epoch_seconds() + validity_configuration.min.as_secs() as u32 + 2;
         let mut limit_order_counter = MockLimitOrderCounting::new();
@@ -1243,7 +1439,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(liquidity_order_owner),
             validity_configuration,
             SignatureConfiguration::all(),
@@ -1329,7 +1525,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration {
                 min: Duration::from_secs(1),
@@ -1529,7 +1725,7 @@ mod tests {
 
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration::any(),
             SignatureConfiguration::all(),
@@ -1568,6 +1764,86 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn post_validate_batch_shares_lookups() {
+        let mut bad_token_detector = MockBadTokenDetecting::new();
+        bad_token_detector
+            .expect_detect()
+            .times(2)
+            .returning(|_| Ok(TokenQuality::Good));
+        let mut balance_fetcher = MockBalanceFetching::new();
+        balance_fetcher
+            .expect_can_transfer()
+            .times(3)
+            .returning(|_, amount| {
+                if amount <= 3.into() {
+                    Ok(())
+                } else {
+                    Err(TransferSimulationError::InsufficientBalance)
+                }
+            });
+        let mut limit_order_counter = MockLimitOrderCounting::new();
+        limit_order_counter.expect_count().returning(|_| Ok(0));
+
+        let validator = OrderValidator::new(
+            dummy_contract!(WETH9, [0xef; 20]),
+            Default::default(),
+            hashset!(),
+            OrderValidPeriodConfiguration::any(),
+            SignatureConfiguration::all(),
+            Arc::new(bad_token_detector),
+            dummy_contract!(HooksTrampoline, [0xcf; 20]),
+            Arc::new(MockOrderQuoting::new()),
+            Arc::new(balance_fetcher),
+            Arc::new(MockSignatureValidating::new()),
+            Arc::new(limit_order_counter),
+            3,
+            Arc::new(MockCodeFetching::new()),
+            Default::default(),
+        )
+        .with_fill_or_kill_limit_orders(true);
+
+        let key = secp256k1::SecretKey::from_str(
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        let orders = [2, 1, 3, 4, 1]
+            .into_iter()
+            .map(|sell_amount| {
+                let order = OrderCreation {
+                    valid_to: u32::MAX,
+                    sell_token: H160::from_low_u64_be(1),
+                    sell_amount: sell_amount.into(),
+                    buy_token: H160::from_low_u64_be(2),
+                    buy_amount: 1.into(),
+                    ..Default::default()
+                }
+                .sign(
+                    EcdsaSigningScheme::Eip712,
+                    &Default::default(),
+                    SecretKeyRef::new(&key),
+                );
+                (order, None)
+            })
+            .collect();
+
+        let results = validator
+            .validate_and_construct_orders(orders, &Default::default(), Default::default())
+            .await;
+        assert_eq!(results.len(), 5);
+        assert!(results[..3].iter().all(Result::is_ok), "{results:?}");
+        assert!(
+            matches!(results[3], Err(ValidationError::InsufficientBalance)),
+            "{results:?}"
+        );
+        // The limit orders accepted earlier in the batch count towards the
+        // maximum.
+        assert!(
+            matches!(results[4], Err(ValidationError::TooManyLimitOrders)),
+            "{results:?}"
+        );
+    }
+
     #[tokio::test]
     async fn post_validate_err_zero_amount() {
         let mut order_quoter = MockOrderQuoting::new();
@@ -1586,7 +1862,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration::any(),
             SignatureConfiguration::all(),
@@ -1637,7 +1913,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration::any(),
             SignatureConfiguration::all(),
@@ -1700,7 +1976,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration::any(),
             SignatureConfiguration::all(),
@@ -1754,7 +2030,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration::any(),
             SignatureConfiguration::all(),
@@ -1803,7 +2079,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration::any(),
             SignatureConfiguration::all(),
@@ -1854,7 +2130,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration::any(),
             SignatureConfiguration::all(),
@@ -1909,7 +2185,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration::any(),
             SignatureConfiguration::all(),
@@ -1958,7 +2234,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration::any(),
             SignatureConfiguration::all(),
@@ -2011,7 +2287,7 @@ mod tests {
         limit_order_counter.expect_count().returning(|_| Ok(0u64));
         let validator = OrderValidator::new(
             dummy_contract!(WETH9, [0xef; 20]),
-            hashset!(),
+            Default::default(),
             hashset!(),
             OrderValidPeriodConfiguration::any(),
             SignatureConfiguration::all(),
@@ -2071,7 +2347,7 @@ mod tests {
             limit_order_counter.expect_count().returning(|_| Ok(0u64));
             let validator = OrderValidator::new(
                 dummy_contract!(WETH9, [0xef; 20]),
-                hashset!(),
+                Default::default(),
                 hashset!(),
                 OrderValidPeriodConfiguration::any(),
                 SignatureConfiguration::all(),
@@ -6,7 +6,7 @@ use {
         trade_finding::Interaction,
     },
     anyhow::{Context, Result},
-    ethcontract::{H160, U256},
+    ethcontract::{H160, H256, U256},
     futures::future::BoxFuture,
     itertools::Itertools,
     model::order::{BuyTokenDestination, OrderKind, SellTokenSource},
@@ -448,6 +448,26 @@ pub struct Verification {
     pub buy_token_destination: BuyTokenDestination,
 }
 
+impl Verification {
+    /// Returns a hash identifying the pre- and post-interactions the trade gets
+    /// simulated with.
+    pub fn hooks_hash(&self) -> H256 {
+        let mut buffer = Vec::new();
+        for interactions in [&self.pre_interactions, &self.post_interactions] {
+            buffer.extend_from_slice(&(interactions.len() as u64).to_be_bytes());
+            for interaction in interactions {
+                let mut value = [0; 32];
+                interaction.value.to_big_endian(&mut value);
+                buffer.extend_from_slice(interaction.target.as_bytes());
+                buffer.extend_from_slice(&value);
+                buffer.extend_from_slice(&(interaction.data.len() as u64).to_be_bytes());
+                buffer.extend_from_slice(&interaction.data);
+            }
+        }
+        H256(web3::signing::keccak256(&buffer))
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize)]
 pub struct Estimate {
     pub out_amount: U256,
@@ -455,6 +475,9 @@ pub struct Estimate {
     pub gas: u64,
     /// Address of the solver that provided the quote.
     pub solver: H160,
+    /// Whether the estimate was verified by simulating the trade.
+    #[serde(default)]
+    pub verified: bool,
 }
 
 impl Estimate {
@@ -9,13 +9,67 @@ use {
     },
     futures::{
         stream::{FuturesUnordered, StreamExt},
+        Future,
         FutureExt as _,
     },
     model::order::OrderKind,
-    primitive_types::H160,
-    std::{cmp::Ordering, fmt::Debug, num::NonZeroUsize, sync::Arc, time::Instant},
+    primitive_types::{H160, U256},
+    std::{
+        cmp::Ordering,
+        collections::HashMap,
+        fmt::Debug,
+        num::NonZeroUsize,
+        sync::{Arc, Mutex, RwLock},
+        time::{Duration, Instant},
+    },
 };
 
+tokio::task_local! {
+    static TRACE: Arc<Mutex<Option<CompetitionTrace>>>;
+}
+
+/// Detailed outcome of a price estimator competition.
+#[derive(Clone, Debug, Default)]
+pub struct CompetitionTrace {
+    /// Name of the estimator that provided the winning result.
+    pub winner: String,
+    /// Results of all the estimators that got queried in the order in which
+    /// they arrived.
+    pub estimates: Vec<(String, PriceEstimateResult)>,
+    /// How long it took the winning estimator to provide its result.
+    pub winner_latency: Duration,
+}
+
+/// Runs the future and records the details of the last price estimator
+/// competition that got executed as part of it.
+///
+/// The competition has to run on the same task as the passed future for it to
+/// be recorded. Nested calls also record the trace for the enclosing call.
+///
+/// Estimators taking part in the competition may share in-flight requests
+/// with other callers (see `RequestSharing`). This doesn't affect the trace
+/// because every caller runs its own competition and records the trace from
+/// the shared results on its own task.
+pub async fn traced<F: Future>(future: F) -> (F::Output, Option<CompetitionTrace>) {
+    let trace = Arc::new(Mutex::new(None));
+    let output = TRACE.scope(trace.clone(), future).await;
+    let trace = trace.lock().unwrap().take();
+    if let Some(trace) = &trace {
+        let _ = TRACE.try_with(|outer| *outer.lock().unwrap() = Some(trace.clone()));
+    }
+    (output, trace)
+}
+
+fn record_trace(race: &Race<Estimate, PriceEstimationError>) {
+    let _ = TRACE.try_with(|trace| {
+        *trace.lock().unwrap() = Some(CompetitionTrace {
+            winner: race.results[race.winner].0.clone(),
+            estimates: race.results.clone(),
+            winner_latency: race.winner_latency,
+        });
+    });
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 struct Trade {
     sell_token: H160,
@@ -43,6 +97,40 @@ struct Wins(u64);
 
 type PriceEstimationStage<T> = Vec<(String, T)>;
 
+/// All results of a competition labelled with the name of the respective
+/// estimator.
+struct Race<R, E> {
+    results: Vec<(String, Result<R, E>)>,
+    /// Index of the best result.
+    winner: usize,
+    /// How long it took until the best result arrived.
+    winner_latency: Duration,
+}
+
+impl<R, E> Race<R, E> {
+    fn into_winner(mut self) -> Result<R, E> {
+        self.results.swap_remove(self.winner).1
+    }
+}
+
+/// Multipliers for the estimates of every estimator that get applied when
+/// comparing estimates. Estimators that historically promised more than orders
+/// eventually got executed for get a weight below 1, which makes their
+/// estimates less attractive. Estimators without a weight have a weight of 1.
+#[derive(Debug, Default)]
+pub struct EstimatorWeights(RwLock<HashMap<String, f64>>);
+
+impl EstimatorWeights {
+    /// Replaces all weights.
+    pub fn set(&self, weights: HashMap<String, f64>) {
+        *self.0.write().unwrap() = weights;
+    }
+
+    pub fn get(&self, estimator: &str) -> f64 {
+        self.0.read().unwrap().get(estimator).copied().unwrap_or(1.)
+    }
+}
+
 /// Price estimator that pulls estimates from various sources
 /// and competes on the best price. Sources are provided as a list of lists, the
 /// outer list representing the sequential stage of the search, and the inner
@@ -53,6 +141,7 @@ type PriceEstimationStage<T> = Vec<(String, T)>;
 pub struct RacingCompetitionEstimator<T> {
     inner: Vec<PriceEstimationStage<T>>,
     successful_results_for_early_return: NonZeroUsize,
+    weights: Arc<EstimatorWeights>,
 }
 
 impl<T: Send + Sync + 'static> RacingCompetitionEstimator<T> {
@@ -64,9 +153,15 @@ impl<T: Send + Sync + 'static> RacingCompetitionEstimator<T> {
         Self {
             inner,
             successful_results_for_early_return,
+            weights: Default::default(),
         }
     }
 
+    /// Applies the weights when comparing estimates of different estimators.
+    pub fn with_weights(self, weights: Arc<EstimatorWeights>) -> Self {
+        Self { weights, ..self }
+    }
+
     fn estimate_generic<
         Q: Clone + Debug + Send + 'static,
         R: Clone + Debug + Send,
@@ -78,8 +173,10 @@ impl<T: Send + Sync + 'static> RacingCompetitionEstimator<T> {
         get_single_result: impl Fn(&T, Q) -> futures::future::BoxFuture<'_, Result<R, E>>
             + Send
             + 'static,
-        compare_results: impl Fn(&Result<R, E>, &Result<R, E>) -> Ordering + Send + 'static,
-    ) -> futures::future::BoxFuture<'_, Result<R, E>> {
+        compare_results: impl Fn((&str, &Result<R, E>), (&str, &Result<R, E>)) -> Ordering
+            + Send
+            + 'static,
+    ) -> futures::future::BoxFuture<'_, Race<R, E>> {
         let start = Instant::now();
         async move {
             let mut results = vec![];
@@ -119,7 +216,7 @@ impl<T: Send + Sync + 'static> RacingCompetitionEstimator<T> {
 
                 let mut futures: FuturesUnordered<_> = requests.into_iter().collect();
                 while let Some((estimator_index, result)) = futures.next().await {
-                    results.push((estimator_index, result.clone()));
+                    results.push((estimator_index, result.clone(), start.elapsed()));
                     let estimator = &self.inner[estimator_index.0][estimator_index.1].0;
                     tracing::debug!(
                         ?query,
@@ -139,12 +236,13 @@ impl<T: Send + Sync + 'static> RacingCompetitionEstimator<T> {
 
             let best_index = results
                 .iter()
-                .map(|(_, result)| result)
+                .map(|(index, result, _)| (self.inner[index.0][index.1].0.as_str(), result))
                 .enumerate()
                 .max_by(|a, b| compare_results(a.1, b.1))
                 .map(|(index, _)| index)
                 .unwrap();
-            let (estimator_index, result) = &results[best_index];
+            let (estimator_index, result, winner_latency) = &results[best_index];
+            let winner_latency = *winner_latency;
             let (estimator, _) = &self.inner[estimator_index.0][estimator_index.1];
             tracing::debug!(
                 ?query,
@@ -172,30 +270,50 @@ impl<T: Send + Sync + 'static> RacingCompetitionEstimator<T> {
                     .with_label_values(&[estimator, kind.label()])
                     .inc();
             }
-            result.clone()
+
+            Race {
+                results: results
+                    .into_iter()
+                    .map(|(index, result, _)| (self.inner[index.0][index.1].0.clone(), result))
+                    .collect(),
+                winner: best_index,
+                winner_latency,
+            }
         }
         .boxed()
     }
 }
 
-fn successes<R, E>(results: &[(EstimatorIndex, Result<R, E>)]) -> usize {
-    results.iter().filter(|(_, result)| result.is_ok()).count()
+fn successes<R, E>(results: &[(EstimatorIndex, Result<R, E>, Duration)]) -> usize {
+    results
+        .iter()
+        .filter(|(_, result, _)| result.is_ok())
+        .count()
 }
 
 impl PriceEstimating for RacingCompetitionEstimator<Arc<dyn PriceEstimating>> {
     fn estimate(&self, query: Arc<Query>) -> futures::future::BoxFuture<'_, PriceEstimateResult> {
-        self.estimate_generic(
+        let weights = self.weights.clone();
+        let race = self.estimate_generic(
             query.clone(),
             query.kind,
             |estimator, query| estimator.estimate(query),
-            move |a, b| {
+            move |(a_name, a), (b_name, b)| {
+                let a = (a, weights.get(a_name));
+                let b = (b, weights.get(b_name));
                 if is_second_quote_result_preferred(query.as_ref(), a, b) {
                     Ordering::Less
                 } else {
                     Ordering::Greater
                 }
             },
-        )
+        );
+        async move {
+            let race = race.await;
+            record_trace(&race);
+            race.into_winner()
+        }
+        .boxed()
     }
 }
 
@@ -208,7 +326,7 @@ impl NativePriceEstimating for RacingCompetitionEstimator<Arc<dyn NativePriceEst
             token,
             OrderKind::Buy,
             |estimator, token| estimator.estimate_native_price(token),
-            move |a, b| {
+            move |(_, a), (_, b)| {
                 if is_second_native_result_preferred(a, b) {
                     Ordering::Less
                 } else {
@@ -216,6 +334,8 @@ impl NativePriceEstimating for RacingCompetitionEstimator<Arc<dyn NativePriceEst
                 }
             },
         )
+        .map(Race::into_winner)
+        .boxed()
     }
 }
 
@@ -234,6 +354,12 @@ impl<T: Send + Sync + 'static> CompetitionEstimator<T> {
             inner: RacingCompetitionEstimator::new(inner, number_of_estimators),
         }
     }
+
+    pub fn with_weights(self, weights: Arc<EstimatorWeights>) -> Self {
+        Self {
+            inner: self.inner.with_weights(weights),
+        }
+    }
 }
 
 impl PriceEstimating for CompetitionEstimator<Arc<dyn PriceEstimating>> {
@@ -242,13 +368,18 @@ impl PriceEstimating for CompetitionEstimator<Arc<dyn PriceEstimating>> {
     }
 }
 
+/// Compares the results together with the weights of their estimators.
 fn is_second_quote_result_preferred(
     query: &Query,
-    a: &PriceEstimateResult,
-    b: &PriceEstimateResult,
+    (a, a_weight): (&PriceEstimateResult, f64),
+    (b, b_weight): (&PriceEstimateResult, f64),
 ) -> bool {
     match (a, b) {
-        (Ok(a), Ok(b)) => is_second_estimate_preferred(query, a, b),
+        (Ok(a), Ok(b)) => is_second_estimate_preferred(
+            query,
+            weighted_out_amount(query, a, a_weight),
+            weighted_out_amount(query, b, b_weight),
+        ),
         (Ok(_), Err(_)) => false,
         (Err(_), Ok(_)) => true,
         (Err(a), Err(b)) => is_second_error_preferred(a, b),
@@ -267,13 +398,27 @@ fn is_second_native_result_preferred(
     }
 }
 
-fn is_second_estimate_preferred(query: &Query, a: &Estimate, b: &Estimate) -> bool {
+fn is_second_estimate_preferred(query: &Query, a_out_amount: U256, b_out_amount: U256) -> bool {
     match query.kind {
-        OrderKind::Buy => b.out_amount < a.out_amount,
-        OrderKind::Sell => a.out_amount < b.out_amount,
+        OrderKind::Buy => b_out_amount < a_out_amount,
+        OrderKind::Sell => a_out_amount < b_out_amount,
     }
 }
 
+/// Scales the out amount of the estimate such that estimates with a lower
+/// weight are less attractive. For buy orders the out amount is the sell
+/// amount so it gets increased instead.
+fn weighted_out_amount(query: &Query, estimate: &Estimate, weight: f64) -> U256 {
+    if weight == 1. {
+        return estimate.out_amount;
+    }
+    let factor = match query.kind {
+        OrderKind::Buy => 1. / weight,
+        OrderKind::Sell => weight,
+    };
+    U256::from_f64_lossy(estimate.out_amount.to_f64_lossy() * factor)
+}
+
 fn is_second_error_preferred(a: &PriceEstimationError, b: &PriceEstimationError) -> bool {
     // Errors are sorted by recoverability. E.g. a rate-limited estimation may
     // succeed if tried again, whereas unsupported order types can never recover
@@ -321,13 +466,16 @@ fn metrics() -> &'static Metrics {
 mod tests {
     use {
         super::*,
-        crate::price_estimation::MockPriceEstimating,
+        crate::{price_estimation::MockPriceEstimating, request_sharing::RequestSharing},
         anyhow::anyhow,
-        futures::channel::oneshot::channel,
+        futures::{channel::oneshot::channel, future::BoxFuture},
         model::order::OrderKind,
         number::nonzero::U256 as NonZeroU256,
         primitive_types::H160,
-        std::time::Duration,
+        std::{
+            sync::atomic::{self, AtomicUsize},
+            time::Duration,
+        },
         tokio::time::sleep,
     };
 
@@ -451,6 +599,175 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn traces_competition() {
+        let query = Arc::new(Query {
+            verification: None,
+            sell_token: H160::from_low_u64_le(0),
+            buy_token: H160::from_low_u64_le(1),
+            in_amount: NonZeroU256::try_from(1).unwrap(),
+            kind: OrderKind::Sell,
+            block_dependent: false,
+        });
+        let estimate = Estimate {
+            out_amount: 1.into(),
+            ..Default::default()
+        };
+
+        let mut first = MockPriceEstimating::new();
+        first
+            .expect_estimate()
+            .times(1)
+            .returning(|_| async { Err(PriceEstimationError::NoLiquidity) }.boxed());
+        let mut second = MockPriceEstimating::new();
+        second
+            .expect_estimate()
+            .times(1)
+            .returning(move |_| async move { Ok(estimate) }.boxed());
+
+        let competition: CompetitionEstimator<Arc<dyn PriceEstimating>> =
+            CompetitionEstimator::new(vec![vec![
+                ("first".to_owned(), Arc::new(first)),
+                ("second".to_owned(), Arc::new(second)),
+            ]]);
+
+        let (result, trace) = traced(competition.estimate(query)).await;
+        assert_eq!(result.unwrap(), estimate);
+        let trace = trace.unwrap();
+        assert_eq!(trace.winner, "second");
+        assert_eq!(trace.estimates.len(), 2);
+        assert!(trace.estimates.iter().any(|(name, result)| name == "first"
+            && matches!(result, Err(PriceEstimationError::NoLiquidity))));
+    }
+
+    #[tokio::test]
+    async fn nested_traces_are_recorded_for_outer_call() {
+        let query = Arc::new(Query {
+            verification: None,
+            sell_token: H160::from_low_u64_le(0),
+            buy_token: H160::from_low_u64_le(1),
+            in_amount: NonZeroU256::try_from(1).unwrap(),
+            kind: OrderKind::Sell,
+            block_dependent: false,
+        });
+        let mut first = MockPriceEstimating::new();
+        first
+            .expect_estimate()
+            .times(1)
+            .returning(|_| async { Ok(Estimate::default()) }.boxed());
+        let competition: CompetitionEstimator<Arc<dyn PriceEstimating>> =
+            CompetitionEstimator::new(vec![vec![("first".to_owned(), Arc::new(first))]]);
+
+        let ((_, inner), outer) = traced(traced(competition.estimate(query))).await;
+        assert_eq!(inner.unwrap().winner, "first");
+        assert_eq!(outer.unwrap().winner, "first");
+    }
+
+    #[tokio::test]
+    async fn traces_concurrent_competitions_sharing_requests() {
+        struct SharingEstimator {
+            sharing: RequestSharing<Arc<Query>, BoxFuture<'static, PriceEstimateResult>>,
+            requests: Arc<AtomicUsize>,
+        }
+
+        impl PriceEstimating for SharingEstimator {
+            fn estimate(&self, query: Arc<Query>) -> BoxFuture<'_, PriceEstimateResult> {
+                let requests = self.requests.clone();
+                let request = async move {
+                    requests.fetch_add(1, atomic::Ordering::SeqCst);
+                    sleep(Duration::from_millis(10)).await;
+                    Ok(Estimate {
+                        out_amount: 1.into(),
+                        ..Default::default()
+                    })
+                };
+                self.sharing.shared(query, request.boxed()).boxed()
+            }
+        }
+
+        let query = Arc::new(Query {
+            verification: None,
+            sell_token: H160::from_low_u64_le(0),
+            buy_token: H160::from_low_u64_le(1),
+            in_amount: NonZeroU256::try_from(1).unwrap(),
+            kind: OrderKind::Sell,
+            block_dependent: false,
+        });
+        let requests = Arc::new(AtomicUsize::new(0));
+        let estimator = SharingEstimator {
+            sharing: RequestSharing::labelled("test".into()),
+            requests: requests.clone(),
+        };
+        let competition: CompetitionEstimator<Arc<dyn PriceEstimating>> =
+            CompetitionEstimator::new(vec![vec![("shared".to_owned(), Arc::new(estimator))]]);
+
+        let ((first, first_trace), (second, second_trace)) = futures::join!(
+            traced(competition.estimate(query.clone())),
+            traced(competition.estimate(query)),
+        );
+        assert_eq!(requests.load(atomic::Ordering::SeqCst), 1);
+        assert_eq!(first.unwrap(), second.unwrap());
+        for trace in [first_trace, second_trace] {
+            let trace = trace.unwrap();
+            assert_eq!(trace.winner, "shared");
+            assert_eq!(trace.estimates.len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn applies_estimator_weights() {
+        let query = |kind| {
+            Arc::new(Query {
+                verification: None,
+                sell_token: H160::from_low_u64_le(0),
+                buy_token: H160::from_low_u64_le(1),
+                in_amount: NonZeroU256::try_from(1).unwrap(),
+                kind,
+                block_dependent: false,
+            })
+        };
+        let estimator = |out_amount: u64| {
+            let mut estimator = MockPriceEstimating::new();
+            estimator.expect_estimate().returning(move |_| {
+                async move {
+                    Ok(Estimate {
+                        out_amount: out_amount.into(),
+                        ..Default::default()
+                    })
+                }
+                .boxed()
+            });
+            Arc::new(estimator) as Arc<dyn PriceEstimating>
+        };
+        let weights = Arc::new(EstimatorWeights::default());
+        let racing = RacingCompetitionEstimator::new(
+            vec![vec![
+                ("optimistic".to_owned(), estimator(100)),
+                ("accurate".to_owned(), estimator(95)),
+            ]],
+            NonZeroUsize::new(2).unwrap(),
+        )
+        .with_weights(weights.clone());
+
+        // Without weights the best estimate wins.
+        let result = racing.estimate(query(OrderKind::Sell)).await.unwrap();
+        assert_eq!(result.out_amount, 100.into());
+        let result = racing.estimate(query(OrderKind::Buy)).await.unwrap();
+        assert_eq!(result.out_amount, 95.into());
+
+        // The optimistic estimator historically over-promised by 10%.
+        weights.set(HashMap::from([("optimistic".to_owned(), 0.9)]));
+        let result = racing.estimate(query(OrderKind::Sell)).await.unwrap();
+        assert_eq!(result.out_amount, 95.into());
+        // The accurate estimator historically under-promised by 10%.
+        weights.set(HashMap::from([("accurate".to_owned(), 1.1)]));
+        let result = racing.estimate(query(OrderKind::Buy)).await.unwrap();
+        assert_eq!(result.out_amount, 95.into());
+        weights.set(HashMap::from([("optimistic".to_owned(), 1.1)]));
+        let result = racing.estimate(query(OrderKind::Buy)).await.unwrap();
+        assert_eq!(result.out_amount, 100.into());
+    }
+
     #[tokio::test]
     async fn racing_estimator_returns_early() {
         let query = Arc::new(Query {
@@ -647,6 +964,7 @@ mod tests {
                     vec![("fourth".to_owned(), Arc::new(fourth))],
                 ],
                 successful_results_for_early_return: NonZeroUsize::new(2).unwrap(),
+                weights: Default::default(),
             };
 
         racing.estimate(query).await.unwrap();
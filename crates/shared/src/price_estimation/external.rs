@@ -5,7 +5,11 @@ use {
         PriceEstimating,
         Query,
     },
-    crate::{rate_limiter::RateLimiter, trade_finding::external::ExternalTradeFinder},
+    crate::{
+        rate_limiter::RateLimiter,
+        request_signing::Signer,
+        trade_finding::external::ExternalTradeFinder,
+    },
     ethrpc::current_block::CurrentBlockStream,
     reqwest::{Client, Url},
     std::sync::Arc,
@@ -17,6 +21,7 @@ impl ExternalPriceEstimator {
     pub fn new(
         driver: Url,
         client: Client,
+        signer: Option<Signer>,
         rate_limiter: Arc<RateLimiter>,
         block_stream: CurrentBlockStream,
     ) -> Self {
@@ -24,6 +29,7 @@ impl ExternalPriceEstimator {
             Arc::new(ExternalTradeFinder::new(
                 driver.clone(),
                 client,
+                signer,
                 block_stream,
             )),
             rate_limiter,
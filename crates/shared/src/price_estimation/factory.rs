@@ -2,7 +2,7 @@ use {
     super::{
         balancer_sor::BalancerSor,
         baseline::BaselinePriceEstimator,
-        competition::{CompetitionEstimator, RacingCompetitionEstimator},
+        competition::{CompetitionEstimator, EstimatorWeights, RacingCompetitionEstimator},
         external::ExternalPriceEstimator,
         http::HttpPriceEstimator,
         instrumented::InstrumentedPriceEstimator,
@@ -33,6 +33,7 @@ use {
         paraswap_api::DefaultParaswapApi,
         price_estimation::native::NativePriceEstimating,
         rate_limiter::RateLimiter,
+        request_signing::Signer,
         sources::{
             balancer_v2::BalancerPoolFetching,
             uniswap_v2::pool_fetching::PoolFetching as UniswapV2PoolFetching,
@@ -58,6 +59,7 @@ pub struct PriceEstimatorFactory<'a> {
     components: Components,
     trade_verifier: Option<TradeVerifier>,
     estimators: HashMap<String, EstimatorEntry>,
+    weights: Arc<EstimatorWeights>,
 }
 
 #[derive(Clone)]
@@ -172,6 +174,7 @@ impl<'a> PriceEstimatorFactory<'a> {
             components,
             trade_verifier,
             estimators: HashMap::new(),
+            weights: Default::default(),
         })
     }
 
@@ -333,12 +336,19 @@ impl<'a> PriceEstimatorFactory<'a> {
         )
     }
 
+    /// Weights of the estimators that the optimal and fast price estimators
+    /// apply when comparing estimates.
+    pub fn weights(&self) -> Arc<EstimatorWeights> {
+        self.weights.clone()
+    }
+
     pub fn price_estimator(
         &mut self,
         sources: &[PriceEstimatorSource],
     ) -> Result<Arc<dyn PriceEstimating>> {
         let estimators = self.get_estimators(sources, |entry| &entry.optimal)?;
-        let competition_estimator = CompetitionEstimator::new(vec![estimators]);
+        let competition_estimator =
+            CompetitionEstimator::new(vec![estimators]).with_weights(self.weights.clone());
         Ok(Arc::new(self.sanitized(Arc::new(competition_estimator))))
     }
 
@@ -348,12 +358,15 @@ impl<'a> PriceEstimatorFactory<'a> {
         fast_price_estimation_results_required: NonZeroUsize,
     ) -> Result<Arc<dyn PriceEstimating>> {
         let estimators = self.get_estimators(sources, |entry| &entry.fast)?;
-        Ok(Arc::new(self.sanitized(Arc::new(
-            RacingCompetitionEstimator::new(
-                vec![estimators],
-                fast_price_estimation_results_required,
-            ),
-        ))))
+        Ok(Arc::new(
+            self.sanitized(Arc::new(
+                RacingCompetitionEstimator::new(
+                    vec![estimators],
+                    fast_price_estimation_results_required,
+                )
+                .with_weights(self.weights.clone()),
+            )),
+        ))
     }
 
     pub fn native_price_estimator(
@@ -581,6 +594,11 @@ impl PriceEstimatorCreating for ExternalPriceEstimator {
         Ok(Self::new(
             params.driver,
             factory.components.http_factory.create(),
+            factory
+                .shared_args
+                .driver_auth_secret
+                .clone()
+                .map(Signer::new),
             factory.rate_limiter(name),
             factory.network.block_stream.clone(),
         ))
@@ -128,6 +128,7 @@ impl Inner {
                     out_amount: quote.out_amount,
                     gas: quote.gas_estimate,
                     solver: quote.solver,
+                    verified: false,
                 })
             }
         }
@@ -337,6 +338,7 @@ impl TradeVerifier {
             out_amount: summary.out_amount(query.kind)?,
             gas: summary.gas_used.as_u64(),
             solver: trade.solver,
+            verified: true,
         };
         tracing::debug!(
             ?query,
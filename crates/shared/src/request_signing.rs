@@ -0,0 +1,470 @@
+//! Authentication of HTTP requests between services with a shared secret.
+//!
+//! The sender signs the HTTP method, the path including the query string and
+//! the body of the request together with the current unix timestamp using
+//! HMAC-SHA256 and attaches the timestamp and the signature in headers. The
+//! receiver checks that the timestamp is recent, that the signature matches one
+//! of its configured secrets and that the same signature was not already used
+//! before for the same route. Since the route is part of the signature,
+//! identical bodies sent at the same time to different routes (e.g. to several
+//! solvers behind one driver) get different signatures, and a signature
+//! captured for one route can't be used for another.
+//!
+//! Secrets can be rotated without downtime by first adding the new secret to
+//! the receivers, then switching the senders over and finally removing the old
+//! secret from the receivers.
+//!
+//! HTTP servers verify requests with [`verify_request`], which their
+//! middlewares wrap.
+
+use {
+    hmac::{Hmac, Mac},
+    sha2::Sha256,
+    std::{
+        collections::HashMap,
+        str::FromStr,
+        sync::Mutex,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    url::Url,
+    warp::{
+        http::{Request, Uri},
+        hyper::Body,
+    },
+};
+
+/// Header containing the unix timestamp in seconds at which the request was
+/// signed.
+pub const TIMESTAMP_HEADER: &str = "X-Auth-Timestamp";
+/// Header containing the hex encoded HMAC-SHA256 signature of the request.
+pub const SIGNATURE_HEADER: &str = "X-Auth-Signature";
+
+/// A shared secret used to sign requests. Never shows up in logs.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Secret(Vec<u8>);
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SECRET")
+    }
+}
+
+impl Secret {
+    /// Derives a secret bound to `context` (e.g. a webhook) from this one, so
+    /// that secrets handed out to third parties don't need to be stored. The
+    /// derived secret is the hex encoded HMAC-SHA256 of the context.
+    pub fn derive(&self, context: &str) -> Self {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC accepts any key size");
+        mac.update(context.as_bytes());
+        Self(hex::encode(mac.finalize().into_bytes()).into_bytes())
+    }
+
+    /// Returns the secret in the form it gets handed out.
+    pub fn reveal(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("secrets are created from strings")
+    }
+}
+
+impl FromStr for Secret {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        anyhow::ensure!(!s.is_empty(), "empty secret");
+        Ok(Self(s.as_bytes().to_vec()))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Secret {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The secret the deliveries to the webhook with the id get signed with. It is
+/// handed out to the integrator when registering the webhook.
+pub fn webhook_secret(key: &Secret, id: i64) -> Secret {
+    key.derive(&format!("webhook/{id}"))
+}
+
+/// Signs outgoing requests.
+#[derive(Clone, Debug)]
+pub struct Signer(Secret);
+
+impl Signer {
+    pub fn new(secret: Secret) -> Self {
+        Self(secret)
+    }
+
+    /// Returns the headers that authenticate a request with the given method,
+    /// URL and body.
+    pub fn headers(&self, method: &str, url: &Url, body: &[u8]) -> [(&'static str, String); 2] {
+        let path = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        self.headers_at(
+            &Route {
+                method,
+                path: &path,
+            },
+            body,
+            now(),
+        )
+    }
+
+    fn headers_at(
+        &self,
+        route: &Route,
+        body: &[u8],
+        timestamp: u64,
+    ) -> [(&'static str, String); 2] {
+        let signature = mac(&self.0, route, timestamp, body).finalize().into_bytes();
+        [
+            (TIMESTAMP_HEADER, timestamp.to_string()),
+            (SIGNATURE_HEADER, hex::encode(signature)),
+        ]
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("missing authentication headers")]
+    Missing,
+    #[error("malformed authentication headers")]
+    Malformed,
+    #[error("request timestamp is outside of the accepted window")]
+    Expired,
+    #[error("invalid signature")]
+    Invalid,
+    #[error("signature was already used")]
+    Replayed,
+    #[error("failed to read request body")]
+    Body,
+}
+
+/// Verifies incoming requests.
+#[derive(Debug)]
+pub struct Verifier {
+    secrets: Vec<Secret>,
+    max_age: Duration,
+    /// Signatures that were already accepted per route together with their
+    /// timestamp. Entries are dropped once their timestamp is too old to be
+    /// accepted anyway.
+    seen: Mutex<HashMap<(String, Vec<u8>), u64>>,
+}
+
+impl Verifier {
+    /// Creates a verifier accepting requests signed with any of `secrets` no
+    /// longer than `max_age` ago.
+    pub fn new(secrets: Vec<Secret>, max_age: Duration) -> Self {
+        Self {
+            secrets,
+            max_age,
+            seen: Default::default(),
+        }
+    }
+
+    /// Verifies the authentication header values for a request with the given
+    /// method, path including the query string and body.
+    pub fn verify(
+        &self,
+        method: &str,
+        path: &str,
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> Result<(), Error> {
+        self.verify_at(&Route { method, path }, timestamp, signature, body, now())
+    }
+
+    fn verify_at(
+        &self,
+        route: &Route,
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        body: &[u8],
+        now: u64,
+    ) -> Result<(), Error> {
+        let (timestamp, signature) = timestamp.zip(signature).ok_or(Error::Missing)?;
+        let timestamp: u64 = timestamp.parse().map_err(|_| Error::Malformed)?;
+        let signature = hex::decode(signature).map_err(|_| Error::Malformed)?;
+
+        let max_age = self.max_age.as_secs();
+        if timestamp.abs_diff(now) > max_age {
+            return Err(Error::Expired);
+        }
+        if !self.secrets.iter().any(|secret| {
+            mac(secret, route, timestamp, body)
+                .verify_slice(&signature)
+                .is_ok()
+        }) {
+            return Err(Error::Invalid);
+        }
+
+        let mut seen = self.seen.lock().unwrap();
+        seen.retain(|_, seen| seen.saturating_add(max_age) >= now);
+        if seen
+            .insert((route.to_string(), signature), timestamp)
+            .is_some()
+        {
+            return Err(Error::Replayed);
+        }
+        Ok(())
+    }
+}
+
+/// Verifies a request received by an HTTP server. Returns the request with
+/// its body buffered, so that it can be passed on to the handler.
+///
+/// `uri` is the URI the request was originally sent to. Servers nesting routes
+/// only see the remainder of the path, but the signature covers the full one.
+pub async fn verify_request(
+    verifier: &Verifier,
+    uri: &Uri,
+    request: Request<Body>,
+) -> Result<Request<Body>, Error> {
+    // The signature covers the body, so it has to be buffered before it can be
+    // passed on to the handler.
+    let (parts, body) = request.into_parts();
+    let body = warp::hyper::body::to_bytes(body)
+        .await
+        .map_err(|_| Error::Body)?;
+    let header = |name| {
+        parts
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+    let path = uri
+        .path_and_query()
+        .map(|path| path.as_str())
+        .unwrap_or_else(|| uri.path());
+    if let Err(err) = verifier.verify(
+        parts.method.as_str(),
+        path,
+        header(TIMESTAMP_HEADER),
+        header(SIGNATURE_HEADER),
+        &body,
+    ) {
+        tracing::warn!(?err, %path, "rejected unauthenticated request");
+        return Err(err);
+    }
+    Ok(Request::from_parts(parts, Body::from(body)))
+}
+
+/// The HTTP method and path including the query string a request is sent to.
+struct Route<'a> {
+    method: &'a str,
+    path: &'a str,
+}
+
+impl std::fmt::Display for Route<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method.to_ascii_uppercase(), self.path)
+    }
+}
+
+fn mac(secret: &Secret, route: &Route, timestamp: u64, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(&secret.0).expect("HMAC accepts any key size");
+    mac.update(route.to_string().as_bytes());
+    mac.update(b"\n");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOLVE: Route = Route {
+        method: "POST",
+        path: "/solver/solve",
+    };
+
+    fn secret(s: &str) -> Secret {
+        s.parse().unwrap()
+    }
+
+    fn verify(
+        verifier: &Verifier,
+        route: &Route,
+        headers: &[(&'static str, String); 2],
+        body: &[u8],
+        now: u64,
+    ) -> Result<(), Error> {
+        verifier.verify_at(route, Some(&headers[0].1), Some(&headers[1].1), body, now)
+    }
+
+    #[test]
+    fn accepts_valid_signature() {
+        let signer = Signer::new(secret("a"));
+        let verifier = Verifier::new(vec![secret("a")], Duration::from_secs(30));
+        let headers = signer.headers_at(&SOLVE, b"body", 100);
+        assert!(verify(&verifier, &SOLVE, &headers, b"body", 110).is_ok());
+    }
+
+    #[test]
+    fn accepts_any_configured_secret() {
+        let signer = Signer::new(secret("new"));
+        let verifier = Verifier::new(vec![secret("old"), secret("new")], Duration::from_secs(30));
+        let headers = signer.headers_at(&SOLVE, b"body", 100);
+        assert!(verify(&verifier, &SOLVE, &headers, b"body", 100).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let signer = Signer::new(secret("a"));
+        let verifier = Verifier::new(vec![secret("b")], Duration::from_secs(30));
+        let headers = signer.headers_at(&SOLVE, b"body", 100);
+        assert!(matches!(
+            verify(&verifier, &SOLVE, &headers, b"body", 100),
+            Err(Error::Invalid)
+        ));
+
+        let verifier = Verifier::new(vec![secret("a")], Duration::from_secs(30));
+        assert!(matches!(
+            verify(&verifier, &SOLVE, &headers, b"other body", 100),
+            Err(Error::Invalid)
+        ));
+        assert!(matches!(
+            verify(&verifier, &SOLVE, &headers, b"body", 131),
+            Err(Error::Expired)
+        ));
+        assert!(matches!(
+            verifier.verify_at(&SOLVE, None, None, b"body", 100),
+            Err(Error::Missing)
+        ));
+        assert!(matches!(
+            verifier.verify_at(&SOLVE, Some("100"), Some("not hex"), b"body", 100),
+            Err(Error::Malformed)
+        ));
+    }
+
+    #[test]
+    fn rejects_signature_for_other_route() {
+        let signer = Signer::new(secret("a"));
+        let verifier = Verifier::new(vec![secret("a")], Duration::from_secs(30));
+        let headers = signer.headers_at(&SOLVE, b"body", 100);
+        for route in [
+            Route {
+                method: "POST",
+                path: "/solver/settle",
+            },
+            Route {
+                method: "POST",
+                path: "/other/solve",
+            },
+            Route {
+                method: "GET",
+                path: "/solver/solve",
+            },
+        ] {
+            assert!(matches!(
+                verify(&verifier, &route, &headers, b"body", 100),
+                Err(Error::Invalid)
+            ));
+        }
+    }
+
+    #[test]
+    fn signs_query() {
+        let signer = Signer::new(secret("a"));
+        let verifier = Verifier::new(vec![secret("a")], Duration::from_secs(30));
+        let url = "http://driver/solver/quote?amount=1".parse().unwrap();
+        let headers = signer.headers("GET", &url, b"");
+        let timestamp = headers[0].1.parse().unwrap();
+        let check = |path| {
+            verify(
+                &verifier,
+                &Route {
+                    method: "GET",
+                    path,
+                },
+                &headers,
+                b"",
+                timestamp,
+            )
+        };
+        assert!(matches!(
+            check("/solver/quote?amount=2"),
+            Err(Error::Invalid)
+        ));
+        assert!(matches!(check("/solver/quote"), Err(Error::Invalid)));
+        assert!(check("/solver/quote?amount=1").is_ok());
+    }
+
+    #[tokio::test]
+    async fn verifies_requests_to_nested_routes() {
+        let signer = Signer::new(secret("a"));
+        let verifier = Verifier::new(vec![secret("a")], Duration::from_secs(30));
+        let url = "http://driver/solver/solve".parse().unwrap();
+        let mut request = Request::post("/solve");
+        for (name, value) in signer.headers("POST", &url, b"auction") {
+            request = request.header(name, value);
+        }
+        let request = request.body(Body::from("auction")).unwrap();
+
+        let request = verify_request(&verifier, &"/solver/solve".parse().unwrap(), request)
+            .await
+            .unwrap();
+        let body = warp::hyper::body::to_bytes(request.into_body())
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), b"auction");
+    }
+
+    #[test]
+    fn rejects_replayed_requests() {
+        let signer = Signer::new(secret("a"));
+        let verifier = Verifier::new(vec![secret("a")], Duration::from_secs(30));
+        let headers = signer.headers_at(&SOLVE, b"body", 100);
+        assert!(verify(&verifier, &SOLVE, &headers, b"body", 100).is_ok());
+        assert!(matches!(
+            verify(&verifier, &SOLVE, &headers, b"body", 101),
+            Err(Error::Replayed)
+        ));
+    }
+
+    #[test]
+    fn accepts_same_body_for_multiple_solvers() {
+        // The autopilot sends the same auction at the same second to every
+        // solver, which one driver can host several of.
+        let signer = Signer::new(secret("a"));
+        let verifier = Verifier::new(vec![secret("a")], Duration::from_secs(30));
+        for path in ["/solver1/solve", "/solver2/solve"] {
+            let route = Route {
+                method: "POST",
+                path,
+            };
+            let headers = signer.headers_at(&route, b"auction", 100);
+            assert!(verify(&verifier, &route, &headers, b"auction", 100).is_ok());
+        }
+    }
+
+    #[test]
+    fn secret_is_not_logged() {
+        assert_eq!(format!("{:?}", secret("hunter2")), "SECRET");
+    }
+
+    #[test]
+    fn derives_secrets_per_context() {
+        let key = secret("key");
+        assert_eq!(key.derive("webhook/1"), key.derive("webhook/1"));
+        assert_ne!(key.derive("webhook/1"), key.derive("webhook/2"));
+        assert_ne!(key.derive("webhook/1"), secret("other").derive("webhook/1"));
+        assert_eq!(key.derive("webhook/1").reveal().len(), 64);
+    }
+}
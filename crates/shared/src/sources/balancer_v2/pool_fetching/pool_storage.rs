@@ -19,7 +19,7 @@
 
 use {
     crate::{
-        event_handling::EventStoring,
+        event_handling::{EventStoring, Reorg},
         sources::balancer_v2::pools::{common, FactoryIndexing, PoolIndexing},
     },
     anyhow::{Context, Result},
@@ -190,6 +190,7 @@ where
         &mut self,
         events: Vec<Event<BasePoolFactoryEvent>>,
         range: RangeInclusive<u64>,
+        _: Option<Reorg>,
     ) -> Result<()> {
         tracing::debug!("replacing {} events for block {:?}", events.len(), range);
 
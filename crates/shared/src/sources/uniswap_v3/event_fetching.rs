@@ -1,5 +1,5 @@
 use {
-    crate::event_handling::{EventRetrieving, EventStoring},
+    crate::event_handling::{EventRetrieving, EventStoring, Reorg},
     anyhow::{Context, Result},
     contracts::{
         uniswap_v3_pool::event_data::{Burn, Mint, Swap},
@@ -120,6 +120,7 @@ impl EventStoring<UniswapV3Event> for RecentEventsCache {
         &mut self,
         events: Vec<Event<UniswapV3Event>>,
         range: RangeInclusive<u64>,
+        _: Option<Reorg>,
     ) -> Result<()> {
         self.remove_events_newer_than_block(*range.start());
         self.append_events(events).await
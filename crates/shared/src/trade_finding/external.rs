@@ -4,6 +4,7 @@ use {
     crate::{
         price_estimation::{PriceEstimationError, Query},
         request_sharing::RequestSharing,
+        request_signing::Signer,
         trade_finding::{Interaction, Quote, Trade, TradeError, TradeFinding},
     },
     anyhow::anyhow,
@@ -25,16 +26,25 @@ pub struct ExternalTradeFinder {
     /// Client to issue http requests with.
     client: Client,
 
+    /// Signs the requests if the driver requires authentication.
+    signer: Option<Signer>,
+
     /// Stream to retrieve latest block information for block-dependent queries.
     block_stream: CurrentBlockStream,
 }
 
 impl ExternalTradeFinder {
-    pub fn new(driver: Url, client: Client, block_stream: CurrentBlockStream) -> Self {
+    pub fn new(
+        driver: Url,
+        client: Client,
+        signer: Option<Signer>,
+        block_stream: CurrentBlockStream,
+    ) -> Self {
         Self {
             quote_endpoint: crate::url::join(&driver, "quote"),
             sharing: RequestSharing::labelled(format!("tradefinder_{}", driver)),
             client,
+            signer,
             block_stream,
         }
     }
@@ -69,9 +79,24 @@ impl ExternalTradeFinder {
             request = request.header("X-REQUEST-ID", id);
         }
 
+        // The signature covers the query, so it can only be computed once the
+        // request is built.
+        let mut request = request
+            .build()
+            .map_err(|err| PriceEstimationError::EstimatorInternal(anyhow!(err)))?;
+        if let Some(signer) = &self.signer {
+            for (name, value) in signer.headers("GET", request.url(), b"") {
+                request.headers_mut().insert(
+                    header::HeaderName::from_bytes(name.as_bytes()).expect("valid header name"),
+                    header::HeaderValue::from_str(&value).expect("valid header value"),
+                );
+            }
+        }
+
         let future = async {
-            let response = request
-                .send()
+            let response = self
+                .client
+                .execute(request)
                 .await
                 .map_err(|err| PriceEstimationError::EstimatorInternal(anyhow!(err)))?;
             if response.status() == 429 {
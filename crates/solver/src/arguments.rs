@@ -3,19 +3,13 @@ use {
         liquidity::slippage,
         s3_instance_upload_arguments::S3UploadArguments,
         settlement_access_list::AccessListEstimatorType,
-        solver::{
-            risk_computation,
-            single_order_solver,
-            ExternalSolverArg,
-            SolverAccountArg,
-            SolverType,
-        },
+        solver::{risk_computation, single_order_solver, ExternalSolverArg, SolverType},
     },
     ethcontract::U256,
     primitive_types::H160,
     reqwest::Url,
     shared::{
-        arguments::{display_list, display_option},
+        arguments::{display_list, display_option, AccountArg},
         http_client,
     },
     std::time::Duration,
@@ -52,7 +46,7 @@ pub struct Arguments {
     /// for signing with a local node account, or a KMS key ID for signing with
     /// AWS.
     #[clap(long, env, hide_env_values = true)]
-    pub solver_account: Option<SolverAccountArg>,
+    pub solver_account: Option<AccountArg>,
 
     /// The target confirmation time in seconds for settlement transactions used
     /// to estimate gas price.
@@ -98,7 +92,7 @@ pub struct Arguments {
         use_value_delimiter = true,
         hide_env_values = true
     )]
-    pub solver_accounts: Option<Vec<SolverAccountArg>>,
+    pub solver_accounts: Option<Vec<AccountArg>>,
 
     /// List of external solvers in the form of `name|url|account`.
     #[clap(long, env, use_value_delimiter = true)]
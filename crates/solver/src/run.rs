@@ -276,6 +276,7 @@ pub async fn run(args: Arguments) {
             )
             .await
             .into_iter()
+            .map(|account| account.expect("failed to load solver account"))
             .zip(args.solvers)
             .collect()
         } else if let Some(account_arg) = args.solver_account {
@@ -286,6 +287,7 @@ pub async fn run(args: Arguments) {
             )
             .await
             .into_iter()
+            .map(|account| account.expect("failed to load solver account"))
             .zip(args.solvers)
             .collect()
         } else {
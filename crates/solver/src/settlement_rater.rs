@@ -165,8 +165,8 @@ impl SettlementRater {
                 access_list,
                 // simulating on block X and tx index A is equal to simulating on block
                 // X+1 and tx index 0.
-                block_number: block_number + 1,
-                tx_index: 0,
+                block_number: Some(block_number + 1),
+                tx_index: Some(0),
                 to: self.settlement_contract.address(),
                 from: solver.account.address(),
                 data: call_data(settlement.clone().encode(internalization)),
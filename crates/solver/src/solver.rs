@@ -34,13 +34,14 @@ use {
     },
     anyhow::{anyhow, Context, Result},
     contracts::{BalancerV2Vault, GPv2Settlement, WETH9},
-    ethcontract::{errors::ExecutionError, transaction::kms, Account, PrivateKey, H160, U256},
+    ethcontract::{errors::ExecutionError, Account, H160, U256},
     ethrpc::current_block::CurrentBlockStream,
     futures::future::join_all,
     model::{auction::AuctionId, order::Order, DomainSeparator},
     reqwest::Url,
     shared::{
         account_balances,
+        arguments::AccountArg,
         balancer_sor_api::DefaultBalancerSorApi,
         baseline_solver::BaseTokens,
         ethrpc::Web3,
@@ -57,7 +58,6 @@ use {
     },
     std::{
         collections::HashMap,
-        fmt::{self, Debug, Formatter},
         str::FromStr,
         sync::Arc,
         time::{Duration, Instant},
@@ -225,91 +225,11 @@ pub enum SolverType {
     BalancerSor,
 }
 
-#[derive(Clone)]
-pub enum SolverAccountArg {
-    PrivateKey(PrivateKey),
-    Kms(Arn),
-    Address(H160),
-}
-
-impl Debug for SolverAccountArg {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        match self {
-            SolverAccountArg::PrivateKey(k) => write!(f, "PrivateKey({:?})", k.public_address()),
-            SolverAccountArg::Kms(key_id) => write!(f, "KMS({key_id:?})"),
-            SolverAccountArg::Address(a) => write!(f, "Address({a:?})"),
-        }
-    }
-}
-
-impl SolverAccountArg {
-    pub async fn into_account(self, chain_id: u64) -> Account {
-        match self {
-            SolverAccountArg::PrivateKey(key) => Account::Offline(key, Some(chain_id)),
-            SolverAccountArg::Kms(key_id) => {
-                let config = ethcontract::aws_config::load_from_env().await;
-                let account = kms::Account::new((&config).into(), &key_id.0)
-                    .await
-                    .unwrap_or_else(|_| panic!("Unable to load KMS account {key_id:?}"));
-                Account::Kms(account, Some(chain_id))
-            }
-            SolverAccountArg::Address(address) => Account::Local(address, None),
-        }
-    }
-}
-
-impl FromStr for SolverAccountArg {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.parse::<PrivateKey>()
-            .map(SolverAccountArg::PrivateKey)
-            .map_err(|pk_err| anyhow!("could not parse as private key: {}", pk_err))
-            .or_else(|error_chain| {
-                Ok(SolverAccountArg::Address(s.parse().map_err(
-                    |addr_err| {
-                        error_chain.context(anyhow!("could not parse as address: {}", addr_err))
-                    },
-                )?))
-            })
-            .or_else(|error_chain: Self::Err| {
-                let key_id = Arn::from_str(s).map_err(|arn_err| {
-                    error_chain.context(anyhow!("could not parse as AWS ARN: {}", arn_err))
-                })?;
-                Ok(SolverAccountArg::Kms(key_id))
-            })
-            .map_err(|err: Self::Err| {
-                err.context(
-                    "invalid solver account, it is neither a private key, an Ethereum address, \
-                     nor a KMS key",
-                )
-            })
-    }
-}
-
-// Wrapper type for AWS ARN identifiers
-#[derive(Debug, Clone)]
-pub struct Arn(pub String);
-
-impl FromStr for Arn {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        // Could be more strict here, but this should suffice to catch unintended
-        // configuration mistakes
-        if s.starts_with("arn:aws:kms:") {
-            Ok(Self(s.to_string()))
-        } else {
-            Err(anyhow!("Invalid ARN identifier: {}", s))
-        }
-    }
-}
-
 #[derive(Clone, Debug)]
 pub struct ExternalSolverArg {
     pub name: String,
     pub url: Url,
-    pub account: SolverAccountArg,
+    pub account: AccountArg,
     pub use_liquidity: bool,
     pub user_balance_support: UserBalanceSupport,
 }
@@ -586,7 +506,11 @@ pub async fn create(
 
     let external_solvers = join_all(external_solvers.into_iter().map(|solver| async move {
         shared(create_http_solver(
-            solver.account.into_account(chain_id).await,
+            solver
+                .account
+                .into_account(chain_id)
+                .await
+                .expect("failed to load solver account"),
             solver.url,
             solver.name,
             SolverConfig {
@@ -667,7 +591,7 @@ fn balance_and_convert_orders(
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {super::*, ethcontract::PrivateKey};
 
     /// Dummy solver returning no settlements
     pub struct NoopSolver();
@@ -688,59 +612,17 @@ mod tests {
         }
     }
 
-    impl PartialEq for SolverAccountArg {
-        fn eq(&self, other: &Self) -> bool {
-            match (self, other) {
-                (SolverAccountArg::PrivateKey(a), SolverAccountArg::PrivateKey(b)) => {
-                    a.public_address() == b.public_address()
-                }
-                (SolverAccountArg::Address(a), SolverAccountArg::Address(b)) => a == b,
-                _ => false,
-            }
-        }
-    }
-
-    #[test]
-    fn parses_solver_account_arg() {
-        assert_eq!(
-            "0x4242424242424242424242424242424242424242424242424242424242424242"
-                .parse::<SolverAccountArg>()
-                .unwrap(),
-            SolverAccountArg::PrivateKey(PrivateKey::from_raw([0x42; 32]).unwrap())
-        );
-        assert_eq!(
-            "0x4242424242424242424242424242424242424242"
-                .parse::<SolverAccountArg>()
-                .unwrap(),
-            SolverAccountArg::Address(H160([0x42; 20])),
-        );
-
-        assert!(matches!(
-            "arn:aws:kms:eu-central-1:42:key/00000000-0000-0000-0000-00000000"
-                .parse::<SolverAccountArg>()
-                .unwrap(),
-            SolverAccountArg::Kms(_)
-        ));
-    }
-
-    #[test]
-    fn errors_on_invalid_solver_account_arg() {
-        assert!("0x010203040506070809101112131415161718192021"
-            .parse::<SolverAccountArg>()
-            .is_err());
-        assert!("not an account".parse::<SolverAccountArg>().is_err());
-    }
-
     #[test]
     fn parse_external_solver_arg() {
         let arg = "name|http://solver.com/|0x4242424242424242424242424242424242424242424242424242424242424242|true|partially_fillable";
         let parsed = ExternalSolverArg::from_str(arg).unwrap();
         assert_eq!(parsed.name, "name");
         assert_eq!(parsed.url.to_string(), "http://solver.com/");
-        assert_eq!(
+        assert!(matches!(
             parsed.account,
-            SolverAccountArg::PrivateKey(PrivateKey::from_raw([0x42; 32]).unwrap())
-        );
+            AccountArg::PrivateKey(key)
+                if key.public_address() == PrivateKey::from_raw([0x42; 32]).unwrap().public_address()
+        ));
         assert!(parsed.use_liquidity);
         assert_eq!(
             parsed.user_balance_support,
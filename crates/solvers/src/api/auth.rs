@@ -0,0 +1,31 @@
+//! Middleware rejecting requests that are not signed with a shared secret.
+
+use {
+    super::routes,
+    axum::{
+        extract::{OriginalUri, State},
+        http::{Request, StatusCode},
+        middleware::Next,
+        response::{IntoResponse, Json, Response},
+    },
+    hyper::Body,
+    shared::request_signing,
+    std::sync::Arc,
+};
+
+pub async fn verify(
+    State(verifier): State<Arc<request_signing::Verifier>>,
+    OriginalUri(uri): OriginalUri,
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    match request_signing::verify_request(&verifier, &uri, request).await {
+        Ok(request) => next.run(request).await,
+        Err(request_signing::Error::Body) => StatusCode::BAD_REQUEST.into_response(),
+        Err(_) => (
+            StatusCode::UNAUTHORIZED,
+            Json(routes::Error::from("request is not correctly signed")),
+        )
+            .into_response(),
+    }
+}
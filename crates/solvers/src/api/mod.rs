@@ -2,15 +2,19 @@
 
 use {
     crate::domain::solver::Solver,
+    shared::request_signing,
     std::{future::Future, net::SocketAddr, sync::Arc},
     tokio::sync::oneshot,
 };
 
+mod auth;
 mod routes;
 
 pub struct Api {
     pub addr: SocketAddr,
     pub solver: Solver,
+    /// Verifies that requests are signed by the driver, if configured.
+    pub auth: Option<Arc<request_signing::Verifier>>,
 }
 
 impl Api {
@@ -19,9 +23,14 @@ impl Api {
         bind: Option<oneshot::Sender<SocketAddr>>,
         shutdown: impl Future<Output = ()> + Send + 'static,
     ) -> Result<(), hyper::Error> {
-        let app = axum::Router::new()
+        let mut app = axum::Router::new()
             .route("/solve", axum::routing::post(routes::solve))
-            .route("/notify", axum::routing::post(routes::notify))
+            .route("/solve/stream", axum::routing::post(routes::solve_stream))
+            .route("/notify", axum::routing::post(routes::notify));
+        if let Some(verifier) = self.auth {
+            app = app.route_layer(axum::middleware::from_fn_with_state(verifier, auth::verify));
+        }
+        let app = app
             .layer(
                 tower::ServiceBuilder::new().layer(tower_http::trace::TraceLayer::new_for_http()),
             )
@@ -3,7 +3,10 @@ use serde::Serialize;
 mod notify;
 mod solve;
 
-pub(super) use {notify::notify, solve::solve};
+pub(super) use {
+    notify::notify,
+    solve::{solve, solve_stream},
+};
 
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
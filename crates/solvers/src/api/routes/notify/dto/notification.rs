@@ -3,6 +3,7 @@ use {
         domain::{auction, eth, notification},
         util::serialize,
     },
+    contracts::ethcontract::web3,
     ethereum_types::{H160, H256, U256},
     serde::Deserialize,
     serde_with::{serde_as, DisplayFromStr},
@@ -56,6 +57,9 @@ impl Notification {
                 Kind::SolverAccountInsufficientBalance { required } => {
                     notification::Kind::SolverAccountInsufficientBalance(eth::Ether(*required))
                 }
+                Kind::SimulationFailed(failure) => {
+                    notification::Kind::SimulationFailed(failure.to_domain())
+                }
                 Kind::DuplicatedSolutionId => notification::Kind::DuplicatedSolutionId,
                 Kind::Settled(kind) => notification::Kind::Settled(match kind {
                     Settlement::Success { transaction } => {
@@ -64,8 +68,14 @@ impl Notification {
                     Settlement::Revert { transaction } => {
                         notification::Settlement::Revert(*transaction)
                     }
-                    Settlement::SimulationRevert => notification::Settlement::SimulationRevert,
-                    Settlement::Fail => notification::Settlement::Fail,
+                    Settlement::SimulationRevert(failure) => {
+                        notification::Settlement::SimulationRevert(
+                            failure.as_ref().map(SimulationFailure::to_domain),
+                        )
+                    }
+                    Settlement::Fail(failure) => notification::Settlement::Fail(
+                        failure.as_ref().map(SimulationFailure::to_domain),
+                    ),
                 }),
             },
         }
@@ -97,6 +107,83 @@ pub enum Kind {
         required: U256,
     },
     Settled(Settlement),
+    SimulationFailed(SimulationFailure),
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationFailure {
+    block: Option<u64>,
+    tx: Tx,
+    revert_reason: Option<RevertReason>,
+    interaction: Option<usize>,
+    #[serde_as(as = "Option<serialize::U256>")]
+    gas_used: Option<U256>,
+}
+
+impl SimulationFailure {
+    fn to_domain(&self) -> notification::SimulationFailure {
+        notification::SimulationFailure {
+            block: self.block,
+            tx: notification::Tx {
+                from: self.tx.from,
+                to: self.tx.to,
+                input: self.tx.input.clone(),
+                value: eth::Ether(self.tx.value),
+                access_list: self
+                    .tx
+                    .access_list
+                    .iter()
+                    .map(|item| (item.address, item.storage_keys.iter().copied().collect()))
+                    .collect(),
+            },
+            reason: self.revert_reason.as_ref().map(|reason| match reason {
+                RevertReason::Message { message } => {
+                    notification::RevertReason::Message(message.clone())
+                }
+                RevertReason::Panic { code } => notification::RevertReason::Panic(*code),
+                RevertReason::Custom { selector, data } => notification::RevertReason::Custom {
+                    selector: *selector,
+                    data: data.clone(),
+                },
+            }),
+            interaction: self.interaction,
+            gas_used: self.gas_used.map(eth::Gas),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tx {
+    from: H160,
+    to: H160,
+    #[serde_as(as = "serialize::Hex")]
+    input: Vec<u8>,
+    #[serde_as(as = "serialize::U256")]
+    value: U256,
+    access_list: web3::types::AccessList,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum RevertReason {
+    Message {
+        message: String,
+    },
+    Panic {
+        #[serde_as(as = "serialize::U256")]
+        code: U256,
+    },
+    Custom {
+        #[serde_as(as = "serialize::Hex")]
+        selector: [u8; 4],
+        #[serde_as(as = "serialize::Hex")]
+        data: Vec<u8>,
+    },
 }
 
 #[serde_as]
@@ -123,6 +210,6 @@ pub enum ScoreKind {
 pub enum Settlement {
     Success { transaction: H256 },
     Revert { transaction: H256 },
-    SimulationRevert,
-    Fail,
+    SimulationRevert(Option<SimulationFailure>),
+    Fail(Option<SimulationFailure>),
 }
@@ -35,6 +35,7 @@ impl Auction {
                                     .map(auction::Price),
                                 available_balance: token.available_balance,
                                 trusted: token.trusted,
+                                transfer_fee_bps: token.transfer_fee_bps,
                             },
                         )
                     })
@@ -142,6 +143,8 @@ struct Token {
     #[serde_as(as = "serialize::U256")]
     available_balance: U256,
     trusted: bool,
+    #[serde(default)]
+    transfer_fee_bps: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,4 +1,4 @@
-use {super::Response, tracing::Instrument};
+use {super::Response, axum::response::IntoResponse, tracing::Instrument};
 
 mod dto;
 
@@ -44,3 +44,54 @@ pub async fn solve(
         .instrument(tracing::info_span!("/solve"))
         .await
 }
+
+/// Like [`solve`] but streams the solutions as soon as the solver finds them.
+/// The response is newline delimited JSON where every line contains the
+/// solutions found since the previous line. The stream ends once the solver is
+/// done or reached the deadline of the auction.
+pub async fn solve_stream(
+    state: axum::extract::State<Arc<Solver>>,
+    auction: axum::extract::Json<dto::Auction>,
+) -> axum::response::Response {
+    let auction = match auction.to_domain() {
+        Ok(value) => value,
+        Err(err) => {
+            tracing::warn!(?err, "invalid auction");
+            return (
+                axum::http::StatusCode::BAD_REQUEST,
+                axum::response::Json(Response::<()>::Err(err)),
+            )
+                .into_response();
+        }
+    };
+
+    tracing::trace!(?auction);
+
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    let span = tracing::info_span!("/solve/stream");
+    let auction_id = auction.id;
+    let solve = async move {
+        state
+            .solve_streaming(auction, |solution| {
+                tracing::trace!(?solution);
+                // The driver might stop reading the response at any time.
+                let _ = sender.send(solution);
+            })
+            .instrument(tracing::info_span!("auction", id = %auction_id))
+            .await
+    };
+    tokio::task::spawn(solve.instrument(span));
+
+    let lines = futures::stream::unfold(receiver, |mut receiver| async move {
+        let solution = receiver.recv().await?;
+        let mut line = serde_json::to_vec(&dto::Solutions::from_domain(&[solution]))
+            .expect("solutions are serializable");
+        line.push(b'\n');
+        Some((Ok::<_, std::convert::Infallible>(line), receiver))
+    });
+    (
+        [(axum::http::header::CONTENT_TYPE, "application/x-ndjson")],
+        axum::body::StreamBody::new(lines),
+    )
+        .into_response()
+}
@@ -11,6 +11,7 @@ use {
         },
     },
     anyhow::{Context as _, Result},
+    contracts::ethcontract::web3,
     ethereum_types::{H160, U256},
     model::order::{OrderKind, OrderUid},
     shared::{
@@ -23,15 +24,18 @@ use {
                 BatchAuctionModel,
                 ConcentratedPoolParameters,
                 ConstantProductPoolParameters,
+                InternalizationStrategy,
                 MetadataModel,
                 OrderModel,
                 Score,
                 SettledBatchAuctionModel,
+                SimulatedTransaction,
                 SolverRejectionReason,
                 StablePoolParameters,
                 SubmissionResult,
                 TokenAmount,
                 TokenInfoModel,
+                TransactionWithError,
                 WeightedPoolTokenData,
                 WeightedProductPoolParameters,
             },
@@ -602,11 +606,41 @@ fn to_boundary_auction_result(notification: &notification::Notification) -> (i64
         Kind::DuplicatedSolutionId => AuctionResult::Rejected(
             SolverRejectionReason::DuplicatedSolutionId(notification.solution_id.0),
         ),
+        Kind::SimulationFailed(failure) => AuctionResult::Rejected(
+            SolverRejectionReason::SimulationFailure(TransactionWithError {
+                transaction: SimulatedTransaction {
+                    block_number: failure.block,
+                    tx_index: None,
+                    internalization: InternalizationStrategy::SkipInternalizableInteraction,
+                    access_list: Some(
+                        failure
+                            .tx
+                            .access_list
+                            .iter()
+                            .map(|(address, storage_keys)| web3::types::AccessListItem {
+                                address: *address,
+                                storage_keys: storage_keys.iter().copied().collect(),
+                            })
+                            .collect(),
+                    ),
+                    from: failure.tx.from,
+                    to: failure.tx.to,
+                    data: failure.tx.input.clone(),
+                    max_fee_per_gas: Default::default(),
+                    max_priority_fee_per_gas: Default::default(),
+                },
+                error: failure
+                    .reason
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            }),
+        ),
         Kind::Settled(kind) => AuctionResult::SubmittedOnchain(match kind {
             Settlement::Success(hash) => SubmissionResult::Success(*hash),
             Settlement::Revert(hash) => SubmissionResult::Revert(*hash),
-            Settlement::SimulationRevert => SubmissionResult::SimulationRevert,
-            Settlement::Fail => SubmissionResult::Fail,
+            Settlement::SimulationRevert(_) => SubmissionResult::SimulationRevert,
+            Settlement::Fail(_) => SubmissionResult::Fail,
         }),
     };
 
@@ -64,6 +64,9 @@ pub struct Token {
     pub reference_price: Option<Price>,
     pub available_balance: U256,
     pub trusted: bool,
+    /// The fee in basis points that the token contract deducts from every
+    /// transfer, if this is a fee-on-transfer token.
+    pub transfer_fee_bps: Option<u32>,
 }
 
 /// The price of a token in wei. This represents how much wei is needed to buy
@@ -123,6 +123,20 @@ impl Swap {
             // since it doesn't really play a role in the final solution.
             self.gas
         };
+        self.into_solution_with_gas(order, gas, gas_price, sell_token, risk)
+    }
+
+    /// Constructs a single order `solution::Solution` for this swap like
+    /// [`Swap::into_solution`], using the specified gas for executing the swap
+    /// instead of computing it.
+    pub fn into_solution_with_gas(
+        self,
+        order: order::Order,
+        gas: eth::Gas,
+        gas_price: auction::GasPrice,
+        sell_token: Option<auction::Price>,
+        risk: &domain::Risk,
+    ) -> Option<solution::Solution> {
         let score = solution::Score::RiskAdjusted(solution::SuccessProbability(
             risk.success_probability(gas, gas_price, 1),
         ));
@@ -110,6 +110,7 @@ mod tests {
             reference_price: Some(auction::Price(ether(e))),
             available_balance: Default::default(),
             trusted: Default::default(),
+            transfer_fee_bps: Default::default(),
         };
 
         let tokens = auction::Tokens(
@@ -4,7 +4,7 @@ use {
         eth::{self, Ether, TokenAddress},
         solution::{self, SuccessProbability},
     },
-    std::collections::BTreeSet,
+    std::collections::{BTreeMap, BTreeSet},
 };
 
 /// The notification about important events happened in driver, that solvers
@@ -27,9 +27,62 @@ pub enum Kind {
     ScoringFailed(ScoreKind),
     NonBufferableTokensUsed(TokensUsed),
     SolverAccountInsufficientBalance(RequiredEther),
+    SimulationFailed(SimulationFailure),
     Settled(Settlement),
 }
 
+/// Diagnostics of the settlement of a solution that reverted in simulation.
+/// Only the details the simulator of the driver could provide are set.
+#[derive(Debug)]
+pub struct SimulationFailure {
+    /// The block the settlement was simulated at.
+    pub block: Option<u64>,
+    pub tx: Tx,
+    pub reason: Option<RevertReason>,
+    /// The index of the interaction of the solution which reverted.
+    pub interaction: Option<usize>,
+    /// The gas used until the settlement reverted.
+    pub gas_used: Option<eth::Gas>,
+}
+
+/// The simulated settlement transaction.
+#[derive(Debug)]
+pub struct Tx {
+    pub from: eth::H160,
+    pub to: eth::H160,
+    pub input: Vec<u8>,
+    pub value: Ether,
+    pub access_list: AccessList,
+}
+
+pub type AccessList = BTreeMap<eth::H160, BTreeSet<eth::H256>>;
+
+/// Why the settlement reverted.
+#[derive(Debug)]
+pub enum RevertReason {
+    /// `Error(string)` or a message reported by the simulator.
+    Message(String),
+    /// `Panic(uint256)` with the panic code.
+    Panic(eth::U256),
+    /// A custom Solidity error.
+    Custom { selector: [u8; 4], data: Vec<u8> },
+}
+
+impl std::fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Message(message) => f.write_str(message),
+            Self::Panic(code) => write!(f, "panic 0x{code:x}"),
+            Self::Custom { selector, data } => write!(
+                f,
+                "custom error 0x{}, data 0x{}",
+                hex::encode(selector),
+                hex::encode(data)
+            ),
+        }
+    }
+}
+
 pub type TransactionHash = eth::H256;
 
 /// The result of winning solver trying to settle the transaction onchain.
@@ -37,8 +90,10 @@ pub type TransactionHash = eth::H256;
 pub enum Settlement {
     Success(TransactionHash),
     Revert(TransactionHash),
-    SimulationRevert,
-    Fail,
+    /// Contains the diagnostics if the settlement still reverted in
+    /// simulation.
+    SimulationRevert(Option<SimulationFailure>),
+    Fail(Option<SimulationFailure>),
 }
 
 #[derive(Debug)]
@@ -212,7 +212,16 @@ impl<'a> Route<'a> {
         Some(Self { segments })
     }
 
-    fn input(&self) -> eth::Asset {
+    /// The segments of the route, in trading order.
+    pub fn segments(&self) -> &[Segment<'a>] {
+        &self.segments
+    }
+
+    pub fn into_segments(self) -> Vec<Segment<'a>> {
+        self.segments
+    }
+
+    pub fn input(&self) -> eth::Asset {
         self.segments[0].input
     }
 
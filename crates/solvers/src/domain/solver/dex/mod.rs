@@ -1,22 +1,32 @@
 //! A simple solver that matches orders directly with swaps from the external
-//! DEX and DEX aggregator APIs.
+//! DEX and DEX aggregator APIs. When configured with multiple APIs, every order
+//! is quoted by all of them and the swap with the best output after gas gets
+//! used.
 
 use {
     crate::{
         domain,
-        domain::{auction, dex::slippage, order, solution, solver::dex::fills::Fills},
+        domain::{
+            auction,
+            dex::{self, slippage},
+            eth,
+            order,
+            solution,
+            solver::dex::fills::Fills,
+        },
         infra,
     },
     futures::{future, stream, FutureExt, StreamExt},
     std::num::NonZeroUsize,
+    tokio::sync::Semaphore,
     tracing::Instrument,
 };
 
 mod fills;
 
 pub struct Dex {
-    /// The DEX API client.
-    dex: infra::dex::Dex,
+    /// The DEX API clients.
+    dexs: Vec<infra::dex::Dex>,
 
     /// A DEX swap gas simulator for computing limit order fees.
     simulator: infra::dex::Simulator,
@@ -27,6 +37,9 @@ pub struct Dex {
     /// The number of concurrent requests to make.
     concurrent_requests: NonZeroUsize,
 
+    /// Limits the requests to all DEX APIs combined to `concurrent_requests`.
+    requests: Semaphore,
+
     /// Helps to manage the strategy to fill orders (especially partially
     /// fillable orders).
     fills: Fills,
@@ -36,9 +49,10 @@ pub struct Dex {
 }
 
 impl Dex {
-    pub fn new(dex: infra::dex::Dex, config: infra::config::dex::Config) -> Self {
+    pub fn new(dexs: Vec<infra::dex::Dex>, config: infra::config::dex::Config) -> Self {
+        assert!(!dexs.is_empty(), "a DEX solver needs at least one DEX API");
         Self {
-            dex,
+            dexs,
             simulator: infra::dex::Simulator::new(
                 &config.node_url,
                 config.contracts.settlement,
@@ -46,6 +60,7 @@ impl Dex {
             ),
             slippage: config.slippage,
             concurrent_requests: config.concurrent_requests,
+            requests: Semaphore::new(config.concurrent_requests.get()),
             fills: Fills::new(config.smallest_partial_fill),
             risk: config.risk,
         }
@@ -53,10 +68,23 @@ impl Dex {
 
     pub async fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution> {
         let mut solutions = Vec::new();
+        self.solve_streaming(auction, |solution| solutions.push(solution))
+            .await;
+        solutions
+    }
+
+    /// Solves the auction like [`Dex::solve`] but hands every solution to
+    /// `found` as soon as it is found instead of waiting for all orders to be
+    /// solved.
+    pub async fn solve_streaming(
+        &self,
+        auction: auction::Auction,
+        mut found: impl FnMut(solution::Solution),
+    ) {
         let solve_orders = async {
             let mut stream = self.solution_stream(&auction);
             while let Some(solution) = stream.next().await {
-                solutions.push(solution);
+                found(solution);
             }
         };
 
@@ -66,8 +94,6 @@ impl Dex {
         }
 
         self.fills.collect_garbage();
-
-        solutions
     }
 
     fn solution_stream<'a>(
@@ -93,40 +119,70 @@ impl Dex {
         gas_price: auction::GasPrice,
     ) -> Option<solution::Solution> {
         let order = order.get();
-        let swap = {
+        let swaps = {
             let order = self.fills.dex_order(order, tokens)?;
             let slippage = self.slippage.relative(&order.amount(), tokens);
-            self.dex.swap(&order, &slippage, tokens, gas_price).await
+            future::join_all(self.dexs.iter().map(|dex| {
+                let (order, slippage) = (&order, &slippage);
+                async move {
+                    let _permit = self
+                        .requests
+                        .acquire()
+                        .await
+                        .expect("request semaphore is never closed");
+                    (dex, dex.swap(order, slippage, tokens, gas_price).await)
+                }
+            }))
+            .await
         };
 
-        let swap = match swap {
-            Ok(swap) => swap,
-            Err(err @ infra::dex::Error::NotFound) => {
-                if order.partially_fillable {
-                    // Only adjust the amount to try next if we are sure the API worked correctly
-                    // yet still wasn't able to provide a swap.
-                    self.fills.reduce_next_try(order.uid);
-                } else {
-                    tracing::debug!(?err, "skipping order");
+        let (mut not_found, mut failed) = (false, false);
+        let swaps = swaps
+            .into_iter()
+            .filter_map(|(dex, swap)| match swap {
+                Ok(swap) => Some((dex, swap)),
+                Err(err @ infra::dex::Error::NotFound) => {
+                    not_found = true;
+                    tracing::debug!(dex = dex.name(), ?err, "no swap found");
+                    None
                 }
-                return None;
-            }
-            Err(err @ infra::dex::Error::OrderNotSupported) => {
-                tracing::debug!(?err, "skipping order");
-                return None;
-            }
-            Err(infra::dex::Error::Other(err)) => {
-                tracing::warn!(?err, "failed to get swap");
-                return None;
+                Err(err @ infra::dex::Error::OrderNotSupported) => {
+                    tracing::debug!(dex = dex.name(), ?err, "skipping order");
+                    None
+                }
+                Err(infra::dex::Error::Other(err)) => {
+                    failed = true;
+                    tracing::warn!(dex = dex.name(), ?err, "failed to get swap");
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if swaps.is_empty() {
+            // Only adjust the amount to try next if we are sure the APIs worked
+            // correctly yet still weren't able to provide a swap.
+            if not_found && !failed && order.partially_fillable {
+                self.fills.reduce_next_try(order.uid);
             }
-        };
+            return None;
+        }
 
         let uid = order.uid;
         let sell = tokens.reference_price(&order.sell.token);
-        let Some(solution) = swap
-            .into_solution(order.clone(), gas_price, sell, &self.risk, &self.simulator)
-            .await
-        else {
+        let solution = match <[_; 1]>::try_from(swaps) {
+            // There is nothing to compare a single swap against, so its gas
+            // only needs to be simulated for computing limit order fees.
+            Ok([(_, swap)]) => {
+                swap.into_solution(order.clone(), gas_price, sell, &self.risk, &self.simulator)
+                    .await
+            }
+            Err(swaps) => {
+                let (dex, swap, gas) = self.best_swap(order, swaps, tokens, gas_price).await?;
+                tracing::debug!(dex = dex.name(), "picked best swap");
+                swap.into_solution_with_gas(order.clone(), gas, gas_price, sell, &self.risk)
+            }
+        };
+        let Some(solution) = solution else {
             tracing::debug!("no solution for swap");
             return None;
         };
@@ -137,4 +193,52 @@ impl Dex {
 
         Some(solution.with_buffers_internalizations(tokens))
     }
+
+    /// Simulates the swaps of the different DEX APIs for an order and picks
+    /// the one with the most output (sell orders) or the least input (buy
+    /// orders) after accounting for the cost of the gas it uses. Swaps that
+    /// fail to simulate are discarded.
+    async fn best_swap<'a>(
+        &self,
+        order: &order::Order,
+        swaps: Vec<(&'a infra::dex::Dex, dex::Swap)>,
+        tokens: &auction::Tokens,
+        gas_price: auction::GasPrice,
+    ) -> Option<(&'a infra::dex::Dex, dex::Swap, eth::Gas)> {
+        let simulated = future::join_all(swaps.into_iter().map(|(dex, swap)| async move {
+            match self.simulator.gas(order.owner(), &swap).await {
+                Ok(gas) => Some((dex, swap, gas)),
+                Err(err) => {
+                    tracing::warn!(dex = dex.name(), ?err, "gas simulation failed");
+                    None
+                }
+            }
+        }))
+        .await;
+
+        // The gas cost, denominated in the specified token. Without a reference
+        // price, the cost is ignored. This still compares the swaps fairly since
+        // all their amounts are denominated in the same token.
+        let cost = |token: &eth::TokenAddress, gas: eth::Gas| {
+            tokens
+                .reference_price(token)
+                .and_then(|price| {
+                    price.ether_value(eth::Ether(gas.0.saturating_mul(gas_price.0 .0)))
+                })
+                .unwrap_or_default()
+        };
+        let simulated = simulated.into_iter().flatten();
+        match order.side {
+            order::Side::Sell => simulated.max_by_key(|(_, swap, gas)| {
+                swap.output
+                    .amount
+                    .saturating_sub(cost(&swap.output.token, *gas))
+            }),
+            order::Side::Buy => simulated.min_by_key(|(_, swap, gas)| {
+                swap.input
+                    .amount
+                    .saturating_add(cost(&swap.input.token, *gas))
+            }),
+        }
+    }
 }
@@ -4,12 +4,14 @@ pub mod baseline;
 pub mod dex;
 pub mod legacy;
 pub mod naive;
+pub mod ring;
 
-pub use self::{baseline::Baseline, dex::Dex, legacy::Legacy, naive::Naive};
+pub use self::{baseline::Baseline, dex::Dex, legacy::Legacy, naive::Naive, ring::Ring};
 
 pub enum Solver {
     Baseline(Baseline),
     Naive(Naive),
+    Ring(Ring),
     Legacy(Legacy),
     Dex(Dex),
 }
@@ -22,17 +24,36 @@ impl Solver {
         match self {
             Solver::Baseline(solver) => solver.solve(auction).await,
             Solver::Naive(solver) => solver.solve(auction).await,
+            Solver::Ring(solver) => solver.solve(auction).await,
             Solver::Legacy(solver) => solver.solve(auction).await,
             Solver::Dex(solver) => solver.solve(auction).await,
         }
     }
 
+    /// Solves a given auction like [`Solver::solve`] but hands every solution
+    /// to `found` as soon as it is available. Only the DEX solvers produce
+    /// their solutions incrementally, the other solvers hand over all of their
+    /// solutions at once when they are done.
+    pub async fn solve_streaming(
+        &self,
+        auction: auction::Auction,
+        found: impl FnMut(solution::Solution),
+    ) {
+        match self {
+            Solver::Dex(solver) => solver.solve_streaming(auction, found).await,
+            Solver::Baseline(_) | Solver::Naive(_) | Solver::Ring(_) | Solver::Legacy(_) => {
+                self.solve(auction).await.into_iter().for_each(found)
+            }
+        }
+    }
+
     /// Notifies the solver about important events. Some of those events are
     /// generated by the solver engine, others by the driver.
     pub fn notify(&self, notification: notification::Notification) {
         match self {
             Solver::Baseline(_) => (),
             Solver::Naive(_) => (),
+            Solver::Ring(_) => (),
             Solver::Legacy(solver) => solver.notify(notification),
             Solver::Dex(_) => (),
         }
@@ -0,0 +1,478 @@
+//! "Ring" solver implementation.
+//!
+//! The ring solver settles orders over arbitrary token pairs at uniform
+//! clearing prices given by the auction's reference prices. It builds a token
+//! graph from the orders and settles every connected part of it as a single
+//! solution, in which orders are matched peer-to-peer in rings (`A -> B -> C ->
+//! A`) and multi-order coincidences of wants, and whatever can't be matched is
+//! routed over the auction's liquidity.
+//!
+//! Rings aren't enumerated explicitly. Instead, the solver starts by executing
+//! all orders in full and repeatedly scales back orders buying tokens that the
+//! settlement would pay out more of than it receives, until the residual of
+//! every token can be covered by on-chain liquidity. What remains are the
+//! orders that cancel each other out along rings and CoWs, plus the ones whose
+//! residual can be routed. Since traders are paid at the reference prices, any
+//! excess from matching or routing stays in the settlement contract's buffers.
+
+use {
+    crate::{
+        boundary,
+        domain::{
+            self,
+            auction,
+            eth,
+            order::{self, UserOrder},
+            solution,
+            solver::baseline,
+        },
+        infra::config,
+        util,
+    },
+    ethereum_types::U256,
+    std::{
+        cmp::Reverse,
+        collections::{BTreeMap, HashMap, HashSet, VecDeque},
+        sync::Arc,
+    },
+};
+
+pub struct Ring(Arc<Inner>);
+
+struct Inner {
+    weth: eth::WethAddress,
+
+    /// Set of tokens to additionally consider as intermediary hops when
+    /// routing residual amounts over liquidity.
+    base_tokens: HashSet<eth::TokenAddress>,
+
+    /// Maximum number of hops that can be considered in the trading path of a
+    /// residual amount. See the baseline solver for more details.
+    max_hops: usize,
+
+    /// Parameters used to calculate the revert risk of a solution.
+    risk: domain::Risk,
+}
+
+/// The maximum number of times orders of a connected part of the token graph
+/// get scaled back before giving up on settling it. This bounds the time spent
+/// on a part of the graph whose orders keep cascading into new residuals.
+const MAX_ROUNDS: usize = 100;
+
+impl Ring {
+    /// Creates a new ring solver for the specified configuration.
+    pub fn new(config: config::ring::Config) -> Self {
+        Self(Arc::new(Inner {
+            weth: config.weth,
+            base_tokens: config.base_tokens.into_iter().collect(),
+            max_hops: config.max_hops,
+            risk: config.risk,
+        }))
+    }
+
+    /// Solves the specified auction, returning a solution for every connected
+    /// part of the auction's token graph that could be settled.
+    pub async fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution> {
+        // Make sure to push the CPU-heavy code to a separate thread in order to
+        // not lock up the [`tokio`] runtime and cause it to slow down handling
+        // the real async things.
+        let inner = self.0.clone();
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || {
+            let _entered = span.enter();
+            inner.solve(auction)
+        })
+        .await
+        .expect("ring solver unexpected panic")
+    }
+}
+
+impl Inner {
+    fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution> {
+        let router =
+            boundary::baseline::Solver::new(&self.weth, &self.base_tokens, &auction.liquidity);
+
+        let candidates = auction
+            .orders
+            .iter()
+            .filter_map(|order| Candidate::new(order, &auction.tokens))
+            .collect();
+
+        components(candidates)
+            .into_iter()
+            .take_while(|_| auction.deadline.remaining().is_some())
+            .filter_map(|component| self.settle(component, &router, &auction))
+            .enumerate()
+            .map(|(i, solution)| solution.with_id(solution::Id(i as u64)))
+            .collect()
+    }
+
+    /// Settles the orders of a connected part of the token graph, scaling
+    /// back orders until the residual of every token can be routed over
+    /// liquidity.
+    fn settle(
+        &self,
+        mut candidates: Vec<Candidate>,
+        router: &boundary::baseline::Solver,
+        auction: &auction::Auction,
+    ) -> Option<solution::Solution> {
+        for _ in 0..MAX_ROUNDS {
+            candidates.retain(|candidate| !candidate.executed.is_zero());
+            if candidates.is_empty() || auction.deadline.remaining().is_none() {
+                return None;
+            }
+
+            match self.route_residuals(&candidates, router) {
+                Ok(segments) => return self.solution(&candidates, segments, auction),
+                Err(deficit) => {
+                    tracing::trace!(?deficit, "scaling back orders");
+                    scale_back(&mut candidates, deficit);
+                }
+            }
+        }
+
+        tracing::debug!(
+            orders = candidates.len(),
+            "giving up on settling orders after {MAX_ROUNDS} rounds"
+        );
+        None
+    }
+
+    /// Routes the surplus of tokens that the settlement receives more of than
+    /// it pays out into the tokens it pays out more of than it receives.
+    /// Returns the first deficit that can't be covered if there is one.
+    fn route_residuals<'a>(
+        &self,
+        candidates: &[Candidate],
+        router: &boundary::baseline::Solver<'a>,
+    ) -> Result<Vec<baseline::Segment<'a>>, Deficit> {
+        let prices = candidates
+            .iter()
+            .flat_map(|candidate| {
+                [
+                    (candidate.order.sell.token, candidate.sell_price),
+                    (candidate.order.buy.token, candidate.buy_price),
+                ]
+            })
+            .collect::<HashMap<_, _>>();
+        let value = |token: &eth::TokenAddress, amount: U256| amount.full_mul(prices[token]);
+
+        let mut surpluses = Vec::new();
+        let mut deficits = Vec::new();
+        for (token, balance) in balances(candidates).ok_or(Deficit::Overflow)? {
+            if balance.input > balance.output {
+                surpluses.push((token, balance.input - balance.output));
+            } else if balance.output > balance.input {
+                deficits.push((token, balance.output - balance.input));
+            }
+        }
+        // Cover the largest deficits first, since they are the hardest to
+        // route.
+        deficits.sort_by_key(|(token, amount)| Reverse(value(token, *amount)));
+
+        let mut segments = Vec::new();
+        let mut used = HashSet::new();
+        for (token, amount) in deficits {
+            surpluses.sort_by_key(|(token, amount)| Reverse(value(token, *amount)));
+            let route = surpluses
+                .iter_mut()
+                .find_map(|(surplus_token, surplus)| {
+                    let route = router.route(
+                        baseline::Request {
+                            sell: eth::Asset {
+                                token: *surplus_token,
+                                amount: *surplus,
+                            },
+                            buy: eth::Asset { token, amount },
+                            side: order::Side::Buy,
+                        },
+                        self.max_hops,
+                    )?;
+                    // The estimated amounts of a route assume the state of its
+                    // liquidity before the settlement, so every liquidity can
+                    // only be used once.
+                    if route
+                        .segments()
+                        .iter()
+                        .any(|segment| used.contains(&segment.liquidity.id))
+                    {
+                        return None;
+                    }
+                    *surplus = surplus.saturating_sub(route.input().amount);
+                    Some(route)
+                })
+                .ok_or(Deficit::Token { token, amount })?;
+
+            used.extend(
+                route
+                    .segments()
+                    .iter()
+                    .map(|segment| segment.liquidity.id.clone()),
+            );
+            segments.extend(route.into_segments());
+        }
+
+        Ok(segments)
+    }
+
+    fn solution(
+        &self,
+        candidates: &[Candidate],
+        segments: Vec<baseline::Segment>,
+        auction: &auction::Auction,
+    ) -> Option<solution::Solution> {
+        let prices = solution::ClearingPrices::new(candidates.iter().flat_map(|candidate| {
+            [
+                (candidate.order.sell.token, candidate.sell_price),
+                (candidate.order.buy.token, candidate.buy_price),
+            ]
+        }));
+        let trades = candidates
+            .iter()
+            .map(|candidate| {
+                solution::Fulfillment::new(
+                    candidate.order.clone(),
+                    candidate.executed,
+                    solution::Fee::Protocol,
+                )
+                .map(solution::Trade::Fulfillment)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let gas = segments.iter().fold(
+            U256::from(
+                solution::INITIALIZATION_COST
+                    + solution::SETTLEMENT
+                    + solution::ERC20_TRANSFER * trades.len() as u64 * 2,
+            ),
+            |acc, segment| acc.saturating_add(segment.gas.0),
+        );
+        let interactions = segments
+            .into_iter()
+            .map(|segment| {
+                solution::Interaction::Liquidity(solution::LiquidityInteraction {
+                    liquidity: segment.liquidity.clone(),
+                    input: segment.input,
+                    output: segment.output,
+                    internalize: false,
+                })
+            })
+            .collect();
+
+        Some(
+            solution::Solution {
+                id: Default::default(),
+                prices,
+                trades,
+                interactions,
+                score: Default::default(),
+            }
+            .with_risk_adjusted_score(&self.risk, eth::Gas(gas), auction.gas_price)
+            .with_buffers_internalizations(&auction.tokens),
+        )
+    }
+}
+
+/// An order considered for settling at the auction's reference prices.
+#[derive(Debug)]
+struct Candidate<'a> {
+    order: &'a order::Order,
+    sell_price: U256,
+    buy_price: U256,
+    /// The executed amount, denominated in the sell token for sell orders and
+    /// in the buy token for buy orders.
+    executed: U256,
+}
+
+impl<'a> Candidate<'a> {
+    /// Creates a candidate for executing the order in full. Returns `None` if
+    /// the order can't be settled at the auction's reference prices.
+    fn new(order: &'a order::Order, tokens: &auction::Tokens) -> Option<Self> {
+        let order = UserOrder::new(order)?.get();
+        // Solver computed fees would need to be accounted for in the clearing
+        // prices, which this solver doesn't support.
+        if order.solver_determines_fee()
+            || order.sell.token == order.buy.token
+            || order.sell.amount.is_zero()
+            || order.buy.amount.is_zero()
+        {
+            return None;
+        }
+
+        let sell_price = tokens.reference_price(&order.sell.token)?.0 .0;
+        let buy_price = tokens.reference_price(&order.buy.token)?.0 .0;
+        if sell_price.is_zero() || buy_price.is_zero() {
+            return None;
+        }
+        if order.sell.amount.full_mul(sell_price) < order.buy.amount.full_mul(buy_price) {
+            return None;
+        }
+
+        let candidate = Self {
+            order,
+            sell_price,
+            buy_price,
+            executed: match order.side {
+                order::Side::Sell => order.sell.amount,
+                order::Side::Buy => order.buy.amount,
+            },
+        };
+        candidate.amounts()?;
+        Some(candidate)
+    }
+
+    /// Returns the amount of sell tokens transferred into and the amount of
+    /// buy tokens transferred out of the settlement contract, rounded the same
+    /// way as the settlement contract does.
+    fn amounts(&self) -> Option<(U256, U256)> {
+        match self.order.side {
+            order::Side::Sell => Some((
+                self.executed,
+                util::math::div_ceil(self.executed.checked_mul(self.sell_price)?, self.buy_price)?,
+            )),
+            order::Side::Buy => Some((
+                self.executed.checked_mul(self.buy_price)? / self.sell_price,
+                self.executed,
+            )),
+        }
+    }
+
+    /// Scales back the execution so that at least `amount` fewer buy tokens
+    /// get transferred out of the settlement contract.
+    fn scale_back(&mut self, amount: U256) {
+        let reduction = match self.order.side {
+            order::Side::Sell => {
+                util::math::div_ceil(amount.saturating_mul(self.buy_price), self.sell_price)
+                    .unwrap_or(U256::max_value())
+            }
+            order::Side::Buy => amount,
+        };
+        self.executed = self.executed.saturating_sub(reduction);
+    }
+}
+
+/// A token that the settlement pays out more of than it receives.
+#[derive(Clone, Copy, Debug)]
+enum Deficit {
+    Token {
+        token: eth::TokenAddress,
+        amount: U256,
+    },
+    /// The amounts of the settlement overflow.
+    Overflow,
+}
+
+#[derive(Debug, Default)]
+struct Balance {
+    input: U256,
+    output: U256,
+}
+
+/// Computes the amounts of every token that the settlement contract receives
+/// from and pays out to the traders.
+fn balances(candidates: &[Candidate]) -> Option<BTreeMap<eth::TokenAddress, Balance>> {
+    let mut balances = BTreeMap::<_, Balance>::new();
+    for candidate in candidates {
+        let (input, output) = candidate.amounts()?;
+        let sell = balances.entry(candidate.order.sell.token).or_default();
+        sell.input = sell.input.checked_add(input)?;
+        let buy = balances.entry(candidate.order.buy.token).or_default();
+        buy.output = buy.output.checked_add(output)?;
+    }
+    Some(balances)
+}
+
+/// Scales back orders buying the token of the deficit. Partially fillable
+/// orders are preferred since they can be scaled back by exactly the deficit.
+/// Otherwise, the smallest fill-or-kill order covering the deficit is dropped,
+/// or the largest one if none covers it on its own.
+fn scale_back(candidates: &mut [Candidate], deficit: Deficit) {
+    let (token, amount) = match deficit {
+        Deficit::Token { token, amount } => (token, amount),
+        Deficit::Overflow => {
+            // Scaling back the largest order is the quickest way to bring
+            // the amounts back into range.
+            if let Some(candidate) = candidates
+                .iter_mut()
+                .max_by_key(|candidate| candidate.executed.full_mul(candidate.sell_price))
+            {
+                candidate.executed = U256::zero();
+            }
+            return;
+        }
+    };
+
+    let output = |candidate: &Candidate| {
+        candidate
+            .amounts()
+            .map(|(_, output)| output)
+            .unwrap_or_default()
+    };
+    let mut buyers = candidates
+        .iter_mut()
+        .filter(|candidate| candidate.order.buy.token == token)
+        .collect::<Vec<_>>();
+
+    if let Some(candidate) = buyers
+        .iter_mut()
+        .filter(|candidate| candidate.order.partially_fillable)
+        .max_by_key(|candidate| output(candidate))
+    {
+        candidate.scale_back(amount);
+        return;
+    }
+
+    let index = buyers
+        .iter()
+        .enumerate()
+        .filter(|(_, candidate)| output(candidate) >= amount)
+        .min_by_key(|(_, candidate)| output(candidate))
+        .or_else(|| {
+            buyers
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, candidate)| output(candidate))
+        })
+        .map(|(index, _)| index);
+    if let Some(index) = index {
+        buyers[index].executed = U256::zero();
+    }
+}
+
+/// Splits the candidates into the connected parts of the token graph formed
+/// by their orders. Orders keep their relative order within a part.
+fn components(candidates: Vec<Candidate>) -> Vec<Vec<Candidate>> {
+    let mut edges = HashMap::<_, Vec<_>>::new();
+    for candidate in &candidates {
+        let (sell, buy) = (candidate.order.sell.token, candidate.order.buy.token);
+        edges.entry(sell).or_default().push(buy);
+        edges.entry(buy).or_default().push(sell);
+    }
+
+    let mut component = HashMap::new();
+    let mut count = 0;
+    for candidate in &candidates {
+        let start = candidate.order.sell.token;
+        if component.contains_key(&start) {
+            continue;
+        }
+
+        component.insert(start, count);
+        let mut queue = VecDeque::from([start]);
+        while let Some(token) = queue.pop_front() {
+            for next in &edges[&token] {
+                if !component.contains_key(next) {
+                    component.insert(*next, count);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    let mut components = (0..count).map(|_| Vec::new()).collect::<Vec<_>>();
+    for candidate in candidates {
+        components[component[&candidate.order.sell.token]].push(candidate);
+    }
+    components
+}
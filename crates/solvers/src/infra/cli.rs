@@ -2,7 +2,8 @@
 
 use {
     clap::{Parser, Subcommand},
-    std::{net::SocketAddr, path::PathBuf},
+    shared::request_signing::Secret,
+    std::{net::SocketAddr, path::PathBuf, time::Duration},
 };
 
 /// Run a solver engine
@@ -21,6 +22,21 @@ pub struct Args {
     #[arg(long, env, default_value = "127.0.0.1:7872")]
     pub addr: SocketAddr,
 
+    /// Comma separated secrets with which requests to the solver engine have
+    /// to be signed. Requests are accepted unsigned if none are configured.
+    /// Configuring more than one secret allows rotating them without downtime.
+    #[arg(long, env, value_delimiter = ',')]
+    pub auth_secrets: Vec<Secret>,
+
+    /// The maximum age of a signed request before it gets rejected.
+    #[arg(
+        long,
+        env,
+        default_value = "30",
+        value_parser = shared::arguments::duration_from_seconds
+    )]
+    pub auth_max_age: Duration,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -40,6 +56,12 @@ pub enum Command {
         #[clap(long, env)]
         config: PathBuf,
     },
+    /// match orders across token pairs in rings and CoWs and route the
+    /// residual over onchain liquidity
+    Ring {
+        #[clap(long, env)]
+        config: PathBuf,
+    },
     /// forward auction to solver implementing the legacy HTTP interface
     Legacy {
         #[clap(long, env)]
@@ -65,4 +87,9 @@ pub enum Command {
         #[clap(long, env)]
         config: PathBuf,
     },
+    /// solve individual orders using the best swap of multiple DEX APIs
+    Meta {
+        #[clap(long, env)]
+        config: PathBuf,
+    },
 }
@@ -12,7 +12,7 @@ use {
 #[serde_as]
 #[derive(Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
-struct Config {
+pub struct Config {
     /// The URL of the Balancer SOR API.
     #[serde_as(as = "serde_with::DisplayFromStr")]
     endpoint: reqwest::Url,
@@ -30,18 +30,24 @@ struct Config {
 pub async fn load(path: &Path) -> super::Config {
     let (base, config) = file::load::<Config>(path).await;
 
-    // Balancer SOR solver only supports mainnet.
-    let contracts = contracts::Contracts::for_chain(eth::ChainId::Mainnet);
-
     super::Config {
-        sor: dex::balancer::Config {
-            endpoint: config.endpoint,
-            vault: config
+        sor: config.into_config(base.contracts.settlement),
+        base,
+    }
+}
+
+impl Config {
+    pub fn into_config(self, settlement: eth::ContractAddress) -> dex::balancer::Config {
+        // Balancer SOR solver only supports mainnet.
+        let contracts = contracts::Contracts::for_chain(eth::ChainId::Mainnet);
+
+        dex::balancer::Config {
+            endpoint: self.endpoint,
+            vault: self
                 .vault
                 .map(eth::ContractAddress)
                 .unwrap_or(contracts.balancer_vault),
-            settlement: base.contracts.settlement,
-        },
-        base,
+            settlement,
+        }
     }
 }
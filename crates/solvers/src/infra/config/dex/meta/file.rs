@@ -0,0 +1,48 @@
+use {
+    crate::infra::config::dex::{balancer, file, oneinch, paraswap, zeroex},
+    serde::Deserialize,
+    std::path::Path,
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Config {
+    /// Settings for the Balancer SOR API, see the `balancer` solver.
+    balancer: Option<balancer::file::Config>,
+
+    /// Settings for the 0x swap API, see the `zeroex` solver.
+    zeroex: Option<zeroex::file::Config>,
+
+    /// Settings for the 1inch API, see the `oneinch` solver.
+    oneinch: Option<oneinch::file::Config>,
+
+    /// Settings for the ParaSwap API, see the `paraswap` solver.
+    paraswap: Option<paraswap::file::Config>,
+}
+
+/// Load the meta DEX solver configuration from a TOML file.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid or on I/O errors.
+pub async fn load(path: &Path) -> super::Config {
+    let (base, config) = file::load::<Config>(path).await;
+
+    assert!(
+        config.balancer.is_some()
+            || config.zeroex.is_some()
+            || config.oneinch.is_some()
+            || config.paraswap.is_some(),
+        "invalid configuration: must configure at least one DEX API",
+    );
+
+    super::Config {
+        balancer: config
+            .balancer
+            .map(|config| config.into_config(base.contracts.settlement)),
+        zeroex: config.zeroex.map(zeroex::file::Config::into_config),
+        oneinch: config.oneinch.map(oneinch::file::Config::into_config),
+        paraswap: config.paraswap.map(paraswap::file::Config::into_config),
+        base,
+    }
+}
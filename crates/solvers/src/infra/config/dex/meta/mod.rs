@@ -0,0 +1,9 @@
+pub mod file;
+
+pub struct Config {
+    pub balancer: Option<crate::infra::dex::balancer::Config>,
+    pub zeroex: Option<crate::infra::dex::zeroex::Config>,
+    pub oneinch: Option<crate::infra::dex::oneinch::Config>,
+    pub paraswap: Option<crate::infra::dex::paraswap::Config>,
+    pub base: super::Config,
+}
@@ -1,5 +1,6 @@
 pub mod balancer;
 mod file;
+pub mod meta;
 pub mod oneinch;
 pub mod paraswap;
 pub mod zeroex;
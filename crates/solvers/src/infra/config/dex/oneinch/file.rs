@@ -12,7 +12,7 @@ use {
 #[serde_as]
 #[derive(Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
-struct Config {
+pub struct Config {
     /// Chain ID used to automatically determine the address of the settlement
     /// contract and for metrics.
     #[serde_as(as = "serialize::ChainId")]
@@ -49,13 +49,20 @@ struct Config {
 pub async fn load(path: &Path) -> super::Config {
     let (base, config) = file::load::<Config>(path).await;
 
-    let settlement = contracts::Contracts::for_chain(config.chain_id).settlement;
-
     super::Config {
-        oneinch: oneinch::Config {
+        oneinch: config.into_config(),
+        base,
+    }
+}
+
+impl Config {
+    pub fn into_config(self) -> oneinch::Config {
+        let settlement = contracts::Contracts::for_chain(self.chain_id).settlement;
+
+        oneinch::Config {
             settlement,
-            endpoint: config.endpoint,
-            liquidity: match (config.include_liquidity, config.exclude_liquidity) {
+            endpoint: self.endpoint,
+            liquidity: match (self.include_liquidity, self.exclude_liquidity) {
                 (Some(include_liquidity), None) => oneinch::Liquidity::Only(include_liquidity),
                 (None, Some(exclude_liquidity)) => oneinch::Liquidity::Exclude(exclude_liquidity),
                 (None, None) => oneinch::Liquidity::Any,
@@ -63,11 +70,10 @@ pub async fn load(path: &Path) -> super::Config {
                     panic!("cannot specify both include-liquidity and exclude-liquidity")
                 }
             },
-            referrer: config.referrer,
-            main_route_parts: config.main_route_parts,
-            connector_tokens: config.connector_tokens,
-            complexity_level: config.complexity_level,
-        },
-        base,
+            referrer: self.referrer,
+            main_route_parts: self.main_route_parts,
+            connector_tokens: self.connector_tokens,
+            complexity_level: self.complexity_level,
+        }
     }
 }
@@ -11,7 +11,7 @@ use {
 #[serde_as]
 #[derive(Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
-struct Config {
+pub struct Config {
     /// The base URL for the ParaSwap API.
     #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
     pub endpoint: Option<reqwest::Url>,
@@ -36,14 +36,20 @@ pub async fn load(path: &Path) -> super::Config {
     let (base, config) = file::load::<Config>(path).await;
 
     super::Config {
-        paraswap: paraswap::Config {
-            endpoint: config
+        paraswap: config.into_config(),
+        base,
+    }
+}
+
+impl Config {
+    pub fn into_config(self) -> paraswap::Config {
+        paraswap::Config {
+            endpoint: self
                 .endpoint
                 .unwrap_or_else(|| paraswap::DEFAULT_URL.parse().unwrap()),
-            exclude_dexs: config.exclude_dexs,
-            address: config.address,
-            partner: config.partner,
-        },
-        base,
+            exclude_dexs: self.exclude_dexs,
+            address: self.address,
+            partner: self.partner,
+        }
     }
 }
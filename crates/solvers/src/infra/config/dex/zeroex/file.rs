@@ -12,7 +12,7 @@ use {
 #[serde_as]
 #[derive(Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
-struct Config {
+pub struct Config {
     /// The versioned URL endpoint for the 0x swap API.
     #[serde(default = "default_endpoint")]
     #[serde_as(as = "serde_with::DisplayFromStr")]
@@ -62,20 +62,26 @@ fn default_affiliate() -> H160 {
 pub async fn load(path: &Path) -> super::Config {
     let (base, config) = file::load::<Config>(path).await;
 
-    // Note that we just assume Mainnet here - this is because this is the
-    // only chain that the 0x solver supports anyway.
-    let settlement = contracts::Contracts::for_chain(eth::ChainId::Mainnet).settlement;
-
     super::Config {
-        zeroex: zeroex::Config {
-            endpoint: config.endpoint,
-            api_key: config.api_key,
-            excluded_sources: config.excluded_sources,
-            affiliate: config.affiliate,
-            settlement,
-            enable_rfqt: config.enable_rfqt,
-            enable_slippage_protection: config.enable_slippage_protection,
-        },
+        zeroex: config.into_config(),
         base,
     }
 }
+
+impl Config {
+    pub fn into_config(self) -> zeroex::Config {
+        // Note that we just assume Mainnet here - this is because this is the
+        // only chain that the 0x solver supports anyway.
+        let settlement = contracts::Contracts::for_chain(eth::ChainId::Mainnet).settlement;
+
+        zeroex::Config {
+            endpoint: self.endpoint,
+            api_key: self.api_key,
+            excluded_sources: self.excluded_sources,
+            affiliate: self.affiliate,
+            settlement,
+            enable_rfqt: self.enable_rfqt,
+            enable_slippage_protection: self.enable_slippage_protection,
+        }
+    }
+}
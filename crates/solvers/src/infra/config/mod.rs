@@ -4,6 +4,7 @@ pub mod baseline;
 pub mod dex;
 pub mod legacy;
 pub mod naive;
+pub mod ring;
 
 /// Unwraps result or logs a `TOML` parsing error.
 fn unwrap_or_log<T, E, P>(result: Result<T, E>, path: &P) -> T
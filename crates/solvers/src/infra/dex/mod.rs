@@ -17,6 +17,16 @@ pub enum Dex {
 }
 
 impl Dex {
+    /// The name of the DEX/DEX aggregator API for logging.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dex::Balancer(_) => "balancer",
+            Dex::OneInch(_) => "oneinch",
+            Dex::ZeroEx(_) => "zeroex",
+            Dex::ParaSwap(_) => "paraswap",
+        }
+    }
+
     /// Computes a swap (including calldata, estimated input and output amounts
     /// and the required allowance) for the specified order.
     ///
@@ -6,7 +6,8 @@ use {
         infra::{cli, config, dex},
     },
     clap::Parser,
-    std::net::SocketAddr,
+    shared::request_signing,
+    std::{net::SocketAddr, sync::Arc},
     tokio::sync::oneshot,
 };
 
@@ -37,6 +38,10 @@ async fn run_with(args: cli::Args, bind: Option<oneshot::Sender<SocketAddr>>) {
             let config = config::naive::file::load(&config).await;
             Solver::Naive(solver::Naive::new(config))
         }
+        cli::Command::Ring { config } => {
+            let config = config::ring::file::load(&config).await;
+            Solver::Ring(solver::Ring::new(config))
+        }
         cli::Command::Legacy { config } => {
             let config = config::legacy::load(&config).await;
             Solver::Legacy(solver::Legacy::new(config))
@@ -44,38 +49,69 @@ async fn run_with(args: cli::Args, bind: Option<oneshot::Sender<SocketAddr>>) {
         cli::Command::ZeroEx { config } => {
             let config = config::dex::zeroex::file::load(&config).await;
             Solver::Dex(solver::Dex::new(
-                dex::Dex::ZeroEx(
+                vec![dex::Dex::ZeroEx(
                     dex::zeroex::ZeroEx::new(config.zeroex).expect("invalid 0x configuration"),
-                ),
+                )],
                 config.base,
             ))
         }
         cli::Command::Balancer { config } => {
             let config = config::dex::balancer::file::load(&config).await;
             Solver::Dex(solver::Dex::new(
-                dex::Dex::Balancer(dex::balancer::Sor::new(config.sor)),
+                vec![dex::Dex::Balancer(dex::balancer::Sor::new(config.sor))],
                 config.base,
             ))
         }
         cli::Command::OneInch { config } => {
             let config = config::dex::oneinch::file::load(&config).await;
             Solver::Dex(solver::Dex::new(
-                dex::Dex::OneInch(dex::oneinch::OneInch::new(config.oneinch).await.unwrap()),
+                vec![dex::Dex::OneInch(
+                    dex::oneinch::OneInch::new(config.oneinch).await.unwrap(),
+                )],
                 config.base,
             ))
         }
         cli::Command::ParaSwap { config } => {
             let config = config::dex::paraswap::file::load(&config).await;
             Solver::Dex(solver::Dex::new(
-                dex::Dex::ParaSwap(dex::paraswap::ParaSwap::new(config.paraswap)),
+                vec![dex::Dex::ParaSwap(dex::paraswap::ParaSwap::new(
+                    config.paraswap,
+                ))],
                 config.base,
             ))
         }
+        cli::Command::Meta { config } => {
+            let config = config::dex::meta::file::load(&config).await;
+            let mut dexs = Vec::new();
+            if let Some(sor) = config.balancer {
+                dexs.push(dex::Dex::Balancer(dex::balancer::Sor::new(sor)));
+            }
+            if let Some(zeroex) = config.zeroex {
+                dexs.push(dex::Dex::ZeroEx(
+                    dex::zeroex::ZeroEx::new(zeroex).expect("invalid 0x configuration"),
+                ));
+            }
+            if let Some(oneinch) = config.oneinch {
+                dexs.push(dex::Dex::OneInch(
+                    dex::oneinch::OneInch::new(oneinch).await.unwrap(),
+                ));
+            }
+            if let Some(paraswap) = config.paraswap {
+                dexs.push(dex::Dex::ParaSwap(dex::paraswap::ParaSwap::new(paraswap)));
+            }
+            Solver::Dex(solver::Dex::new(dexs, config.base))
+        }
     };
 
     crate::api::Api {
         addr: args.addr,
         solver,
+        auth: (!args.auth_secrets.is_empty()).then(|| {
+            Arc::new(request_signing::Verifier::new(
+                args.auth_secrets,
+                args.auth_max_age,
+            ))
+        }),
     }
     .serve(bind, shutdown_signal())
     .await
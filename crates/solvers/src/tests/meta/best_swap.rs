@@ -0,0 +1,79 @@
+//! This test ensures that the meta solver picks the swap with the most output
+//! after accounting for the cost of the gas it uses.
+
+use {
+    crate::tests::{self, meta, mock},
+    serde_json::json,
+};
+
+#[tokio::test]
+async fn picks_best_output_after_gas() {
+    // 0x quotes 1 BAL more than Balancer but its swap uses 1,000,000 gas. At
+    // 15 Gwei that costs about 3.47 BAL while Balancer's 88,892 gas only cost
+    // about 0.31 BAL, so Balancer's swap is better.
+    let balancer = meta::balancer("227598784442065388110").await;
+    let zeroex = meta::zeroex("228598784442065388110", "1000000").await;
+    // Simulations returning no gas make the solver use the gas estimated by
+    // the APIs.
+    let simulation = || mock::http::Expectation::Post {
+        path: mock::http::Path::Any,
+        req: mock::http::RequestBody::Any,
+        res: json!({
+            "id": 1,
+            "jsonrpc": "2.0",
+            "result": "0x0000000000000000000000000000000000000000000000000000000000000000"
+        }),
+    };
+    let node = mock::http::setup(vec![simulation(), simulation()]).await;
+
+    let engine = tests::SolverEngine::new(
+        "meta",
+        meta::config(Some(&node.address), &balancer.address, &zeroex.address),
+    )
+    .await;
+
+    let solution = engine.solve(meta::auction()).await;
+
+    let solutions = solution["solutions"].as_array().unwrap();
+    assert_eq!(solutions.len(), 1);
+    let interactions = solutions[0]["interactions"].as_array().unwrap();
+    assert_eq!(interactions.len(), 1);
+    assert_eq!(interactions[0]["target"], meta::BALANCER_VAULT);
+    assert_eq!(
+        interactions[0]["outputs"][0]["amount"],
+        "227598784442065388110"
+    );
+}
+
+#[tokio::test]
+async fn picks_most_output_for_same_gas() {
+    // Without the big gas difference the better quote of 0x wins.
+    let balancer = meta::balancer("227598784442065388110").await;
+    let zeroex = meta::zeroex("228598784442065388110", "88892").await;
+    let simulation = || mock::http::Expectation::Post {
+        path: mock::http::Path::Any,
+        req: mock::http::RequestBody::Any,
+        res: json!({
+            "id": 1,
+            "jsonrpc": "2.0",
+            "result": "0x0000000000000000000000000000000000000000000000000000000000000000"
+        }),
+    };
+    let node = mock::http::setup(vec![simulation(), simulation()]).await;
+
+    let engine = tests::SolverEngine::new(
+        "meta",
+        meta::config(Some(&node.address), &balancer.address, &zeroex.address),
+    )
+    .await;
+
+    let solution = engine.solve(meta::auction()).await;
+
+    let interactions = solution["solutions"][0]["interactions"].as_array().unwrap();
+    assert_eq!(interactions.len(), 1);
+    assert_eq!(interactions[0]["target"], meta::ZEROEX_PROXY);
+    assert_eq!(
+        interactions[0]["outputs"][0]["amount"],
+        "228598784442065388110"
+    );
+}
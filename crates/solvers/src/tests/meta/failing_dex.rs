@@ -0,0 +1,68 @@
+//! This test ensures that the meta solver keeps solving orders with the
+//! remaining DEX APIs when some of them fail.
+
+use {
+    crate::tests::{self, meta, mock},
+    serde_json::json,
+};
+
+/// A Balancer SOR API responding with garbage.
+async fn failing_balancer() -> mock::http::ServerHandle {
+    mock::http::setup(vec![mock::http::Expectation::Post {
+        path: mock::http::Path::exact("sor"),
+        req: mock::http::RequestBody::Any,
+        res: json!("internal server error"),
+    }])
+    .await
+}
+
+/// A 0x API responding with an error.
+async fn failing_zeroex() -> mock::http::ServerHandle {
+    mock::http::setup(vec![mock::http::Expectation::Get {
+        path: mock::http::Path::glob("swap/v1/quote*"),
+        res: json!({
+            "code": 500,
+            "reason": "Internal Server Error",
+        }),
+    }])
+    .await
+}
+
+#[tokio::test]
+async fn skips_failing_dex() {
+    let balancer = failing_balancer().await;
+    let zeroex = meta::zeroex("228598784442065388110", "127886").await;
+
+    let engine = tests::SolverEngine::new(
+        "meta",
+        meta::config(None, &balancer.address, &zeroex.address),
+    )
+    .await;
+
+    let solution = engine.solve(meta::auction()).await;
+
+    let interactions = solution["solutions"][0]["interactions"].as_array().unwrap();
+    assert_eq!(interactions.len(), 1);
+    assert_eq!(interactions[0]["target"], meta::ZEROEX_PROXY);
+}
+
+#[tokio::test]
+async fn all_dexs_failing() {
+    let balancer = failing_balancer().await;
+    let zeroex = failing_zeroex().await;
+
+    let engine = tests::SolverEngine::new(
+        "meta",
+        meta::config(None, &balancer.address, &zeroex.address),
+    )
+    .await;
+
+    let solution = engine.solve(meta::auction()).await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": []
+        }),
+    );
+}
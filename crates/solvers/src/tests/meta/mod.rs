@@ -0,0 +1,129 @@
+use {
+    crate::tests::{self, mock},
+    serde_json::json,
+    std::net::SocketAddr,
+};
+
+mod best_swap;
+mod failing_dex;
+
+/// Creates a temporary file containing the config of a meta solver using the
+/// Balancer SOR and the 0x APIs.
+pub fn config(
+    node_addr: Option<&SocketAddr>,
+    balancer_addr: &SocketAddr,
+    zeroex_addr: &SocketAddr,
+) -> tests::Config {
+    let node_url = match node_addr {
+        Some(addr) => format!("http://{addr}"),
+        None => "http://localhost:8545".to_owned(),
+    };
+    tests::Config::String(format!(
+        r"
+node-url = '{node_url}'
+risk-parameters = [0,0,0,0]
+[dex.balancer]
+endpoint = 'http://{balancer_addr}/sor'
+[dex.zeroex]
+endpoint = 'http://{zeroex_addr}/swap/v1/'
+api-key = 'SUPER_SECRET_API_KEY'
+        ",
+    ))
+}
+
+/// An auction with a single order selling 1 WETH for at least 200 BAL.
+pub fn auction() -> serde_json::Value {
+    json!({
+        "id": "1",
+        "tokens": {
+            "0xba100000625a3754423978a60c9317c58a424e3D": {
+                "decimals": 18,
+                "symbol": "BAL",
+                "referencePrice": "4327903683155778",
+                "availableBalance": "1583034704488033979459",
+                "trusted": true
+            },
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                "decimals": 18,
+                "symbol": "WETH",
+                "referencePrice": "1000000000000000000",
+                "availableBalance": "482725140468789680",
+                "trusted": false
+            },
+        },
+        "orders": [
+            {
+                "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                          2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                          2a2a2a2a",
+                "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                "buyToken": "0xba100000625a3754423978a60c9317c58a424e3D",
+                "sellAmount": "1000000000000000000",
+                "buyAmount": "200000000000000000000",
+                "feeAmount": "1000000000000000",
+                "kind": "sell",
+                "partiallyFillable": false,
+                "class": "market",
+            }
+        ],
+        "liquidity": [],
+        "effectiveGasPrice": "15000000000",
+        "deadline": "2106-01-01T00:00:00.000Z"
+    })
+}
+
+/// A Balancer SOR API expecting the order of the [`auction`] and swapping it
+/// in a single pool, which is estimated to use 88,892 gas.
+pub async fn balancer(return_amount: &str) -> mock::http::ServerHandle {
+    mock::http::setup(vec![mock::http::Expectation::Post {
+        path: mock::http::Path::exact("sor"),
+        req: mock::http::RequestBody::Any,
+        res: json!({
+            "tokenAddresses": [
+                "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "0xba100000625a3754423978a60c9317c58a424e3d"
+            ],
+            "swaps": [
+                {
+                    "poolId": "0x5c6ee304399dbdb9c8ef030ab642b10820db8f56000200000000000000000014",
+                    "assetInIndex": 0,
+                    "assetOutIndex": 1,
+                    "amount": "1000000000000000000",
+                    "userData": "0x",
+                    "returnAmount": return_amount,
+                }
+            ],
+            "swapAmount": "1000000000000000000",
+            "swapAmountForSwaps": "1000000000000000000",
+            "returnAmount": return_amount,
+            "returnAmountFromSwaps": return_amount,
+            "returnAmountConsideringFees": return_amount,
+            "tokenIn": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "tokenOut": "0xba100000625a3754423978a60c9317c58a424e3d",
+            "marketSp": "0.004393607339632106",
+        }),
+    }])
+    .await
+}
+
+/// A 0x API quoting the order of the [`auction`].
+pub async fn zeroex(buy_amount: &str, estimated_gas: &str) -> mock::http::ServerHandle {
+    mock::http::setup(vec![mock::http::Expectation::Get {
+        path: mock::http::Path::glob("swap/v1/quote*"),
+        res: json!({
+            "to": "0xdef1c0ded9bec7f1a1670819833240f027b25eff",
+            "data": "0x01",
+            "estimatedGas": estimated_gas,
+            "sellAmount": "1000000000000000000",
+            "buyAmount": buy_amount,
+            "allowanceTarget": "0xdef1c0ded9bec7f1a1670819833240f027b25eff",
+        }),
+    }])
+    .await
+}
+
+/// The address of the Balancer vault which executes Balancer swaps.
+pub const BALANCER_VAULT: &str = "0xba12222222228d8ba445958a75a0704d566bf2c8";
+
+/// The address of the 0x exchange proxy which executes 0x swaps.
+pub const ZEROEX_PROXY: &str = "0xdef1c0ded9bec7f1a1670819833240f027b25eff";
@@ -14,10 +14,12 @@ mod balancer;
 mod baseline;
 mod dex;
 mod legacy;
+mod meta;
 mod mock;
 mod naive;
 mod oneinch;
 mod paraswap;
+mod ring;
 mod zeroex;
 
 /// A solver engine handle for E2E testing.
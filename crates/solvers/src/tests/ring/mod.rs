@@ -0,0 +1,3 @@
+mod matches_ring;
+mod partial_fill;
+mod residual;
@@ -0,0 +1,127 @@
+//! Tests that the ring solver partially fills orders that exceed the other
+//! orders of a ring.
+
+use {crate::tests, serde_json::json};
+
+#[tokio::test]
+async fn test() {
+    let engine = tests::SolverEngine::new(
+        "ring",
+        tests::Config::File("config/example.ring.toml".into()),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0x000000000000000000000000000000000000000a": {
+                    "decimals": 18,
+                    "symbol": "AAA",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": false
+                },
+                "0x000000000000000000000000000000000000000b": {
+                    "decimals": 18,
+                    "symbol": "BBB",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": false
+                },
+                "0x000000000000000000000000000000000000000c": {
+                    "decimals": 18,
+                    "symbol": "CCC",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": false
+                },
+            },
+            "orders": [
+                {
+                    "uid": "0x0101010101010101010101010101010101010101010101010101010101010101\
+                              0101010101010101010101010101010101010101\
+                              01010101",
+                    "sellToken": "0x000000000000000000000000000000000000000a",
+                    "buyToken": "0x000000000000000000000000000000000000000b",
+                    "sellAmount": "100000000000000000000",
+                    "buyAmount": "90000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": true,
+                    "class": "market",
+                },
+                {
+                    "uid": "0x0202020202020202020202020202020202020202020202020202020202020202\
+                              0202020202020202020202020202020202020202\
+                              02020202",
+                    "sellToken": "0x000000000000000000000000000000000000000b",
+                    "buyToken": "0x000000000000000000000000000000000000000c",
+                    "sellAmount": "50000000000000000000",
+                    "buyAmount": "45000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                },
+                {
+                    "uid": "0x0303030303030303030303030303030303030303030303030303030303030303\
+                              0303030303030303030303030303030303030303\
+                              03030303",
+                    "sellToken": "0x000000000000000000000000000000000000000c",
+                    "buyToken": "0x000000000000000000000000000000000000000a",
+                    "sellAmount": "50000000000000000000",
+                    "buyAmount": "45000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                },
+            ],
+            "liquidity": [],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0x000000000000000000000000000000000000000a": "1000000000000000000",
+                    "0x000000000000000000000000000000000000000b": "1000000000000000000",
+                    "0x000000000000000000000000000000000000000c": "1000000000000000000",
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x0101010101010101010101010101010101010101010101010101010101010101\
+                                    0101010101010101010101010101010101010101\
+                                    01010101",
+                        "executedAmount": "50000000000000000000",
+                    },
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x0202020202020202020202020202020202020202020202020202020202020202\
+                                    0202020202020202020202020202020202020202\
+                                    02020202",
+                        "executedAmount": "50000000000000000000",
+                    },
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x0303030303030303030303030303030303030303030303030303030303030303\
+                                    0303030303030303030303030303030303030303\
+                                    03030303",
+                        "executedAmount": "50000000000000000000",
+                    },
+                ],
+                "interactions": [],
+                "score": {
+                    "riskadjusted": 0.5
+                }
+            }]
+        }),
+    );
+}
@@ -0,0 +1,124 @@
+//! Tests that the ring solver routes the residual of a CoW over liquidity.
+
+use {crate::tests, serde_json::json};
+
+#[tokio::test]
+async fn test() {
+    let engine = tests::SolverEngine::new(
+        "ring",
+        tests::Config::File("config/example.ring.toml".into()),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0x000000000000000000000000000000000000000a": {
+                    "decimals": 18,
+                    "symbol": "AAA",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": false
+                },
+                "0x000000000000000000000000000000000000000b": {
+                    "decimals": 18,
+                    "symbol": "BBB",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": false
+                },
+            },
+            "orders": [
+                {
+                    "uid": "0x0101010101010101010101010101010101010101010101010101010101010101\
+                              0101010101010101010101010101010101010101\
+                              01010101",
+                    "sellToken": "0x000000000000000000000000000000000000000a",
+                    "buyToken": "0x000000000000000000000000000000000000000b",
+                    "sellAmount": "100000000000000000000",
+                    "buyAmount": "90000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                },
+                {
+                    "uid": "0x0202020202020202020202020202020202020202020202020202020202020202\
+                              0202020202020202020202020202020202020202\
+                              02020202",
+                    "sellToken": "0x000000000000000000000000000000000000000b",
+                    "buyToken": "0x000000000000000000000000000000000000000a",
+                    "sellAmount": "50000000000000000000",
+                    "buyAmount": "45000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                },
+            ],
+            "liquidity": [
+                {
+                    "kind": "constantproduct",
+                    "tokens": {
+                        "0x000000000000000000000000000000000000000a": {
+                            "balance": "1000000000000000000000"
+                        },
+                        "0x000000000000000000000000000000000000000b": {
+                            "balance": "2000000000000000000000"
+                        }
+                    },
+                    "fee": "0.003",
+                    "id": "0",
+                    "address": "0xffffffffffffffffffffffffffffffffffffffff",
+                    "gasEstimate": "110000"
+                },
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0x000000000000000000000000000000000000000a": "1000000000000000000",
+                    "0x000000000000000000000000000000000000000b": "1000000000000000000",
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x0101010101010101010101010101010101010101010101010101010101010101\
+                                    0101010101010101010101010101010101010101\
+                                    01010101",
+                        "executedAmount": "100000000000000000000",
+                    },
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x0202020202020202020202020202020202020202020202020202020202020202\
+                                    0202020202020202020202020202020202020202\
+                                    02020202",
+                        "executedAmount": "50000000000000000000",
+                    },
+                ],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0x000000000000000000000000000000000000000a",
+                        "outputToken": "0x000000000000000000000000000000000000000b",
+                        "inputAmount": "25718180181570352082",
+                        "outputAmount": "50000000000000000000"
+                    },
+                ],
+                "score": {
+                    "riskadjusted": 0.5
+                }
+            }]
+        }),
+    );
+}
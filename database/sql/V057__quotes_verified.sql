@@ -0,0 +1,4 @@
+-- Remember whether a quote was verified by simulating the trade. Verified quotes already account
+-- for the gas used by the order's hooks so it must not be added on top again when creating an
+-- order with such a quote.
+ALTER TABLE quotes ADD COLUMN verified boolean NOT NULL DEFAULT false;
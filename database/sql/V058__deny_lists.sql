@@ -0,0 +1,8 @@
+-- Allows operators to manage deny lists at runtime without restarting or redeploying the services.
+CREATE TYPE DenyListKind AS ENUM ('unsupported_token', 'banned_user');
+
+CREATE TABLE deny_lists (
+    kind DenyListKind NOT NULL,
+    address bytea NOT NULL,
+    PRIMARY KEY (kind, address)
+);
@@ -0,0 +1,7 @@
+-- Persists the results of the bad token detection so they survive restarts and are shared between services.
+CREATE TABLE token_quality (
+    token bytea PRIMARY KEY,
+    good boolean NOT NULL,
+    reason text,
+    expiration timestamptz NOT NULL
+);
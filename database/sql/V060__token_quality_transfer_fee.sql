@@ -0,0 +1,5 @@
+-- Tokens that take a fee on transfer can be classified as "taxed" instead of being rejected.
+ALTER TABLE token_quality ADD COLUMN transfer_fee_bps integer;
+-- Whether a token is supported depends on whether taxed tokens are allowed so verdicts are stored per mode.
+ALTER TABLE token_quality ADD COLUMN allow_taxed_tokens boolean NOT NULL DEFAULT false;
+ALTER TABLE token_quality DROP CONSTRAINT token_quality_pkey, ADD PRIMARY KEY (token, allow_taxed_tokens);
@@ -0,0 +1,13 @@
+-- Audit trail of reorgs that caused indexed events (and the data derived from them) to be rolled back.
+CREATE TABLE reorgs (
+    id bigserial PRIMARY KEY,
+    detected_at timestamptz NOT NULL,
+    source text NOT NULL,
+    reorged_block_number bigint NOT NULL,
+    reorged_block_hash bytea NOT NULL,
+    replaced_from_block bigint NOT NULL,
+    current_block_number bigint NOT NULL,
+    current_block_hash bytea NOT NULL
+);
+
+CREATE INDEX reorgs_reorged_block_number ON reorgs USING BTREE (reorged_block_number);
@@ -0,0 +1,15 @@
+-- Signed requests to cancel all off-chain orders of an owner that were created before a timestamp.
+-- Only the latest request of every owner and token filter combination is stored.
+CREATE TABLE owner_order_cancellations (
+    owner bytea NOT NULL,
+    cancelled_before timestamptz NOT NULL,
+    -- NULL means orders of any token are cancelled.
+    sell_token bytea,
+    buy_token bytea,
+    submitted_at timestamptz NOT NULL
+);
+
+-- NULL tokens are mapped to an empty address so that they don't count as distinct.
+CREATE UNIQUE INDEX owner_order_cancellations_filter ON owner_order_cancellations USING BTREE (
+    owner, COALESCE(sell_token, ''::bytea), COALESCE(buy_token, ''::bytea)
+);
@@ -0,0 +1,44 @@
+-- Daily aggregates of settled trades and settlements that back the analytics endpoints. They are
+-- maintained incrementally by the autopilot so that queries don't have to scan all trades.
+CREATE TABLE trade_analytics (
+    day date NOT NULL,
+    owner bytea NOT NULL,
+    sell_token bytea NOT NULL,
+    buy_token bytea NOT NULL,
+    -- Empty if the full app data of the order is unknown or doesn't specify an app code.
+    app_code text NOT NULL,
+    trades bigint NOT NULL,
+    sell_amount numeric(78,0) NOT NULL,
+    buy_amount numeric(78,0) NOT NULL,
+    -- Amounts below are denominated in wei of the native token.
+    volume numeric(78,0) NOT NULL,
+    surplus numeric(78,0) NOT NULL,
+    fee numeric(78,0) NOT NULL,
+    PRIMARY KEY (day, owner, sell_token, buy_token, app_code)
+);
+
+CREATE INDEX trade_analytics_owner ON trade_analytics USING BTREE (owner, day);
+CREATE INDEX trade_analytics_sell_token ON trade_analytics USING BTREE (sell_token, day);
+CREATE INDEX trade_analytics_buy_token ON trade_analytics USING BTREE (buy_token, day);
+CREATE INDEX trade_analytics_app_code ON trade_analytics USING BTREE (app_code, day);
+
+CREATE TABLE settlement_analytics (
+    day date NOT NULL,
+    solver bytea NOT NULL,
+    settlements bigint NOT NULL,
+    gas_used numeric(78,0) NOT NULL,
+    -- Amounts below are denominated in wei of the native token.
+    gas_cost numeric(78,0) NOT NULL,
+    surplus numeric(78,0) NOT NULL,
+    fee numeric(78,0) NOT NULL,
+    PRIMARY KEY (day, solver)
+);
+
+CREATE TYPE AnalyticsSource AS ENUM ('trades', 'settlements');
+
+-- The last event of every source that has been added to the aggregates.
+CREATE TABLE analytics_progress (
+    source AnalyticsSource PRIMARY KEY,
+    block_number bigint NOT NULL,
+    log_index bigint NOT NULL
+);
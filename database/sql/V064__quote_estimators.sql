@@ -0,0 +1,9 @@
+-- The price estimator that won the quote competition and how long it took to provide its estimate.
+-- Used to measure the accuracy of every estimator once orders created with its quotes got executed.
+ALTER TABLE quotes
+    ADD COLUMN estimator text,
+    ADD COLUMN estimator_latency_ms bigint;
+
+ALTER TABLE order_quotes
+    ADD COLUMN estimator text,
+    ADD COLUMN estimator_latency_ms bigint;
@@ -0,0 +1,5 @@
+-- Events emitted by the refunder so users can follow the refund of their expired EthFlow orders.
+ALTER TYPE OrderEventLabel ADD VALUE 'refundpending';
+ALTER TYPE OrderEventLabel ADD VALUE 'refundsubmitted';
+ALTER TYPE OrderEventLabel ADD VALUE 'refundfailed';
+ALTER TYPE OrderEventLabel ADD VALUE 'refunded';
@@ -0,0 +1,49 @@
+-- Endpoints of integrators that get notified about order events. A webhook without owner and app
+-- code receives the events of all orders.
+CREATE TABLE webhooks (
+    id bigserial PRIMARY KEY,
+    url text NOT NULL,
+    -- Used to sign the deliveries so the integrator can authenticate them.
+    secret text NOT NULL,
+    -- Only notify about orders of this owner.
+    owner bytea,
+    -- Only notify about orders whose full app data specifies this app code.
+    app_code text,
+    created_at timestamptz NOT NULL
+);
+
+CREATE TYPE WebhookEvent AS ENUM ('traded', 'partiallyfilled', 'expired', 'filtered');
+
+CREATE TYPE WebhookDeliveryStatus AS ENUM ('pending', 'delivered', 'dead');
+
+-- Queue of notifications. Deliveries stay in the queue until the webhook acknowledged them or they
+-- ran out of attempts, which makes them end up in the dead letter view.
+CREATE TABLE webhook_deliveries (
+    id bigserial PRIMARY KEY,
+    webhook_id bigint NOT NULL,
+    order_uid bytea NOT NULL,
+    event WebhookEvent NOT NULL,
+    event_timestamp timestamptz NOT NULL,
+    payload text NOT NULL,
+    status WebhookDeliveryStatus NOT NULL,
+    attempts integer NOT NULL,
+    next_attempt timestamptz NOT NULL,
+    last_error text,
+    delivered_at timestamptz,
+    UNIQUE (webhook_id, order_uid, event, event_timestamp)
+);
+
+CREATE INDEX webhook_deliveries_due ON webhook_deliveries USING BTREE (next_attempt) WHERE status = 'pending';
+CREATE INDEX webhook_deliveries_dead ON webhook_deliveries USING BTREE (webhook_id, id) WHERE status = 'dead';
+CREATE INDEX webhook_deliveries_order ON webhook_deliveries USING BTREE (order_uid);
+
+CREATE TYPE WebhookSource AS ENUM ('orderevents', 'expiries');
+
+-- Up to which point in time the events of every source have been turned into deliveries.
+CREATE TABLE webhook_progress (
+    source WebhookSource PRIMARY KEY,
+    timestamp timestamptz NOT NULL
+);
+
+-- Allows reading the events that trigger notifications in chronological order.
+CREATE INDEX order_events_webhooks ON order_events USING BTREE (timestamp) WHERE label IN ('traded', 'filtered');
@@ -0,0 +1,5 @@
+-- Solvers can submit their settlements from additional accounts, so store the account that the
+-- winning settlement gets submitted from. Before, settlements were always submitted by the winner.
+ALTER TABLE settlement_scores ADD COLUMN submission_address bytea;
+UPDATE settlement_scores SET submission_address = winner;
+ALTER TABLE settlement_scores ALTER COLUMN submission_address SET NOT NULL;
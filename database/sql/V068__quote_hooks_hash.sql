@@ -0,0 +1,3 @@
+-- Hash of the hooks a quote got simulated with. Verified quotes only cover the gas of these hooks, so
+-- orders with different hooks have to pay for their gas on top of the quote.
+ALTER TABLE quotes ADD COLUMN hooks_hash bytea;
@@ -0,0 +1,6 @@
+-- The day every block with aggregated events was added to. Allows the analytics updater to look up the
+-- day without querying the node again and to recompute the aggregates of the affected days on reorgs.
+CREATE TABLE analytics_blocks (
+    block_number bigint PRIMARY KEY,
+    day date NOT NULL
+);
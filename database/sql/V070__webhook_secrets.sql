@@ -0,0 +1,3 @@
+-- The secrets of webhooks are derived from a key only known to the services, so they no longer get
+-- stored.
+ALTER TABLE webhooks DROP COLUMN secret;